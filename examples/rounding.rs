@@ -1,5 +1,5 @@
 extern crate inter;
-extern crate num;
+extern crate num_traits as num;
 
 use inter::rounding::Rounding;
 use num::{
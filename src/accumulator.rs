@@ -0,0 +1,139 @@
+//! Hulls and intersections of many intervals, with undo.
+//!
+//! Backtracking search adds contributions to a domain while it descends and takes them back in
+//! reverse order when it returns. Accumulators keep the running result after every contribution,
+//! so both adding and removing the last one take constant time and nothing is recomputed on
+//! backtracking. Search can also remember the number of contributions at a choice point and
+//! restore it later with `truncate`.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use inter::Interval;
+//! use inter::accumulator::Intersection;
+//!
+//! let mut domain = Intersection::new();
+//! domain.push(Interval::with_range(0., 10.));
+//!
+//! let choice = domain.len();
+//! domain.push(Interval::with_range(2., 20.));
+//! domain.push(Interval::with_range(-5., 3.));
+//! assert_eq!(domain.value(), Interval::with_range(2., 3.));
+//!
+//! domain.truncate(choice);
+//! assert_eq!(domain.value(), Interval::with_range(0., 10.));
+//! ```
+
+use num::{Bounded, Float};
+
+use interval::Interval;
+use rounding::RoundedArith;
+
+macro_rules! accumulator {
+    ($name:ident, $doc:expr, $combine:ident, $identity:ident) => {
+        #[doc = $doc]
+        #[derive(Clone, Debug, Default, PartialEq)]
+        pub struct $name<T> {
+            // Pairs of contribution and result after it
+            stack: Vec<(Interval<T>, Interval<T>)>,
+        }
+
+        impl<T> $name<T>
+            where T: Float + Bounded + RoundedArith
+        {
+            /// Accumulator without contributions
+            pub fn new() -> Self {
+                $name { stack: Vec::new() }
+            }
+
+            /// Combine the result with `x`
+            pub fn push(&mut self, x: Interval<T>) {
+                let value = self.value().$combine(&x);
+                self.stack.push((x, value));
+            }
+
+            /// Take back the last contribution and return it
+            pub fn pop(&mut self) -> Option<Interval<T>> {
+                self.stack.pop().map(|(x, _)| x)
+            }
+
+            /// Take back all but first `len` contributions
+            pub fn truncate(&mut self, len: usize) {
+                self.stack.truncate(len);
+            }
+
+            /// Result of combining all contributions
+            pub fn value(&self) -> Interval<T> {
+                self.stack.last().map_or_else(Interval::$identity, |&(_, value)| value)
+            }
+
+            /// Number of contributions
+            pub fn len(&self) -> usize {
+                self.stack.len()
+            }
+
+            /// Check if there are no contributions
+            pub fn is_empty(&self) -> bool {
+                self.stack.is_empty()
+            }
+        }
+
+        impl<T> Extend<Interval<T>> for $name<T>
+            where T: Float + Bounded + RoundedArith
+        {
+            fn extend<I: IntoIterator<Item = Interval<T>>>(&mut self, iter: I) {
+                for x in iter {
+                    self.push(x);
+                }
+            }
+        }
+    }
+}
+
+accumulator!(Hull,
+             "Convex hull of intervals, which is empty without contributions",
+             hull,
+             empty);
+accumulator!(Intersection,
+             "Intersection of intervals, which is the whole line without contributions",
+             intersect,
+             entire);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn undo() {
+        let xs = [Interval::with_range(1., 2.),
+                  Interval::with_range(-1., 1.5),
+                  Interval::with_range(0., 5.),
+                  Interval::empty()];
+        let (mut hull, mut intersection) = (Hull::new(), Intersection::new());
+        assert!(hull.value().is_empty() && hull.is_empty());
+        assert_eq!(intersection.value(), Interval::entire());
+
+        let mut values = Vec::new();
+        for x in &xs {
+            values.push((hull.value(), intersection.value()));
+            hull.push(*x);
+            intersection.push(*x);
+        }
+        assert_eq!(hull.value(), Interval::with_range(-1., 5.));
+        assert!(intersection.value().is_empty());
+        assert_eq!(hull.len(), 4);
+
+        // Backtracking restores every earlier value
+        for x in xs.iter().rev() {
+            assert_eq!((hull.pop(), intersection.pop()), (Some(*x), Some(*x)));
+            assert_eq!((hull.value(), intersection.value()), values.pop().unwrap());
+        }
+        assert_eq!(hull.pop(), None);
+
+        intersection.extend(xs.iter().cloned());
+        intersection.truncate(2);
+        assert_eq!(intersection.value(), Interval::with_range(1., 1.5));
+        intersection.truncate(5);
+        assert_eq!(intersection.len(), 2);
+    }
+}
@@ -0,0 +1,175 @@
+//! Enclosures of activation functions for bounding outputs of neural networks.
+//!
+//! Combined with `affine` these propagate a box of inputs through a feed-forward network layer
+//! by layer, which is what interval bound propagation verifiers do.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use inter::Interval;
+//! use inter::activation::{affine, relu};
+//!
+//! let input = [Interval::with_range(-1., 1.), Interval::with_range(0., 2.)];
+//! let hidden = affine(&[[1., 1.], [1., -1.]], &[0., 1.], &input);
+//! let output: Vec<_> = hidden.iter().map(|&x| relu(x)).collect();
+//!
+//! assert_eq!(output, vec![Interval::with_range(0., 3.), Interval::with_range(0., 2.)]);
+//! ```
+
+use num::Float;
+
+use interval::{Interval, LIBM_ULPS, widen};
+use interval_box::IntervalBox;
+use rounding::RoundedArith;
+use utils::{partial_min, partial_max};
+
+/// Rectified linear unit `max(x, 0)`
+pub fn relu<T>(x: Interval<T>) -> Interval<T>
+    where T: Float
+{
+    Interval {
+        start: partial_max(x.start, T::zero()),
+        end: partial_max(x.end, T::zero()),
+    }
+}
+
+/// Leaky rectified linear unit, `x` for positive values and `slope * x` otherwise
+///
+/// # Panics
+///
+/// This will panic if `slope` is not in `[0, 1]`.
+pub fn leaky_relu<T>(x: Interval<T>, slope: T) -> Interval<T>
+    where T: Float + RoundedArith
+{
+    assert!(slope >= T::zero() && slope <= T::one());
+
+    let zero = T::zero();
+
+    Interval {
+        start: if x.start < zero { x.start.mul_down(slope) } else { x.start },
+        end: if x.end < zero { x.end.mul_up(slope) } else { x.end },
+    }
+}
+
+/// Logistic function `1 / (1 + e⁻ˣ)`
+///
+/// ## Example
+///
+/// ```rust
+/// use inter::Interval;
+/// use inter::activation::sigmoid;
+///
+/// let y = sigmoid(Interval::with_range(-1000., 0.));
+///
+/// assert!(y.contains(0.) && y.contains(0.5));
+/// assert!(!y.contains(0.51));
+/// ```
+pub fn sigmoid<T>(x: Interval<T>) -> Interval<T>
+    where T: Float + RoundedArith
+{
+    let one = Interval::exact(T::one());
+    // `x` occurs once, so interval expression is as tight as rounding allows
+    let y = one / (one + (-x).exp());
+
+    Interval {
+        start: partial_max(y.start, T::zero()),
+        end: partial_min(y.end, T::one()),
+    }
+}
+
+/// Smooth approximation of ReLU, `ln(1 + eˣ)`
+///
+/// ## Example
+///
+/// ```rust
+/// use inter::Interval;
+/// use inter::activation::softplus;
+///
+/// let y = softplus(Interval::with_range(-800., 800.));
+///
+/// assert!(y.contains(0.) && y.contains(800.));
+/// assert!(!y.contains(800.001));
+/// ```
+pub fn softplus<T>(x: Interval<T>) -> Interval<T>
+    where T: Float + RoundedArith
+{
+    // `max(t, 0) + ln(1 + e^-|t|)` does not overflow; every step adds relative error of at
+    // most `LIBM_ULPS` and sum of non-negative values adds one rounding
+    let f = |t: T| partial_max(t, T::zero()) + (-t.abs()).exp().ln_1p();
+    let ulps = 2 * LIBM_ULPS + 1;
+
+    Interval {
+        start: partial_max(widen(f(x.start), ulps).0, T::zero()),
+        end: widen(f(x.end), ulps).1,
+    }
+}
+
+/// Affine layer `W x + b` where `weights` are rows of `W`
+///
+/// # Panics
+///
+/// This will panic if number of rows differs from length of `bias` or any row has different
+/// length than `x`.
+pub fn affine<T, R>(weights: &[R], bias: &[T], x: &[Interval<T>]) -> IntervalBox<T>
+    where T: Float + RoundedArith,
+          R: AsRef<[T]>
+{
+    assert_eq!(weights.len(), bias.len());
+
+    weights.iter()
+        .zip(bias)
+        .map(|(row, &b)| {
+            let row = row.as_ref();
+            assert_eq!(row.len(), x.len());
+
+            row.iter()
+                .zip(x)
+                .fold(Interval::exact(b), |acc, (&w, &xi)| acc + Interval::exact(w) * xi)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn i(start: f64, end: f64) -> Interval<f64> {
+        Interval::with_range(start, end)
+    }
+
+    #[test]
+    fn piecewise_linear() {
+        assert_eq!(relu(i(-2., -1.)), Interval::exact(0.));
+        assert_eq!(relu(i(-2., 3.)), i(0., 3.));
+        assert_eq!(leaky_relu(i(-2., 3.), 0.25), i(-0.5, 3.));
+        assert_eq!(leaky_relu(i(-2., -1.), 0.5), i(-1., -0.5));
+        assert_eq!(leaky_relu(i(1., 2.), 0.), i(1., 2.));
+    }
+
+    #[test]
+    fn smooth() {
+        let sigmoid_of = |t: f64| 1. / (1. + (-t).exp());
+        let softplus_of = |t: f64| t.exp().ln_1p();
+
+        for &(a, b) in &[(-3., -1.), (-0.5, 0.5), (1., 20.), (-40., 40.)] {
+            let (s, p) = (sigmoid(i(a, b)), softplus(i(a, b)));
+            for k in 0..=16 {
+                let t = a + (b - a) * k as f64 / 16.;
+                assert!(s.contains(sigmoid_of(t)), "sigmoid({}) not in {}", t, s);
+                assert!(p.contains(softplus_of(t)), "softplus({}) not in {}", t, p);
+            }
+            assert!(s.width() <= sigmoid_of(b) - sigmoid_of(a) + 1e-15);
+            assert!(p.width() <= (softplus_of(b) - softplus_of(a)) * (1. + 1e-14));
+        }
+
+        assert_eq!(sigmoid(Interval::<f64>::entire()), i(0., 1.));
+        assert!(softplus(Interval::exact(1e300)).contains(1e300));
+    }
+
+    #[test]
+    fn layer() {
+        let y = affine(&[vec![0.5, -2.], vec![0., 1.]], &[1., -1.], &[i(0., 2.), i(-1., 1.)]);
+
+        assert_eq!(y.into_inner(), vec![i(-1., 4.), i(-2., 0.)]);
+    }
+}
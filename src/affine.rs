@@ -0,0 +1,285 @@
+//! Affine arithmetic.
+//!
+//! Affine form `x₀ + Σ xᵢ εᵢ ± e` describes a quantity depending linearly on noise symbols
+//! `εᵢ ∈ [-1, 1]`. Forms sharing a symbol are correlated, so `x - x` is exactly zero and
+//! `x * (2 - x)` is not overestimated as much as in interval arithmetic. Rounding errors and
+//! nonlinear terms of products go to the error `e`, which is not shared with any other form.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use inter::Interval;
+//! use inter::affine::Affine;
+//!
+//! let i = Interval::with_range(0., 2.);
+//! let x = Affine::from_interval(i);
+//!
+//! assert_eq!((x.clone() - x.clone()).to_interval(), Interval::exact(0.));
+//! assert_eq!((x.clone() * (Affine::constant(2.) - x)).to_interval(),
+//!            Interval::with_range(0., 2.));
+//! assert_eq!(i * (Interval::exact(2.) - i), Interval::with_range(0., 4.));
+//! ```
+
+use std::ops::{Add, Sub, Mul, Neg};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use num::Float;
+
+use interval::Interval;
+use rounding::RoundedArith;
+use utils::partial_max;
+
+static SYMBOLS: AtomicUsize = AtomicUsize::new(0);
+
+/// Noise symbol not used by any form created so far
+pub(crate) fn fresh_symbol() -> usize {
+    SYMBOLS.fetch_add(1, Ordering::Relaxed)
+}
+
+/// Affine form `center + Σ coefficientᵢ εᵢ ± error`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Affine<T> {
+    center: T,
+    /// Coefficients sorted by noise symbol, without zeros
+    terms: Vec<(usize, T)>,
+    error: T,
+}
+
+impl<T> Affine<T>
+    where T: Float + RoundedArith
+{
+    /// Form exactly representing `value`
+    pub fn constant(value: T) -> Self {
+        Affine {
+            center: value,
+            terms: Vec::new(),
+            error: T::zero(),
+        }
+    }
+
+    /// Form ranging over interval `x`, depending on a new noise symbol
+    ///
+    /// Unbounded intervals are represented by infinite error, so they are not correlated with
+    /// anything.
+    ///
+    /// # Panics
+    ///
+    /// This will panic if `x` is empty.
+    pub fn from_interval(x: Interval<T>) -> Self {
+        assert!(!x.is_empty());
+
+        let center = x.mid();
+        let radius = partial_max(x.end.sub_up(center), center.sub_up(x.start));
+        let mut ret = Affine::constant(center);
+        if !radius.is_finite() {
+            ret.error = T::infinity();
+        } else if radius > T::zero() {
+            ret.terms.push((fresh_symbol(), radius));
+        }
+
+        ret
+    }
+
+    /// Central value
+    pub fn center(&self) -> T {
+        self.center
+    }
+
+    /// Upper bound of the distance from center, `Σ |coefficientᵢ| + error`
+    pub fn radius(&self) -> T {
+        self.terms.iter().fold(self.error, |acc, &(_, x)| acc.add_up(x.abs()))
+    }
+
+    /// Interval containing every value of the form
+    pub fn to_interval(&self) -> Interval<T> {
+        let radius = self.radius();
+        let (start, end) = (self.center.sub_down(radius), self.center.add_up(radius));
+
+        if start.is_nan() || end.is_nan() {
+            Interval::entire()
+        } else {
+            Interval { start, end }
+        }
+    }
+
+    /// Add or subtract `other`, depending on `negate`
+    fn combine(self, other: Self, negate: bool) -> Self {
+        let sign = |x: T| if negate { -x } else { x };
+        let mut error = self.error.add_up(other.error);
+        let mut sum = |a: T, b: T| {
+            let (value, err) = rounded(a.add_down(sign(b)), a.add_up(sign(b)));
+            error = error.add_up(err);
+            value
+        };
+
+        let center = sum(self.center, other.center);
+        let terms = merge(&self.terms, &other.terms, sum);
+
+        Affine {
+            center,
+            terms,
+            error,
+        }
+    }
+}
+
+/// Value and error of the result with exact value between `down` and `up`
+fn rounded<T: RoundedArith>(down: T, up: T) -> (T, T) {
+    (down, up.sub_up(down))
+}
+
+/// Product rounded downward and upward, zero if either factor is zero
+fn mul<T: Float + RoundedArith>(a: T, b: T) -> (T, T) {
+    if a == T::zero() || b == T::zero() {
+        (T::zero(), T::zero())
+    } else {
+        (a.mul_down(b), a.mul_up(b))
+    }
+}
+
+/// Apply `f` to coefficients of the same symbols, missing coefficients are zero
+fn merge<T, F>(a: &[(usize, T)], b: &[(usize, T)], mut f: F) -> Vec<(usize, T)>
+    where T: Float,
+          F: FnMut(T, T) -> T
+{
+    let mut ret = Vec::with_capacity(a.len() + b.len());
+    let (mut i, mut j) = (0, 0);
+
+    while i < a.len() || j < b.len() {
+        let (symbol, x, y) = match (a.get(i), b.get(j)) {
+            (Some(&(s, x)), Some(&(t, _))) if s < t => {
+                i += 1;
+                (s, x, T::zero())
+            }
+            (Some(&(s, x)), Some(&(t, y))) if s == t => {
+                i += 1;
+                j += 1;
+                (s, x, y)
+            }
+            (_, Some(&(t, y))) => {
+                j += 1;
+                (t, T::zero(), y)
+            }
+            (Some(&(s, x)), None) => {
+                i += 1;
+                (s, x, T::zero())
+            }
+            (None, None) => unreachable!(),
+        };
+
+        let value = f(x, y);
+        if value != T::zero() {
+            ret.push((symbol, value));
+        }
+    }
+
+    ret
+}
+
+impl<T> Add for Affine<T>
+    where T: Float + RoundedArith
+{
+    type Output = Affine<T>;
+
+    fn add(self, other: Self) -> Self {
+        self.combine(other, false)
+    }
+}
+
+impl<T> Sub for Affine<T>
+    where T: Float + RoundedArith
+{
+    type Output = Affine<T>;
+
+    fn sub(self, other: Self) -> Self {
+        self.combine(other, true)
+    }
+}
+
+impl<T> Mul for Affine<T>
+    where T: Float + RoundedArith
+{
+    type Output = Affine<T>;
+
+    /// Multiply forms, bounding the quadratic term by product of radii
+    fn mul(self, other: Self) -> Self {
+        let (a, b) = (self.center, other.center);
+        let mut error = mul(self.radius(), other.radius()).1
+            .add_up(mul(a.abs(), other.error).1)
+            .add_up(mul(b.abs(), self.error).1);
+
+        let (down, up) = mul(a, b);
+        let (center, err) = rounded(down, up);
+        error = error.add_up(err);
+
+        let terms = merge(&self.terms, &other.terms, |x, y| {
+            let (p, q) = (mul(a, y), mul(b, x));
+            let (value, err) = rounded(p.0.add_down(q.0), p.1.add_up(q.1));
+            error = error.add_up(err);
+            value
+        });
+
+        Affine {
+            center,
+            terms,
+            error,
+        }
+    }
+}
+
+impl<T> Neg for Affine<T>
+    where T: Float
+{
+    type Output = Affine<T>;
+
+    fn neg(self) -> Self {
+        Affine {
+            center: -self.center,
+            terms: self.terms.into_iter().map(|(symbol, x)| (symbol, -x)).collect(),
+            error: self.error,
+        }
+    }
+}
+
+impl<T> From<T> for Affine<T>
+    where T: Float + RoundedArith
+{
+    fn from(value: T) -> Self {
+        Affine::constant(value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn correlation() {
+        let x = Affine::from_interval(Interval::with_range(1., 3.));
+        let y = Affine::from_interval(Interval::with_range(1., 3.));
+
+        assert_eq!((x.clone() + x.clone() - x.clone()).to_interval(), Interval::with_range(1., 3.));
+        assert_eq!((x.clone() - y.clone()).to_interval(), Interval::with_range(-2., 2.));
+        assert_eq!((-(x.clone() * y)).to_interval(), Interval::with_range(-9., 1.));
+        assert_eq!((x.clone() * x).to_interval(), Interval::with_range(-1., 9.));
+    }
+
+    #[test]
+    fn rounding() {
+        let x = Affine::from_interval(Interval::with_range(0.1, 0.3));
+        let sum = (0..10).fold(Affine::constant(0.), |acc, _| acc + x.clone());
+
+        // Exact bounds of the sum are `10 * 0.1` and `10 * 0.3` of the nearest `f64`
+        assert!(sum.to_interval().contains_all(&[1f64.next_up(), 3f64.next_down()]));
+        assert!(sum.to_interval().width() < 2. + 1e-12);
+    }
+
+    #[test]
+    fn unbounded() {
+        let x = Affine::from_interval(Interval::with_range(0., f64::INFINITY));
+
+        assert_eq!(x.to_interval(), Interval::entire());
+        assert_eq!((x.clone() * Affine::constant(0.)).to_interval(), Interval::exact(0.));
+        assert_eq!((x.clone() - x).to_interval(), Interval::entire());
+    }
+}
@@ -0,0 +1,150 @@
+//! Elementwise operations on slices of intervals.
+//!
+//! Operators on intervals round every bound in the right direction, which either switches
+//! rounding mode of the FPU twice per operation (with `fesetround`) or runs error-free
+//! transformations to correct the result by one ulp. Functions of this module instead compute
+//! bounds with the default rounding and move every one of them one ulp outward, which is always
+//! enough for a single correctly rounded operation. Loops are then plain floating point code
+//! without mode switches that compilers can vectorize, at the price of results being up to one
+//! ulp wider than those of operators, even when they are exact.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use inter::Interval;
+//! use inter::batch;
+//!
+//! let signal: Vec<_> = (0..8).map(|i| Interval::with_epsilon(i as f64, 0.25)).collect();
+//! let gain = batch::scale(&signal, Interval::with_range(0.9, 1.1));
+//! let total = batch::add_slices(&signal, &gain);
+//!
+//! assert!(total[3].contains(3. + 3.) && total[3].contains(2.75 + 2.75 * 0.9));
+//! assert!(batch::dot(&signal, &signal).contains(140.));
+//! ```
+
+use interval::Interval;
+
+/// Elementwise sums
+///
+/// # Panics
+///
+/// This will panic if slices have different lengths.
+pub fn add_slices(a: &[Interval<f64>], b: &[Interval<f64>]) -> Vec<Interval<f64>> {
+    zip(a, b, |x, y| widen(x.start + y.start, x.end + y.end))
+}
+
+/// Elementwise differences
+///
+/// # Panics
+///
+/// This will panic if slices have different lengths.
+pub fn sub_slices(a: &[Interval<f64>], b: &[Interval<f64>]) -> Vec<Interval<f64>> {
+    zip(a, b, |x, y| widen(x.start - y.end, x.end - y.start))
+}
+
+/// Elementwise products
+///
+/// # Panics
+///
+/// This will panic if slices have different lengths.
+pub fn mul_slices(a: &[Interval<f64>], b: &[Interval<f64>]) -> Vec<Interval<f64>> {
+    zip(a, b, mul)
+}
+
+/// Every interval of `a` multiplied by `c`
+pub fn scale(a: &[Interval<f64>], c: Interval<f64>) -> Vec<Interval<f64>> {
+    a.iter().map(|&x| if x.is_empty() { x } else { mul(x, c) }).collect()
+}
+
+/// Dot product, with products widened by an ulp and summed with directed rounding
+///
+/// # Panics
+///
+/// This will panic if slices have different lengths.
+pub fn dot(a: &[Interval<f64>], b: &[Interval<f64>]) -> Interval<f64> {
+    mul_slices(a, b).into_iter().sum()
+}
+
+fn zip<F>(a: &[Interval<f64>], b: &[Interval<f64>], op: F) -> Vec<Interval<f64>>
+    where F: Fn(Interval<f64>, Interval<f64>) -> Interval<f64>
+{
+    assert_eq!(a.len(), b.len(), "slices have different lengths");
+
+    a.iter()
+        .zip(b)
+        .map(|(&x, &y)| if x.is_empty() || y.is_empty() { Interval::empty() } else { op(x, y) })
+        .collect()
+}
+
+/// Product of non-empty intervals
+fn mul(x: Interval<f64>, y: Interval<f64>) -> Interval<f64> {
+    // Zero times infinity needs care of the operator
+    if !(x.is_bounded() && y.is_bounded()) {
+        return x * y;
+    }
+
+    let (a, b, c, d) = (x.start * y.start, x.start * y.end, x.end * y.start, x.end * y.end);
+
+    widen(a.min(b).min(c.min(d)), a.max(b).max(c.max(d)))
+}
+
+/// Interval from bounds rounded to nearest, moved one ulp outward
+fn widen(start: f64, end: f64) -> Interval<f64> {
+    Interval {
+        start: start.next_down(),
+        end: end.next_up(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn samples() -> Vec<Interval<f64>> {
+        let mut samples: Vec<_> = (0..20)
+            .map(|i| {
+                let t = i as f64 * 0.37 - 3.;
+                Interval::with_range(t, t + (i % 4) as f64 * 0.1)
+            })
+            .collect();
+        samples.extend_from_slice(&[Interval::exact(0.),
+                                    Interval::with_range(0., f64::INFINITY),
+                                    Interval::entire(),
+                                    Interval::exact(f64::MAX),
+                                    Interval::exact(1e-310),
+                                    Interval::empty()]);
+        samples
+    }
+
+    #[test]
+    fn enclose_operators() {
+        let a = samples();
+        let mut b = a.clone();
+        b.reverse();
+
+        let results = [(add_slices(&a, &b), a.iter().zip(&b).map(|(&x, &y)| x + y).collect()),
+                       (sub_slices(&a, &b), a.iter().zip(&b).map(|(&x, &y)| x - y).collect()),
+                       (mul_slices(&a, &b), a.iter().zip(&b).map(|(&x, &y)| x * y).collect()),
+                       (scale(&a, b[3]), a.iter().map(|&x| x * b[3]).collect::<Vec<_>>())];
+        for (batch, exact) in &results {
+            for (x, y) in batch.iter().zip(exact) {
+                assert_eq!(x.is_empty(), y.is_empty());
+                assert!(x.superset(y), "{:?} {:?}", x, y);
+                if y.is_bounded() && !y.is_empty() {
+                    assert!(x.inf().next_up() >= y.inf() && x.sup().next_down() <= y.sup());
+                }
+            }
+        }
+
+        assert_eq!(mul_slices(&[Interval::exact(0.)], &[Interval::entire()]),
+                   vec![Interval::exact(0.)]);
+        let values: Vec<_> = (1..=4).map(|i| Interval::exact(i as f64 / 10.)).collect();
+        assert!(dot(&values, &values).contains(0.3) && dot(&[], &[]) == Interval::exact(0.));
+    }
+
+    #[test]
+    #[should_panic(expected = "slices have different lengths")]
+    fn lengths() {
+        add_slices(&[Interval::exact(1.)], &[]);
+    }
+}
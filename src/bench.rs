@@ -0,0 +1,160 @@
+//! Standard workloads for comparing interval arithmetic backends.
+//!
+//! Every workload returns a `Report` with the width of the computed enclosure and the time it took,
+//! so changes to the rounding backend can be compared on the same hardware.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use inter::bench;
+//!
+//! for report in bench::run_all() {
+//!     println!("{}", report);
+//! }
+//! ```
+
+use std::fmt;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use interval::Interval;
+use optimize::{self, Config};
+use testfns::{self, TestFunction};
+
+/// Result of a single workload
+#[derive(Clone, Debug)]
+pub struct Report {
+    /// Name of the workload
+    pub name: &'static str,
+    /// How many times the workload was repeated
+    pub iterations: usize,
+    /// Width of the enclosure computed by the workload
+    pub width: f64,
+    /// Total time of all iterations
+    pub elapsed: Duration,
+}
+
+impl Report {
+    /// Average time of a single iteration
+    pub fn per_iteration(&self) -> Duration {
+        self.elapsed.div_f64(self.iterations as f64)
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt,
+               "{:20} width = {:e}, time = {:?} ({:?}/iter)",
+               self.name,
+               self.width,
+               self.elapsed,
+               self.per_iteration())
+    }
+}
+
+/// Run `workload` `iterations` times and report the width of the last result
+///
+/// # Panics
+///
+/// This will panic if `iterations` is zero.
+pub fn measure<F>(name: &'static str, iterations: usize, mut workload: F) -> Report
+    where F: FnMut() -> Interval<f64>
+{
+    assert!(iterations > 0);
+
+    let now = Instant::now();
+    let mut result = black_box(workload());
+    for _ in 1..iterations {
+        result = black_box(workload());
+    }
+    let elapsed = now.elapsed();
+
+    Report {
+        name,
+        iterations,
+        width: result.width(),
+        elapsed,
+    }
+}
+
+/// Dot product of two vectors of length `n` with slightly uncertain entries
+pub fn dot_product(n: usize, iterations: usize) -> Report {
+    let xs: Vec<_> = (0..n)
+        .map(|i| Interval::with_epsilon(1. / (i + 1) as f64, 1e-12))
+        .collect();
+    let ys: Vec<_> = (0..n)
+        .map(|i| {
+            let sign = if i % 2 == 0 { 1. } else { -1. };
+            Interval::with_epsilon(sign * (i + 1) as f64 / n as f64, 1e-12)
+        })
+        .collect();
+
+    measure("dot product", iterations, || {
+        xs.iter().zip(&ys).fold(Interval::exact(0.), |acc, (&x, &y)| acc + x * y)
+    })
+}
+
+/// Horner evaluation of alternating harmonic polynomial of given `degree` over `[0.4, 0.6]`
+pub fn horner(degree: usize, iterations: usize) -> Report {
+    let coeffs: Vec<_> = (0..degree + 1)
+        .map(|k| {
+            let sign = if k % 2 == 0 { 1. } else { -1. };
+            sign / (k + 1) as f64
+        })
+        .collect();
+    let x = Interval::with_range(0.4, 0.6);
+
+    measure("horner", iterations, || x.eval_poly(&coeffs))
+}
+
+/// Branch-and-bound enclosure of the global minimum of standard test `function`
+///
+/// Boxes are bisected down to width `1e-4`, but at most `10_000` times, so every test function
+/// takes comparable time.
+pub fn branch_and_bound(function: &TestFunction, iterations: usize) -> Report {
+    let domain = function.domain();
+    let config = Config {
+        tolerance: 1e-4,
+        max_boxes: 10_000,
+        ..Config::default()
+    };
+
+    measure(function.name(), iterations, || {
+        optimize::minimize(|x| function.eval(x), &domain, &config).value
+    })
+}
+
+/// Run all workloads with default sizes, with branch-and-bound over every function of `testfns`
+pub fn run_all() -> Vec<Report> {
+    let mut reports = vec![dot_product(1_000, 100), horner(50, 1_000)];
+    reports.extend(testfns::all().iter().map(|function| branch_and_bound(function, 1)));
+
+    reports
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn dot_product_width() {
+        let report = dot_product(10, 1);
+        assert_eq!(report.iterations, 1);
+        assert!(report.width > 0. && report.width < 1e-9);
+    }
+
+    #[test]
+    fn horner_width() {
+        let report = horner(10, 2);
+        assert!(report.width >= 0. && report.width < 1.);
+    }
+
+    #[test]
+    fn branch_and_bound_width() {
+        for function in &[testfns::SPHERE, testfns::BOOTH, testfns::MATYAS] {
+            let report = branch_and_bound(function, 2);
+            assert_eq!((report.name, report.iterations), (function.name(), 2));
+            assert!(report.width < 1e-2, "{}", report);
+        }
+    }
+}
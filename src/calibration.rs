@@ -0,0 +1,139 @@
+//! Piecewise linear calibration tables.
+//!
+//! Sensor conversions are often given as a table of raw readings and the physical values they
+//! correspond to, with linear interpolation in between. `Calibration` borrows such a table, can
+//! be built in `const` context, and maps intervals of readings to enclosures of the interpolated
+//! values, rounded outward. It needs neither allocation nor anything else beyond `core`, so
+//! firmware can keep the table in a `static` and carry certified bounds through the conversion.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use inter::Interval;
+//! use inter::calibration::Calibration;
+//!
+//! // ADC counts to degrees Celsius
+//! static THERMISTOR: Calibration = Calibration::new(&[(0., -40.), (512., 25.), (1024., 125.)]);
+//!
+//! let reading = Interval::with_range(500., 530.);
+//! let temperature = THERMISTOR.map(reading);
+//!
+//! assert!(temperature.contains(25.) && temperature.contains(25. + 18. * 100. / 512.));
+//! assert!(temperature.contains(25. - 12. * 65. / 512.));
+//! ```
+
+use interval::Interval;
+
+/// Table of points `(input, output)` with strictly increasing inputs
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Calibration<'a> {
+    points: &'a [(f64, f64)],
+}
+
+impl<'a> Calibration<'a> {
+    /// Table interpolating between `points`
+    ///
+    /// # Panics
+    ///
+    /// This will panic (or fail to compile in `const` context) if there are less than two
+    /// points, some coordinate is not finite, or inputs are not strictly increasing.
+    pub const fn new(points: &'a [(f64, f64)]) -> Self {
+        assert!(points.len() >= 2, "calibration table needs at least two points");
+
+        let mut i = 0;
+        while i < points.len() {
+            let (x, y) = points[i];
+            assert!(x.is_finite() && y.is_finite(), "calibration point is not finite");
+            assert!(i == 0 || points[i - 1].0 < x, "calibration inputs are not increasing");
+            i += 1;
+        }
+
+        Calibration { points }
+    }
+
+    /// Points of the table
+    pub fn points(&self) -> &'a [(f64, f64)] {
+        self.points
+    }
+
+    /// Inputs covered by the table
+    pub fn domain(&self) -> Interval<f64> {
+        Interval::with_range(self.points[0].0, self.points[self.points.len() - 1].0)
+    }
+
+    /// Enclosure of interpolated outputs of all inputs in `x`
+    ///
+    /// Table does not extrapolate, so inputs outside of `domain` are ignored, and `x` disjoint
+    /// with it gives empty interval.
+    pub fn map(&self, x: Interval<f64>) -> Interval<f64> {
+        let x = x.intersect(&self.domain());
+        if x.is_empty() {
+            return x;
+        }
+
+        // First segment ending at or after the start of `x`
+        let first = self.points.partition_point(|point| point.0 < x.start).max(1) - 1;
+        let mut result = Interval::empty();
+        for segment in self.points[first..].windows(2) {
+            let ((x0, y0), (x1, y1)) = (segment[0], segment[1]);
+            if x0 > x.end {
+                break;
+            }
+
+            let piece = x.intersect(&Interval::with_range(x0, x1));
+            let slope = (Interval::exact(y1) - Interval::exact(y0)) /
+                        (Interval::exact(x1) - Interval::exact(x0));
+            let value = Interval::exact(y0) + slope * (piece - Interval::exact(x0));
+            result = result.hull(&value);
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const TABLE: Calibration<'static> = Calibration::new(&[(-1., 3.), (0., 0.1), (2., 0.3),
+                                                            (3., -5.)]);
+
+    #[test]
+    fn interpolation() {
+        let f = |x: f64| if x <= 0. {
+            3. - 2.9 * (x + 1.)
+        } else if x <= 2. {
+            0.1 + 0.1 * x
+        } else {
+            0.3 - 5.3 * (x - 2.)
+        };
+
+        for &(a, b) in &[(-1., 3.), (-0.5, 0.5), (0.25, 0.75), (1., 2.5), (2., 2.), (-0.1, 0.)] {
+            let y = TABLE.map(Interval::with_range(a, b));
+            for i in 0..=100 {
+                let x = a + (b - a) * i as f64 / 100.;
+                // Reference is rounded as well
+                assert!(y.inf() <= f(x) + 1e-12 && f(x) - 1e-12 <= y.sup(), "{} {:?}", x, y);
+            }
+            assert!(y.inf() > f(a).min(f(b)).min(0.1) - 1e-12);
+        }
+
+        // Extremes are at endpoints of input or at knots inside of it
+        assert_eq!(TABLE.map(Interval::with_range(-0.5, 2.5)).sup(), 1.55);
+        assert!(TABLE.map(Interval::with_range(1., 2.5)).contains(0.3));
+        assert_eq!(TABLE.map(Interval::exact(2.)), Interval::exact(0.3));
+
+        // Outside the table
+        assert!(TABLE.map(Interval::with_range(4., 5.)).is_empty());
+        assert!(TABLE.map(Interval::empty()).is_empty());
+        assert_eq!(TABLE.map(Interval::entire()), TABLE.map(TABLE.domain()));
+        assert_eq!(TABLE.domain(), Interval::with_range(-1., 3.));
+        assert_eq!(TABLE.points().len(), 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "calibration inputs are not increasing")]
+    fn unsorted() {
+        Calibration::new(&[(0., 0.), (0., 1.)]);
+    }
+}
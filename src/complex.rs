@@ -0,0 +1,199 @@
+//! Rectangular complex intervals.
+//!
+//! Complex interval is a rectangle in the complex plane, given by intervals of real and
+//! imaginary parts. Operations enclose results for every pair of complex numbers from their
+//! arguments, which is what verified computation of complex roots of polynomials needs.
+//! Rectangles are not closed under multiplication, so products are the smallest rectangles
+//! containing the exact (rotated) results.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use inter::Interval;
+//! use inter::complex::ComplexInterval;
+//!
+//! let i = ComplexInterval::from((0., 1.));
+//! let z = ComplexInterval::new(Interval::with_range(1., 2.), Interval::exact(0.));
+//!
+//! assert_eq!(i * i, ComplexInterval::from((-1., 0.)));
+//! assert_eq!((z * i).im, Interval::with_range(1., 2.));
+//! assert!(ComplexInterval::from((3., 4.)).abs().contains(5.));
+//! ```
+
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use num::Float;
+
+use interval::Interval;
+use rounding::RoundedArith;
+
+/// Complex interval `re + im i`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ComplexInterval<T> {
+    /// Real part
+    pub re: Interval<T>,
+    /// Imaginary part
+    pub im: Interval<T>,
+}
+
+impl<T> ComplexInterval<T> {
+    /// Complex interval with given real and imaginary parts
+    pub fn new(re: Interval<T>, im: Interval<T>) -> Self {
+        ComplexInterval { re, im }
+    }
+}
+
+impl<T> ComplexInterval<T>
+    where T: Float + RoundedArith
+{
+    /// Check if complex number `re + im i` lies in rectangle
+    pub fn contains(&self, re: T, im: T) -> bool {
+        self.re.contains(re) && self.im.contains(im)
+    }
+
+    /// Check if rectangle contains no numbers
+    pub fn is_empty(&self) -> bool {
+        self.re.is_empty() || self.im.is_empty()
+    }
+
+    /// Complex conjugate `re - im i`
+    pub fn conj(self) -> Self {
+        ComplexInterval::new(self.re, -self.im)
+    }
+
+    /// Squared modulus `re² + im²`
+    pub fn norm_sqr(&self) -> Interval<T> {
+        self.re.sqr() + self.im.sqr()
+    }
+
+    /// Modulus `√(re² + im²)`, which is tight as both parts occur only once
+    pub fn abs(&self) -> Interval<T> {
+        self.norm_sqr().sqrt()
+    }
+}
+
+impl<T> From<(T, T)> for ComplexInterval<T>
+    where T: Float
+{
+    /// Degenerate rectangle of a single number, from real and imaginary part
+    fn from((re, im): (T, T)) -> Self {
+        ComplexInterval::new(Interval::exact(re), Interval::exact(im))
+    }
+}
+
+impl<T> From<Interval<T>> for ComplexInterval<T>
+    where T: Float
+{
+    /// Real interval
+    fn from(re: Interval<T>) -> Self {
+        ComplexInterval::new(re, Interval::exact(T::zero()))
+    }
+}
+
+impl<T> Add for ComplexInterval<T>
+    where T: Float + RoundedArith
+{
+    type Output = ComplexInterval<T>;
+
+    fn add(self, other: Self) -> Self {
+        ComplexInterval::new(self.re + other.re, self.im + other.im)
+    }
+}
+
+impl<T> Sub for ComplexInterval<T>
+    where T: Float + RoundedArith
+{
+    type Output = ComplexInterval<T>;
+
+    fn sub(self, other: Self) -> Self {
+        ComplexInterval::new(self.re - other.re, self.im - other.im)
+    }
+}
+
+impl<T> Mul for ComplexInterval<T>
+    where T: Float + RoundedArith
+{
+    type Output = ComplexInterval<T>;
+
+    fn mul(self, other: Self) -> Self {
+        let (a, b) = (self, other);
+
+        ComplexInterval::new(a.re * b.re - a.im * b.im, a.re * b.im + a.im * b.re)
+    }
+}
+
+impl<T> Div for ComplexInterval<T>
+    where T: Float + RoundedArith
+{
+    type Output = ComplexInterval<T>;
+
+    /// Divide by multiplying with conjugate of denominator
+    ///
+    /// Parts of the result are unbounded when the denominator can be zero.
+    fn div(self, other: Self) -> Self {
+        let numerator = self * other.conj();
+        let denominator = other.norm_sqr();
+
+        ComplexInterval::new(numerator.re / denominator, numerator.im / denominator)
+    }
+}
+
+impl<T> Neg for ComplexInterval<T>
+    where T: Neg<Output = T> + Copy
+{
+    type Output = ComplexInterval<T>;
+
+    fn neg(self) -> Self {
+        ComplexInterval::new(-self.re, -self.im)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn samples(z: ComplexInterval<f64>) -> Vec<(f64, f64)> {
+        let points = |x: Interval<f64>| [x.inf(), x.mid(), x.sup()];
+        let mut samples = Vec::new();
+        for &re in &points(z.re) {
+            for &im in &points(z.im) {
+                samples.push((re, im));
+            }
+        }
+        samples
+    }
+
+    #[test]
+    fn arithmetic() {
+        let a = ComplexInterval::new(Interval::with_range(1., 2.), Interval::with_range(-1., 0.5));
+        let b = ComplexInterval::new(Interval::with_range(-3., -2.), Interval::with_range(1., 2.));
+
+        // Results enclose operations on sampled numbers
+        for (x, y) in samples(a) {
+            for (u, v) in samples(b) {
+                assert!((a + b).contains(x + u, y + v) && (a - b).contains(x - u, y - v));
+                assert!((a * b).contains(x * u - y * v, x * v + y * u));
+                let d = u * u + v * v;
+                assert!((a / b).contains((x * u + y * v) / d, (y * u - x * v) / d));
+                assert!(a.abs().contains(x.hypot(y)));
+            }
+        }
+
+        let i = ComplexInterval::from((0., 1.));
+        assert_eq!(i * i.conj(), ComplexInterval::from((1., 0.)));
+        assert_eq!(ComplexInterval::from((1., 0.)) / i, -i);
+        assert_eq!(-(-a), a);
+        assert_eq!(ComplexInterval::from(Interval::with_range(1., 2.)).im, Interval::exact(0.));
+    }
+
+    #[test]
+    fn division_by_zero() {
+        let one = ComplexInterval::from((1., 0.));
+        let around = ComplexInterval::new(Interval::with_range(-1., 1.), Interval::exact(0.));
+        let quotient = one / around;
+
+        assert!(!quotient.re.is_bounded());
+        assert!(ComplexInterval::from((0., 0.)).abs().contains(0.));
+        assert!(ComplexInterval::new(Interval::empty(), Interval::exact(1.)).is_empty());
+    }
+}
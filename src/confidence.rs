@@ -0,0 +1,209 @@
+//! Intervals from statistical confidence intervals.
+//!
+//! Estimate `x` with standard error `s` at confidence level `p` is reported as `x ± k s`, where
+//! `k` is the two-sided critical value of the sampling distribution. Critical values here are
+//! enclosures: quantiles are computed to far better accuracy than the relative margin they are
+//! widened by, and `k s` is rounded up, so the result contains every value of the statistical
+//! interval. It is then as valid for interval computations as the statistical model itself.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use inter::Interval;
+//! use inter::confidence::Distribution;
+//!
+//! // Mean of 12 samples, 95% confidence
+//! let x = Interval::from_confidence(9.81, 0.02, 0.95, Distribution::StudentT(11));
+//!
+//! assert!(x.contains(9.81 - 2.2009 * 0.02) && x.contains(9.81 + 2.2009 * 0.02));
+//! assert!(x.width() < 2. * 2.201 * 0.02);
+//! ```
+
+use std::f64::consts::FRAC_PI_2;
+
+use interval::Interval;
+
+/// Sampling distribution of standardized estimate
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Distribution {
+    /// Standard normal distribution, for known variance or large samples
+    Normal,
+    /// Student's t distribution with given degrees of freedom
+    StudentT(u32),
+}
+
+impl Distribution {
+    /// Enclosure of `k` such that `|X| ≤ k` with probability `level`
+    ///
+    /// # Panics
+    ///
+    /// This will panic if `level` is not strictly between `0` and `1`, or for Student's
+    /// distribution with no degrees of freedom.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::confidence::Distribution;
+    ///
+    /// assert!(Distribution::Normal.critical_value(0.95).contains(1.959963984540054));
+    /// assert!(Distribution::StudentT(1).critical_value(0.9).contains(6.313751514675043));
+    /// ```
+    pub fn critical_value(self, level: f64) -> Interval<f64> {
+        assert!(level > 0. && level < 1., "invalid confidence level: {}", level);
+
+        let (k, terms) = match self {
+            // Upper tail is exact for levels from 1/2, and keeps the accuracy near 1
+            Distribution::Normal => (-normal_quantile((1. - level) / 2.), 0),
+            Distribution::StudentT(dof) => {
+                assert!(dof > 0, "Student's t distribution needs degrees of freedom");
+                (student_critical(level, dof), dof)
+            }
+        };
+
+        // Rounding errors of the quantiles grow with number of summed terms
+        let margin = 1e-12 * (terms as f64 + 10.);
+        Interval::with_range(k * (1. - margin), k * (1. + margin))
+    }
+}
+
+impl Interval<f64> {
+    /// Interval `estimate ± k standard_error` covering confidence interval at `level`
+    ///
+    /// See `Distribution::critical_value` for `k`.
+    ///
+    /// # Panics
+    ///
+    /// This will panic if `standard_error` is negative, or on invalid `level` or `distribution`.
+    pub fn from_confidence(estimate: f64,
+                           standard_error: f64,
+                           level: f64,
+                           distribution: Distribution)
+                           -> Self {
+        Interval::from_sigma(estimate, standard_error, distribution.critical_value(level).sup())
+    }
+}
+
+/// Quantile of standard normal distribution, by algorithm AS241 of Wichura
+#[allow(clippy::excessive_precision)]
+fn normal_quantile(p: f64) -> f64 {
+    let poly = |coeffs: &[f64], r: f64| coeffs.iter().rev().fold(0., |acc, &c| acc * r + c);
+
+    let q = p - 0.5;
+    if q.abs() <= 0.425 {
+        let r = 0.180625 - q * q;
+        let num = [3.387132872796366608e0, 1.3314166789178437745e2, 1.9715909503065514427e3,
+                   1.3731693765509461125e4, 4.5921953931549871457e4, 6.7265770927008700853e4,
+                   3.3430575583588128105e4, 2.5090809287301226727e3];
+        let den = [1., 4.2313330701600911252e1, 6.8718700749205790830e2, 5.3941960214247511077e3,
+                   2.1213794301586595867e4, 3.9307895800092710610e4, 2.8729085735721942674e4,
+                   5.2264952788528545610e3];
+        return q * poly(&num, r) / poly(&den, r);
+    }
+
+    let r = (-(if q < 0. { p } else { 1. - p }).ln()).sqrt();
+    let x = if r <= 5. {
+        let num = [1.42343711074968357734e0, 4.63033784615654529590e0, 5.76949722146069140550e0,
+                   3.64784832476320460504e0, 1.27045825245236838258e0, 2.41780725177450611770e-1,
+                   2.27238449892691845833e-2, 7.74545014278341407640e-4];
+        let den = [1., 2.05319162663775882187e0, 1.67638483018380384940e0,
+                   6.89767334985100004550e-1, 1.48103976427480074590e-1,
+                   1.51986665636164571966e-2, 5.47593808499534494600e-4,
+                   1.05075007164441684324e-9];
+        poly(&num, r - 1.6) / poly(&den, r - 1.6)
+    } else {
+        let num = [6.65790464350110377720e0, 5.46378491116411436990e0, 1.78482653991729133580e0,
+                   2.96560571828504891230e-1, 2.65321895265761230930e-2,
+                   1.24266094738807843860e-3, 2.71155556874348757815e-5,
+                   2.01033439929228813265e-7];
+        let den = [1., 5.99832206555887937690e-1, 1.36929880922735805310e-1,
+                   1.48753612908506148525e-2, 7.86869131145613259100e-4,
+                   1.84631831751005468180e-5, 1.42151175831644588870e-7,
+                   2.04426310338993978564e-15];
+        poly(&num, r - 5.) / poly(&den, r - 5.)
+    };
+
+    if q < 0. { -x } else { x }
+}
+
+/// `P(|T| ≤ √dof tan θ)` for Student's `T`, from finite series for integer degrees of freedom
+fn student_probability(theta: f64, dof: u32) -> f64 {
+    let (sin, cos) = theta.sin_cos();
+    let cos2 = cos * cos;
+
+    let (mut term, mut sum) = (1., 1.);
+    if dof.is_multiple_of(2) {
+        for k in 1..dof / 2 {
+            term *= cos2 * (2 * k - 1) as f64 / (2 * k) as f64;
+            sum += term;
+        }
+        sin * sum
+    } else if dof == 1 {
+        theta / FRAC_PI_2
+    } else {
+        for k in 1..(dof - 1) / 2 {
+            term *= cos2 * (2 * k) as f64 / (2 * k + 1) as f64;
+            sum += term;
+        }
+        (theta + sin * cos * sum) / FRAC_PI_2
+    }
+}
+
+/// Two-sided critical value of Student's distribution, by bisection over angle
+fn student_critical(level: f64, dof: u32) -> f64 {
+    let (mut low, mut high) = (0f64, FRAC_PI_2);
+    while low.next_up() < high {
+        let mid = low + (high - low) / 2.;
+        if mid <= low || mid >= high {
+            break;
+        }
+        if student_probability(mid, dof) < level {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+
+    (dof as f64).sqrt() * high.tan()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn critical_values() {
+        let normal = [(0.5, 0.6744897501960817), (0.95, 1.959963984540054),
+                      (0.99, 2.5758293035489004), (1. - 1e-9, 6.109410209383451)];
+        for &(level, k) in &normal {
+            let x = Distribution::Normal.critical_value(level);
+            assert!(x.contains(k) && x.rel_width() < 1e-10, "{} {:?}", level, x);
+        }
+
+        // Closed forms for one and two degrees of freedom
+        for &level in &[0.5, 0.9, 0.95, 0.999] {
+            let one = (level * FRAC_PI_2).tan();
+            let two = level * (2. / (1. - level * level)).sqrt();
+            assert!(Distribution::StudentT(1).critical_value(level).contains(one));
+            assert!(Distribution::StudentT(2).critical_value(level).contains(two));
+        }
+        let tables = [(4, 2.7764451051977944), (10, 2.228138851986275), (30, 2.042272456301238)];
+        for &(dof, k) in &tables {
+            let x = Distribution::StudentT(dof).critical_value(0.95);
+            assert!((x.mid() - k).abs() < 1e-12 && x.contains(k), "{} {:?}", dof, x);
+        }
+
+        // Approaches normal distribution
+        let t = Distribution::StudentT(100_000).critical_value(0.95);
+        assert!(t.inf() > 1.959963984540054 && t.sup() < 1.96);
+    }
+
+    #[test]
+    fn from_confidence() {
+        let x = Interval::from_confidence(1., 0.5, 0.95, Distribution::Normal);
+        assert!(x.contains(1. - 0.5 * 1.959963984540054) && !x.contains(1. + 0.5 * 1.96));
+        assert!(Interval::from_confidence(1., 0., 0.99, Distribution::StudentT(4)).is_exact());
+        assert!(::std::panic::catch_unwind(|| Distribution::Normal.critical_value(1.)).is_err());
+        assert!(::std::panic::catch_unwind(|| Distribution::StudentT(0).critical_value(0.5))
+            .is_err());
+    }
+}
@@ -0,0 +1,190 @@
+//! Configuration of interval computations as a single value.
+//!
+//! Strategy of evaluation, semantics of indeterminate forms, policy for arguments outside of
+//! domains and rounding mode of the FPU are all per-thread state, each switched by its own
+//! `execute`. A library calling into this crate should not depend on what its caller put in
+//! effect, nor leak its own choices to it. `InterConfig` bundles all of them, so a library keeps
+//! its configuration as a value, passes it around explicitly and puts it in effect only around
+//! its own computations, with `execute` or with the guard returned by `scoped`. Two libraries
+//! with different configurations then coexist in one process, also on the same thread.
+//!
+//! Arithmetic of bounds is chosen when compiling, by `RoundedArith` of the type and the
+//! `fesetround` feature, or per operation with `rounding::RoundedOps`, so the configuration
+//! holds only the rounding mode the FPU is put in.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use inter::Interval;
+//! use inter::config::InterConfig;
+//! use inter::policy::DomainPolicy;
+//!
+//! let strict = InterConfig {
+//!     policy: DomainPolicy::Entire,
+//!     ..InterConfig::default()
+//! };
+//! let x = Interval::with_range(-1., 4.);
+//!
+//! assert_eq!(strict.execute(|| x.sqrt()), Interval::entire());
+//! assert_eq!(x.sqrt(), Interval::with_range(0., 2.));
+//! ```
+
+use std::marker::PhantomData;
+
+#[cfg(feature = "libc")]
+use rounding::{Rounding, RoundingGuard};
+use policy::DomainPolicy;
+use semantics::Semantics;
+use strategy::Strategy;
+
+/// Settings of interval computations
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InterConfig {
+    /// Method of evaluating operations on `Value`s
+    pub strategy: Strategy,
+    /// Convention for indeterminate forms
+    pub semantics: Semantics,
+    /// Handling of arguments outside of domains of functions
+    pub policy: DomainPolicy,
+    /// Rounding mode of the FPU, or `None` to leave it as it is
+    ///
+    /// Arithmetic of this crate expects `Rounding::ToNearest`, which is the default.
+    #[cfg(feature = "libc")]
+    pub rounding: Option<Rounding>,
+}
+
+// Without `libc` every field has the default value
+#[cfg_attr(not(feature = "libc"), allow(clippy::derivable_impls))]
+impl Default for InterConfig {
+    fn default() -> Self {
+        InterConfig {
+            strategy: Strategy::default(),
+            semantics: Semantics::default(),
+            policy: DomainPolicy::default(),
+            #[cfg(feature = "libc")]
+            rounding: Some(Rounding::ToNearest),
+        }
+    }
+}
+
+impl InterConfig {
+    /// Configuration in effect on current thread
+    pub fn current() -> Self {
+        InterConfig {
+            strategy: Strategy::current(),
+            semantics: Semantics::current(),
+            policy: DomainPolicy::current(),
+            #[cfg(feature = "libc")]
+            rounding: Rounding::current(),
+        }
+    }
+
+    /// Put this configuration in effect until the returned guard is dropped
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::config::InterConfig;
+    /// use inter::strategy::Strategy;
+    ///
+    /// {
+    ///     let _affine = InterConfig { strategy: Strategy::Affine, ..InterConfig::default() }
+    ///         .scoped();
+    ///     assert_eq!(Strategy::current(), Strategy::Affine);
+    /// }
+    /// assert_eq!(Strategy::current(), Strategy::Natural);
+    /// ```
+    pub fn scoped(self) -> ConfigGuard {
+        ConfigGuard {
+            #[cfg(feature = "libc")]
+            _rounding: self.rounding.map(Rounding::scoped),
+            strategy: self.strategy.replace(),
+            semantics: self.semantics.replace(),
+            policy: self.policy.replace(),
+            _thread: PhantomData,
+        }
+    }
+
+    /// Run `func` with this configuration in effect, restoring the previous one afterwards
+    ///
+    /// Configuration is restored also when `func` panics.
+    pub fn execute<R, F: FnOnce() -> R>(self, func: F) -> R {
+        let _guard = self.scoped();
+
+        func()
+    }
+}
+
+/// Guard restoring the previous configuration of the current thread when dropped, created by
+/// `InterConfig::scoped`
+///
+/// Like `RoundingGuard`, it is not `Send`, and nested guards have to be dropped in reverse
+/// order.
+#[must_use = "previous configuration is restored as soon as the guard is dropped"]
+#[derive(Debug)]
+pub struct ConfigGuard {
+    strategy: Strategy,
+    semantics: Semantics,
+    policy: DomainPolicy,
+    // Dropped after the other settings are restored
+    #[cfg(feature = "libc")]
+    _rounding: Option<RoundingGuard>,
+    _thread: PhantomData<*const ()>,
+}
+
+impl Drop for ConfigGuard {
+    fn drop(&mut self) {
+        self.strategy.replace();
+        self.semantics.replace();
+        self.policy.replace();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use interval::Interval;
+
+    #[test]
+    fn scopes() {
+        let outer = InterConfig {
+            semantics: Semantics::Cset,
+            policy: DomainPolicy::Entire,
+            ..InterConfig::default()
+        };
+        let inner = InterConfig {
+            strategy: Strategy::Centered,
+            policy: DomainPolicy::Panic,
+            ..InterConfig::default()
+        };
+        let before = InterConfig::current();
+        assert_eq!(before, InterConfig::default());
+
+        outer.execute(|| {
+            assert_eq!(InterConfig::current(), outer);
+            assert_eq!(Interval::with_range(-1., 0.).ln(), Interval::entire());
+            assert_eq!(inner.execute(InterConfig::current), inner);
+            assert!(::std::panic::catch_unwind(|| inner.execute(|| Interval::exact(-1.).sqrt()))
+                .is_err());
+            assert_eq!(InterConfig::current(), outer);
+        });
+        assert_eq!(InterConfig::current(), before);
+    }
+
+    #[test]
+    #[cfg(feature = "libc")]
+    fn rounding() {
+        let upward = InterConfig {
+            rounding: Some(Rounding::Upward),
+            ..InterConfig::default()
+        };
+        assert_eq!(upward.execute(Rounding::current), Some(Rounding::Upward));
+
+        let keep = InterConfig {
+            rounding: None,
+            ..InterConfig::default()
+        };
+        assert_eq!(upward.execute(|| keep.execute(Rounding::current)), Some(Rounding::Upward));
+        assert_eq!(Rounding::current(), Some(Rounding::ToNearest));
+    }
+}
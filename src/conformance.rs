@@ -0,0 +1,278 @@
+//! Algebraic checks of interval arithmetic over custom endpoint types.
+//!
+//! Any type implementing `RoundedArith` can be an endpoint, like decimals, fixed point numbers
+//! or wrappers of arbitrary precision floats. Rounding in the wrong direction, or differently
+//! for positive and negative values, loses enclosures silently, and `check` finds such bugs by
+//! verifying properties that interval arithmetic has for every correct implementation:
+//!
+//! - inclusion isotonicity: `a ⊆ A` and `b ⊆ B` imply `a ∘ b ⊆ A ∘ B` for `+`, `-`, `*` and
+//!   `abs`, checked for degenerate intervals at endpoints and for samples that are subsets of
+//!   other samples,
+//! - subdistributivity: `a (b + c) ⊆ a b + a c` when `b + c` is exact, and otherwise that
+//!   both sides overlap, as rounding of the sum can widen the left one,
+//! - commutativity of `+` and `*`,
+//! - identities of negation: `-(-a) = a`, `a - b = a + (-b)`, `(-a) b = -(a b)` and
+//!   `|-a| = |a|`,
+//! - `a + 0 = a` and `0 ∈ a - a`.
+//!
+//! Properties are checked for every pair (or triple) of given samples, so a few dozen of them
+//! with various signs and magnitudes, including extremes of the type, are enough. Empty samples
+//! are skipped. For integers, results have to be representable.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use inter::Interval;
+//! use inter::conformance;
+//!
+//! let samples = [Interval::with_range(-3, 2), Interval::with_range(1, 7), Interval::exact(-5)];
+//! let report = conformance::check(&samples);
+//!
+//! assert!(report.checked > 0);
+//! assert!(report.is_ok(), "{}", report);
+//! ```
+
+use std::fmt;
+use std::ops::Neg;
+
+use num::Num;
+
+use interval::Interval;
+use rounding::RoundedArith;
+
+/// How many violations are kept in `Report`
+pub const MAX_VIOLATIONS: usize = 64;
+
+/// Operands for which some property does not hold
+#[derive(Clone, Debug, PartialEq)]
+pub struct Violation<T> {
+    /// Name of the property
+    pub property: &'static str,
+    /// Operands in order of the property
+    pub operands: Vec<Interval<T>>,
+}
+
+/// Result of `check`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Report<T> {
+    /// Number of properties checked
+    pub checked: u64,
+    /// Number of properties that failed
+    pub failed: u64,
+    /// First `MAX_VIOLATIONS` violations
+    pub violations: Vec<Violation<T>>,
+}
+
+impl<T> Report<T> {
+    /// Check if all properties hold
+    pub fn is_ok(&self) -> bool {
+        self.failed == 0
+    }
+
+    fn record(&mut self, property: &'static str, holds: bool, operands: &[Interval<T>])
+        where T: Copy
+    {
+        self.checked += 1;
+        if !holds {
+            self.failed += 1;
+            if self.violations.len() < MAX_VIOLATIONS {
+                self.violations.push(Violation {
+                    property,
+                    operands: operands.to_vec(),
+                });
+            }
+        }
+    }
+}
+
+impl<T> fmt::Display for Report<T>
+    where T: fmt::Debug
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "{} properties checked, {} failed", self.checked, self.failed)?;
+        for violation in &self.violations {
+            write!(fmt, "\n  {} for {:?}", violation.property, violation.operands)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Check properties of interval arithmetic over all combinations of `samples`
+pub fn check<T>(samples: &[Interval<T>]) -> Report<T>
+    where T: RoundedArith + Num + Neg<Output = T>
+{
+    let samples: Vec<_> = samples.iter().cloned().filter(|x| !x.is_empty()).collect();
+    let zero = Interval::exact(T::zero());
+    let mut report = Report {
+        checked: 0,
+        failed: 0,
+        violations: Vec::new(),
+    };
+
+    for &a in &samples {
+        report.record("-(-a) = a", -(-a) == a, &[a]);
+        report.record("|-a| = |a|", (-a).abs() == a.abs(), &[a]);
+        report.record("a + 0 = a", a + zero == a, &[a]);
+        // Dependency problem makes it wider than zero, but it has to contain it
+        #[allow(clippy::eq_op)]
+        report.record("0 ∈ a - a", (a - a).contains(T::zero()), &[a]);
+        for &part in &parts(a, &samples) {
+            report.record("isotone abs", part.abs().subset(&a.abs()), &[part, a]);
+        }
+
+        for &b in &samples {
+            report.record("a + b = b + a", a + b == b + a, &[a, b]);
+            report.record("a b = b a", a * b == b * a, &[a, b]);
+            report.record("a - b = a + (-b)", a - b == a + -b, &[a, b]);
+            report.record("(-a) b = -(a b)", -a * b == -(a * b), &[a, b]);
+
+            let (sum, difference, product) = (a + b, a - b, a * b);
+            for &x in &parts(a, &samples) {
+                for &y in &parts(b, &samples) {
+                    report.record("isotone +", (x + y).subset(&sum), &[x, y, a, b]);
+                    report.record("isotone -", (x - y).subset(&difference), &[x, y, a, b]);
+                    report.record("isotone *", (x * y).subset(&product), &[x, y, a, b]);
+                }
+            }
+
+            for &c in &samples {
+                let (factored, expanded) = (a * (b + c), a * b + a * c);
+                // Rounding of `b + c` can widen the product beyond the expanded form
+                let exact = |x: T, y: T| x.add_down(y) == x.add_up(y);
+                if exact(b.start, c.start) && exact(b.end, c.end) {
+                    report.record("a (b + c) ⊆ a b + a c", factored.subset(&expanded), &[a, b, c]);
+                } else {
+                    let holds = !factored.intersect(&expanded).is_empty();
+                    report.record("a (b + c) ∩ (a b + a c) ≠ ∅", holds, &[a, b, c]);
+                }
+            }
+        }
+    }
+
+    report
+}
+
+/// Subsets of `x`: points at its endpoints and samples contained in it
+fn parts<T>(x: Interval<T>, samples: &[Interval<T>]) -> Vec<Interval<T>>
+    where T: Num + Copy + PartialOrd
+{
+    let mut parts = vec![Interval::exact(x.start), Interval::exact(x.end)];
+    parts.extend(samples.iter().filter(|s| s.subset(&x)));
+
+    parts
+}
+
+#[cfg(test)]
+mod test {
+    use std::ops::{Add, Div, Mul, Rem, Sub};
+
+    use num::{Bounded, One, Zero};
+
+    use super::*;
+
+    fn samples() -> Vec<Interval<f64>> {
+        let bounds = [(-3., 2.), (0.1, 0.3), (1e300, f64::MAX), (-f64::INFINITY, -1.), (0., 0.),
+                      (-0.7, -0.7), (1. / 3., 1.), (5e-324, 1e-300)];
+        let mut samples: Vec<_> = bounds.iter().map(|&(a, b)| Interval::with_range(a, b)).collect();
+        samples.push(Interval::empty());
+        samples
+    }
+
+    #[test]
+    fn builtin_types_conform() {
+        let report = check(&samples());
+        assert!(report.is_ok(), "{}", report);
+
+        let integers = [Interval::with_range(-7i64, 3), Interval::with_range(2, 9),
+                        Interval::exact(0), Interval::with_range(-1000, -999)];
+        assert!(check(&integers).is_ok());
+        assert_eq!(check::<i32>(&[]).checked, 0);
+    }
+
+    /// Float rounding products downward in both directions
+    #[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+    struct Truncated(f64);
+
+    macro_rules! truncated_ops {
+        ($($op:ident $method:ident)*) => {
+            $(
+                impl $op for Truncated {
+                    type Output = Truncated;
+
+                    fn $method(self, other: Self) -> Self {
+                        Truncated(self.0.$method(other.0))
+                    }
+                }
+            )*
+        };
+    }
+
+    truncated_ops!(Add add Sub sub Mul mul Div div Rem rem);
+
+    impl Zero for Truncated {
+        fn zero() -> Self {
+            Truncated(0.)
+        }
+
+        fn is_zero(&self) -> bool {
+            self.0 == 0.
+        }
+    }
+
+    impl One for Truncated {
+        fn one() -> Self {
+            Truncated(1.)
+        }
+    }
+
+    impl Bounded for Truncated {
+        fn min_value() -> Self {
+            Truncated(f64::MIN)
+        }
+
+        fn max_value() -> Self {
+            Truncated(f64::MAX)
+        }
+    }
+
+    impl Num for Truncated {
+        type FromStrRadixErr = <f64 as Num>::FromStrRadixErr;
+
+        fn from_str_radix(s: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+            f64::from_str_radix(s, radix).map(Truncated)
+        }
+    }
+
+    impl Neg for Truncated {
+        type Output = Truncated;
+
+        fn neg(self) -> Self {
+            Truncated(-self.0)
+        }
+    }
+
+    impl RoundedArith for Truncated {
+        fn add_down(self, other: Self) -> Self { Truncated(self.0.add_down(other.0)) }
+        fn add_up(self, other: Self) -> Self { Truncated(self.0.add_up(other.0)) }
+        fn sub_down(self, other: Self) -> Self { Truncated(self.0.sub_down(other.0)) }
+        fn sub_up(self, other: Self) -> Self { Truncated(self.0.sub_up(other.0)) }
+        fn mul_down(self, other: Self) -> Self { Truncated(self.0.mul_down(other.0)) }
+        fn mul_up(self, other: Self) -> Self { Truncated(self.0.mul_down(other.0)) }
+        fn div_down(self, other: Self) -> Self { Truncated(self.0.div_down(other.0)) }
+        fn div_up(self, other: Self) -> Self { Truncated(self.0.div_up(other.0)) }
+    }
+
+    #[test]
+    fn finds_wrong_rounding() {
+        let samples: Vec<_> = samples()
+            .into_iter()
+            .map(|x| Interval { start: Truncated(x.start), end: Truncated(x.end) })
+            .collect();
+        let report = check(&samples);
+
+        assert!(!report.is_ok());
+        assert!(report.violations.iter().any(|v| v.property == "(-a) b = -(a b)"));
+        assert!(report.to_string().contains("failed\n  "));
+    }
+}
@@ -0,0 +1,243 @@
+//! Certified continuation of solution branches.
+//!
+//! Branch of solutions of `f(x, p) = 0` is followed as parameter `p` ranges over an interval.
+//! Parameter range is cut into pieces and for every piece `P` an interval `X` is found, such that
+//! parametric Newton operator `m - f(m, P) / ∂f/∂x(X, P)` lies in the interior of `X`. Then for
+//! every `p` in `P` there is exactly one solution in `X`, so the pieces together enclose the
+//! branch and prove it does not fold or bifurcate there. Pieces shrink near points where the
+//! derivative vanishes and continuation stops before them.
+//!
+//! Solution as a function of parameter can also be enclosed over the whole parameter interval at
+//! once with `implicit`, which is cheaper when the interval is narrow, for example tolerances of
+//! a design.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use inter::Interval;
+//! use inter::continuation;
+//!
+//! // x² = p, followed from x = 1
+//! let f = |x: Interval<f64>, p: Interval<f64>| x * x - p;
+//! let dfdx = |x: Interval<f64>, _| Interval::exact(2.) * x;
+//! let branch = continuation::follow(f, dfdx, 1., Interval::with_range(1., 4.), 100);
+//!
+//! assert_eq!(branch.last().unwrap().parameter.sup(), 4.);
+//! assert!(branch.iter().any(|piece| piece.parameter.contains(2.) &&
+//!                                   piece.solution.contains(2f64.sqrt())));
+//! ```
+
+use interval::Interval;
+
+/// Enclosure of the branch over part of the parameter range
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Piece {
+    /// Range of parameter
+    pub parameter: Interval<f64>,
+    /// Interval containing exactly one solution for every parameter from `parameter`
+    pub solution: Interval<f64>,
+}
+
+/// Follow branch of solutions of `f(x, p) = 0` starting near `x0` at the start of `params`
+///
+/// `dfdx` has to enclose partial derivative of `f` with respect to `x` over boxes it is called
+/// with. Returned pieces are adjacent and begin at the start of `params`. They cover the whole
+/// range unless certification fails (for example near fold of the branch, or when `x0` is not
+/// close to a solution) or `max_pieces` were made, so the end of the last piece tells how far
+/// the branch was followed.
+pub fn follow<F, D>(f: F,
+                    dfdx: D,
+                    x0: f64,
+                    params: Interval<f64>,
+                    max_pieces: usize)
+                    -> Vec<Piece>
+    where F: Fn(Interval<f64>, Interval<f64>) -> Interval<f64>,
+          D: Fn(Interval<f64>, Interval<f64>) -> Interval<f64>
+{
+    let mut pieces = Vec::new();
+    let mut guess = Interval::exact(x0);
+    let mut start = params.start;
+    let mut step = params.width() / 16.;
+
+    while start < params.end && pieces.len() < max_pieces {
+        let end = if start + step < params.end { start + step } else { params.end };
+        if end == start {
+            break;
+        }
+
+        let parameter = Interval::with_range(start, end);
+        match certify(&f, &dfdx, guess, parameter) {
+            Some(solution) => {
+                pieces.push(Piece { parameter, solution });
+                guess = solution;
+                start = end;
+                step *= 2.;
+            }
+            None => step /= 2.,
+        }
+    }
+
+    pieces
+}
+
+/// Enclosure of `x(p)` for all `p` in `params`, where `x(p)` is the solution of `f(x, p) = 0`
+/// near `x0`
+///
+/// `dfdx` has to enclose partial derivative of `f` with respect to `x`. Interval `X` is searched
+/// for, such that Krawczyk operator `m - C f(m, P) + (1 - C ∂f/∂x(X, P)) (X - m)`, with `C`
+/// approximate inverse of derivative at the middle of `X`, lies in its interior. By implicit
+/// function theorem `x(p)` is then well defined, continuous and unique in `X` over all `params`.
+/// Returns `None` when no such interval is found, for example when `params` is too wide or
+/// contains fold.
+///
+/// ## Example
+///
+/// ```rust
+/// use inter::Interval;
+/// use inter::continuation;
+///
+/// // x³ + x = p for p within 10% of 2
+/// let f = |x: Interval<f64>, p: Interval<f64>| x.powi(3) + x - p;
+/// let dfdx = |x: Interval<f64>, _| Interval::exact(3.) * x.powi(2) + Interval::exact(1.);
+/// let x = continuation::implicit(f, dfdx, 1., Interval::with_range(1.8, 2.2)).unwrap();
+///
+/// assert!(x.contains(1.) && x.subset(&Interval::with_range(0.9, 1.1)));
+/// ```
+pub fn implicit<F, D>(f: F, dfdx: D, x0: f64, params: Interval<f64>) -> Option<Interval<f64>>
+    where F: Fn(Interval<f64>, Interval<f64>) -> Interval<f64>,
+          D: Fn(Interval<f64>, Interval<f64>) -> Interval<f64>
+{
+    let (one, p) = (Interval::exact(1.), Interval::exact(params.mid()));
+    let krawczyk = |x: Interval<f64>| {
+        let m = Interval::exact(x.mid());
+        let slope = dfdx(m, p).mid();
+        let c = Interval::exact(1. / slope);
+        let k = m - c * f(m, params) + (one - c * dfdx(x, params)) * (x - m);
+
+        if slope != 0. && k.is_bounded() { Some(k) } else { None }
+    };
+
+    let mut x = Interval::exact(x0);
+    for _ in 0..20 {
+        let delta = 0.1 * x.width() + 1e-12 * x.mag().max(1.);
+        x += Interval::with_range(-delta, delta);
+
+        let k = krawczyk(x)?;
+        if k.interior_of(&x) {
+            let mut solution = k;
+            for _ in 0..5 {
+                if let Some(k) = krawczyk(solution) {
+                    solution = k.intersect(&solution);
+                }
+            }
+            return Some(solution);
+        }
+        x = k;
+    }
+
+    None
+}
+
+/// Find interval mapped into its interior by Newton operator over `parameter`, by iterating the
+/// operator from inflated `guess`
+fn certify<F, D>(f: &F, dfdx: &D, guess: Interval<f64>, parameter: Interval<f64>)
+                 -> Option<Interval<f64>>
+    where F: Fn(Interval<f64>, Interval<f64>) -> Interval<f64>,
+          D: Fn(Interval<f64>, Interval<f64>) -> Interval<f64>
+{
+    let newton = |x: Interval<f64>| {
+        let m = Interval::exact(x.mid());
+        f(m, parameter).checked_div(dfdx(x, parameter)).map(|q| m - q)
+    };
+
+    let mut x = guess;
+    for _ in 0..20 {
+        let delta = 0.1 * x.width() + 1e-12 * x.mag().max(1.);
+        x += Interval::with_range(-delta, delta);
+
+        let n = newton(x)?;
+        if !n.is_bounded() {
+            return None;
+        }
+        if n.interior_of(&x) {
+            // Narrowing keeps the solution, and is still valid enclosure after any iteration
+            let mut solution = n;
+            for _ in 0..5 {
+                match newton(solution) {
+                    Some(n) => solution = n.intersect(&solution),
+                    None => break,
+                }
+            }
+            return Some(solution);
+        }
+        x = n;
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn square(x: Interval<f64>, p: Interval<f64>) -> Interval<f64> {
+        x * x - p
+    }
+
+    fn double(x: Interval<f64>, _: Interval<f64>) -> Interval<f64> {
+        Interval::exact(2.) * x
+    }
+
+    #[test]
+    fn follows_branch() {
+        let branch = follow(square, double, 1., Interval::with_range(1., 4.), 100);
+
+        assert_eq!(branch[0].parameter.inf(), 1.);
+        assert_eq!(branch.last().unwrap().parameter.sup(), 4.);
+        for (a, b) in branch.iter().zip(&branch[1..]) {
+            assert_eq!(a.parameter.sup(), b.parameter.inf());
+        }
+        for piece in &branch {
+            for &p in &[piece.parameter.inf(), piece.parameter.mid(), piece.parameter.sup()] {
+                assert!(piece.solution.contains(p.sqrt()));
+            }
+        }
+
+        // Negative branch is followed from negative guess
+        let negative = follow(square, double, -1.1, Interval::with_range(1., 2.), 100);
+        assert!(negative.iter().all(|piece| piece.solution.sup() < 0.));
+        assert_eq!(negative.last().unwrap().parameter.sup(), 2.);
+    }
+
+    #[test]
+    fn stops_before_fold() {
+        // Branches of x² = -p meet at p = 0, where solution is not unique any more
+        let fold = |x: Interval<f64>, p: Interval<f64>| x * x + p;
+        let branch = follow(fold, double, 1., Interval::with_range(-1., 1.), 200);
+        let end = branch.last().unwrap().parameter.sup();
+
+        assert!(end < 0. && end > -0.1);
+        assert!(branch.iter().all(|piece| piece.solution.inf() > 0.));
+
+        // No solutions at all
+        assert!(follow(fold, double, 1., Interval::with_range(1., 2.), 100).is_empty());
+    }
+
+    #[test]
+    fn implicit_function() {
+        let x = implicit(square, double, 1.4, Interval::with_range(1.9, 2.1)).unwrap();
+        assert!(x.contains_all(&[1.9f64.sqrt(), 2f64.sqrt(), 2.1f64.sqrt()]));
+        assert!(x.subset(&Interval::with_range(1.37, 1.46)));
+
+        // Enclosure of a single point is tight
+        let point = implicit(square, double, 1.5, Interval::exact(2.)).unwrap();
+        assert!(point.contains(2f64.sqrt()) && point.width() < 1e-14);
+        let negative = implicit(square, double, -1., Interval::exact(2.)).unwrap();
+        assert!(negative.contains(-2f64.sqrt()));
+
+        // Parameters around fold do not define unique solution
+        assert_eq!(implicit(square, double, 0.1, Interval::with_range(-0.1, 0.1)), None);
+        assert_eq!(implicit(square, double, 0., Interval::exact(1.)), None);
+        assert_eq!(implicit(square, double, 1., Interval::exact(-1.)), None);
+    }
+}
@@ -0,0 +1,94 @@
+//! Continued fractions with rigorous truncation.
+//!
+//! Continued fraction `b₀ + a₁ / (b₁ + a₂ / (b₂ + …))` is evaluated backwards from its `n`-th
+//! term, starting from an enclosure of the tail `tₙ₊₁ = aₙ₊₁ / (bₙ₊₁ + …)` that was cut off.
+//! When the tail really lies in that enclosure the result contains the value of the infinite
+//! fraction, so the truncation error is accounted for together with rounding errors. For
+//! fractions with positive terms the tail is always in `[0, ∞)`.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use inter::Interval;
+//! use inter::continued_fraction;
+//!
+//! // √2 = 1 + 1 / (2 + 1 / (2 + …)), with tail √2 - 1 in [0, 1]
+//! let (one, two) = (Interval::exact(1.), Interval::exact(2.));
+//! let tail = Interval::with_range(0., 1.);
+//! let sqrt2 = continued_fraction::eval(one, 25, |_| (one, two), tail).unwrap();
+//!
+//! assert!(sqrt2.contains(2f64.sqrt()) && sqrt2.width() < 1e-15);
+//! ```
+
+use num::Float;
+
+use interval::Interval;
+use rounding::RoundedArith;
+
+/// Enclosure of `b₀ + a₁ / (b₁ + … + aₙ / (bₙ + tail))`, where `term(k)` gives `(aₖ, bₖ)`
+///
+/// Terms are requested for `k` from `n` down to `1`. Returns `None` when some denominator can
+/// be zero, as then the truncated fraction is not bounded.
+pub fn eval<T, F>(b0: Interval<T>, n: usize, term: F, tail: Interval<T>) -> Option<Interval<T>>
+    where T: Float + RoundedArith,
+          F: Fn(usize) -> (Interval<T>, Interval<T>)
+{
+    let mut t = tail;
+    for k in (1..=n).rev() {
+        let (a, b) = term(k);
+        t = a.checked_div(b + t)?;
+    }
+
+    Some(b0 + t)
+}
+
+/// Enclosure of fraction with positive `aₖ` and `bₖ` for all `k`, see `eval`
+///
+/// Tail of such fraction lies in `[0, ∞)`, so the result is the hull of the `n`-th and
+/// `(n - 1)`-th convergents.
+pub fn eval_positive<T, F>(b0: Interval<T>, n: usize, term: F) -> Option<Interval<T>>
+    where T: Float + RoundedArith,
+          F: Fn(usize) -> (Interval<T>, Interval<T>)
+{
+    eval(b0, n, term, Interval::with_range(T::zero(), T::infinity()))
+}
+
+#[cfg(test)]
+mod test {
+    use std::f64::consts::E;
+
+    use super::*;
+
+    #[test]
+    fn convergence() {
+        let one = Interval::exact(1.);
+        let phi = (1. + 5f64.sqrt()) / 2.;
+
+        // Golden ratio converges slowly, but every truncation encloses it
+        let mut width = f64::INFINITY;
+        for n in 1..40 {
+            let x = eval_positive(one, n, |_| (one, one)).unwrap();
+            assert!(x.contains(phi) && x.width() <= width);
+            width = x.width();
+        }
+        assert!(width < 1e-14);
+
+        // e = 2 + 1 / (1 + 1 / (2 + 2 / (3 + 3 / (4 + …))))
+        let term = |k: usize| (Interval::exact(k.max(2) as f64 - 1.), Interval::exact(k as f64));
+        let e = eval_positive(Interval::exact(2.), 30, term).unwrap();
+        assert!(e.contains(E) && e.width() < 1e-14);
+    }
+
+    #[test]
+    fn truncation() {
+        let one = Interval::exact(1.);
+
+        // Without any terms the result is just tail
+        assert_eq!(eval(one, 0, |_| (one, one), Interval::with_range(0., 1.)),
+                   Some(Interval::with_range(1., 2.)));
+        // Tail that allows zero denominator gives no bound
+        assert_eq!(eval(one, 3, |_| (one, one), Interval::with_range(-2., 0.)), None);
+        assert_eq!(eval_positive(one, 0, |_| (one, one)),
+                   Some(Interval::with_range(1., f64::INFINITY)));
+    }
+}
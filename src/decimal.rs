@@ -0,0 +1,379 @@
+//! Outward-rounded parsing and formatting of decimal numbers, and exact hexadecimal ones.
+
+use std::cmp::Ordering;
+use std::fmt::{Display, LowerExp};
+use std::str::FromStr;
+
+use num::Float;
+
+use utils::{next_up, next_down, scale_pow2};
+
+/// Number of fractional digits that is enough to print every `f64` exactly
+const EXACT_DIGITS: usize = 1100;
+
+/// Format `x` in fixed notation with `digits` fractional digits, rounded upward or downward
+///
+/// Non-finite values are formatted as by `Display`.
+pub fn format_directed<T>(x: T, digits: usize, up: bool) -> String
+    where T: Float + Display
+{
+    if !x.is_finite() {
+        return format!("{}", x);
+    }
+    if digits >= EXACT_DIGITS {
+        return format!("{:.*}", digits, x);
+    }
+
+    let negative = x.is_sign_negative();
+    let exact = format!("{:.*}", EXACT_DIGITS, x.abs());
+    let point = exact.find('.').unwrap();
+    let (int, frac) = (&exact[..point], &exact[point + 1..]);
+
+    let mut ret: Vec<u8> = int.bytes().chain(frac[..digits].bytes()).collect();
+    let inexact = frac[digits..].bytes().any(|d| d != b'0');
+    if inexact && up != negative {
+        // Increase magnitude by one unit in the last place, propagating carry
+        let carry = ret.iter_mut().rev().all(|d| if *d == b'9' {
+            *d = b'0';
+            true
+        } else {
+            *d += 1;
+            false
+        });
+        if carry {
+            ret.insert(0, b'1');
+        }
+    }
+    if digits > 0 {
+        let point = ret.len() - digits;
+        ret.insert(point, b'.');
+    }
+    if negative {
+        ret.insert(0, b'-');
+    }
+
+    String::from_utf8(ret).unwrap()
+}
+
+/// Decimal number `±0.d₁d₂d₃… × 10^exp` with no leading or trailing zero digits
+#[derive(Debug, PartialEq)]
+struct Decimal {
+    negative: bool,
+    digits: Vec<u8>,
+    exp: i64,
+}
+
+impl Decimal {
+    fn parse(s: &str) -> Option<Decimal> {
+        let s = s.trim();
+        let (negative, s) = match s.as_bytes().first() {
+            Some(&b'-') => (true, &s[1..]),
+            Some(&b'+') => (false, &s[1..]),
+            _ => (false, s),
+        };
+        let (mantissa, exp) = match s.find(['e', 'E']) {
+            Some(i) => (&s[..i], s[i + 1..].parse::<i64>().ok()?),
+            None => (s, 0),
+        };
+        let (int, frac) = match mantissa.find('.') {
+            Some(i) => (&mantissa[..i], &mantissa[i + 1..]),
+            None => (mantissa, ""),
+        };
+        if int.is_empty() && frac.is_empty() {
+            return None;
+        }
+        if !int.bytes().chain(frac.bytes()).all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+
+        let mut digits: Vec<u8> = int.bytes().chain(frac.bytes()).map(|b| b - b'0').collect();
+        let mut exp = exp.checked_add(int.len() as i64)?;
+        let leading = digits.iter().take_while(|&&d| d == 0).count();
+        digits.drain(..leading);
+        exp = exp.checked_sub(leading as i64)?;
+        while digits.last() == Some(&0) {
+            digits.pop();
+        }
+        if digits.is_empty() {
+            exp = 0;
+        }
+
+        Some(Decimal {
+            negative,
+            digits,
+            exp,
+        })
+    }
+
+    /// Compare absolute values
+    fn cmp_abs(&self, other: &Decimal) -> Ordering {
+        match (self.digits.is_empty(), other.digits.is_empty()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+            (false, false) => {
+                self.exp.cmp(&other.exp).then_with(|| self.digits.cmp(&other.digits))
+            }
+        }
+    }
+}
+
+/// Parse decimal number into the tightest pair of values of `T` enclosing it
+///
+/// Returns `None` when string is not a decimal number. The last element tells whether the value
+/// was exactly representable.
+pub fn parse_enclosure<T>(s: &str) -> Option<(T, T, bool)>
+    where T: Float + FromStr + LowerExp
+{
+    let decimal = Decimal::parse(s)?;
+    let rounded: T = s.trim().parse().ok()?;
+
+    if rounded.is_infinite() {
+        return Some(if decimal.negative {
+            (rounded, -T::max_value(), false)
+        } else {
+            (T::max_value(), rounded, false)
+        });
+    }
+
+    // Exponential formatting with that many digits is exact for every finite `f64`
+    let exact = Decimal::parse(&format!("{:.1100e}", rounded.abs())).unwrap();
+
+    Some(match (decimal.cmp_abs(&exact), decimal.negative) {
+        (Ordering::Equal, _) => (rounded, rounded, true),
+        (Ordering::Greater, false) | (Ordering::Less, true) => (rounded, next_up(rounded), false),
+        (Ordering::Less, false) | (Ordering::Greater, true) => (next_down(rounded), rounded, false),
+    })
+}
+
+/// Format interval in concise uncertainty notation, like `1.31(7)` for `[1.25, 1.375]`
+///
+/// Uncertainty (in units of the last printed digit) has one or two digits and together with
+/// printed center covers the whole interval. Returns `None` when endpoints are not finite or the
+/// interval is too far from zero relative to its width to be written that way.
+pub fn format_uncertainty<T>(start: T, end: T) -> Option<String>
+    where T: Float + Display
+{
+    if !start.is_finite() || !end.is_finite() {
+        return None;
+    }
+
+    let radius = (end - start) / (T::one() + T::one());
+    if radius == T::zero() {
+        // Exact decimal expansion, which is finite for every binary float
+        let exact = format!("{:.*}", EXACT_DIGITS, start);
+        return Some(exact.trim_end_matches('0').trim_end_matches('.').to_string());
+    }
+    let digits = (-radius.log10().floor().to_i32()?).max(0) as usize;
+
+    // Outward decimal bounds in units of the last digit
+    let units = |x: T, up: bool| format_directed(x, digits, up).replace('.', "").parse::<i128>();
+    let (lo, hi) = (units(start, false).ok()?, units(end, true).ok()?);
+    let center = lo + (hi - lo) / 2;
+    let uncertainty = (hi - center).max(center - lo);
+
+    let mut ret = format!("{:0w$}", center.abs(), w = digits + 1);
+    if digits > 0 {
+        ret.insert(ret.len() - digits, '.');
+    }
+    if center < 0 {
+        ret.insert(0, '-');
+    }
+    if uncertainty > 0 {
+        ret.push_str(&format!("({})", uncertainty));
+    }
+
+    Some(ret)
+}
+
+/// Format `x` exactly as hexadecimal float, like `0x1.8p+1` for `3`
+///
+/// Non-finite values are formatted as by `Display`.
+pub fn format_hex<T>(x: T) -> String
+    where T: Float + Display
+{
+    if !x.is_finite() {
+        return format!("{}", x);
+    }
+
+    let (mantissa, exp, sign) = x.integer_decode();
+    let sign = if sign < 0 { "-" } else { "" };
+    if mantissa == 0 {
+        return format!("{}0x0p+0", sign);
+    }
+
+    // Normalize to `1.fraction × 2^exp`, with fraction padded to whole hexadecimal digits
+    let bits = 63 - mantissa.leading_zeros();
+    let exp = exp as i32 + bits as i32;
+    let len = bits.div_ceil(4);
+    let fraction = (mantissa & ((1 << bits) - 1)) << (4 * len - bits);
+    let fraction = format!("{:0w$x}", fraction, w = len as usize);
+    let fraction = fraction.trim_end_matches('0');
+
+    if fraction.is_empty() {
+        format!("{}0x1p{:+}", sign, exp)
+    } else {
+        format!("{}0x1.{}p{:+}", sign, fraction, exp)
+    }
+}
+
+/// Parse hexadecimal float into the tightest pair of values of `T` enclosing it
+///
+/// Returns `None` when string is not a hexadecimal float or its mantissa is not representable
+/// in `T`. The last element tells whether the value was exactly representable.
+pub fn parse_hex<T>(s: &str) -> Option<(T, T, bool)>
+    where T: Float
+{
+    let s = s.trim();
+    let (negative, s) = match s.as_bytes().first() {
+        Some(&b'-') => (true, &s[1..]),
+        Some(&b'+') => (false, &s[1..]),
+        _ => (false, s),
+    };
+    let s = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X"))?;
+    let (mantissa, exp) = match s.find(['p', 'P']) {
+        Some(i) => (&s[..i], s[i + 1..].parse::<i32>().ok()?),
+        None => (s, 0),
+    };
+    let (int, frac) = match mantissa.find('.') {
+        Some(i) => (&mantissa[..i], mantissa[i + 1..].trim_end_matches('0')),
+        None => (mantissa, ""),
+    };
+    if int.is_empty() && frac.is_empty() {
+        return None;
+    }
+
+    let mut m = 0u64;
+    for c in int.chars().chain(frac.chars()) {
+        m = m.checked_mul(16)?.checked_add(c.to_digit(16)? as u64)?;
+    }
+    let exp = exp.checked_sub(4 * frac.len() as i32)?;
+    let x = T::from(m)?;
+    if x.to_u64() != Some(m) {
+        return None;
+    }
+
+    let (lo, hi, exact) = match scale_pow2(x, exp) {
+        (lo, hi) if lo == hi => (lo, hi, true),
+        // Subnormal result, which can still be exact
+        (lo, hi) => match [lo, next_up(lo), hi].iter().find(|&&c| scale_pow2(c, -exp) == (x, x)) {
+            Some(&c) => (c, c, true),
+            None => (lo, hi, false),
+        },
+    };
+
+    Some(if negative { (-hi, -lo, exact) } else { (lo, hi, exact) })
+}
+
+#[cfg(test)]
+mod test {
+    use std::f64;
+
+    use super::{Decimal, format_directed, format_hex, format_uncertainty, parse_enclosure,
+                parse_hex};
+
+    #[test]
+    fn directed_format() {
+        assert_eq!(format_directed(0.1, 3, false), "0.100");
+        assert_eq!(format_directed(0.1, 20, false), "0.10000000000000000555");
+        assert_eq!(format_directed(0.1, 20, true), "0.10000000000000000556");
+        assert_eq!(format_directed(-0.1, 20, false), "-0.10000000000000000556");
+        assert_eq!(format_directed(-0.1, 20, true), "-0.10000000000000000555");
+        assert_eq!(format_directed(9.995, 2, true), "10.00");
+        assert_eq!(format_directed(9.5, 0, true), "10");
+        assert_eq!(format_directed(9.5, 0, false), "9");
+        assert_eq!(format_directed(1.5, 3, true), "1.500");
+        assert_eq!(format_directed(f64::NEG_INFINITY, 3, true), "-inf");
+        assert_eq!(format_directed(1e-300, 2, true), "0.01");
+    }
+
+    #[test]
+    fn parse_decimal() {
+        let d = Decimal::parse("-001.2300e2").unwrap();
+        assert!(d.negative);
+        assert_eq!(d.digits, vec![1, 2, 3]);
+        assert_eq!(d.exp, 3);
+
+        assert_eq!(Decimal::parse(".05").unwrap().exp, -1);
+        assert_eq!(Decimal::parse("0.0").unwrap().digits, vec![]);
+        assert_eq!(Decimal::parse("."), None);
+        assert_eq!(Decimal::parse("1e"), None);
+        assert_eq!(Decimal::parse("inf"), None);
+    }
+
+    #[test]
+    fn exact_values() {
+        assert_eq!(parse_enclosure::<f64>("0.5"), Some((0.5, 0.5, true)));
+        assert_eq!(parse_enclosure::<f64>("-1.25e2"), Some((-125., -125., true)));
+        assert_eq!(parse_enclosure::<f32>("0"), Some((0., 0., true)));
+    }
+
+    #[test]
+    fn inexact_values() {
+        let (lo, hi, exact) = parse_enclosure::<f64>("0.1").unwrap();
+        assert!(!exact);
+        assert_eq!((lo, hi), (0.1f64.next_down(), 0.1));
+
+        let (lo, hi, _) = parse_enclosure::<f64>("-0.1").unwrap();
+        assert_eq!((lo, hi), (-0.1, (-0.1f64).next_up()));
+
+        let (lo, hi, _) = parse_enclosure::<f64>("0.3").unwrap();
+        assert_eq!((lo, hi), (0.3, 0.3f64.next_up()));
+
+        let (lo, hi, _) = parse_enclosure::<f64>("1e400").unwrap();
+        assert_eq!((lo, hi), (f64::MAX, f64::INFINITY));
+
+        let (lo, hi, _) = parse_enclosure::<f64>("1e-400").unwrap();
+        assert_eq!((lo, hi), (0., f64::MIN_POSITIVE * f64::EPSILON));
+    }
+
+    #[test]
+    fn extreme_exponents() {
+        assert_eq!(Decimal::parse("0.0001e-9223372036854775808"), None);
+        assert_eq!(Decimal::parse("1e9223372036854775807"), None);
+        assert_eq!(Decimal::parse(".1e9223372036854775807").unwrap().exp, i64::MAX);
+        assert_eq!(Decimal::parse("1e-9223372036854775808").unwrap().exp, i64::MIN + 1);
+
+        let tiny = f64::MIN_POSITIVE * f64::EPSILON;
+        assert_eq!(parse_enclosure::<f64>("1e-9223372036854775808"), Some((0., tiny, false)));
+        assert_eq!(parse_enclosure::<f64>("-1e9223372036854775806"),
+                   Some((f64::NEG_INFINITY, -f64::MAX, false)));
+        assert_eq!(parse_enclosure::<f64>("0.0001e-9223372036854775808"), None);
+    }
+
+    #[test]
+    fn uncertainty() {
+        let format = |a: f64, b: f64| format_uncertainty(a, b).unwrap();
+
+        assert_eq!(format(1.25, 1.375), "1.31(7)");
+        assert_eq!(format(-1.375, -1.25), "-1.32(7)");
+        // Neither endpoint is exactly representable, so printed bounds are wider
+        assert_eq!(format(1.19, 1.27), "1.23(5)");
+        assert_eq!(format(1230., 1290.), "1260(30)");
+        assert_eq!(format(-0.0625, 0.0625), "0.00(7)");
+        assert_eq!(format(0.5, 0.5), "0.5");
+        assert_eq!(format(-20., -20.), "-20");
+        assert_eq!(format(0.1, 0.1), "0.1000000000000000055511151231257827021181583404541015625");
+        assert_eq!(format(0.1f64.next_down(), 0.1), "0.099999999999999998(8)");
+        assert_eq!(format_uncertainty(0., f64::INFINITY), None);
+    }
+
+    #[test]
+    fn hexadecimal() {
+        assert_eq!(format_hex(3.), "0x1.8p+1");
+        assert_eq!(format_hex(-0.1), "-0x1.999999999999ap-4");
+        assert_eq!(format_hex(1f32), "0x1p+0");
+        assert_eq!(format_hex(-0.), "-0x0p+0");
+        assert_eq!(format_hex(5e-324), "0x1p-1074");
+        assert_eq!(format_hex(f64::NEG_INFINITY), "-inf");
+
+        for &x in &[3., -0.1, 0., 5e-324, 1e-310, f64::MAX, f64::MIN_POSITIVE, 1. / 3.] {
+            assert_eq!(parse_hex::<f64>(&format_hex(x)), Some((x, x, true)));
+        }
+        assert_eq!(parse_hex::<f64>("0X10.80P-4"), Some((1.03125, 1.03125, true)));
+        assert_eq!(parse_hex::<f32>("0x1p-150"), Some((0., 1e-45, false)));
+        assert_eq!(parse_hex::<f32>("0x1000001"), None);
+        assert_eq!(parse_hex::<f64>("0x"), None);
+        assert_eq!(parse_hex::<f64>("1.5"), None);
+    }
+}
@@ -0,0 +1,403 @@
+//! Decorated intervals of IEEE 1788-2015.
+//!
+//! Decoration records what is known about the function evaluated to obtain an interval. Every
+//! operation computes its local decoration from the inputs and returns the worse of it and the
+//! decorations of the inputs, so after a long computation the decoration tells whether any step
+//! left its domain (like square root of negative number or division by an interval containing
+//! zero), even if the final enclosure looks harmless.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use inter::Interval;
+//! use inter::decorated::{Decoration, DecoratedInterval};
+//!
+//! let x = DecoratedInterval::new(Interval::with_range(-1., 4.));
+//! let y = x.sqrt() + DecoratedInterval::new(Interval::exact(1.));
+//!
+//! assert_eq!(y.interval(), Interval::with_range(1., 3.));
+//! assert_eq!(y.decoration(), Decoration::Trv);
+//!
+//! let z = (x * x).exp();
+//! assert_eq!(z.decoration(), Decoration::Com);
+//! ```
+
+use std::fmt;
+use std::ops::{Add, Sub, Mul, Div, Neg};
+
+use num::{Bounded, Float, FloatConst, FromPrimitive};
+
+use interval::Interval;
+use rounding::RoundedArith;
+
+/// Decoration of interval, ordered from the worst to the best
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Decoration {
+    /// Ill-formed, result of invalid construction (NaI)
+    Ill,
+    /// Trivial, nothing is known
+    Trv,
+    /// Defined, function was defined on the whole input
+    Def,
+    /// Defined and continuous on the whole input
+    Dac,
+    /// Common, defined, continuous and bounded on bounded, non-empty input
+    Com,
+}
+
+impl fmt::Display for Decoration {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str(match *self {
+            Decoration::Ill => "ill",
+            Decoration::Trv => "trv",
+            Decoration::Def => "def",
+            Decoration::Dac => "dac",
+            Decoration::Com => "com",
+        })
+    }
+}
+
+/// Interval with decoration
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DecoratedInterval<T> {
+    interval: Interval<T>,
+    decoration: Decoration,
+}
+
+impl<T> DecoratedInterval<T>
+    where T: Float + Bounded + RoundedArith
+{
+    /// Decorate interval with the best decoration it can have
+    ///
+    /// That is `com` for bounded, `dac` for unbounded and `trv` for empty intervals.
+    pub fn new(interval: Interval<T>) -> Self {
+        DecoratedInterval {
+            interval,
+            decoration: best(&interval),
+        }
+    }
+
+    /// Decorate interval with `decoration`, or the best it can have if that is worse
+    ///
+    /// Ill-formed decoration makes the interval empty.
+    pub fn with_decoration(interval: Interval<T>, decoration: Decoration) -> Self {
+        if decoration == Decoration::Ill {
+            return DecoratedInterval::ill();
+        }
+
+        DecoratedInterval {
+            interval,
+            decoration: decoration.min(best(&interval)),
+        }
+    }
+
+    /// Not an interval, the result of invalid construction
+    pub fn ill() -> Self {
+        DecoratedInterval {
+            interval: Interval::empty(),
+            decoration: Decoration::Ill,
+        }
+    }
+
+    /// Decorated interval with bounds `start` and `end`, ill-formed if they are invalid
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::decorated::{Decoration, DecoratedInterval};
+    ///
+    /// assert_eq!(DecoratedInterval::with_range(1., 2.).decoration(), Decoration::Com);
+    /// assert!(DecoratedInterval::with_range(2., 1.).is_nai());
+    /// ```
+    pub fn with_range(start: T, end: T) -> Self {
+        match Interval::try_with_range(start, end) {
+            Ok(interval) => DecoratedInterval::new(interval),
+            Err(_) => DecoratedInterval::ill(),
+        }
+    }
+
+    /// Undecorated interval
+    pub fn interval(&self) -> Interval<T> {
+        self.interval
+    }
+
+    /// Decoration of interval
+    pub fn decoration(&self) -> Decoration {
+        self.decoration
+    }
+
+    /// Check if this is not an interval, i.e. it is decorated with `ill`
+    pub fn is_nai(&self) -> bool {
+        self.decoration == Decoration::Ill
+    }
+
+    /// Result of operation on `inputs` that gave `interval` and was `defined` on all of them
+    fn lift(interval: Interval<T>, defined: bool, inputs: &[Decoration]) -> Self {
+        let local = if defined { best(&interval) } else { Decoration::Trv };
+        let decoration = inputs.iter().fold(local, |acc, &d| acc.min(d));
+
+        if decoration == Decoration::Ill {
+            DecoratedInterval::ill()
+        } else {
+            DecoratedInterval {
+                interval,
+                decoration,
+            }
+        }
+    }
+
+    /// Apply unary `f` that is defined and continuous where `defined` returns `true`
+    fn apply<F, D>(self, f: F, defined: D) -> Self
+        where F: FnOnce(Interval<T>) -> Interval<T>,
+              D: FnOnce(&Interval<T>) -> bool
+    {
+        let ok = !self.interval.is_empty() && defined(&self.interval);
+
+        DecoratedInterval::lift(f(self.interval), ok, &[self.decoration])
+    }
+
+    /// Absolute value
+    pub fn abs(self) -> Self {
+        self.apply(|x| x.abs(), |_| true)
+    }
+
+    /// Exponential
+    pub fn exp(self) -> Self {
+        self.apply(Interval::exp, |_| true)
+    }
+
+    /// Natural logarithm, defined for positive values
+    pub fn ln(self) -> Self {
+        self.apply(Interval::ln, |x| x.start > T::zero())
+    }
+
+    /// Base 2 logarithm, defined for positive values
+    pub fn log2(self) -> Self {
+        self.apply(Interval::log2, |x| x.start > T::zero())
+    }
+
+    /// Base 10 logarithm, defined for positive values
+    pub fn log10(self) -> Self {
+        self.apply(Interval::log10, |x| x.start > T::zero())
+    }
+
+    /// Square root, defined for non-negative values
+    pub fn sqrt(self) -> Self {
+        self.apply(Interval::sqrt, |x| x.start >= T::zero())
+    }
+
+    /// Cube root
+    pub fn cbrt(self) -> Self {
+        self.apply(Interval::cbrt, |_| true)
+    }
+
+    /// Hyperbolic sine
+    pub fn sinh(self) -> Self {
+        self.apply(Interval::sinh, |_| true)
+    }
+
+    /// Hyperbolic cosine
+    pub fn cosh(self) -> Self {
+        self.apply(Interval::cosh, |_| true)
+    }
+
+    /// Hyperbolic tangent
+    pub fn tanh(self) -> Self {
+        self.apply(Interval::tanh, |_| true)
+    }
+
+    /// Inverse hyperbolic sine
+    pub fn asinh(self) -> Self {
+        self.apply(Interval::asinh, |_| true)
+    }
+
+    /// Inverse hyperbolic cosine, defined for values not below one
+    pub fn acosh(self) -> Self {
+        self.apply(Interval::acosh, |x| x.start >= T::one())
+    }
+
+    /// Inverse hyperbolic tangent, defined for values in `(-1, 1)`
+    pub fn atanh(self) -> Self {
+        self.apply(Interval::atanh, |x| x.start > -T::one() && x.end < T::one())
+    }
+
+    /// Integer power, negative powers are defined for values other than zero
+    pub fn powi(self, n: i32) -> Self {
+        self.apply(|x| x.powi(n), |x| n >= 0 || !x.contains_zero())
+    }
+}
+
+impl<T> DecoratedInterval<T>
+    where T: Float + FloatConst + Bounded + RoundedArith
+{
+    /// Sine
+    pub fn sin(self) -> Self {
+        self.apply(Interval::sin, |_| true)
+    }
+
+    /// Cosine
+    pub fn cos(self) -> Self {
+        self.apply(Interval::cos, |_| true)
+    }
+
+    /// Tangent, defined for intervals without poles
+    pub fn tan(self) -> Self {
+        let tan = self.interval.tan();
+
+        self.apply(|_| tan, |_| tan.is_bounded())
+    }
+
+    /// Arcsine, defined for values in `[-1, 1]`
+    pub fn asin(self) -> Self {
+        self.apply(Interval::asin, |x| x.start >= -T::one() && x.end <= T::one())
+    }
+
+    /// Arccosine, defined for values in `[-1, 1]`
+    pub fn acos(self) -> Self {
+        self.apply(Interval::acos, |x| x.start >= -T::one() && x.end <= T::one())
+    }
+
+    /// Arctangent
+    pub fn atan(self) -> Self {
+        self.apply(Interval::atan, |_| true)
+    }
+}
+
+/// The best decoration of interval, which is not a result of any operation
+fn best<T: Float>(x: &Interval<T>) -> Decoration {
+    if x.is_empty() {
+        Decoration::Trv
+    } else if x.start.is_finite() && x.end.is_finite() {
+        Decoration::Com
+    } else {
+        Decoration::Dac
+    }
+}
+
+macro_rules! decorated_ops {
+    ($($imp:ident, $method:ident, $defined:expr;)*) => {
+        $(
+            impl<T> $imp for DecoratedInterval<T>
+                where T: Float + Bounded + RoundedArith
+            {
+                type Output = DecoratedInterval<T>;
+
+                fn $method(self, other: Self) -> Self {
+                    let defined: fn(&Interval<T>) -> bool = $defined;
+                    let (x, y) = (self.interval, other.interval);
+                    let ok = !x.is_empty() && !y.is_empty() && defined(&y);
+
+                    DecoratedInterval::lift(x.$method(y), ok, &[self.decoration, other.decoration])
+                }
+            }
+        )*
+    };
+}
+
+decorated_ops! {
+    Add, add, |_| true;
+    Sub, sub, |_| true;
+    Mul, mul, |_| true;
+    Div, div, |y| !y.contains_zero();
+}
+
+impl<T> Neg for DecoratedInterval<T>
+    where T: Neg<Output = T> + Copy
+{
+    type Output = DecoratedInterval<T>;
+
+    fn neg(self) -> Self {
+        DecoratedInterval {
+            interval: -self.interval,
+            decoration: self.decoration,
+        }
+    }
+}
+
+impl<T> From<Interval<T>> for DecoratedInterval<T>
+    where T: Float + Bounded + RoundedArith
+{
+    fn from(interval: Interval<T>) -> Self {
+        DecoratedInterval::new(interval)
+    }
+}
+
+impl<T> fmt::Display for DecoratedInterval<T>
+    where T: Float + FromPrimitive + fmt::Display + RoundedArith
+{
+    /// Interval followed by its decoration, like `[1, 2]_com`
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        if self.decoration == Decoration::Ill {
+            return fmt.write_str("[nai]");
+        }
+
+        write!(fmt, "{}_{}", self.interval, self.decoration)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::f64;
+
+    use super::*;
+
+    fn d(start: f64, end: f64) -> DecoratedInterval<f64> {
+        DecoratedInterval::with_range(start, end)
+    }
+
+    #[test]
+    fn construction() {
+        assert_eq!(d(1., 2.).decoration(), Decoration::Com);
+        assert_eq!(d(1., f64::INFINITY).decoration(), Decoration::Dac);
+        assert_eq!(DecoratedInterval::new(Interval::<f64>::empty()).decoration(), Decoration::Trv);
+        assert!(d(f64::NAN, 1.).is_nai() && d(2., 1.).interval().is_empty());
+
+        let x = DecoratedInterval::with_decoration(Interval::with_range(1., 2.), Decoration::Def);
+        assert_eq!(x.decoration(), Decoration::Def);
+        let x = DecoratedInterval::with_decoration(Interval::<f64>::entire(), Decoration::Com);
+        assert_eq!(x.decoration(), Decoration::Dac);
+        assert!(Decoration::Ill < Decoration::Trv && Decoration::Dac < Decoration::Com);
+    }
+
+    #[test]
+    fn propagation() {
+        let (x, y) = (d(1., 2.), d(-1., 1.));
+
+        assert_eq!((x + y * x - x / x).decoration(), Decoration::Com);
+        assert_eq!((-x).powi(-2).decoration(), Decoration::Com);
+        assert_eq!((x / y).decoration(), Decoration::Trv);
+        assert_eq!(y.powi(-1).decoration(), Decoration::Trv);
+        assert_eq!(y.ln().decoration(), Decoration::Trv);
+        assert_eq!(x.ln().log2().decoration(), Decoration::Trv);
+        assert_eq!(x.ln().log10().abs().cbrt().decoration(), Decoration::Trv);
+        assert_eq!(y.sqrt().exp().decoration(), Decoration::Trv);
+        assert_eq!(y.sinh().cosh().tanh().asinh().atanh().decoration(), Decoration::Com);
+        assert_eq!(y.atanh().decoration(), Decoration::Trv);
+        assert_eq!(x.acosh().decoration(), Decoration::Com);
+        assert_eq!(y.acosh().decoration(), Decoration::Trv);
+        assert_eq!((x * y.sqrt()).interval(), Interval::with_range(0., 2.));
+
+        // Unbounded result makes `dac`, which stays after bounded operations
+        let big = d(1., f64::MAX) * d(2., 2.);
+        assert_eq!(big.decoration(), Decoration::Dac);
+        assert_eq!(big.powi(-1).decoration(), Decoration::Dac);
+        assert!((DecoratedInterval::ill() + x).is_nai());
+    }
+
+    #[test]
+    fn trigonometric() {
+        assert_eq!(d(-10., 10.).sin().cos().decoration(), Decoration::Com);
+        assert_eq!(d(0., 1.).tan().decoration(), Decoration::Com);
+        assert_eq!(d(1., 2.).tan().decoration(), Decoration::Trv);
+        assert_eq!(d(-1., 1.).asin().atan().decoration(), Decoration::Com);
+        assert_eq!(d(-0.5, 0.5).acos().decoration(), Decoration::Com);
+        assert_eq!(d(0., 2.).acos().decoration(), Decoration::Trv);
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(d(1., 2.).to_string(), "[1, 2]_com");
+        assert_eq!(d(-1., 1.).sqrt().to_string(), "[0, 1]_trv");
+        assert_eq!(d(2., 1.).to_string(), "[nai]");
+    }
+}
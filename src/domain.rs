@@ -0,0 +1,310 @@
+//! Domains of variables for constraint propagation and search.
+//!
+//! Store keeps interval domains of variables together with contractors, functions narrowing
+//! domains of some variables to values consistent with a constraint. Whenever a domain narrows,
+//! contractors watching the variable are scheduled again, and propagation runs them until no
+//! domain narrows noticeably (by more than `1/1024` of its width by default), so it stops even
+//! when contractors converge slowly.
+//!
+//! Every change is recorded on a trail, so search can take a checkpoint before a choice, narrow
+//! and propagate, and restore all domains as they were when backtracking.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use inter::Interval;
+//! use inter::domain::DomainStore;
+//!
+//! let mut store = DomainStore::new();
+//! let x = store.add_variable(Interval::with_range(0., 10.));
+//! let y = store.add_variable(Interval::with_range(0., 10.));
+//!
+//! // x + y = 4
+//! let four = Interval::exact(4.);
+//! store.add_contractor(&[x, y], move |d: &mut [Interval<f64>]| {
+//!     d[0] = d[0].add_rev(d[1], four);
+//!     d[1] = d[1].add_rev(d[0], four);
+//! });
+//! assert!(store.propagate());
+//! assert_eq!(store.domain(x), Interval::with_range(0., 4.));
+//!
+//! // Choose x ≥ 3 and backtrack
+//! let checkpoint = store.checkpoint();
+//! store.narrow(x, Interval::with_range(3., 10.));
+//! assert!(store.propagate());
+//! assert_eq!(store.domain(y), Interval::with_range(0., 1.));
+//!
+//! store.restore(checkpoint);
+//! assert_eq!(store.domain(y), Interval::with_range(0., 4.));
+//! ```
+
+use std::collections::VecDeque;
+
+use num::{Bounded, Float};
+
+use interval::Interval;
+use rounding::RoundedArith;
+
+type Contractor<'a, T> = dyn Fn(&mut [Interval<T>]) + 'a;
+
+/// Domains of variables with contractors between them
+pub struct DomainStore<'a, T> {
+    domains: Vec<Interval<T>>,
+    // Variable and its domain before every change
+    trail: Vec<(usize, Interval<T>)>,
+    contractors: Vec<(Vec<usize>, Box<Contractor<'a, T>>)>,
+    watchers: Vec<Vec<usize>>,
+    queue: VecDeque<usize>,
+    queued: Vec<bool>,
+    ratio: T,
+}
+
+impl<'a, T> DomainStore<'a, T>
+    where T: Float + Bounded + RoundedArith
+{
+    /// Store without variables
+    pub fn new() -> Self {
+        DomainStore {
+            domains: Vec::new(),
+            trail: Vec::new(),
+            contractors: Vec::new(),
+            watchers: Vec::new(),
+            queue: VecDeque::new(),
+            queued: Vec::new(),
+            ratio: T::one() / T::from(1024).unwrap(),
+        }
+    }
+
+    /// Fraction of width a domain has to lose to schedule contractors watching it again
+    pub fn set_ratio(&mut self, ratio: T) {
+        self.ratio = ratio;
+    }
+
+    /// Add variable with given domain and return its index
+    ///
+    /// Variables are kept when restoring checkpoints taken before they were added.
+    pub fn add_variable(&mut self, domain: Interval<T>) -> usize {
+        self.domains.push(domain);
+        self.watchers.push(Vec::new());
+
+        self.domains.len() - 1
+    }
+
+    /// Add contractor of variables `vars` and schedule it
+    ///
+    /// Contractor gets domains of `vars` in the same order and narrows them in place. It has to
+    /// keep every solution of its constraint, and results are intersected with current domains.
+    ///
+    /// # Panics
+    ///
+    /// This will panic if any of `vars` is not a variable of the store.
+    pub fn add_contractor<F>(&mut self, vars: &[usize], contractor: F)
+        where F: Fn(&mut [Interval<T>]) + 'a
+    {
+        let index = self.contractors.len();
+        for &var in vars {
+            self.watchers[var].push(index);
+        }
+        self.contractors.push((vars.to_vec(), Box::new(contractor)));
+        self.queued.push(false);
+        self.schedule(index);
+    }
+
+    /// Domain of variable `var`
+    ///
+    /// # Panics
+    ///
+    /// This will panic if `var` is not a variable of the store.
+    pub fn domain(&self, var: usize) -> Interval<T> {
+        self.domains[var]
+    }
+
+    /// Domains of all variables, in order they were added
+    pub fn domains(&self) -> &[Interval<T>] {
+        &self.domains
+    }
+
+    /// Intersect domain of `var` with `x`, scheduling contractors watching it when it narrows
+    ///
+    /// Returns `true` when the domain changed.
+    ///
+    /// # Panics
+    ///
+    /// This will panic if `var` is not a variable of the store.
+    pub fn narrow(&mut self, var: usize, x: Interval<T>) -> bool {
+        self.update(var, x, None)
+    }
+
+    /// Run scheduled contractors until no domain narrows noticeably
+    ///
+    /// Returns `false` when some domain became empty, so constraints have no solution within
+    /// domains.
+    pub fn propagate(&mut self) -> bool {
+        if self.domains.iter().any(Interval::is_empty) {
+            self.clear_queue();
+            return false;
+        }
+
+        while let Some(index) = self.queue.pop_front() {
+            self.queued[index] = false;
+
+            let (vars, contracted) = {
+                let (ref vars, ref contractor) = self.contractors[index];
+                let mut domains: Vec<_> = vars.iter().map(|&var| self.domains[var]).collect();
+                contractor(&mut domains);
+                (vars.clone(), domains)
+            };
+
+            for (var, x) in vars.into_iter().zip(contracted) {
+                self.update(var, x, Some(index));
+                if self.domains[var].is_empty() {
+                    self.clear_queue();
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
+
+    /// Mark for restoring domains to their current state
+    pub fn checkpoint(&self) -> usize {
+        self.trail.len()
+    }
+
+    /// Undo all changes made to domains since `checkpoint` was taken
+    ///
+    /// Scheduled contractors are dropped, as they were scheduled for restored domains.
+    pub fn restore(&mut self, checkpoint: usize) {
+        while self.trail.len() > checkpoint {
+            let (var, domain) = self.trail.pop().unwrap();
+            self.domains[var] = domain;
+        }
+        self.clear_queue();
+    }
+
+    /// Variables whose domains changed since `checkpoint`, in increasing order
+    pub fn changed_since(&self, checkpoint: usize) -> Vec<usize> {
+        let mut vars: Vec<_> = self.trail[checkpoint..].iter().map(|&(var, _)| var).collect();
+        vars.sort();
+        vars.dedup();
+
+        vars
+    }
+
+    fn update(&mut self, var: usize, x: Interval<T>, running: Option<usize>) -> bool {
+        let old = self.domains[var];
+        let new = old.intersect(&x);
+        if new == old || (old.is_empty() && new.is_empty()) {
+            return false;
+        }
+
+        self.trail.push((var, old));
+        self.domains[var] = new;
+
+        let (before, after) = (old.width(), new.width());
+        if !old.is_bounded() || new.is_empty() || before - after > self.ratio * before {
+            for i in 0..self.watchers[var].len() {
+                let index = self.watchers[var][i];
+                if Some(index) != running {
+                    self.schedule(index);
+                }
+            }
+        }
+
+        true
+    }
+
+    fn schedule(&mut self, index: usize) {
+        if !self.queued[index] {
+            self.queued[index] = true;
+            self.queue.push_back(index);
+        }
+    }
+
+    fn clear_queue(&mut self) {
+        for index in self.queue.drain(..) {
+            self.queued[index] = false;
+        }
+    }
+}
+
+impl<'a, T> Default for DomainStore<'a, T>
+    where T: Float + Bounded + RoundedArith
+{
+    fn default() -> Self {
+        DomainStore::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // x = y², with y ≥ 0
+    fn square(d: &mut [Interval<f64>]) {
+        d[0] = d[0].intersect(&d[1].sqr());
+        d[1] = d[1].intersect(&d[0].sqrt());
+    }
+
+    // x = y² and x = y + 2 meet at x = 4
+    fn intersection(ratio: f64) -> Interval<f64> {
+        let mut store = DomainStore::new();
+        store.set_ratio(ratio);
+        let x = store.add_variable(Interval::with_range(0., 10.));
+        let y = store.add_variable(Interval::with_range(1., 10.));
+        store.add_contractor(&[x, y], square);
+
+        let two = Interval::exact(2.);
+        store.add_contractor(&[y, x], move |d: &mut [Interval<f64>]| {
+            d[1] = d[1].intersect(&(d[0] + two));
+            d[0] = d[0].intersect(&(d[1] - two));
+        });
+
+        assert!(store.propagate());
+        assert!(store.domain(y).contains(2.));
+        store.domain(x)
+    }
+
+    #[test]
+    fn fixpoint() {
+        let coarse = intersection(1. / 1024.);
+        assert!(coarse.contains(4.) && coarse.width() < 1e-2);
+
+        // Stricter ratio gets closer to the fixpoint
+        let fine = intersection(1e-12);
+        assert!(fine.contains(4.) && fine.width() < 1e-9 && fine.subset(&coarse));
+    }
+
+    #[test]
+    fn backtracking() {
+        let mut store = DomainStore::default();
+        let x = store.add_variable(Interval::with_range(0., 16.));
+        let y = store.add_variable(Interval::with_range(-4., 4.));
+        store.add_contractor(&[x, y], square);
+        assert!(store.propagate());
+        assert_eq!(store.domains(), &[Interval::with_range(0., 16.), Interval::with_range(0., 4.)]);
+
+        let start = store.checkpoint();
+        assert!(store.narrow(x, Interval::with_range(-1., 4.)));
+        assert!(!store.narrow(x, Interval::with_range(-1., 4.)));
+        assert!(store.propagate());
+        assert_eq!(store.domain(y), Interval::with_range(0., 2.));
+        assert_eq!(store.changed_since(start), vec![x, y]);
+
+        let choice = store.checkpoint();
+        store.narrow(y, Interval::with_range(3., 4.));
+        assert!(!store.propagate());
+        store.restore(choice);
+        assert_eq!(store.domain(y), Interval::with_range(0., 2.));
+        assert_eq!(store.changed_since(choice), vec![]);
+
+        store.restore(start);
+        assert_eq!(store.domain(x), Interval::with_range(0., 16.));
+        assert_eq!(store.domain(y), Interval::with_range(0., 4.));
+
+        // Empty domain makes propagation fail even without contractors to run
+        store.narrow(x, Interval::with_range(20., 30.));
+        assert!(store.domain(x).is_empty() && !store.propagate());
+    }
+}
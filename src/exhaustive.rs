@@ -0,0 +1,149 @@
+//! Exhaustive verification of unary functions on `Interval<f32>`.
+//!
+//! Available with the `exhaustive` feature. Every `f32` is small enough to enumerate, so unlike
+//! random sampling this proves that enclosure of a degenerate interval contains the exact result
+//! for every input, given a reference implementation in higher precision. Error of reference in
+//! `f64` is far below resolution of `f32`, so it does not hide real failures.
+//!
+//! Checking all 2³² inputs takes minutes, `verify` checks a range of bit patterns instead.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use inter::Interval;
+//! use inter::exhaustive;
+//!
+//! let bits = 0x3f80_0000..=0x3f80_ffff;
+//! let report = exhaustive::verify(|x: Interval<f32>| x.exp(), f64::exp, bits);
+//!
+//! assert_eq!(report.checked, 0x1_0000);
+//! assert!(report.is_ok());
+//! ```
+
+use std::fmt;
+use std::ops::RangeInclusive;
+
+use interval::Interval;
+
+/// How many failures are kept in `Report`
+pub const MAX_FAILURES: usize = 64;
+
+/// Input whose enclosure does not contain reference value
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Failure {
+    /// Input value
+    pub input: f32,
+    /// Enclosure computed for degenerate interval `[input, input]`
+    pub enclosure: Interval<f32>,
+    /// Reference value computed in `f64`
+    pub reference: f64,
+}
+
+/// Result of verification
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Report {
+    /// Number of inputs checked, NaN inputs and results outside of domain are skipped
+    pub checked: u64,
+    /// Number of inputs that failed
+    pub failed: u64,
+    /// First `MAX_FAILURES` failures
+    pub failures: Vec<Failure>,
+}
+
+impl Report {
+    /// Check if all inputs passed
+    pub fn is_ok(&self) -> bool {
+        self.failed == 0
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "{} inputs checked, {} failed", self.checked, self.failed)?;
+        for failure in &self.failures {
+            write!(fmt,
+                   "\n  f({:e}) = {:e} not in {}",
+                   failure.input,
+                   failure.reference,
+                   failure.enclosure)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Check `f` against `reference` for every `f32` with bit pattern in `bits`
+pub fn verify<F, R>(f: F, reference: R, bits: RangeInclusive<u32>) -> Report
+    where F: Fn(Interval<f32>) -> Interval<f32>,
+          R: Fn(f64) -> f64
+{
+    let mut report = Report::default();
+
+    for bits in bits {
+        let input = f32::from_bits(bits);
+        let exact = reference(input as f64);
+        if input.is_nan() || exact.is_nan() {
+            continue;
+        }
+
+        let enclosure = f(Interval::exact(input));
+        report.checked += 1;
+        if !(enclosure.start as f64 <= exact && exact <= enclosure.end as f64) {
+            report.failed += 1;
+            if report.failures.len() < MAX_FAILURES {
+                report.failures.push(Failure {
+                    input,
+                    enclosure,
+                    reference: exact,
+                });
+            }
+        }
+    }
+
+    report
+}
+
+/// Check `f` against `reference` for every `f32`
+pub fn verify_all<F, R>(f: F, reference: R) -> Report
+    where F: Fn(Interval<f32>) -> Interval<f32>,
+          R: Fn(f64) -> f64
+{
+    verify(f, reference, 0..=u32::MAX)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Bit patterns of all positive and negative `f32` in `[1, 1 + 2⁻⁸)`
+    const AROUND_ONE: [RangeInclusive<u32>; 2] = [0x3f80_0000..=0x3f80_7fff,
+                                                   0xbf80_0000..=0xbf80_7fff];
+
+    fn check<F, R>(f: F, reference: R)
+        where F: Fn(Interval<f32>) -> Interval<f32>,
+              R: Fn(f64) -> f64
+    {
+        for bits in AROUND_ONE.iter().cloned() {
+            let report = verify(&f, &reference, bits);
+            assert!(report.is_ok(), "{}", report);
+        }
+    }
+
+    #[test]
+    fn elementary_functions() {
+        check(|x| x.exp(), f64::exp);
+        check(|x| x.ln(), f64::ln);
+        check(|x| x.sin(), f64::sin);
+        check(|x| x.sqrt(), f64::sqrt);
+        check(|x| x * x, |x| x * x);
+    }
+
+    #[test]
+    fn reports_failures() {
+        let report = verify(|x| x, |x| x + 1e-3, 0x3f80_0000..=0x3f80_00ff);
+
+        assert_eq!((report.checked, report.failed), (256, 256));
+        assert_eq!(report.failures.len(), MAX_FAILURES);
+        assert_eq!(report.failures[0].input, 1.);
+    }
+}
@@ -0,0 +1,1759 @@
+//! Expression trees over intervals.
+//!
+//! `Expr` is built with the usual operators from variables and constants, then evaluated over a
+//! box. Evaluation uses `strategy::Value`, so the same expression can be evaluated with plain
+//! interval arithmetic, affine arithmetic or centered forms by wrapping the call in
+//! `Strategy::execute`. Subexpressions are reference counted and cheap to clone, and `Cache`
+//! evaluates shared ones only once, as does `Tape` compiled for repeated evaluation. `simplify`
+//! merges equal subexpressions and rewrites them for
+//! tighter enclosures before evaluation. `Env` gives names to variables and keeps their domains.
+//! Besides bounds, `relax` gives McCormick convex and concave relaxations for global optimization.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use inter::Interval;
+//! use inter::expr::Expr;
+//! use inter::strategy::Strategy;
+//!
+//! let x = Expr::var(0);
+//! let f = x.clone() * (Expr::from(1.) - x);
+//! let domain = [Interval::with_range(0., 1.)];
+//!
+//! assert_eq!(f.eval(&domain), Interval::with_range(0., 1.));
+//! assert_eq!(Strategy::Affine.execute(|| f.eval(&domain)), Interval::with_range(0., 0.5));
+//! ```
+
+use std::collections::HashMap;
+use std::error;
+use std::fmt;
+use std::ops::{Add, Sub, Mul, Div, Neg};
+use std::rc::Rc;
+
+use num::{Float, FloatConst};
+
+use interval::Interval;
+use linalg::IntervalMatrix;
+use rounding::RoundedArith;
+use strategy::Value;
+use tribool::Tribool;
+
+/// Elementary function of single argument
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Function {
+    /// Square root
+    Sqrt,
+    /// Exponential
+    Exp,
+    /// Natural logarithm
+    Ln,
+    /// Sine
+    Sin,
+    /// Cosine
+    Cos,
+    /// Integer power
+    Powi(i32),
+    /// Reciprocal `1 / x`
+    Recip,
+}
+
+impl Function {
+    /// Enclosure of the range of function over `x`
+    pub fn apply<T>(self, x: Interval<T>) -> Interval<T>
+        where T: Float + FloatConst + RoundedArith
+    {
+        match self {
+            Function::Sqrt => x.sqrt(),
+            Function::Exp => x.exp(),
+            Function::Ln => x.ln(),
+            Function::Sin => x.sin(),
+            Function::Cos => x.cos(),
+            Function::Powi(n) => x.powi(n),
+            Function::Recip => Interval::exact(T::one()) / x,
+        }
+    }
+
+    /// Enclosure of the range of derivative over `x`
+    pub fn derivative<T>(self, x: Interval<T>) -> Interval<T>
+        where T: Float + FloatConst + RoundedArith
+    {
+        let one = Interval::exact(T::one());
+
+        match self {
+            Function::Sqrt => one / (x.sqrt() * Interval::exact(T::one() + T::one())),
+            Function::Exp => x.exp(),
+            Function::Ln => one / x,
+            Function::Sin => x.cos(),
+            Function::Cos => -x.sin(),
+            Function::Powi(0) => Interval::exact(T::zero()),
+            Function::Powi(n) => Interval::exact(T::from(n).unwrap()) * x.powi(n - 1),
+            Function::Recip => -(one / x.powi(2)),
+        }
+    }
+}
+
+/// Expression over intervals
+#[derive(Clone, Debug, PartialEq)]
+pub enum Expr<T> {
+    /// Interval constant
+    Constant(Interval<T>),
+    /// Variable with given index
+    Variable(usize),
+    /// Negation
+    Neg(Rc<Expr<T>>),
+    /// Sum
+    Add(Rc<Expr<T>>, Rc<Expr<T>>),
+    /// Difference
+    Sub(Rc<Expr<T>>, Rc<Expr<T>>),
+    /// Product
+    Mul(Rc<Expr<T>>, Rc<Expr<T>>),
+    /// Quotient
+    Div(Rc<Expr<T>>, Rc<Expr<T>>),
+    /// Elementary function
+    Apply(Function, Rc<Expr<T>>),
+}
+
+impl<T> Expr<T> {
+    /// Variable with index `i`
+    pub fn var(i: usize) -> Self {
+        Expr::Variable(i)
+    }
+
+    /// Constant interval
+    pub fn constant(x: Interval<T>) -> Self {
+        Expr::Constant(x)
+    }
+
+    /// Number of variables needed to evaluate expression, one more than the greatest index
+    pub fn arity(&self) -> usize {
+        match *self {
+            Expr::Constant(_) => 0,
+            Expr::Variable(i) => i + 1,
+            Expr::Neg(ref a) | Expr::Apply(_, ref a) => a.arity(),
+            Expr::Add(ref a, ref b) |
+            Expr::Sub(ref a, ref b) |
+            Expr::Mul(ref a, ref b) |
+            Expr::Div(ref a, ref b) => a.arity().max(b.arity()),
+        }
+    }
+
+    /// Apply elementary function
+    pub fn apply(self, f: Function) -> Self {
+        Expr::Apply(f, Rc::new(self))
+    }
+
+    /// Square root
+    pub fn sqrt(self) -> Self {
+        self.apply(Function::Sqrt)
+    }
+
+    /// Exponential
+    pub fn exp(self) -> Self {
+        self.apply(Function::Exp)
+    }
+
+    /// Natural logarithm
+    pub fn ln(self) -> Self {
+        self.apply(Function::Ln)
+    }
+
+    /// Sine
+    pub fn sin(self) -> Self {
+        self.apply(Function::Sin)
+    }
+
+    /// Cosine
+    pub fn cos(self) -> Self {
+        self.apply(Function::Cos)
+    }
+
+    /// Integer power
+    pub fn powi(self, n: i32) -> Self {
+        self.apply(Function::Powi(n))
+    }
+}
+
+impl<T> Expr<T>
+    where T: Float + FloatConst + RoundedArith
+{
+    /// Enclose range of expression over box `x` using strategy in effect
+    ///
+    /// # Panics
+    ///
+    /// This will panic if `x` has less than `arity()` coordinates.
+    pub fn eval(&self, x: &[Interval<T>]) -> Interval<T> {
+        let vars: Vec<_> = x.iter().map(|&x| Value::variable(x)).collect();
+
+        self.eval_with(&vars).enclosure()
+    }
+
+    /// Evaluate expression with variables replaced by `vars`
+    ///
+    /// # Panics
+    ///
+    /// This will panic if `vars` has less than `arity()` elements.
+    pub fn eval_with(&self, vars: &[Value<T>]) -> Value<T> {
+        match *self {
+            Expr::Constant(x) => Value::constant(x),
+            Expr::Variable(i) => vars[i].clone(),
+            Expr::Neg(ref a) => -a.eval_with(vars),
+            Expr::Add(ref a, ref b) => a.eval_with(vars) + b.eval_with(vars),
+            Expr::Sub(ref a, ref b) => a.eval_with(vars) - b.eval_with(vars),
+            Expr::Mul(ref a, ref b) => a.eval_with(vars) * b.eval_with(vars),
+            Expr::Div(ref a, ref b) => a.eval_with(vars) / b.eval_with(vars),
+            Expr::Apply(f, ref a) => a.eval_with(vars).apply(f),
+        }
+    }
+
+    /// Enclosures of Taylor coefficients of expression in variable `var`, up to `order`
+    ///
+    /// Coefficient `k` encloses `∂ᵏf / ∂xᵏ / k!` (with `x` being variable `var`) over all points
+    /// of box `x`, computed by recurrences of automatic differentiation. For a point box and
+    /// small `h` these give Taylor polynomial of `f` in the direction of `var`, and evaluated over
+    /// a box containing the whole step they enclose Lagrange remainder, so
+    /// `f(x + h) ∈ Σₖ₌₀ⁿ⁻¹ cₖ(x) hᵏ + cₙ(x + [0, h]) hⁿ`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    /// use inter::expr::Expr;
+    ///
+    /// // exp(sin x) = 1 + x + x²/2 - x⁴/8 - ...
+    /// let f = Expr::var(0).sin().exp();
+    /// let coeffs = f.taylor(&[Interval::exact(0.)], 0, 4);
+    ///
+    /// assert!(coeffs[2].contains(0.5) && coeffs[3].contains(0.) && coeffs[4].contains(-0.125));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This will panic if `x` has less than `arity()` coordinates.
+    pub fn taylor(&self, x: &[Interval<T>], var: usize, order: usize) -> Vec<Interval<T>> {
+        let len = order + 1;
+
+        match *self {
+            Expr::Constant(c) => series::constant(c, len),
+            Expr::Variable(i) => {
+                let mut coeffs = series::constant(x[i], len);
+                if i == var && len > 1 {
+                    coeffs[1] = Interval::exact(T::one());
+                }
+                coeffs
+            }
+            Expr::Neg(ref a) => a.taylor(x, var, order).into_iter().map(|c| -c).collect(),
+            Expr::Add(ref a, ref b) => {
+                let (a, b) = (a.taylor(x, var, order), b.taylor(x, var, order));
+                a.iter().zip(&b).map(|(&a, &b)| a + b).collect()
+            }
+            Expr::Sub(ref a, ref b) => {
+                let (a, b) = (a.taylor(x, var, order), b.taylor(x, var, order));
+                a.iter().zip(&b).map(|(&a, &b)| a - b).collect()
+            }
+            Expr::Mul(ref a, ref b) => {
+                series::product(&a.taylor(x, var, order), &b.taylor(x, var, order))
+            }
+            Expr::Div(ref a, ref b) => {
+                series::quotient(&a.taylor(x, var, order), &b.taylor(x, var, order))
+            }
+            Expr::Apply(f, ref a) => series::apply(f, &a.taylor(x, var, order)),
+        }
+    }
+
+    /// Enclosure of gradient of expression over box `x`, by reverse differentiation of compiled
+    /// tape
+    ///
+    /// Result has a component for every coordinate of `x`, zero for variables not used.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    /// use inter::expr::Expr;
+    ///
+    /// let f = Expr::var(0) * Expr::var(1) + Expr::var(0).powi(2);
+    /// let x = [Interval::exact(1.), Interval::with_range(1., 2.)];
+    ///
+    /// assert_eq!(f.gradient(&x), vec![Interval::with_range(3., 4.), Interval::exact(1.)]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This will panic if `x` has less than `arity()` coordinates.
+    pub fn gradient(&self, x: &[Interval<T>]) -> Vec<Interval<T>> {
+        self.compile().gradient(x).1
+    }
+
+    /// Enclosures of second derivatives `∂²f / ∂xᵢ²` over box `x`, for every coordinate
+    ///
+    /// Every derivative comes from second order Taylor coefficient along its variable, which
+    /// is forward differentiation applied twice.
+    ///
+    /// # Panics
+    ///
+    /// This will panic if `x` has less than `arity()` coordinates.
+    pub fn hessian_diagonal(&self, x: &[Interval<T>]) -> Vec<Interval<T>> {
+        let two = Interval::exact(T::one() + T::one());
+
+        (0..x.len()).map(|var| two * self.taylor(x, var, 2)[2]).collect()
+    }
+
+    /// Enclosure of Hessian matrix of expression over box `x`
+    ///
+    /// Value, gradient and Hessian of every subexpression are propagated together by forward
+    /// differentiation, which costs a multiple of `x.len()²` per operation.
+    ///
+    /// # Panics
+    ///
+    /// This will panic if `x` has less than `arity()` coordinates.
+    pub fn hessian(&self, x: &[Interval<T>]) -> IntervalMatrix<T> {
+        let n = x.len();
+
+        IntervalMatrix::new(n, n, self.jet(x).hessian)
+    }
+
+    /// Check if expression is convex over box `x`
+    ///
+    /// Function is certainly convex when every matrix in the enclosure of its Hessian is
+    /// diagonally dominant with non-negative diagonal, and so positive semidefinite. It is
+    /// certainly not convex when some second derivative `∂²f / ∂xᵢ²` is negative over the whole
+    /// box. Coordinates in which the box is degenerate are left out of both tests.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::{Interval, Tribool};
+    /// use inter::expr::Expr;
+    ///
+    /// let (x, y) = (Expr::var(0), Expr::var(1));
+    /// let f = x.clone().powi(2) + x.clone() * y.clone() + y.clone().powi(2) + x.exp();
+    /// let domain = [Interval::with_range(-1., 1.), Interval::with_range(-1., 1.)];
+    ///
+    /// assert_eq!(f.convexity(&domain), Tribool::True);
+    /// assert_eq!((-f).convexity(&domain), Tribool::False);
+    /// assert_eq!(y.sin().convexity(&domain), Tribool::Unknown);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This will panic if `x` has less than `arity()` coordinates.
+    pub fn convexity(&self, x: &[Interval<T>]) -> Tribool {
+        let n = x.len();
+        let hessian = self.hessian(x);
+        let zero = T::zero();
+
+        // Box does not extend in degenerate coordinates, so only the rest is checked
+        let free: Vec<_> = (0..n).filter(|&i| x[i].width() > zero).collect();
+
+        if free.iter().any(|&i| hessian[(i, i)].sup() < zero) {
+            return Tribool::False;
+        }
+
+        let dominant = free.iter().all(|&i| {
+            let off = free.iter()
+                .filter(|&&j| j != i)
+                .fold(zero, |sum, &j| sum.add_up(hessian[(i, j)].mag()));
+            hessian[(i, i)].inf() >= off
+        });
+
+        if dominant { Tribool::True } else { Tribool::Unknown }
+    }
+
+    /// Check if expression is concave over box `x`, see `convexity`
+    ///
+    /// # Panics
+    ///
+    /// This will panic if `x` has less than `arity()` coordinates.
+    pub fn concavity(&self, x: &[Interval<T>]) -> Tribool {
+        Expr::Neg(Rc::new(self.clone())).convexity(x)
+    }
+
+    /// McCormick relaxations of expression over box `x`, evaluated at `point` from the box
+    ///
+    /// Convex underestimator and concave overestimator of the expression are built over the
+    /// syntax tree from envelopes of products and of convex or concave elementary functions, with
+    /// curvature and monotonicity of functions decided from enclosures of their derivatives. Where
+    /// no envelope applies, the bounds of the range serve as constant relaxations. Relaxations are
+    /// values of functions of `point`, so they can be minimized as convex problems and are much
+    /// tighter than bounds over narrow boxes. Results are rounded outward.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    /// use inter::expr::Expr;
+    ///
+    /// let f = Expr::var(0) * Expr::var(1);
+    /// let square = [Interval::with_range(0., 1.); 2];
+    /// let relaxation = f.relax(&square, &[0.5, 0.5]);
+    ///
+    /// assert_eq!(relaxation.bounds, Interval::with_range(0., 1.));
+    /// assert_eq!((relaxation.convex, relaxation.concave), (0., 0.5));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This will panic if `x` or `point` has less than `arity()` coordinates.
+    pub fn relax(&self, x: &[Interval<T>], point: &[T]) -> Relaxation<T> {
+        match *self {
+            Expr::Constant(c) => Relaxation::new(c, c, c),
+            Expr::Variable(i) => {
+                let p = Interval::exact(point[i]);
+                Relaxation::new(x[i], p, p)
+            }
+            Expr::Neg(ref a) => -a.relax(x, point),
+            Expr::Add(ref a, ref b) => a.relax(x, point).add(b.relax(x, point)),
+            Expr::Sub(ref a, ref b) => a.relax(x, point).add(-b.relax(x, point)),
+            Expr::Mul(ref a, ref b) => a.relax(x, point).mul(b.relax(x, point)),
+            Expr::Div(ref a, ref b) => {
+                a.relax(x, point).mul(b.relax(x, point).apply(Function::Recip))
+            }
+            Expr::Apply(f, ref a) => a.relax(x, point).apply(f),
+        }
+    }
+
+    fn jet(&self, x: &[Interval<T>]) -> Jet<T> {
+        let n = x.len();
+        let zero = Interval::exact(T::zero());
+
+        match *self {
+            Expr::Constant(c) => Jet::constant(c, n),
+            Expr::Variable(i) => {
+                let mut jet = Jet::constant(x[i], n);
+                jet.gradient[i] = Interval::exact(T::one());
+                jet
+            }
+            Expr::Neg(ref a) => {
+                let zero = Jet::constant(zero, n);
+                zero.combine(&a.jet(x), |_, b| -b)
+            }
+            Expr::Add(ref a, ref b) => a.jet(x).combine(&b.jet(x), |a, b| a + b),
+            Expr::Sub(ref a, ref b) => a.jet(x).combine(&b.jet(x), |a, b| a - b),
+            Expr::Mul(ref a, ref b) => a.jet(x).mul(&b.jet(x)),
+            Expr::Div(ref a, ref b) => a.jet(x).mul(&b.jet(x).apply(Function::Recip)),
+            Expr::Apply(f, ref a) => a.jet(x).apply(f),
+        }
+    }
+}
+
+/// Value, gradient and Hessian (row by row) of expression
+struct Jet<T> {
+    value: Interval<T>,
+    gradient: Vec<Interval<T>>,
+    hessian: Vec<Interval<T>>,
+}
+
+impl<T> Jet<T>
+    where T: Float + FloatConst + RoundedArith
+{
+    fn constant(value: Interval<T>, n: usize) -> Self {
+        let zero = Interval::exact(T::zero());
+
+        Jet {
+            value,
+            gradient: vec![zero; n],
+            hessian: vec![zero; n * n],
+        }
+    }
+
+    /// Apply linear operation to all parts
+    fn combine<F>(&self, other: &Self, op: F) -> Self
+        where F: Fn(Interval<T>, Interval<T>) -> Interval<T>
+    {
+        let zip = |a: &[Interval<T>], b: &[Interval<T>]| {
+            a.iter().zip(b).map(|(&a, &b)| op(a, b)).collect()
+        };
+
+        Jet {
+            value: op(self.value, other.value),
+            gradient: zip(&self.gradient, &other.gradient),
+            hessian: zip(&self.hessian, &other.hessian),
+        }
+    }
+
+    /// Product rule, `(ab)'' = a'' b + a' b'ᵀ + b' a'ᵀ + a b''`
+    fn mul(&self, other: &Self) -> Self {
+        let n = self.gradient.len();
+        let (a, b) = (self, other);
+        let gradient = (0..n).map(|i| a.gradient[i] * b.value + a.value * b.gradient[i]).collect();
+        let hessian = (0..n * n)
+            .map(|k| {
+                let (i, j) = (k / n, k % n);
+                a.hessian[k] * b.value + a.value * b.hessian[k] +
+                a.gradient[i] * b.gradient[j] + b.gradient[i] * a.gradient[j]
+            })
+            .collect();
+
+        Jet {
+            value: a.value * b.value,
+            gradient,
+            hessian,
+        }
+    }
+
+    /// Chain rule, `f(a)'' = f''(a) a' a'ᵀ + f'(a) a''`
+    fn apply(&self, f: Function) -> Self {
+        let n = self.gradient.len();
+        let one = Interval::exact(T::one());
+        let d = f.derivative(self.value);
+        // Second Taylor coefficient of f(a + t) is f''(a) / 2
+        let taylor = series::apply(f, &[self.value, one, Interval::exact(T::zero())]);
+        let d2 = Interval::exact(T::one() + T::one()) * taylor[2];
+        let hessian = (0..n * n)
+            .map(|k| {
+                let (i, j) = (k / n, k % n);
+                d2 * self.gradient[i] * self.gradient[j] + d * self.hessian[k]
+            })
+            .collect();
+
+        Jet {
+            value: f.apply(self.value),
+            gradient: self.gradient.iter().map(|&g| d * g).collect(),
+            hessian,
+        }
+    }
+}
+
+/// McCormick relaxations of expression at a point, see `Expr::relax`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Relaxation<T> {
+    /// Enclosure of the range over the box
+    pub bounds: Interval<T>,
+    /// Value of convex underestimator, not below lower bound
+    pub convex: T,
+    /// Value of concave overestimator, not above upper bound
+    pub concave: T,
+}
+
+impl<T> Relaxation<T>
+    where T: Float + FloatConst + RoundedArith
+{
+    /// Relaxations from enclosures of their values, where empty ones fall back to bounds
+    fn new(bounds: Interval<T>, convex: Interval<T>, concave: Interval<T>) -> Self {
+        if bounds.is_empty() {
+            return Relaxation {
+                bounds,
+                convex: T::nan(),
+                concave: T::nan(),
+            };
+        }
+
+        let (lo, hi) = (bounds.inf(), bounds.sup());
+        Relaxation {
+            bounds,
+            convex: if convex.is_empty() { lo } else { lo.max(convex.inf()) },
+            concave: if concave.is_empty() { hi } else { hi.min(concave.sup()) },
+        }
+    }
+
+    fn add(self, other: Self) -> Self {
+        let (a, b) = (self, other);
+
+        Relaxation::new(a.bounds + b.bounds,
+                        Interval::exact(a.convex) + Interval::exact(b.convex),
+                        Interval::exact(a.concave) + Interval::exact(b.concave))
+    }
+
+    /// Envelope `ab ≥ max(b̲ a + a̲ b - a̲ b̲, b̅ a + a̅ b - a̅ b̅)`, and the converse one
+    fn mul(self, other: Self) -> Self {
+        let (a, b) = (self, other);
+        let bounds = a.bounds * b.bounds;
+        if !(a.bounds.is_bounded() && b.bounds.is_bounded()) || a.convex.is_nan() ||
+           b.convex.is_nan() {
+            return Relaxation::new(bounds, bounds, bounds);
+        }
+
+        let zero = T::zero();
+        let exact = Interval::exact;
+        // Relaxation of `c x` from the side of its lower or upper bound
+        let under = |c: T, x: &Self| exact(c) * exact(if c >= zero { x.convex } else { x.concave });
+        let over = |c: T, x: &Self| exact(c) * exact(if c >= zero { x.concave } else { x.convex });
+        let (al, au, bl, bu) = (a.bounds.inf(), a.bounds.sup(), b.bounds.inf(), b.bounds.sup());
+
+        let convex = (under(bl, &a) + under(al, &b) - exact(al) * exact(bl))
+            .inf()
+            .max((under(bu, &a) + under(au, &b) - exact(au) * exact(bu)).inf());
+        let concave = (over(bu, &a) + over(al, &b) - exact(al) * exact(bu))
+            .sup()
+            .min((over(bl, &a) + over(au, &b) - exact(au) * exact(bl)).sup());
+
+        Relaxation::new(bounds, exact(convex), exact(concave))
+    }
+
+    /// Composition with `f` convex or concave over bounds of the argument
+    ///
+    /// Convex `f` is underestimated by its value at the minimizer over `[convex, concave]` and
+    /// overestimated by the secant over bounds, and concave `f` the other way around.
+    fn apply(self, f: Function) -> Self {
+        let x = self.bounds;
+        let bounds = f.apply(x);
+        let fallback = Relaxation::new(bounds, bounds, bounds);
+        let (lo, hi) = (Interval::exact(x.inf()), Interval::exact(x.sup()));
+        if x.is_empty() || self.convex.is_nan() || !x.is_bounded() || x.width() <= T::zero() ||
+           f.apply(lo).is_empty() || f.apply(hi).is_empty() {
+            return fallback;
+        }
+
+        let zero = T::zero();
+        let (cv, cc) = (Interval::exact(self.convex), Interval::exact(self.concave));
+        let slope = f.derivative(x);
+        let (increasing, decreasing) = (slope.inf() >= zero, slope.sup() <= zero);
+        // Second Taylor coefficient of f(x + t) is f''(x) / 2
+        let one = Interval::exact(T::one());
+        let curvature = series::apply(f, &[x, one, Interval::exact(zero)])[2];
+        let secant = |fl: T, fu: T, z: Interval<T>| {
+            let (fl, fu) = (Interval::exact(fl), Interval::exact(fu));
+            fl + (fu - fl) * ((z - lo) / (hi - lo))
+        };
+
+        if curvature.inf() >= zero {
+            let (fl, fu) = (f.apply(lo).sup(), f.apply(hi).sup());
+            let convex = match f {
+                _ if increasing => f.apply(cv),
+                _ if decreasing => f.apply(cc),
+                // Even powers have minimum at zero
+                Function::Powi(n) if n > 0 && n % 2 == 0 => {
+                    f.apply(Interval::exact(zero.max(self.convex).min(self.concave)))
+                }
+                _ => bounds,
+            };
+            let concave = secant(fl, fu, if fu >= fl { cc } else { cv });
+
+            Relaxation::new(bounds, convex, concave)
+        } else if curvature.sup() <= zero {
+            let (fl, fu) = (f.apply(lo).inf(), f.apply(hi).inf());
+            let concave = if increasing {
+                f.apply(cc)
+            } else if decreasing {
+                f.apply(cv)
+            } else {
+                bounds
+            };
+            let convex = secant(fl, fu, if fu >= fl { cv } else { cc });
+
+            Relaxation::new(bounds, convex, concave)
+        } else {
+            fallback
+        }
+    }
+}
+
+impl<T> Neg for Relaxation<T>
+    where T: Neg<Output = T> + Copy
+{
+    type Output = Relaxation<T>;
+
+    fn neg(self) -> Self {
+        Relaxation {
+            bounds: -self.bounds,
+            convex: -self.concave,
+            concave: -self.convex,
+        }
+    }
+}
+
+impl<T> Expr<T>
+    where T: Float + FloatConst + RoundedArith
+{
+    /// Rewrite expression into equivalent one with enclosures never wider
+    ///
+    /// Equal subexpressions are merged into a single shared node, so `Cache` evaluates them
+    /// once and products of a subexpression with itself become `powi(2)`, which is never
+    /// negative. Operations on constants are folded into constant intervals computed the way
+    /// evaluation would compute them. Reassociation and factoring are not done, as with outward
+    /// rounding they can make enclosures looser.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    /// use inter::expr::Expr;
+    ///
+    /// let f = (Expr::var(0) + 1.) * (Expr::var(0) + 1.);
+    /// let x = [Interval::with_range(-3., 0.)];
+    ///
+    /// assert_eq!(f.eval(&x), Interval::with_range(-2., 4.));
+    /// assert_eq!(f.simplify().eval(&x), Interval::with_range(0., 4.));
+    /// assert_eq!(f.simplify(), (Expr::var(0) + 1.).powi(2));
+    /// ```
+    pub fn simplify(&self) -> Expr<T> {
+        let mut nodes = Vec::new();
+
+        (*rewrite(self, &mut nodes)).clone()
+    }
+}
+
+/// Simplify `expr` bottom-up, reusing equal nodes already in `nodes`
+fn rewrite<T>(expr: &Expr<T>, nodes: &mut Vec<Rc<Expr<T>>>) -> Rc<Expr<T>>
+    where T: Float + FloatConst + RoundedArith
+{
+    let node = match *expr {
+        Expr::Constant(_) | Expr::Variable(_) => expr.clone(),
+        Expr::Neg(ref a) => {
+            let a = rewrite(a, nodes);
+            match *a {
+                Expr::Constant(x) => Expr::Constant(-x),
+                _ => Expr::Neg(a),
+            }
+        }
+        Expr::Apply(f, ref a) => {
+            let a = rewrite(a, nodes);
+            match *a {
+                Expr::Constant(x) => Expr::Constant(f.apply(x)),
+                _ => Expr::Apply(f, a),
+            }
+        }
+        Expr::Add(ref a, ref b) |
+        Expr::Sub(ref a, ref b) |
+        Expr::Mul(ref a, ref b) |
+        Expr::Div(ref a, ref b) => {
+            let (a, b) = (rewrite(a, nodes), rewrite(b, nodes));
+            match (expr, &*a, &*b) {
+                (_, &Expr::Constant(x), &Expr::Constant(y)) => {
+                    Expr::Constant(match *expr {
+                        Expr::Add(..) => x + y,
+                        Expr::Sub(..) => x - y,
+                        Expr::Mul(..) => x * y,
+                        _ => x / y,
+                    })
+                }
+                (&Expr::Mul(..), _, _) if Rc::ptr_eq(&a, &b) => Expr::Apply(Function::Powi(2), a),
+                (&Expr::Add(..), _, _) => Expr::Add(a, b),
+                (&Expr::Sub(..), _, _) => Expr::Sub(a, b),
+                (&Expr::Mul(..), _, _) => Expr::Mul(a, b),
+                _ => Expr::Div(a, b),
+            }
+        }
+    };
+
+    if let Some(existing) = nodes.iter().find(|existing| same_node(existing, &node)) {
+        return existing.clone();
+    }
+    let node = Rc::new(node);
+    nodes.push(node.clone());
+
+    node
+}
+
+/// Check if nodes are equal, given that their operands are already merged
+fn same_node<T: PartialEq>(a: &Expr<T>, b: &Expr<T>) -> bool {
+    match (a, b) {
+        (Expr::Constant(x), Expr::Constant(y)) => x == y,
+        (Expr::Variable(i), Expr::Variable(j)) => i == j,
+        (Expr::Neg(x), Expr::Neg(y)) => Rc::ptr_eq(x, y),
+        (Expr::Apply(f, x), Expr::Apply(g, y)) => f == g && Rc::ptr_eq(x, y),
+        (Expr::Add(a, b), Expr::Add(c, d)) |
+        (Expr::Sub(a, b), Expr::Sub(c, d)) |
+        (Expr::Mul(a, b), Expr::Mul(c, d)) |
+        (Expr::Div(a, b), Expr::Div(c, d)) => {
+            Rc::ptr_eq(a, c) && Rc::ptr_eq(b, d)
+        }
+        _ => false,
+    }
+}
+
+/// Natural interval evaluation over a box, remembering enclosures of subexpressions
+///
+/// Enclosures are kept for every shared (`Rc`) subexpression together with variables it
+/// depends on, so repeated evaluation of expressions sharing subtrees, as in sweeps of
+/// constraint propagation, computes every subtree once. Contracting a variable of the box
+/// forgets only enclosures depending on it. Cache holds references to subexpressions it has
+/// seen, which keeps them alive until it is dropped.
+///
+/// ## Example
+///
+/// ```rust
+/// use inter::Interval;
+/// use inter::expr::{Cache, Expr};
+///
+/// let (x, y) = (Expr::var(0), Expr::var(1));
+/// let common = (x.clone() * y).exp();
+/// let (f, g) = (common.clone() + x.clone(), common - x);
+///
+/// let mut cache = Cache::new(vec![Interval::with_range(0., 1.), Interval::with_range(1., 2.)]);
+/// assert_eq!(cache.eval(&f), f.eval(cache.domain()));
+/// assert_eq!(cache.eval(&g), g.eval(cache.domain()));
+///
+/// cache.contract(1, Interval::with_range(0., 1.));
+/// assert_eq!(cache.eval(&f), f.eval(&[Interval::with_range(0., 1.), Interval::exact(1.)]));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Cache<T> {
+    domain: Vec<Interval<T>>,
+    entries: HashMap<*const Expr<T>, Entry<T>>,
+}
+
+#[derive(Clone, Debug)]
+struct Entry<T> {
+    // Keeps the address from being reused by another expression
+    _node: Rc<Expr<T>>,
+    value: Interval<T>,
+    vars: Vec<usize>,
+}
+
+impl<T> Cache<T>
+    where T: Float + FloatConst + RoundedArith
+{
+    /// Empty cache for evaluation over `domain`
+    pub fn new(domain: Vec<Interval<T>>) -> Self {
+        Cache {
+            domain,
+            entries: HashMap::new(),
+        }
+    }
+
+    /// Current box
+    pub fn domain(&self) -> &[Interval<T>] {
+        &self.domain
+    }
+
+    /// Number of remembered subexpressions
+    pub fn cached(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Intersect variable `var` of the box with `x`, returning its new domain
+    ///
+    /// # Panics
+    ///
+    /// This will panic if `var` is not a variable of the box.
+    pub fn contract(&mut self, var: usize, x: Interval<T>) -> Interval<T> {
+        let contracted = self.domain[var].intersect(&x);
+        self.set(var, contracted);
+
+        contracted
+    }
+
+    /// Replace variable `var` of the box with `x`
+    ///
+    /// # Panics
+    ///
+    /// This will panic if `var` is not a variable of the box.
+    pub fn set(&mut self, var: usize, x: Interval<T>) {
+        if self.domain[var] != x {
+            self.domain[var] = x;
+            self.entries.retain(|_, entry| !entry.vars.contains(&var));
+        }
+    }
+
+    /// Enclose range of expression over the box with plain interval arithmetic
+    ///
+    /// Unlike `Expr::eval` this ignores strategy in effect, as enclosures of other
+    /// strategies depend on whole evaluation and cannot be reused.
+    ///
+    /// # Panics
+    ///
+    /// This will panic if the box has less than `arity()` variables.
+    pub fn eval(&mut self, expr: &Expr<T>) -> Interval<T> {
+        self.eval_node(expr).0
+    }
+
+    fn eval_shared(&mut self, node: &Rc<Expr<T>>) -> (Interval<T>, Vec<usize>) {
+        let key = &**node as *const Expr<T>;
+        if let Some(entry) = self.entries.get(&key) {
+            return (entry.value, entry.vars.clone());
+        }
+
+        let (value, vars) = self.eval_node(node);
+        if let Expr::Constant(_) | Expr::Variable(_) = **node {
+            return (value, vars);
+        }
+        self.entries.insert(key,
+                            Entry {
+                                _node: node.clone(),
+                                value,
+                                vars: vars.clone(),
+                            });
+
+        (value, vars)
+    }
+
+    fn eval_node(&mut self, expr: &Expr<T>) -> (Interval<T>, Vec<usize>) {
+        let merge = |mut a: Vec<usize>, b: Vec<usize>| {
+            for var in b {
+                if !a.contains(&var) {
+                    a.push(var);
+                }
+            }
+            a
+        };
+
+        match *expr {
+            Expr::Constant(x) => (x, Vec::new()),
+            Expr::Variable(i) => (self.domain[i], vec![i]),
+            Expr::Neg(ref a) => {
+                let (a, vars) = self.eval_shared(a);
+                (-a, vars)
+            }
+            Expr::Apply(f, ref a) => {
+                let (a, vars) = self.eval_shared(a);
+                (f.apply(a), vars)
+            }
+            Expr::Add(ref a, ref b) |
+            Expr::Sub(ref a, ref b) |
+            Expr::Mul(ref a, ref b) |
+            Expr::Div(ref a, ref b) => {
+                let ((a, left), (b, right)) = (self.eval_shared(a), self.eval_shared(b));
+                let value = match *expr {
+                    Expr::Add(..) => a + b,
+                    Expr::Sub(..) => a - b,
+                    Expr::Mul(..) => a * b,
+                    _ => a / b,
+                };
+                (value, merge(left, right))
+            }
+        }
+    }
+}
+
+/// Expression compiled to a flat list of operations
+///
+/// Every operation reads results of earlier ones by index, so evaluation is a single loop over
+/// the list without recursion or chasing pointers, and shared subexpressions are computed once.
+/// Like `Cache` it uses plain interval arithmetic, which gives the same enclosures as natural
+/// evaluation of the expression.
+///
+/// ## Example
+///
+/// ```rust
+/// use inter::Interval;
+/// use inter::expr::Expr;
+///
+/// let (x, y) = (Expr::var(0), Expr::var(1));
+/// let f = (x.clone() * y.clone()).sin() + x / y;
+/// let tape = f.compile();
+///
+/// let domain = [Interval::with_range(1., 2.), Interval::with_range(3., 4.)];
+/// assert_eq!(tape.eval(&domain), f.eval(&domain));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Tape<T> {
+    ops: Vec<Op<T>>,
+    arity: usize,
+}
+
+/// Operation of tape, with operands given by indices of earlier operations
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Op<T> {
+    Constant(Interval<T>),
+    Variable(usize),
+    Neg(usize),
+    Add(usize, usize),
+    Sub(usize, usize),
+    Mul(usize, usize),
+    Div(usize, usize),
+    Apply(Function, usize),
+}
+
+impl<T> Expr<T>
+    where T: Copy
+{
+    /// Compile expression to a tape of operations, for cheaper repeated evaluation
+    pub fn compile(&self) -> Tape<T> {
+        let mut tape = Tape {
+            ops: Vec::new(),
+            arity: self.arity(),
+        };
+        tape.push(self, &mut HashMap::new());
+
+        tape
+    }
+}
+
+impl<T> Tape<T>
+    where T: Copy
+{
+    /// Number of variables needed to evaluate the tape
+    pub fn arity(&self) -> usize {
+        self.arity
+    }
+
+    /// Append operations of `expr`, returning index of its result
+    fn push(&mut self, expr: &Expr<T>, seen: &mut HashMap<*const Expr<T>, usize>) -> usize {
+        let op = match *expr {
+            Expr::Constant(x) => Op::Constant(x),
+            Expr::Variable(i) => Op::Variable(i),
+            Expr::Neg(ref a) => Op::Neg(self.push_shared(a, seen)),
+            Expr::Apply(f, ref a) => Op::Apply(f, self.push_shared(a, seen)),
+            Expr::Add(ref a, ref b) |
+            Expr::Sub(ref a, ref b) |
+            Expr::Mul(ref a, ref b) |
+            Expr::Div(ref a, ref b) => {
+                let (a, b) = (self.push_shared(a, seen), self.push_shared(b, seen));
+                match *expr {
+                    Expr::Add(..) => Op::Add(a, b),
+                    Expr::Sub(..) => Op::Sub(a, b),
+                    Expr::Mul(..) => Op::Mul(a, b),
+                    _ => Op::Div(a, b),
+                }
+            }
+        };
+        self.ops.push(op);
+
+        self.ops.len() - 1
+    }
+
+    fn push_shared(&mut self, node: &Rc<Expr<T>>, seen: &mut HashMap<*const Expr<T>, usize>)
+                   -> usize {
+        // Expression is borrowed for the whole compilation, so addresses are not reused
+        let key = &**node as *const Expr<T>;
+        if let Some(&index) = seen.get(&key) {
+            return index;
+        }
+
+        let index = self.push(node, seen);
+        seen.insert(key, index);
+
+        index
+    }
+}
+
+impl<T> Tape<T>
+    where T: Float + FloatConst + RoundedArith
+{
+    /// Enclose range of compiled expression over box `x`
+    ///
+    /// # Panics
+    ///
+    /// This will panic if `x` has less than `arity()` coordinates.
+    pub fn eval(&self, x: &[Interval<T>]) -> Interval<T> {
+        let mut values: Vec<Interval<T>> = Vec::with_capacity(self.ops.len());
+
+        for op in &self.ops {
+            let value = match *op {
+                Op::Constant(c) => c,
+                Op::Variable(i) => x[i],
+                Op::Neg(a) => -values[a],
+                Op::Add(a, b) => values[a] + values[b],
+                Op::Sub(a, b) => values[a] - values[b],
+                Op::Mul(a, b) => values[a] * values[b],
+                Op::Div(a, b) => values[a] / values[b],
+                Op::Apply(f, a) => f.apply(values[a]),
+            };
+            values.push(value);
+        }
+
+        values[values.len() - 1]
+    }
+
+    /// Enclosures of value and gradient of compiled expression over box `x`
+    ///
+    /// Adjoints of operations are accumulated from the result back to variables, so the whole
+    /// gradient costs a small multiple of a single evaluation.
+    ///
+    /// # Panics
+    ///
+    /// This will panic if `x` has less than `arity()` coordinates.
+    pub fn gradient(&self, x: &[Interval<T>]) -> (Interval<T>, Vec<Interval<T>>) {
+        let zero = Interval::exact(T::zero());
+        let mut values: Vec<Interval<T>> = Vec::with_capacity(self.ops.len());
+        for op in &self.ops {
+            let value = match *op {
+                Op::Constant(c) => c,
+                Op::Variable(i) => x[i],
+                Op::Neg(a) => -values[a],
+                Op::Add(a, b) => values[a] + values[b],
+                Op::Sub(a, b) => values[a] - values[b],
+                Op::Mul(a, b) => values[a] * values[b],
+                Op::Div(a, b) => values[a] / values[b],
+                Op::Apply(f, a) => f.apply(values[a]),
+            };
+            values.push(value);
+        }
+
+        let last = self.ops.len() - 1;
+        let mut adjoints = vec![zero; self.ops.len()];
+        adjoints[last] = Interval::exact(T::one());
+        let mut gradient = vec![zero; x.len()];
+        for (i, op) in self.ops.iter().enumerate().rev() {
+            let adjoint = adjoints[i];
+            match *op {
+                Op::Constant(_) => {}
+                Op::Variable(j) => gradient[j] += adjoint,
+                Op::Neg(a) => adjoints[a] -= adjoint,
+                Op::Add(a, b) => {
+                    adjoints[a] += adjoint;
+                    adjoints[b] += adjoint;
+                }
+                Op::Sub(a, b) => {
+                    adjoints[a] += adjoint;
+                    adjoints[b] -= adjoint;
+                }
+                Op::Mul(a, b) => {
+                    adjoints[a] += adjoint * values[b];
+                    adjoints[b] += adjoint * values[a];
+                }
+                Op::Div(a, b) => {
+                    let d = adjoint / values[b];
+                    adjoints[a] += d;
+                    adjoints[b] -= d * values[i];
+                }
+                Op::Apply(f, a) => adjoints[a] += adjoint * f.derivative(values[a]),
+            }
+        }
+
+        (values[last], gradient)
+    }
+}
+
+/// Environment of named variables with their domains
+///
+/// Variables get indices in order they are declared, so expressions built from `var` can be
+/// evaluated, cached or compiled as usual, and errors tell which variable has no domain.
+/// Overrides of `scoped` let the same expression be evaluated over many boxes.
+///
+/// ## Example
+///
+/// ```rust
+/// use inter::Interval;
+/// use inter::expr::Env;
+///
+/// let mut env = Env::new();
+/// let x = env.bind("x", Interval::with_range(0., 1.));
+/// let f = x * env.var("y");
+/// assert_eq!(env.eval(&f).unwrap_err().to_string(), "variable `y` has no domain");
+///
+/// env.bind("y", Interval::with_range(2., 3.));
+/// assert_eq!(env.eval(&f), Ok(Interval::with_range(0., 3.)));
+///
+/// let narrow = env.scoped(&[("x", Interval::exact(1.))], |env| env.eval(&f));
+/// assert_eq!(narrow, Ok(Interval::with_range(2., 3.)));
+/// assert_eq!(env.get("x"), Some(Interval::with_range(0., 1.)));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Env<T> {
+    names: Vec<String>,
+    domains: Vec<Option<Interval<T>>>,
+}
+
+/// Error returned when expression uses variable without domain
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnboundVariable {
+    /// Index of variable
+    pub index: usize,
+    /// Name of variable, `None` when it was not declared in environment
+    pub name: Option<String>,
+}
+
+impl fmt::Display for UnboundVariable {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self.name {
+            Some(ref name) => write!(fmt, "variable `{}` has no domain", name),
+            None => write!(fmt, "variable {} is not declared", self.index),
+        }
+    }
+}
+
+impl error::Error for UnboundVariable {}
+
+impl<T> Env<T>
+    where T: Copy
+{
+    /// Environment without variables
+    pub fn new() -> Self {
+        Env {
+            names: Vec::new(),
+            domains: Vec::new(),
+        }
+    }
+
+    /// Variable `name`, declared without domain if it is new
+    pub fn var(&mut self, name: &str) -> Expr<T> {
+        Expr::var(self.declare(name))
+    }
+
+    /// Variable `name` with domain `x`, declared if it is new
+    pub fn bind(&mut self, name: &str, x: Interval<T>) -> Expr<T> {
+        let index = self.declare(name);
+        self.domains[index] = Some(x);
+
+        Expr::var(index)
+    }
+
+    /// Index of variable `name`
+    pub fn index(&self, name: &str) -> Option<usize> {
+        self.names.iter().position(|n| n == name)
+    }
+
+    /// Name of variable with index `i`
+    pub fn name(&self, i: usize) -> Option<&str> {
+        self.names.get(i).map(|name| &name[..])
+    }
+
+    /// Domain of variable `name`
+    pub fn get(&self, name: &str) -> Option<Interval<T>> {
+        self.index(name).and_then(|i| self.domains[i])
+    }
+
+    /// Run `f` with domains of variables replaced by `overrides`, restoring them afterwards
+    ///
+    /// Variables of `overrides` that were not declared yet stay declared, without domain.
+    pub fn scoped<F, R>(&mut self, overrides: &[(&str, Interval<T>)], f: F) -> R
+        where F: FnOnce(&mut Env<T>) -> R
+    {
+        let saved: Vec<_> = overrides.iter()
+            .map(|&(name, x)| {
+                let index = self.declare(name);
+                let old = self.domains[index];
+                self.domains[index] = Some(x);
+                (index, old)
+            })
+            .collect();
+
+        let result = f(self);
+
+        // Restore in reverse, so the oldest domain wins for variables overridden twice
+        for &(index, old) in saved.iter().rev() {
+            self.domains[index] = old;
+        }
+
+        result
+    }
+
+    /// Box of domains of all variables, in order of indices
+    pub fn domain(&self) -> Result<Vec<Interval<T>>, UnboundVariable> {
+        (0..self.domains.len()).map(|i| self.lookup(i)).collect()
+    }
+
+    fn declare(&mut self, name: &str) -> usize {
+        self.index(name).unwrap_or_else(|| {
+            self.names.push(name.to_string());
+            self.domains.push(None);
+            self.names.len() - 1
+        })
+    }
+
+    fn lookup(&self, i: usize) -> Result<Interval<T>, UnboundVariable> {
+        match self.domains.get(i) {
+            Some(&Some(x)) => Ok(x),
+            _ => {
+                Err(UnboundVariable {
+                    index: i,
+                    name: self.name(i).map(str::to_string),
+                })
+            }
+        }
+    }
+
+    /// Check that every variable of `expr` has a domain
+    fn check(&self, expr: &Expr<T>) -> Result<(), UnboundVariable> {
+        match *expr {
+            Expr::Constant(_) => Ok(()),
+            Expr::Variable(i) => self.lookup(i).map(|_| ()),
+            Expr::Neg(ref a) | Expr::Apply(_, ref a) => self.check(a),
+            Expr::Add(ref a, ref b) |
+            Expr::Sub(ref a, ref b) |
+            Expr::Mul(ref a, ref b) |
+            Expr::Div(ref a, ref b) => self.check(a).and_then(|_| self.check(b)),
+        }
+    }
+}
+
+impl<T> Env<T>
+    where T: Float + FloatConst + RoundedArith
+{
+    /// Enclose range of `expr` over domains of variables, using strategy in effect
+    pub fn eval(&self, expr: &Expr<T>) -> Result<Interval<T>, UnboundVariable> {
+        self.check(expr)?;
+
+        // Variables without domain are not used by `expr`
+        let domain: Vec<_> = self.domains
+            .iter()
+            .map(|x| x.unwrap_or_else(Interval::entire))
+            .collect();
+
+        Ok(expr.eval(&domain))
+    }
+}
+
+impl<T> From<T> for Expr<T>
+    where T: Copy
+{
+    fn from(value: T) -> Self {
+        Expr::Constant(Interval::from(value))
+    }
+}
+
+macro_rules! expr_ops {
+    ($($imp:ident, $method:ident;)*) => {
+        $(
+            impl<T> $imp for Expr<T> {
+                type Output = Expr<T>;
+
+                fn $method(self, other: Self) -> Self {
+                    Expr::$imp(Rc::new(self), Rc::new(other))
+                }
+            }
+
+            impl<T> $imp<T> for Expr<T>
+                where T: Copy
+            {
+                type Output = Expr<T>;
+
+                fn $method(self, other: T) -> Self {
+                    self.$method(Expr::from(other))
+                }
+            }
+        )*
+    };
+}
+
+expr_ops! {
+    Add, add;
+    Sub, sub;
+    Mul, mul;
+    Div, div;
+}
+
+impl<T> Neg for Expr<T> {
+    type Output = Expr<T>;
+
+    fn neg(self) -> Self {
+        Expr::Neg(Rc::new(self))
+    }
+}
+
+/// Recurrences for Taylor coefficients, where slice `a` holds coefficients of `a(t)`
+mod series {
+    use std::ops::Range;
+
+    use num::{Float, FloatConst};
+
+    use super::Function;
+    use interval::Interval;
+    use rounding::RoundedArith;
+
+    fn weight(j: usize) -> u64 {
+        j as u64
+    }
+
+    pub fn constant<T: Float>(c: Interval<T>, len: usize) -> Vec<Interval<T>> {
+        let mut coeffs = vec![Interval::exact(T::zero()); len];
+        if len > 0 {
+            coeffs[0] = c;
+        }
+        coeffs
+    }
+
+    /// `Σ wⱼ aⱼ bₖ₋ⱼ` for integer weights `w`, over `j` from `js`
+    fn convolution<T, W>(a: &[Interval<T>], b: &[Interval<T>], k: usize, js: Range<usize>, w: W)
+                         -> Interval<T>
+        where T: Float + RoundedArith,
+              W: Fn(usize) -> u64
+    {
+        js.fold(Interval::exact(T::zero()),
+                |acc, j| acc + (a[j] * b[k - j]).mul_by_uint(w(j)))
+    }
+
+    pub fn product<T>(a: &[Interval<T>], b: &[Interval<T>]) -> Vec<Interval<T>>
+        where T: Float + RoundedArith
+    {
+        (0..a.len()).map(|k| convolution(a, b, k, 0..k + 1, |_| 1)).collect()
+    }
+
+    /// `c = a / b`, from `a = b c`
+    pub fn quotient<T>(a: &[Interval<T>], b: &[Interval<T>]) -> Vec<Interval<T>>
+        where T: Float + RoundedArith
+    {
+        let mut c: Vec<Interval<T>> = Vec::with_capacity(a.len());
+        for k in 0..a.len() {
+            let known = (0..k).fold(Interval::exact(T::zero()), |acc, j| acc + c[j] * b[k - j]);
+            c.push((a[k] - known) / b[0]);
+        }
+        c
+    }
+
+    pub fn apply<T>(f: Function, a: &[Interval<T>]) -> Vec<Interval<T>>
+        where T: Float + FloatConst + RoundedArith
+    {
+        let len = a.len();
+        let one = constant(Interval::exact(T::one()), len);
+        let mut c = constant(f.apply(a[0]), len);
+
+        match f {
+            // c' = a' c
+            Function::Exp => {
+                for k in 1..len {
+                    c[k] = convolution(a, &c, k, 1..k + 1, weight).div_by_uint(k as u64);
+                }
+            }
+            // a' = a c', so k aₖ = Σ j cⱼ aₖ₋ⱼ
+            Function::Ln => {
+                for k in 1..len {
+                    let known = (1..k).fold(Interval::exact(T::zero()),
+                                            |acc, j| acc + (c[j] * a[k - j]).mul_by_uint(j as u64));
+                    c[k] = (a[k] - known.div_by_uint(k as u64)) / a[0];
+                }
+            }
+            // a = c²
+            Function::Sqrt => {
+                let twice = c[0].mul_pow2(1);
+                for k in 1..len {
+                    c[k] = (a[k] - convolution(&c, &c, k, 1..k, |_| 1)) / twice;
+                }
+            }
+            Function::Sin | Function::Cos => {
+                let (mut sin, mut cos) = (constant(a[0].sin(), len), constant(a[0].cos(), len));
+                for k in 1..len {
+                    sin[k] = convolution(a, &cos, k, 1..k + 1, weight).div_by_uint(k as u64);
+                    cos[k] = -convolution(a, &sin, k, 1..k + 1, weight).div_by_uint(k as u64);
+                }
+                c = if f == Function::Sin { sin } else { cos };
+            }
+            Function::Powi(n) => {
+                let (mut power, mut base) = (one.clone(), a.to_vec());
+                let mut exp = n.unsigned_abs();
+                while exp > 0 {
+                    if exp % 2 == 1 {
+                        power = product(&power, &base);
+                    }
+                    base = product(&base, &base);
+                    exp /= 2;
+                }
+                c = if n < 0 { quotient(&one, &power) } else { power };
+            }
+            Function::Recip => c = quotient(&one, a),
+        }
+
+        c
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn derivatives() {
+        let x = Interval::with_range(1., 2.);
+        let functions = [Function::Sqrt, Function::Exp, Function::Ln, Function::Sin,
+                         Function::Cos, Function::Powi(3), Function::Recip];
+
+        // Difference quotients over small subintervals lie in the derivative enclosure
+        for &f in &functions {
+            let derivative = f.derivative(x);
+            for piece in x.subdivide(16) {
+                let (a, b) = (f.apply(Interval::exact(piece.start)).mid(),
+                              f.apply(Interval::exact(piece.end)).mid());
+                assert!(derivative.contains((b - a) / piece.width()), "{:?}", f);
+            }
+        }
+        assert_eq!(Function::Powi(0).derivative(x), Interval::exact(0.));
+    }
+
+    #[test]
+    fn taylor_coefficients() {
+        let x = Expr::var(0);
+        let zero = [Interval::exact(0.)];
+        let check = |f: Expr<f64>, expected: &[f64]| {
+            let coeffs = f.taylor(&zero, 0, expected.len() - 1);
+            for (c, &e) in coeffs.iter().zip(expected) {
+                assert!(c.contains(e) && c.width() < 1e-14, "{:?} {:?}", coeffs, expected);
+            }
+        };
+
+        check(x.clone().sin().exp(), &[1., 1., 0.5, 0., -0.125, -1. / 15.]);
+        check(x.clone().cos(), &[1., 0., -0.5, 0., 1. / 24.]);
+        check((x.clone() + 1.).sqrt(), &[1., 0.5, -0.125, 0.0625]);
+        check((x.clone() + 1.).ln(), &[0., 1., -0.5, 1. / 3., -0.25]);
+        check(Expr::from(1.) / (Expr::from(1.) - x.clone()), &[1., 1., 1., 1.]);
+        check((x.clone() + 1.).powi(3), &[1., 3., 3., 1., 0.]);
+        check((x.clone() + 1.).powi(-1), &[1., -1., 1., -1.]);
+        check((x.clone() + 2.).apply(Function::Recip), &[0.5, -0.25, 0.125]);
+
+        // Other variables are constant, and the box is covered
+        let y = Expr::var(1);
+        let f = x.clone() * y.clone() * y;
+        assert_eq!(f.taylor(&[Interval::exact(2.), Interval::exact(3.)], 1, 3),
+                   vec![Interval::exact(18.), Interval::exact(12.), Interval::exact(2.),
+                        Interval::exact(0.)]);
+        let wide = x.clone().exp().taylor(&[Interval::with_range(0., 1.)], 0, 2);
+        assert!(wide[2].contains_all(&[0.5, 1f64.exp() / 2.]));
+        assert_eq!(x.taylor(&zero, 0, 0), vec![Interval::exact(0.)]);
+    }
+
+    #[test]
+    fn taylor_remainder() {
+        let f = Expr::var(0).sin();
+        let h = 0.1;
+        let (order, x0) = (5, Interval::exact(0.3));
+
+        // Taylor polynomial at x0 plus remainder over the step encloses the value
+        let coeffs = f.taylor(&[x0], 0, order);
+        let remainder = f.taylor(&[x0 + Interval::with_range(0., h)], 0, order)[order];
+        let h = Interval::exact(h);
+        let value = coeffs[..order]
+            .iter()
+            .rev()
+            .fold(remainder, |acc, &c| acc * h + c);
+        assert!(value.contains(0.4f64.sin()) && value.width() < 1e-7);
+    }
+
+    #[test]
+    fn cached_evaluation() {
+        let (x, y, z) = (Expr::var(0), Expr::var(1), Expr::var(2));
+        let common = (x.clone() * y.clone()).sin() + Expr::from(1.);
+        let f = common.clone() * common.clone() - z.clone().exp();
+        let g = common * z.clone() + y;
+        let domain = vec![Interval::with_range(-1., 2.), Interval::with_range(0.5, 3.),
+                          Interval::with_range(-2., 0.)];
+
+        let mut cache = Cache::new(domain.clone());
+        assert_eq!(cache.eval(&f), f.eval(&domain));
+        let after_f = cache.cached();
+        assert_eq!(cache.eval(&g), g.eval(&domain));
+        // sin(x y) + 1 and its subtrees are reused
+        assert_eq!(cache.cached(), after_f + 2);
+
+        // Contraction forgets only dependent enclosures, here of exp(z) and common * z
+        let contracted = cache.contract(2, Interval::with_range(-1., 5.));
+        assert_eq!(contracted, Interval::with_range(-1., 0.));
+        assert_eq!(cache.cached(), after_f);
+        let mut domain = domain;
+        domain[2] = contracted;
+        assert_eq!(cache.eval(&f), f.eval(&domain));
+        assert_eq!(cache.eval(&g), g.eval(&domain));
+
+        cache.contract(0, Interval::with_range(0., 1.));
+        // Only exp(z) does not depend on x
+        assert_eq!(cache.cached(), 1);
+        domain[0] = Interval::with_range(0., 1.);
+        assert_eq!(cache.eval(&g), g.eval(&domain));
+        assert_eq!(cache.domain(), &domain[..]);
+
+        // Setting the same domain keeps everything
+        let cached = cache.cached();
+        cache.set(1, domain[1]);
+        assert_eq!(cache.cached(), cached);
+    }
+
+    #[test]
+    fn simplification() {
+        let (x, y) = (Expr::var(0), Expr::var(1));
+        let square = |e: Expr<f64>| e.clone() * e;
+        let f = square(x.clone().sin() + y.clone()) - (Expr::from(2.) * 3.).exp() / x.clone() +
+                (y.clone() - -Expr::from(1.)).sqrt() * (y.clone() + 1.).sqrt();
+        let simplified = f.simplify();
+
+        // Enclosures are never wider, and tighter for squares
+        let boxes = [[Interval::with_range(-1., 2.), Interval::with_range(-0.5, 3.)],
+                     [Interval::with_range(0.1, 0.2), Interval::with_range(-3., -1.)],
+                     [Interval::exact(0.5), Interval::exact(0.25)]];
+        for b in &boxes {
+            assert!(simplified.eval(b).subset(&f.eval(b)));
+        }
+        let b = [Interval::with_range(-1., 1.), Interval::with_range(-2., 2.)];
+        assert!(square(x.clone() + y.clone()).simplify().eval(&b).inf() == 0.);
+
+        // Constants are folded and equal subtrees shared
+        assert_eq!((Expr::from(2.) * 3. + x.clone()).simplify(), Expr::from(6.) + x.clone());
+        assert_eq!((-Expr::from(1.)).exp().simplify(),
+                   Expr::constant(Interval::exact(-1.).exp()));
+        match (x.clone().sin() + x.clone().sin()).simplify() {
+            Expr::Add(ref a, ref b) => assert!(Rc::ptr_eq(a, b)),
+            ref e => panic!("{:?}", e),
+        }
+        assert_eq!(x.simplify(), x);
+        assert_eq!((x.clone() * y.clone()).simplify(), x * y);
+    }
+
+    #[test]
+    fn compiled() {
+        let (x, y) = (Expr::var(0), Expr::var(1));
+        let common = (x.clone() - y.clone()).exp();
+        let f = common.clone() * common / (x.clone().powi(3) + 2.) - -y.clone().sqrt().ln();
+        let tape = f.compile();
+
+        for b in &[[Interval::with_range(-1., 2.), Interval::with_range(0.5, 3.)],
+                   [Interval::exact(0.25), Interval::exact(4.)],
+                   [Interval::with_range(-2., -1.), Interval::with_range(-1., 1.)]] {
+            assert_eq!(tape.eval(b), f.eval(b));
+        }
+        assert_eq!(tape.arity(), 2);
+
+        // Shared x - y is compiled once, and merged equal subtrees are too
+        assert_eq!(tape.ops.len(), 16);
+        let simplified = f.simplify().compile();
+        assert_eq!(simplified.ops.len(), 13);
+        assert!(simplified.eval(&[Interval::exact(1.), Interval::exact(2.)])
+            .subset(&tape.eval(&[Interval::exact(1.), Interval::exact(2.)])));
+        assert_eq!(Expr::from(1.).compile().eval(&[]), Interval::exact(1.));
+        assert_eq!(y.compile().arity(), 2);
+    }
+
+    #[test]
+    fn build_and_eval() {
+        let x = Expr::var(0);
+        let y = Expr::var(2);
+        let f = x.clone() * y / 2. - -x.powi(2);
+
+        assert_eq!(f.arity(), 3);
+        let domain = [Interval::with_range(1., 2.), Interval::exact(0.), Interval::exact(3.)];
+        assert_eq!(f.eval(&domain), Interval::with_range(2.5, 7.));
+        assert_eq!(Expr::constant(Interval::with_range(1., 2.)).eval(&[]),
+                   Interval::with_range(1., 2.));
+    }
+
+    #[test]
+    fn environment() {
+        let mut env = Env::new();
+        let x = env.bind("x", Interval::with_range(-1., 2.));
+        let y = env.var("y");
+        let f = x.clone() * x + Expr::from(1.);
+
+        // Unused variable does not need a domain
+        assert_eq!(env.eval(&f), Ok(Interval::with_range(-1., 5.)));
+        assert_eq!((env.index("y"), env.name(0), env.get("y")), (Some(1), Some("x"), None));
+        let missing = UnboundVariable {
+            index: 1,
+            name: Some("y".to_string()),
+        };
+        assert_eq!(env.eval(&(f.clone() + y.clone())), Err(missing.clone()));
+        assert_eq!(env.domain(), Err(missing));
+        assert_eq!(Env::<f64>::new().eval(&Expr::var(3)).unwrap_err().to_string(),
+                   "variable 3 is not declared");
+
+        // Many boxes with the same tree, overrides are undone even if nested
+        let boxes: Vec<_> = (0..4)
+            .map(|i| {
+                let x = Interval::exact(i as f64);
+                let g = y.clone() + f.clone();
+                env.scoped(&[("x", x), ("y", x)], |env| {
+                    env.scoped(&[("y", Interval::exact(0.))], |env| env.eval(&g))
+                })
+            })
+            .collect();
+        assert_eq!(boxes, vec![Ok(Interval::exact(1.)), Ok(Interval::exact(2.)),
+                               Ok(Interval::exact(5.)), Ok(Interval::exact(10.))]);
+        assert_eq!(env.get("x"), Some(Interval::with_range(-1., 2.)));
+        assert_eq!(env.get("y"), None);
+
+        env.bind("y", Interval::exact(3.));
+        env.scoped(&[("z", Interval::exact(1.)), ("z", Interval::exact(2.))], |env| {
+            assert_eq!(env.get("z"), Some(Interval::exact(2.)));
+        });
+        assert_eq!(env.get("z"), None);
+        env.bind("z", Interval::exact(0.));
+        let domain = env.domain().unwrap();
+        assert_eq!(env.eval(&(f.clone() + y)), Ok(f.compile().eval(&domain) + Interval::exact(3.)));
+    }
+
+    #[test]
+    fn gradient_and_hessian() {
+        // f = x² y + sin x, with shared subexpression
+        let (x, y) = (Expr::var(0), Expr::var(1));
+        let square = x.clone() * x.clone();
+        let f = square.clone() * y + x.sin() + square;
+        let df = |x: f64, y: f64| [2. * x * y + x.cos() + 2. * x, x * x];
+        let d2f = |x: f64, y: f64| [2. * y - x.sin() + 2., 0.];
+
+        let domain = [Interval::with_range(-1., 0.5), Interval::with_range(2., 3.)];
+        let (value, gradient) = f.compile().gradient(&domain);
+        let hessian = f.hessian_diagonal(&domain);
+        assert_eq!(value, f.compile().eval(&domain));
+        assert_eq!(gradient, f.gradient(&domain));
+        for i in 0..=4 {
+            for j in 0..=4 {
+                let (a, b) = (-1. + 0.375 * i as f64, 2. + 0.25 * j as f64);
+                for k in 0..2 {
+                    assert!(gradient[k].contains(df(a, b)[k]) && hessian[k].contains(d2f(a, b)[k]));
+                }
+            }
+        }
+
+        // Point box gives tight derivatives, unused variables zero
+        let point = [Interval::exact(0.5), Interval::exact(2.), Interval::exact(7.)];
+        let gradient = f.gradient(&point);
+        assert!(gradient[0].contains(df(0.5, 2.)[0]) && gradient[0].width() < 1e-15);
+        assert_eq!(&gradient[1..], &[Interval::exact(0.25), Interval::exact(0.)]);
+        assert_eq!(f.hessian_diagonal(&point)[1..], [Interval::exact(0.); 2]);
+
+        let g = Expr::from(1.) / Expr::var(0) - Expr::var(0).ln();
+        assert_eq!(g.gradient(&[Interval::exact(2.)]), vec![Interval::exact(-0.75)]);
+    }
+
+    #[test]
+    fn convexity() {
+        let (x, y) = (Expr::var(0), Expr::var(1));
+        // f = x² y + ln(x) / y
+        let f = x.clone().powi(2) * y.clone() + x.clone().ln() / y.clone();
+        let hessian = |x: f64, y: f64| {
+            [[2. * y - 1. / (x * x * y), 2. * x - 1. / (x * y * y)],
+             [2. * x - 1. / (x * y * y), 2. * x.ln() / (y * y * y)]]
+        };
+
+        let domain = [Interval::with_range(1., 2.), Interval::with_range(0.5, 1.)];
+        let h = f.hessian(&domain);
+        for &(a, b) in &[(1., 0.5), (1.5, 0.7), (2., 1.), (1.2, 0.9)] {
+            for i in 0..2 {
+                for j in 0..2 {
+                    assert!(h[(i, j)].contains(hessian(a, b)[i][j]), "{} {}", i, j);
+                }
+            }
+        }
+        let diagonal = f.hessian_diagonal(&domain);
+        assert!(!h[(0, 0)].disjoint(&diagonal[0]) && !h[(1, 1)].disjoint(&diagonal[1]));
+        assert_eq!(h[(0, 1)], h[(1, 0)]);
+
+        let bowl = x.clone().powi(2) + y.clone().powi(2) - x.clone() * y.clone();
+        let square = [Interval::with_range(-1., 1.); 2];
+        assert_eq!(bowl.convexity(&square), Tribool::True);
+        assert_eq!(bowl.concavity(&square), Tribool::False);
+        // Saddle has convex and concave directions
+        let saddle = x.clone() * x.clone() - y.clone() * y;
+        assert_eq!((saddle.convexity(&square), saddle.concavity(&square)),
+                   (Tribool::False, Tribool::False));
+        // Restriction of saddle to a line is convex
+        let line = [Interval::with_range(-1., 1.), Interval::exact(0.)];
+        assert_eq!(saddle.convexity(&line), Tribool::True);
+        assert_eq!(x.clone().sin().convexity(&[Interval::with_range(-1., 1.)]), Tribool::Unknown);
+        assert_eq!(x.sin().concavity(&[Interval::with_range(0.5, 3.)]), Tribool::True);
+    }
+
+    #[test]
+    fn relaxations() {
+        let (x, y) = (Expr::var(0), Expr::var(1));
+        type Exact = fn(f64, f64) -> f64;
+        let functions: Vec<(Expr<f64>, Exact)> = vec![
+            (x.clone() * y.clone() - x.clone().exp(), |x, y| x * y - x.exp()),
+            (x.clone().powi(2) / (y.clone() + 3.), |x, y| x * x / (y + 3.)),
+            ((x.clone() + y.clone() + 2.).sqrt() * y.clone().sin(),
+             |x, y| (x + y + 2.).sqrt() * y.sin()),
+            ((y.clone() + 2.).ln() - (x.clone() * 2.).cos() + x.clone().powi(3),
+             |x, y| (y + 2.).ln() - (2. * x).cos() + x * x * x),
+        ];
+        let domain = [Interval::with_range(-1., 0.5), Interval::with_range(-0.5, 1.)];
+        let point = |i: usize, j: usize| [-1. + 0.375 * i as f64, -0.5 + 0.375 * j as f64];
+
+        for &(ref f, exact) in &functions {
+            for i in 0..=4 {
+                for j in 0..=4 {
+                    let p = point(i, j);
+                    let r = f.relax(&domain, &p);
+                    let value = exact(p[0], p[1]);
+                    assert!(r.bounds.contains(value));
+                    assert!(r.bounds.inf() <= r.convex && r.convex <= value);
+                    assert!(value <= r.concave && r.concave <= r.bounds.sup());
+
+                    // Relaxations are convex and concave along segments between points
+                    let q = point(4 - i, (j + 2) % 5);
+                    let m = [(p[0] + q[0]) / 2., (p[1] + q[1]) / 2.];
+                    let (s, t) = (f.relax(&domain, &q), f.relax(&domain, &m));
+                    assert!(t.convex <= (r.convex + s.convex) / 2. + 1e-12);
+                    assert!(t.concave >= (r.concave + s.concave) / 2. - 1e-12);
+                }
+            }
+        }
+
+        // Relaxations of convex function are the function and the secant
+        let square = x.clone().powi(2).relax(&[Interval::with_range(-1., 2.)], &[0.5]);
+        assert_eq!((square.convex, square.concave), (0.25, 2.5));
+        // Bilinear envelope is exact at corners, and the bounds are kept otherwise
+        let corner = (x.clone() * y.clone()).relax(&domain, &[0.5, 1.]);
+        assert_eq!((corner.convex, corner.concave), (0.5, 0.5));
+        let pole = (Expr::from(1.) / x.clone()).relax(&domain, &[0.5, 0.]);
+        assert!(!pole.bounds.is_bounded() && pole.convex == f64::NEG_INFINITY);
+        let outside = x.ln().relax(&[Interval::with_range(-2., -1.)], &[-1.5]);
+        assert!(outside.bounds.is_empty() && outside.convex.is_nan());
+    }
+}
@@ -0,0 +1,107 @@
+//! Tables of factorials and their reciprocals.
+//!
+//! Series of elementary functions divide every term by a factorial, and computing `1 / k!` for
+//! every term of every call is often slower than the rest of the evaluation. Enclosures of `n!`
+//! and `1 / n!` for all `n` up to `170`, the largest with finite `n!`, are computed on the first
+//! use and shared by all threads afterwards. Factorials are exact up to `22!`, so reciprocals are
+//! the tightest enclosures there, and beyond they accumulate at most one rounding per factor.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use inter::Interval;
+//! use inter::factorial;
+//!
+//! // e = Σ 1 / k!, with the tail after 18 terms below 1 / 18!
+//! let partial: Interval<f64> = (0..18).map(factorial::inv_factorial).sum();
+//! let tail = Interval::with_range(0., factorial::inv_factorial(18).sup());
+//!
+//! assert!((partial + tail).contains(std::f64::consts::E));
+//! assert!((partial + tail).width() < 1e-14);
+//! ```
+
+use std::sync::OnceLock;
+
+use interval::Interval;
+
+/// Number of factorials in table, `n!` overflows for larger `n`
+const LEN: usize = 171;
+
+static TABLE: OnceLock<Table> = OnceLock::new();
+
+struct Table {
+    factorials: Vec<Interval<f64>>,
+    reciprocals: Vec<Interval<f64>>,
+}
+
+fn table() -> &'static Table {
+    TABLE.get_or_init(|| {
+        let mut factorials = vec![Interval::exact(1.)];
+        for n in 1..LEN {
+            let next = factorials[n - 1].mul_by_uint(n as u64);
+            factorials.push(next);
+        }
+        let reciprocals = factorials.iter().map(|&x| Interval::exact(1.) / x).collect();
+
+        Table {
+            factorials,
+            reciprocals,
+        }
+    })
+}
+
+/// Enclosure of `n!`
+///
+/// For `n` above `170` this is `[f64::MAX, ∞]`.
+pub fn factorial(n: usize) -> Interval<f64> {
+    match table().factorials.get(n) {
+        Some(&x) => x,
+        None => Interval::with_range(f64::MAX, f64::INFINITY),
+    }
+}
+
+/// Enclosure of `1 / n!`
+///
+/// For `n` above `170` this is `[0, 1 / f64::MAX]`, rounded up.
+pub fn inv_factorial(n: usize) -> Interval<f64> {
+    match table().reciprocals.get(n) {
+        Some(&x) => x,
+        None => Interval::exact(1.) / factorial(n),
+    }
+}
+
+/// Enclosures of `1 / k!` for all `k` below `n`, or all in table when `n` is above `171`
+pub fn inv_factorials(n: usize) -> &'static [Interval<f64>] {
+    let reciprocals = &table().reciprocals;
+    &reciprocals[..n.min(reciprocals.len())]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn enclosures() {
+        // Exact up to 22!, which is the last factorial with 53 significant bits
+        let mut exact = 1u128;
+        for n in 0..=34 {
+            if n > 0 {
+                exact *= n as u128;
+            }
+            let x = factorial(n);
+            assert!(x.inf() as u128 <= exact && exact <= x.sup() as u128);
+            assert_eq!(x.is_exact(), n <= 22, "{}", n);
+            if n <= 22 {
+                assert!(inv_factorial(n).contains(1. / exact as f64));
+            }
+        }
+        assert_eq!(inv_factorial(3), Interval::exact(1.) / Interval::exact(6.));
+        assert_eq!(inv_factorial(20).inf().next_up(), inv_factorial(20).sup());
+
+        assert!(factorial(170).is_bounded());
+        assert_eq!(factorial(171), Interval::with_range(f64::MAX, f64::INFINITY));
+        assert!(inv_factorial(1000).inf() == 0. && inv_factorial(1000).sup() > 0.);
+        assert_eq!(inv_factorials(4)[2], Interval::exact(0.5));
+        assert_eq!(inv_factorials(usize::MAX).len(), 171);
+    }
+}
@@ -0,0 +1,125 @@
+//! Randomized self-check of inclusion functions.
+//!
+//! Interval extension `F` of function `f` must satisfy `F(x) ⊆ F(X)` for every point `x` of box
+//! `X`, because `F(x)` encloses `f(x)` as tightly as `F` can. Evaluating `F` on random points
+//! and comparing with enclosure over the whole box finds violations of this property, which
+//! always mean a bug in the enclosure (or in the function used to build it).
+//!
+//! ## Example
+//!
+//! ```rust
+//! use inter::Interval;
+//! use inter::falsify;
+//!
+//! let inputs = [Interval::with_range(-1., 2.)];
+//!
+//! // Squaring endpoints is wrong for intervals containing zero
+//! let wrong = |x: &[Interval<f64>]| {
+//!     let (a, b) = (x[0].inf() * x[0].inf(), x[0].sup() * x[0].sup());
+//!     Interval::with_range(a.min(b), a.max(b))
+//! };
+//! assert!(!falsify::sample(wrong, &inputs, 1000, 42).is_empty());
+//!
+//! let right = |x: &[Interval<f64>]| x[0].powi(2);
+//! assert!(falsify::sample(right, &inputs, 1000, 42).is_empty());
+//! ```
+
+use interval::Interval;
+
+/// Point whose enclosure escapes enclosure over the whole box
+#[derive(Clone, Debug, PartialEq)]
+pub struct Escape {
+    /// Coordinates of the point
+    pub point: Vec<f64>,
+    /// Result of function evaluated at `point`
+    pub value: Interval<f64>,
+    /// Result of function evaluated over the whole box
+    pub enclosure: Interval<f64>,
+}
+
+/// Evaluate `f` at `samples` random points of box `inputs` and return those escaping `f(inputs)`
+///
+/// Points are drawn uniformly, except that every coordinate is set to one of endpoints with
+/// probability 1/4, as that is where enclosures are most often wrong. The same `seed` gives the
+/// same points.
+pub fn sample<F>(f: F, inputs: &[Interval<f64>], samples: usize, seed: u64) -> Vec<Escape>
+    where F: Fn(&[Interval<f64>]) -> Interval<f64>
+{
+    let enclosure = f(inputs);
+    let mut random = SplitMix(seed);
+    let mut escapes = Vec::new();
+
+    for _ in 0..samples {
+        let point: Vec<f64> = inputs.iter().map(|x| random.point(x)).collect();
+        let exact: Vec<_> = point.iter().map(|&xi| Interval::exact(xi)).collect();
+        let value = f(&exact);
+
+        if !value.subset(&enclosure) {
+            escapes.push(Escape {
+                point,
+                value,
+                enclosure,
+            });
+        }
+    }
+
+    escapes
+}
+
+/// SplitMix64 generator, good enough for sampling and reproducible across platforms
+struct SplitMix(u64);
+
+impl SplitMix {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+
+        z ^ (z >> 31)
+    }
+
+    /// Uniform value from `[0, 1)`
+    fn unit(&mut self) -> f64 {
+        (self.next() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    fn point(&mut self, x: &Interval<f64>) -> f64 {
+        match self.next() % 8 {
+            0 => x.inf(),
+            1 => x.sup(),
+            _ => x.point_at(self.unit()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use testfns;
+
+    #[test]
+    fn test_functions_are_inclusion_isotone() {
+        for function in testfns::all() {
+            let domain = function.domain();
+            let escapes = sample(|x| function.eval(x), &domain, 200, 7);
+
+            assert!(escapes.is_empty(), "{}: {:?}", function.name(), escapes.first());
+        }
+    }
+
+    #[test]
+    fn reproducible() {
+        let inputs = [Interval::with_range(0., 1.), Interval::with_range(-1., f64::INFINITY)];
+        let shrunk = |x: &[Interval<f64>]| {
+            Interval::with_range(x[0].inf(), x[0].mid()) - x[1]
+        };
+
+        let escapes = sample(shrunk, &inputs, 100, 1);
+        assert!(!escapes.is_empty());
+        assert_eq!(escapes, sample(shrunk, &inputs, 100, 1));
+        for escape in &escapes {
+            assert!(inputs[0].contains(escape.point[0]) && inputs[1].contains(escape.point[1]));
+        }
+    }
+}
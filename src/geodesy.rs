@@ -0,0 +1,135 @@
+//! Great-circle distances and bearings of uncertain positions.
+//!
+//! Positions are given by intervals of latitude and longitude, for example GNSS fixes with
+//! their error bounds, and the Earth is approximated by a sphere. Results enclose distance or
+//! bearing between every pair of points of the two regions, so they can be used to propagate
+//! measurement uncertainty rigorously.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use inter::Interval;
+//! use inter::geodesy::{self, Point};
+//!
+//! let err = Interval::with_range(-1e-4, 1e-4);
+//! let paris = Point::from_degrees(Interval::exact(48.8566) + err, Interval::exact(2.3522) + err);
+//! let london = Point::from_degrees(Interval::exact(51.5074), Interval::exact(-0.1278));
+//! let d = geodesy::distance(&paris, &london);
+//!
+//! assert!(d.subset(&Interval::with_range(343_500., 343_600.)));
+//! ```
+
+use interval::Interval;
+
+/// Mean radius of the Earth in metres
+pub const EARTH_RADIUS: f64 = 6_371_008.8;
+
+/// Position on a sphere
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Point {
+    /// Latitude in radians
+    pub lat: Interval<f64>,
+    /// Longitude in radians
+    pub lon: Interval<f64>,
+}
+
+impl Point {
+    /// Position with latitude and longitude in radians
+    pub fn new(lat: Interval<f64>, lon: Interval<f64>) -> Self {
+        Point { lat, lon }
+    }
+
+    /// Position with latitude and longitude in degrees
+    pub fn from_degrees(lat: Interval<f64>, lon: Interval<f64>) -> Self {
+        let radian = Interval::pi() / Interval::exact(180.);
+
+        Point::new(lat * radian, lon * radian)
+    }
+}
+
+/// Enclosure of the central angle between `a` and `b`, in radians, by the haversine formula
+pub fn central_angle(a: &Point, b: &Point) -> Interval<f64> {
+    let two = Interval::exact(2.);
+    let dlat = ((b.lat - a.lat) / two).sin().powi(2);
+    let dlon = ((b.lon - a.lon) / two).sin().powi(2);
+    let h = dlat + a.lat.cos() * b.lat.cos() * dlon;
+
+    two * h.intersect(&Interval::with_range(0., 1.)).sqrt().asin()
+}
+
+/// Enclosure of the great-circle distance between `a` and `b` on the Earth, in metres
+pub fn distance(a: &Point, b: &Point) -> Interval<f64> {
+    central_angle(a, b) * Interval::exact(EARTH_RADIUS)
+}
+
+/// Enclosure of the initial bearing from `a` to `b`, in radians clockwise from north
+///
+/// Bearings lie in `[-π, π]`. When regions allow directions on both sides of south the result
+/// is the whole `[-π, π]`, and so it is when `a` and `b` can be the same point or a pole.
+pub fn bearing(a: &Point, b: &Point) -> Interval<f64> {
+    let dlon = b.lon - a.lon;
+    let y = dlon.sin() * b.lat.cos();
+    let x = a.lat.cos() * b.lat.sin() - a.lat.sin() * b.lat.cos() * dlon.cos();
+
+    y.atan2(x)
+}
+
+#[cfg(test)]
+mod test {
+    use std::f64::consts::PI;
+
+    use super::*;
+
+    fn point(lat: f64, lon: f64) -> Point {
+        Point::from_degrees(Interval::exact(lat), Interval::exact(lon))
+    }
+
+    #[test]
+    fn distances() {
+        let (a, b) = (point(48.8566, 2.3522), point(51.5074, -0.1278));
+        let angle = central_angle(&a, &b);
+
+        // Plain floating-point haversine lies in tight enclosure
+        let (lat1, lon1, lat2, lon2) =
+            (48.8566f64.to_radians(), 2.3522f64.to_radians(), 51.5074f64.to_radians(),
+             (-0.1278f64).to_radians());
+        let h = ((lat2 - lat1) / 2.).sin().powi(2) +
+                lat1.cos() * lat2.cos() * ((lon2 - lon1) / 2.).sin().powi(2);
+        assert!(angle.contains(2. * h.sqrt().asin()) && angle.width() < 1e-14);
+
+        assert!(central_angle(&a, &a).contains(0.));
+        assert!(central_angle(&point(0., 0.), &point(0., 180.)).contains(PI));
+        assert!(distance(&point(0., 0.), &point(0., 90.)).contains(EARTH_RADIUS * PI / 2.));
+    }
+
+    #[test]
+    fn uncertain_positions() {
+        let err = Interval::with_range(-1e-3, 1e-3);
+        let a = Point::from_degrees(Interval::exact(10.) + err, Interval::exact(20.) + err);
+        let b = point(10.5, 20.5);
+        let d = distance(&a, &b);
+
+        // Sampled positions from the region are all covered
+        for &(dlat, dlon) in &[(-1e-3, -1e-3), (1e-3, 1e-3), (0., -1e-3), (1e-3, 0.)] {
+            let p = point(10. + dlat, 20. + dlon);
+            assert!(d.subset(&Interval::with_range(0., 1e6)));
+            assert!(distance(&p, &b).subset(&d));
+            assert!(bearing(&p, &b).subset(&bearing(&a, &b)));
+        }
+    }
+
+    #[test]
+    fn bearings() {
+        let origin = point(0., 0.);
+
+        assert!(bearing(&origin, &point(1., 0.)).contains(0.));
+        assert!(bearing(&origin, &point(0., 1.)).contains(PI / 2.));
+        assert!(bearing(&origin, &point(0., -1.)).contains(-PI / 2.));
+        assert!(bearing(&origin, &point(0., 1.)).width() < 1e-15);
+
+        // Directions around south cross the branch cut
+        let south = Point::from_degrees(Interval::exact(-1.), Interval::with_range(-0.1, 0.1));
+        assert_eq!(bearing(&origin, &south), bearing(&origin, &origin));
+        assert!(bearing(&origin, &south).contains_all(&[-PI, PI]));
+    }
+}
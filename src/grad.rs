@@ -0,0 +1,315 @@
+//! Forward automatic differentiation with interval values.
+//!
+//! `GradInterval` carries enclosure of the value of a function together with enclosure of its
+//! derivative, both over the same interval of the argument. Arithmetic operators and elementary
+//! functions apply the chain rule, so any function written generically over the operators gives
+//! rigorous derivative enclosures for Newton methods and monotonicity tests, and certified bounds
+//! on Lipschitz constants with `lipschitz_bound`.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use inter::Interval;
+//! use inter::grad::GradInterval;
+//! use inter::solve;
+//!
+//! fn f<T>(x: T, two: T) -> T
+//!     where T: Copy + std::ops::Mul<Output = T> + std::ops::Sub<Output = T>
+//! {
+//!     x * x - two
+//! }
+//!
+//! let two = Interval::exact(2.);
+//! let df = |x| f(GradInterval::variable(x), GradInterval::constant(two)).derivative;
+//! let roots = solve::newton(|x| f(x, two), df, Interval::with_range(0., 3.), 1e-12, 100);
+//!
+//! assert!(roots[0].unique && roots[0].interval.contains(2f64.sqrt()));
+//! ```
+
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use num::{Float, FloatConst};
+
+use expr::Function;
+use interval::Interval;
+use rounding::RoundedArith;
+
+/// Enclosures of value and derivative of a function
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GradInterval<T> {
+    /// Enclosure of value
+    pub value: Interval<T>,
+    /// Enclosure of derivative
+    pub derivative: Interval<T>,
+}
+
+impl<T> GradInterval<T>
+    where T: Float + FloatConst + RoundedArith
+{
+    /// Independent variable ranging over `x`, with derivative `1`
+    pub fn variable(x: Interval<T>) -> Self {
+        GradInterval {
+            value: x,
+            derivative: Interval::exact(T::one()),
+        }
+    }
+
+    /// Constant, with derivative `0`
+    pub fn constant(x: Interval<T>) -> Self {
+        GradInterval {
+            value: x,
+            derivative: Interval::exact(T::zero()),
+        }
+    }
+
+    /// Apply elementary function, multiplying its derivative by derivative of argument
+    pub fn apply(self, f: Function) -> Self {
+        self.chain(f.apply(self.value), f.derivative(self.value))
+    }
+
+    /// Square root
+    pub fn sqrt(self) -> Self {
+        self.apply(Function::Sqrt)
+    }
+
+    /// Exponential
+    pub fn exp(self) -> Self {
+        self.apply(Function::Exp)
+    }
+
+    /// Natural logarithm
+    pub fn ln(self) -> Self {
+        self.apply(Function::Ln)
+    }
+
+    /// Sine
+    pub fn sin(self) -> Self {
+        self.apply(Function::Sin)
+    }
+
+    /// Cosine
+    pub fn cos(self) -> Self {
+        self.apply(Function::Cos)
+    }
+
+    /// Tangent, with derivative `1 + tan²`
+    pub fn tan(self) -> Self {
+        let tan = self.value.tan();
+        self.chain(tan, Interval::exact(T::one()) + tan.sqr())
+    }
+
+    /// Arc tangent, with derivative `1 / (1 + x²)`
+    pub fn atan(self) -> Self {
+        let one = Interval::exact(T::one());
+        self.chain(self.value.atan(), one / (one + self.value.sqr()))
+    }
+
+    /// Square, with derivative tighter than that of `self * self`
+    pub fn sqr(self) -> Self {
+        let two = Interval::exact(T::one() + T::one());
+        self.chain(self.value.sqr(), two * self.value)
+    }
+
+    /// Integer power
+    pub fn powi(self, n: i32) -> Self {
+        self.apply(Function::Powi(n))
+    }
+
+    /// Outer function with given value and derivative over `self.value`
+    fn chain(self, value: Interval<T>, derivative: Interval<T>) -> Self {
+        GradInterval {
+            value,
+            derivative: derivative * self.derivative,
+        }
+    }
+}
+
+impl<T> From<Interval<T>> for GradInterval<T>
+    where T: Float + FloatConst + RoundedArith
+{
+    /// Constant
+    fn from(x: Interval<T>) -> Self {
+        GradInterval::constant(x)
+    }
+}
+
+impl<T> Add for GradInterval<T>
+    where T: Float + RoundedArith
+{
+    type Output = GradInterval<T>;
+
+    fn add(self, other: Self) -> Self {
+        GradInterval {
+            value: self.value + other.value,
+            derivative: self.derivative + other.derivative,
+        }
+    }
+}
+
+impl<T> Sub for GradInterval<T>
+    where T: Float + RoundedArith
+{
+    type Output = GradInterval<T>;
+
+    fn sub(self, other: Self) -> Self {
+        GradInterval {
+            value: self.value - other.value,
+            derivative: self.derivative - other.derivative,
+        }
+    }
+}
+
+impl<T> Mul for GradInterval<T>
+    where T: Float + RoundedArith
+{
+    type Output = GradInterval<T>;
+
+    fn mul(self, other: Self) -> Self {
+        GradInterval {
+            value: self.value * other.value,
+            derivative: self.derivative * other.value + self.value * other.derivative,
+        }
+    }
+}
+
+impl<T> Div for GradInterval<T>
+    where T: Float + RoundedArith
+{
+    type Output = GradInterval<T>;
+
+    /// Quotient `q = u / v` with derivative `(u' - q v') / v`
+    fn div(self, other: Self) -> Self {
+        let value = self.value / other.value;
+
+        GradInterval {
+            value,
+            derivative: (self.derivative - value * other.derivative) / other.value,
+        }
+    }
+}
+
+impl<T> Neg for GradInterval<T>
+    where T: Neg<Output = T> + Copy
+{
+    type Output = GradInterval<T>;
+
+    fn neg(self) -> Self {
+        GradInterval {
+            value: -self.value,
+            derivative: -self.derivative,
+        }
+    }
+}
+
+/// Certified bound on Lipschitz constant of `f` over box `domain`, in Euclidean norm
+///
+/// Partial derivatives are enclosed over the whole box with one evaluation of `f` per variable,
+/// with only that variable differentiated, and the bound is the norm of their magnitudes, rounded
+/// up. `f` has to be defined on the whole box, and result is infinite when some derivative is not
+/// bounded there.
+///
+/// ## Example
+///
+/// ```rust
+/// use inter::Interval;
+/// use inter::grad::{self, GradInterval};
+///
+/// // x sin y over [0, 3] × [-1, 1] has gradient (sin y, x cos y)
+/// let f = |x: &[GradInterval<f64>]| x[0] * x[1].sin();
+/// let domain = [Interval::with_range(0., 3.), Interval::with_range(-1., 1.)];
+/// let bound = grad::lipschitz_bound(f, &domain);
+///
+/// assert!(bound >= 1f64.sin().hypot(3.) && bound < 3.2);
+/// ```
+pub fn lipschitz_bound<T, F>(f: F, domain: &[Interval<T>]) -> T
+    where T: Float + FloatConst + RoundedArith,
+          F: Fn(&[GradInterval<T>]) -> GradInterval<T>
+{
+    let mut vars: Vec<_> = domain.iter().map(|&x| GradInterval::constant(x)).collect();
+    let mut sum = T::zero();
+
+    for (i, &x) in domain.iter().enumerate() {
+        vars[i] = GradInterval::variable(x);
+        let derivative = f(&vars).derivative;
+        vars[i] = GradInterval::constant(x);
+
+        if derivative.is_empty() {
+            return T::infinity();
+        }
+        let mag = derivative.mag();
+        sum = sum.add_up(mag.mul_up(mag));
+    }
+
+    Interval::exact(sum).sqrt().sup()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn derivative<F>(f: F, x: f64) -> f64
+        where F: Fn(f64) -> f64
+    {
+        let h = 1e-6;
+        (f(x + h) - f(x - h)) / (2. * h)
+    }
+
+    #[test]
+    fn chain_rule() {
+        let c = |x: f64| GradInterval::constant(Interval::exact(x));
+        let g = |x: GradInterval<f64>| (x.sin() * x.exp() + c(1.)) / (x.sqr() + c(2.)).sqrt();
+        let h = |x: GradInterval<f64>| x.atan().powi(3) - x.tan() * x.ln() + -x.cos();
+        let (g_f, h_f) = (|x: f64| (x.sin() * x.exp() + 1.) / (x * x + 2.).sqrt(),
+                          |x: f64| x.atan().powi(3) - x.tan() * x.ln() - x.cos());
+
+        // Derivatives over narrow intervals enclose finite differences at their points
+        for &x in &[0.3, 0.7, 1.1, 1.5] {
+            let x0 = GradInterval::variable(Interval::with_epsilon(x, 1e-5));
+            let (y, z) = (g(x0), h(x0));
+            assert!(y.value.contains(g_f(x)) && z.value.contains(h_f(x)));
+            assert!(y.derivative.contains(derivative(g_f, x)) && y.derivative.width() < 1e-3);
+            assert!(z.derivative.contains(derivative(h_f, x)) && z.derivative.rel_width() < 1e-3);
+        }
+
+        // Wide argument gives enclosures of the whole range of derivative
+        let x = GradInterval::variable(Interval::with_range(0.1, 1.));
+        let y = x / (x + c(1.));
+        for i in 0..=10 {
+            let t = 0.1 + 0.09 * i as f64;
+            assert!(y.derivative.contains(1. / ((t + 1.) * (t + 1.))));
+        }
+        assert_eq!(x.sqr().derivative, Interval::with_range(0.2, 2.));
+        assert_eq!((x * x).derivative, Interval::with_range(0.2, 2.));
+        assert_eq!(GradInterval::from(x.value).derivative, Interval::exact(0.));
+        assert_eq!((x - x).derivative, Interval::exact(0.));
+    }
+
+    #[test]
+    fn lipschitz() {
+        // Slope of x² over [-2, 1] is at most 4
+        let square = |x: &[GradInterval<f64>]| x[0].sqr();
+        assert_eq!(lipschitz_bound(square, &[Interval::with_range(-2., 1.)]), 4.);
+
+        // |(3, 4)| = 5 for linear function, and differences never exceed the bound
+        let linear = |x: &[GradInterval<f64>]| {
+            GradInterval::constant(Interval::exact(3.)) * x[0] +
+            GradInterval::constant(Interval::exact(4.)) * x[1]
+        };
+        let domain = [Interval::with_range(-1., 1.); 2];
+        assert_eq!(lipschitz_bound(linear, &domain), 5.);
+
+        let f = |x: &[GradInterval<f64>]| (x[0] * x[1]).exp();
+        let g = |x: f64, y: f64| (x * y).exp();
+        let bound = lipschitz_bound(f, &domain);
+        for &(a, b) in &[((0., 0.), (1., 1.)), ((-1., 1.), (1., -1.)), ((0.9, 1.), (1., 1.))] {
+            let distance = (a.0 - b.0).hypot(a.1 - b.1);
+            assert!((g(a.0, a.1) - g(b.0, b.1)).abs() <= bound * distance);
+        }
+
+        assert_eq!(lipschitz_bound(square, &[Interval::with_range(0., f64::INFINITY)]),
+                   f64::INFINITY);
+        let root = |x: &[GradInterval<f64>]| x[0].sqrt();
+        assert_eq!(lipschitz_bound(root, &[Interval::with_range(0., 1.)]), f64::INFINITY);
+        assert_eq!(lipschitz_bound(square, &[]), 0.);
+    }
+}
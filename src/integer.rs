@@ -0,0 +1,531 @@
+//! Integer interval operations matching semantics of Rust primitive integers.
+//!
+//! These model exactly what the corresponding method of the underlying type does, so they can be
+//! used for abstract interpretation of code that relies on them.
+
+use std::mem;
+
+use num::{AsPrimitive, PrimInt};
+
+use interval::Interval;
+use one_or_two::OneOrTwo;
+use utils::{partial_min, partial_max};
+
+/// Multiply saturating at the bounds of `T`, like `saturating_mul` of primitive integers
+fn saturating_mul<T: PrimInt>(a: T, b: T) -> T {
+    a.checked_mul(&b).unwrap_or_else(|| {
+        if (a < T::zero()) != (b < T::zero()) {
+            T::min_value()
+        } else {
+            T::max_value()
+        }
+    })
+}
+
+/// Range of divisors with the same sign
+type Part<T> = Option<(T, T)>;
+
+/// Negative and positive part of divisor, skipping zero
+fn divisor_parts<T: PrimInt>(b: Interval<T>) -> (Part<T>, Part<T>) {
+    let (zero, one) = (T::zero(), T::one());
+    let negative = if b.start < zero {
+        Some((b.start, partial_min(b.end, zero - one)))
+    } else {
+        None
+    };
+    let positive = if b.end > zero {
+        Some((partial_max(b.start, one), b.end))
+    } else {
+        None
+    };
+
+    (negative, positive)
+}
+
+/// Hull of `f` evaluated in corners of `a` and every part of divisor
+fn corners<T, F>(a: Interval<T>, b: Interval<T>, f: F) -> Option<Interval<T>>
+    where T: PrimInt,
+          F: Fn(T, T) -> T
+{
+    let (negative, positive) = divisor_parts(b);
+
+    negative.into_iter().chain(positive).fold(None, |acc, (lo, hi)| {
+        let values = [f(a.start, lo), f(a.start, hi), f(a.end, lo), f(a.end, hi)];
+        let part = Interval {
+            start: values.iter().cloned().fold(T::max_value(), partial_min),
+            end: values.iter().cloned().fold(T::min_value(), partial_max),
+        };
+
+        Some(acc.map_or(part, |acc: Interval<T>| acc.hull(&part)))
+    })
+}
+
+/// Largest absolute value of remainder by the smallest and by the largest non-zero divisor
+fn remainder_bounds<T: PrimInt>(b: Interval<T>) -> Option<(T, T)> {
+    let (zero, one) = (T::zero(), T::one());
+    let (negative, positive) = divisor_parts(b);
+
+    // `-(x + 1)` does not overflow even for `x == T::min_value()`
+    let negative = negative.map(|(lo, hi)| (zero - (hi + one), zero - (lo + one)));
+    let positive = positive.map(|(lo, hi)| (lo - one, hi - one));
+
+    negative.into_iter().chain(positive).fold(None, |acc, (min, max)| {
+        Some(acc.map_or((min, max), |(acc_min, acc_max)| {
+            (partial_min(acc_min, min), partial_max(acc_max, max))
+        }))
+    })
+}
+
+/// Check if every division is either by zero or `MIN / -1`
+fn always_overflows<T: PrimInt>(a: Interval<T>, b: Interval<T>) -> bool {
+    let minus_one = T::zero().checked_sub(&T::one());
+
+    a.start == T::min_value() && a.end == T::min_value() &&
+    divisor_parts(b) == (minus_one.map(|x| (x, x)), None)
+}
+
+/// Euclidean quotient built from truncating one, `None` on overflow
+fn checked_div_euclid<T: PrimInt>(a: T, b: T) -> Option<T> {
+    let q = a.checked_div(&b)?;
+
+    if a - q * b < T::zero() {
+        Some(if b > T::zero() { q - T::one() } else { q + T::one() })
+    } else {
+        Some(q)
+    }
+}
+
+impl<T> Interval<T>
+    where T: PrimInt
+{
+    /// Enclosure of `a.saturating_add(b)`
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// let a = Interval::with_range(100u8, 200);
+    ///
+    /// assert_eq!(a.saturating_add(Interval::exact(100)), Interval::with_range(200, 255));
+    /// ```
+    pub fn saturating_add(self, other: Self) -> Self {
+        Interval {
+            start: self.start.saturating_add(other.start),
+            end: self.end.saturating_add(other.end),
+        }
+    }
+
+    /// Enclosure of `a.saturating_sub(b)`
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// let a = Interval::with_range(10u8, 20);
+    ///
+    /// assert_eq!(a.saturating_sub(Interval::with_range(5, 15)), Interval::with_range(0, 15));
+    /// ```
+    pub fn saturating_sub(self, other: Self) -> Self {
+        Interval {
+            start: self.start.saturating_sub(other.end),
+            end: self.end.saturating_sub(other.start),
+        }
+    }
+
+    /// Enclosure of `a.saturating_mul(b)`
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// let a = Interval::with_range(-100i8, 2);
+    ///
+    /// assert_eq!(a.saturating_mul(Interval::exact(3)), Interval::with_range(-128, 6));
+    /// assert_eq!(a.saturating_mul(a), Interval::with_range(-128, 127));
+    /// ```
+    pub fn saturating_mul(self, other: Self) -> Self {
+        // Saturation is monotone, so extremes are still attained in corners
+        let corners = [saturating_mul(self.start, other.start),
+                       saturating_mul(self.start, other.end),
+                       saturating_mul(self.end, other.start),
+                       saturating_mul(self.end, other.end)];
+
+        Interval {
+            start: corners.iter().cloned().fold(T::max_value(), partial_min),
+            end: corners.iter().cloned().fold(T::min_value(), partial_max),
+        }
+    }
+
+    /// Exact sum, or `None` when `a + b` can overflow for some `a` and `b`
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// let a = Interval::with_range(100u8, 200);
+    ///
+    /// assert_eq!(a.checked_add(Interval::exact(50)), Some(Interval::with_range(150, 250)));
+    /// assert_eq!(a.checked_add(Interval::exact(100)), None);
+    /// ```
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        Some(Interval {
+            start: self.start.checked_add(&other.start)?,
+            end: self.end.checked_add(&other.end)?,
+        })
+    }
+
+    /// Exact difference, or `None` when `a - b` can overflow for some `a` and `b`
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        Some(Interval {
+            start: self.start.checked_sub(&other.end)?,
+            end: self.end.checked_sub(&other.start)?,
+        })
+    }
+
+    /// Exact product, or `None` when `a * b` can overflow for some `a` and `b`
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// let a = Interval::with_range(-10i8, 12);
+    ///
+    /// assert_eq!(a.checked_mul(Interval::exact(-10)), Some(Interval::with_range(-120, 100)));
+    /// assert_eq!(a.checked_mul(a), None);
+    /// ```
+    pub fn checked_mul(self, other: Self) -> Option<Self> {
+        // Overflow cannot happen between corners, as product is monotone in each argument
+        let corners = [self.start.checked_mul(&other.start)?,
+                       self.start.checked_mul(&other.end)?,
+                       self.end.checked_mul(&other.start)?,
+                       self.end.checked_mul(&other.end)?];
+
+        Some(Interval {
+            start: corners.iter().cloned().fold(T::max_value(), partial_min),
+            end: corners.iter().cloned().fold(T::min_value(), partial_max),
+        })
+    }
+
+    /// Enclosure of truncating division `a / b`
+    ///
+    /// Pairs for which the division panics (division by zero and `MIN / -1`) are skipped, so
+    /// `None` is returned when every division panics.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// let a = Interval::with_range(-7i32, 7);
+    ///
+    /// assert_eq!(a.div_trunc(Interval::exact(2)), Some(Interval::with_range(-3, 3)));
+    /// assert_eq!(a.div_trunc(Interval::with_range(0, 2)), Some(a));
+    /// assert_eq!(a.div_trunc(Interval::exact(0)), None);
+    /// ```
+    pub fn div_trunc(self, other: Self) -> Option<Self> {
+        if always_overflows(self, other) {
+            return None;
+        }
+
+        // Only `MIN / -1` overflows and its exact result is just above `MAX`
+        corners(self, other, |a, b| a.checked_div(&b).unwrap_or(T::max_value()))
+    }
+
+    /// Enclosure of truncating remainder `a % b`
+    ///
+    /// Pairs for which the remainder panics are skipped as in `div_trunc`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// let a = Interval::with_range(-7i32, 7);
+    ///
+    /// assert_eq!(a.rem_trunc(Interval::exact(3)), Some(Interval::with_range(-2, 2)));
+    /// assert_eq!(Interval::with_range(7, 8).rem_trunc(Interval::exact(-3)),
+    ///            Some(Interval::with_range(1, 2)));
+    /// ```
+    pub fn rem_trunc(self, other: Self) -> Option<Self> {
+        let zero = T::zero();
+        let (min_rem, max_rem) = remainder_bounds(other)?;
+        if max_rem == zero {
+            return Some(Interval::exact(zero));
+        }
+
+        if let Some(exact) = self.rem_in_one_period(other, min_rem, |a, b| a % b) {
+            return Some(exact);
+        }
+
+        Some(if self.start >= zero {
+            let start = if self.end <= min_rem { self.start } else { zero };
+            Interval::with_range(start, partial_min(self.end, max_rem))
+        } else if self.end <= zero {
+            let end = if self.start >= zero - min_rem { self.end } else { zero };
+            Interval::with_range(partial_max(self.start, zero - max_rem), end)
+        } else {
+            Interval::with_range(partial_max(self.start, zero - max_rem),
+                                 partial_min(self.end, max_rem))
+        })
+    }
+
+    /// Enclosure of `a.div_euclid(b)`
+    ///
+    /// Pairs for which the division panics are skipped as in `div_trunc`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// let a = Interval::with_range(-7i32, 7);
+    ///
+    /// assert_eq!(a.div_euclid(Interval::exact(2)), Some(Interval::with_range(-4, 3)));
+    /// assert_eq!(a.div_euclid(Interval::exact(-2)), Some(Interval::with_range(-3, 4)));
+    /// ```
+    pub fn div_euclid(self, other: Self) -> Option<Self> {
+        if always_overflows(self, other) {
+            return None;
+        }
+
+        corners(self, other, |a, b| checked_div_euclid(a, b).unwrap_or(T::max_value()))
+    }
+
+    /// Enclosure of `a.rem_euclid(b)`
+    ///
+    /// Pairs for which the remainder panics are skipped as in `div_trunc`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// let a = Interval::with_range(-7i32, 7);
+    ///
+    /// assert_eq!(a.rem_euclid(Interval::exact(-3)), Some(Interval::with_range(0, 2)));
+    /// assert_eq!(Interval::with_range(-5, -4).rem_euclid(Interval::exact(3)),
+    ///            Some(Interval::with_range(1, 2)));
+    /// ```
+    pub fn rem_euclid(self, other: Self) -> Option<Self> {
+        let zero = T::zero();
+        let (min_rem, max_rem) = remainder_bounds(other)?;
+        if max_rem == zero {
+            return Some(Interval::exact(zero));
+        }
+
+        let rem_euclid = |a: T, b: T| {
+            let r = a % b;
+            if r >= zero {
+                r
+            } else if b > zero {
+                r + b
+            } else {
+                r - b
+            }
+        };
+        if let Some(exact) = self.rem_in_one_period(other, min_rem, rem_euclid) {
+            return Some(exact);
+        }
+
+        Some(if self.start >= zero && self.end <= min_rem {
+            self
+        } else if self.start >= zero {
+            Interval::with_range(zero, partial_min(self.end, max_rem))
+        } else {
+            Interval::with_range(zero, max_rem)
+        })
+    }
+
+    /// Remainder by exact divisor of interval that does not wrap around it
+    ///
+    /// Divisor must not be `-1`, `0` or `1`, and `max_rem` is its absolute value minus one.
+    fn rem_in_one_period<F>(self, other: Self, max_rem: T, rem: F) -> Option<Self>
+        where F: Fn(T, T) -> T
+    {
+        let zero = T::zero();
+        let crosses_zero = self.start < zero && self.end > zero;
+        let short = self.end.checked_sub(&self.start).is_some_and(|width| width <= max_rem);
+
+        if other.start != other.end || crosses_zero || !short {
+            return None;
+        }
+
+        let (start, end) = (rem(self.start, other.start), rem(self.end, other.start));
+        if start <= end {
+            Some(Interval { start, end })
+        } else {
+            None
+        }
+    }
+}
+
+impl<T> Interval<T>
+    where T: PrimInt + AsPrimitive<u128>
+{
+    /// Enclosure of `x as U`, wrapping like casts of primitive integers
+    ///
+    /// When the cast wraps around exactly once the result consists of two intervals, one at each
+    /// end of the range of `U`. When it wraps more than that the result is the full range.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::{Interval, OneOrTwo};
+    ///
+    /// assert_eq!(Interval::with_range(100i32, 200).wrapping_cast::<u8>(),
+    ///            OneOrTwo::One(Interval::with_range(100, 200)));
+    /// assert_eq!(Interval::with_range(100i32, 200).wrapping_cast::<i8>(),
+    ///            OneOrTwo::Two(Interval::with_range(-128, -56), Interval::with_range(100, 127)));
+    /// assert_eq!(Interval::with_range(-1i8, 1).wrapping_cast::<u16>(),
+    ///            OneOrTwo::Two(Interval::with_range(0, 1), Interval::with_range(65535, 65535)));
+    /// assert_eq!(Interval::with_range(0u32, 1000).wrapping_cast::<u8>(),
+    ///            OneOrTwo::One(Interval::with_range(0, 255)));
+    /// ```
+    pub fn wrapping_cast<U>(self) -> OneOrTwo<U>
+        where T: AsPrimitive<U>,
+              U: PrimInt + AsPrimitive<u128>
+    {
+        let (start, end): (U, U) = (self.start.as_(), self.end.as_());
+        let bits = 8 * mem::size_of::<U>() as u32;
+
+        // Differences of two's complement representations are exact modulo 2^128
+        let bits_of = |x: T| -> u128 { x.as_() };
+        let width = bits_of(self.end).wrapping_sub(bits_of(self.start));
+        let wraps_at_most_once = bits == 128 || width < 1 << bits;
+        let target_width = AsPrimitive::<u128>::as_(end).wrapping_sub(start.as_());
+
+        if start <= end && target_width == width {
+            OneOrTwo::One(Interval { start, end })
+        } else if start > end && wraps_at_most_once {
+            OneOrTwo::Two(Interval::with_range(U::min_value(), end),
+                          Interval::with_range(start, U::max_value()))
+        } else {
+            OneOrTwo::One(Interval::with_range(U::min_value(), U::max_value()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use interval::Interval;
+    use one_or_two::OneOrTwo;
+
+    fn check(f: fn(Interval<i8>, Interval<i8>) -> Interval<i8>, g: fn(i8, i8) -> i8) {
+        let ranges = [(-128, -100), (-3, 4), (0, 0), (50, 127), (-128, 127)];
+
+        for &(a0, a1) in &ranges {
+            for &(b0, b1) in &ranges {
+                let (a, b) = (Interval::with_range(a0, a1), Interval::with_range(b0, b1));
+                let values: Vec<_> =
+                    (a0..=a1).flat_map(|x| (b0..=b1).map(move |y| g(x, y))).collect();
+                let result = f(a, b);
+
+                assert!(result.contains_all(&values));
+                assert!(values.contains(&result.start) && values.contains(&result.end));
+            }
+        }
+    }
+
+    fn check_division(f: fn(Interval<i8>, Interval<i8>) -> Option<Interval<i8>>,
+                      g: fn(i8, i8) -> Option<i8>) {
+        let ranges = [(-128, -128), (-128, -100), (-9, -4), (-3, 4), (0, 0), (-1, -1), (1, 1),
+                      (5, 9), (50, 127), (-128, 127)];
+
+        for &(a0, a1) in &ranges {
+            for &(b0, b1) in &ranges {
+                let (a, b) = (Interval::with_range(a0, a1), Interval::with_range(b0, b1));
+                let values: Vec<_> =
+                    (a0..=a1).flat_map(|x| (b0..=b1).filter_map(move |y| g(x, y))).collect();
+
+                match f(a, b) {
+                    Some(result) => {
+                        assert!(result.contains_all(&values), "{} {} {}", a, b, result)
+                    }
+                    None => assert!(values.is_empty(), "{} {}", a, b),
+
+                }
+            }
+        }
+    }
+
+    /// Result has to be exact, and defined exactly when no pair overflows
+    fn check_checked(f: fn(Interval<i8>, Interval<i8>) -> Option<Interval<i8>>,
+                     g: fn(i8, i8) -> Option<i8>) {
+        let ranges = [(-128, -100), (-3, 4), (0, 0), (-12, 10), (50, 127), (-128, 127)];
+
+        for &(a0, a1) in &ranges {
+            for &(b0, b1) in &ranges {
+                let (a, b) = (Interval::with_range(a0, a1), Interval::with_range(b0, b1));
+                let values: Option<Vec<_>> =
+                    (a0..=a1).flat_map(|x| (b0..=b1).map(move |y| g(x, y))).collect();
+
+                match (f(a, b), values) {
+                    (Some(result), Some(values)) => {
+                        assert!(result.contains_all(&values));
+                        assert!(values.contains(&result.start) && values.contains(&result.end));
+                    }
+                    (None, None) => {}
+                    (result, _) => panic!("{} {} {:?}", a, b, result),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn division() {
+        check_division(Interval::div_trunc, i8::checked_div);
+        check_division(Interval::rem_trunc, i8::checked_rem);
+        check_division(Interval::div_euclid, i8::checked_div_euclid);
+        check_division(Interval::rem_euclid, i8::checked_rem_euclid);
+
+        let a = Interval::with_range(10u8, 250);
+        assert_eq!(a.div_trunc(Interval::with_range(0, 5)), Some(Interval::with_range(2, 250)));
+        assert_eq!(a.rem_euclid(Interval::exact(16)), Some(Interval::with_range(0, 15)));
+        assert_eq!(Interval::with_range(20u8, 25).rem_trunc(Interval::exact(16)),
+                   Some(Interval::with_range(4, 9)));
+    }
+
+    #[test]
+    fn casts() {
+        let ranges = [(-128i16, -100), (-3, 4), (0, 0), (100, 300), (250, 260), (-300, 300),
+                      (i16::MIN, i16::MAX)];
+
+        for &(start, end) in &ranges {
+            let values: Vec<_> = (start..=end).map(|x| x as i8).collect();
+            let result = Interval::with_range(start, end).wrapping_cast::<i8>();
+            assert!(values.iter().all(|&x| result.contains(x)), "{} {}", start, end);
+
+            let values: Vec<_> = (start..=end).map(|x| x as u8).collect();
+            let result = Interval::with_range(start, end).wrapping_cast::<u8>();
+            assert!(values.iter().all(|&x| result.contains(x)), "{} {}", start, end);
+            for piece in result {
+                assert!(values.contains(&piece.start) && values.contains(&piece.end));
+            }
+        }
+
+        assert_eq!(Interval::with_range(-1i64, 0).wrapping_cast::<u128>(),
+                   OneOrTwo::Two(Interval::exact(0), Interval::exact(u128::MAX)));
+        assert_eq!(Interval::with_range(0u128, u128::MAX).wrapping_cast::<i128>(),
+                   OneOrTwo::Two(Interval::with_range(i128::MIN, -1),
+                                 Interval::with_range(0, i128::MAX)));
+    }
+
+    #[test]
+    fn exact_enclosures() {
+        check(Interval::saturating_add, i8::saturating_add);
+        check(Interval::saturating_sub, i8::saturating_sub);
+        check(Interval::saturating_mul, i8::saturating_mul);
+    }
+
+    #[test]
+    fn checked() {
+        check_checked(Interval::checked_add, i8::checked_add);
+        check_checked(Interval::checked_sub, i8::checked_sub);
+        check_checked(Interval::checked_mul, i8::checked_mul);
+    }
+}
@@ -0,0 +1,175 @@
+//! Verified numerical integration.
+//!
+//! Integral of `f` over interval `X` lies in `f(X) |X|` for any enclosure `f(X)` of its range, so
+//! sum of such products over pieces of the domain encloses the integral, with every product and
+//! sum rounded outward. Pieces contributing the widest terms are bisected until the sum is narrow
+//! enough. Enclosures of range converge linearly with width of pieces, so every bisection of all
+//! pieces halves the width of the result.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use inter::Interval;
+//! use inter::integrate;
+//!
+//! let integral = integrate::integrate(|x| x.sin(), Interval::with_range(0., 3.), 1e-3, 10_000);
+//!
+//! assert!(integral.complete && integral.value.width() <= 1e-3);
+//! assert!(integral.value.contains(1. - 3f64.cos()));
+//! ```
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use interval::Interval;
+
+/// Result of `integrate`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Integral {
+    /// Enclosure of the integral
+    pub value: Interval<f64>,
+    /// Pieces of the domain, in increasing order
+    pub pieces: Vec<Interval<f64>>,
+    /// Value is not wider than requested
+    pub complete: bool,
+}
+
+/// Enclose integral of `f` over `domain`, bisecting it into at most `max_pieces` pieces until
+/// the enclosure is at most `width` wide
+///
+/// `f` has to enclose the range of function over every interval it is called with. Function
+/// has to be defined on the whole domain, pieces where `f` is empty make the result empty.
+/// Unbounded domain is not split and gives unbounded result, unless `f` is zero there.
+pub fn integrate<F>(f: F, domain: Interval<f64>, width: f64, max_pieces: usize) -> Integral
+    where F: Fn(Interval<f64>) -> Interval<f64>
+{
+    if domain.is_empty() {
+        return Integral {
+            value: Interval::exact(0.),
+            pieces: Vec::new(),
+            complete: true,
+        };
+    }
+
+    let piece = |x: Interval<f64>| {
+        let length = Interval::exact(x.end) - Interval::exact(x.start);
+        Piece {
+            x,
+            value: f(x) * length,
+        }
+    };
+
+    let mut heap = BinaryHeap::new();
+    let mut done = Vec::new();
+    heap.push(piece(domain));
+
+    // Running sum of widths only decides when to check the exact one
+    let mut total = heap.peek().unwrap().width();
+    while total > width && heap.len() + done.len() < max_pieces {
+        let widest = match heap.pop() {
+            Some(widest) => widest,
+            None => break,
+        };
+        let x = widest.x;
+        let mid = x.mid();
+        if !x.is_bounded() || mid <= x.start || mid >= x.end {
+            done.push(widest);
+            continue;
+        }
+
+        let (left, right) = (piece(Interval::with_range(x.start, mid)),
+                             piece(Interval::with_range(mid, x.end)));
+        total += left.width() + right.width() - widest.width();
+        heap.push(left);
+        heap.push(right);
+        if total <= width || total.is_nan() {
+            total = heap.iter().chain(&done).map(Piece::width).sum();
+        }
+    }
+
+    let mut pieces: Vec<_> = heap.into_vec();
+    pieces.extend(done);
+    pieces.sort_by(|a, b| a.x.start.total_cmp(&b.x.start));
+    let value = pieces.iter().fold(Interval::exact(0.), |sum, piece| sum + piece.value);
+
+    Integral {
+        complete: value.width() <= width,
+        value,
+        pieces: pieces.into_iter().map(|piece| piece.x).collect(),
+    }
+}
+
+/// Piece of domain with enclosure of integral over it
+struct Piece {
+    x: Interval<f64>,
+    value: Interval<f64>,
+}
+
+impl Piece {
+    fn width(&self) -> f64 {
+        if self.value.is_empty() { 0. } else { self.value.width() }
+    }
+}
+
+impl PartialEq for Piece {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Piece {}
+
+impl PartialOrd for Piece {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Piece {
+    /// Widest contribution first
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.width().total_cmp(&other.width())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn encloses_integral() {
+        let domain = Interval::with_range(-1., 2.);
+        let integral = integrate(|x| x.exp(), domain, 1e-3, 100_000);
+        let exact = 2f64.exp() - (-1f64).exp();
+
+        assert!(integral.complete && integral.value.contains(exact));
+        assert!(integral.value.width() <= 1e-3);
+        assert_eq!(integral.pieces[0].inf(), -1.);
+        assert_eq!(integral.pieces.last().unwrap().sup(), 2.);
+        for pair in integral.pieces.windows(2) {
+            assert_eq!(pair[0].sup(), pair[1].inf());
+        }
+
+        // Pieces are finer where the function changes quickly
+        let integral = integrate(|x| x.powi(4), Interval::with_range(0., 1.), 1e-3, 100_000);
+        let (first, last) = (integral.pieces[0], *integral.pieces.last().unwrap());
+        assert!(integral.value.contains(0.2) && first.width() > last.width());
+    }
+
+    #[test]
+    fn limits() {
+        let f = |x: Interval<f64>| Interval::exact(1.) / (Interval::exact(1.) + x.powi(2));
+
+        // Not enough pieces for the requested width
+        let integral = integrate(f, Interval::with_range(0., 1.), 1e-6, 10);
+        assert!(!integral.complete && integral.pieces.len() == 10);
+        assert!(integral.value.contains(::std::f64::consts::FRAC_PI_4));
+
+        let empty = integrate(f, Interval::empty(), 1e-6, 10);
+        assert!(empty.complete && empty.value == Interval::exact(0.) && empty.pieces.is_empty());
+        let point = integrate(f, Interval::exact(1.), 0., 10);
+        assert_eq!((point.value, point.pieces.len()), (Interval::exact(0.), 1));
+        let unbounded = integrate(f, Interval::with_range(0., f64::INFINITY), 1e-3, 100);
+        assert!(!unbounded.complete && !unbounded.value.is_bounded());
+    }
+}
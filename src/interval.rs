@@ -1,11 +1,45 @@
-use std::ops::{Add, Sub, Mul, Div, Neg};
+use std::ops::{Add, Sub, Mul, Div, Rem, Neg, AddAssign, SubAssign, MulAssign, DivAssign, RemAssign,
+               Bound};
+use std::error;
 use std::fmt;
 use std::cmp::Ordering;
+use std::collections::{btree_map, BTreeMap};
+use std::iter::{Product, Sum};
 
-use num::{Float, Zero, One, Num, FromPrimitive, one};
+use num::{Bounded, Float, FloatConst, Zero, One, Num, FromPrimitive, one};
 
+use decimal::{format_directed, format_hex, format_uncertainty};
+use one_or_two::OneOrTwo;
+use policy;
+use rounding::{RoundedArith, RoundedOps};
+#[cfg(feature = "libc")]
 use rounding::Rounding;
-use utils::{partial_min, partial_max};
+use table::pad;
+use utils::{partial_min, partial_max, scale_pow2, frexp, precision, next_up, next_down, ordinal};
+#[cfg(feature = "validate")]
+use validate;
+
+/// Error returned when bounds do not describe a valid interval
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum IntervalError {
+    /// Lower bound is greater than upper bound
+    Reversed,
+    /// Bound is not comparable with itself, i.e. it is NaN
+    NaN,
+}
+
+impl fmt::Display for IntervalError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let msg = match *self {
+            IntervalError::Reversed => "lower bound is greater than upper bound",
+            IntervalError::NaN => "bound is NaN",
+        };
+
+        fmt.write_str(msg)
+    }
+}
+
+impl error::Error for IntervalError {}
 
 /// Range arithmetic structure
 ///
@@ -22,32 +56,30 @@ use utils::{partial_min, partial_max};
 /// println!("{}", sum);
 /// ```
 ///
-/// ## Quirks
-/// - this implement `PartialEq` for type `T`
+/// ## Comparisons with scalars
 ///
-///   ```rust
-///   use inter::Interval;
+/// Comparing interval with a single value has no unambiguous meaning, so there are explicit
+/// methods for each case:
 ///
-///   assert!(Interval::with_range(1., 2.) == 1.5);
-///   ```
+/// ```rust
+/// use inter::Interval;
 ///
-///   which may be strange when we see that `PartialEq` require to `a == b && b == c && a == c`,
-///   but when we accept that this is *interval arithmetic* then this is all true within
-///   `Interval::epsilon()`.
-/// - this implement `PartialOrd` for type `T`
+/// let a = Interval::with_range(1., 2.);
 ///
-///   ```rust
-///   use inter::Interval;
+/// assert!(a.contains(1.5));
+/// assert!(a.possibly_eq(1.5));
+/// assert!(!a.certainly_eq(1.5));
+/// assert!(Interval::exact(1.5).certainly_eq(1.5));
+/// ```
 ///
-///   assert!(Interval::with_range(1., 2.) < 3.);
-///   assert!(Interval::with_range(1., 2.) > 0.5);
-///   assert!(Interval::with_range(1., 2.) <= 1.5);
-///   assert!(Interval::with_range(1., 2.) >= 1.5);
-///   ```
+/// Enabling `legacy-cmp` feature restores old `PartialEq<T>` and `PartialOrd<T>` implementations,
+/// where `interval == value` means that `value` is contained in interval and `interval < value`
+/// means that whole interval is less than `value`. This breaks `a == b && b == c => a == c`
+/// which can be true only within `Interval::epsilon()`.
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Interval<T> {
-    start: T,
-    end: T,
+    pub(crate) start: T,
+    pub(crate) end: T,
 }
 
 impl<T> Interval<T>
@@ -57,44 +89,279 @@ impl<T> Interval<T>
     ///
     /// # Panics
     ///
-    /// This will panic if `start` is greater than `end`. Only proper intervals are allowed.
+    /// This will panic if `start` is greater than `end` or either bound is NaN. Use
+    /// `try_with_range` to handle these cases and `empty` to create empty interval.
     pub fn with_range(start: T, end: T) -> Self {
-        assert!(start <= end);
+        match Interval::try_with_range(start, end) {
+            Ok(interval) => interval,
+            Err(err) => panic!("invalid interval: {}", err),
+        }
+    }
 
+    /// Create interval with start and end of range, checking that bounds are valid
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use std::f64;
+    /// use inter::Interval;
+    /// use inter::interval::IntervalError;
+    ///
+    /// assert_eq!(Interval::try_with_range(1., 2.), Ok(Interval::with_range(1., 2.)));
+    /// assert_eq!(Interval::try_with_range(2., 1.), Err(IntervalError::Reversed));
+    /// assert_eq!(Interval::try_with_range(f64::NAN, 1.), Err(IntervalError::NaN));
+    /// ```
+    pub fn try_with_range(start: T, end: T) -> Result<Self, IntervalError> {
         Interval {
-            start: start,
-            end: end,
+            start,
+            end,
         }
+        .checked()
+        .and_then(|interval| if interval.start <= interval.end {
+            Ok(interval)
+        } else {
+            Err(IntervalError::Reversed)
+        })
     }
 
     /// Create interval with central element and deviation ε
+    ///
+    /// # Panics
+    ///
+    /// This will panic if `epsilon` is negative or either value is NaN.
     pub fn with_epsilon<P>(center: P, epsilon: P) -> Self
         where P: Add<Output = T> + Sub<Output = T> + Copy
     {
         Interval::with_range(center - epsilon, center + epsilon)
     }
 
+    /// Create interval with central element and deviation ε, checking that bounds are valid
+    pub fn try_with_epsilon<P>(center: P, epsilon: P) -> Result<Self, IntervalError>
+        where P: Add<Output = T> + Sub<Output = T> + Copy
+    {
+        Interval::try_with_range(center - epsilon, center + epsilon)
+    }
+
+    /// Check that neither bound is NaN
+    ///
+    /// Arithmetic never produces NaN bounds from valid operands, not even functions evaluated
+    /// outside of their domain, which give empty interval instead. NaN bounds come from NaN
+    /// values, like in `Interval::from(f64::NAN)`. Empty interval is valid.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    /// use inter::interval::IntervalError;
+    ///
+    /// let x = Interval::with_range(-2., -1.);
+    ///
+    /// assert_eq!(x.checked(), Ok(x));
+    /// assert!(x.powf(Interval::exact(0.5)).checked().is_ok());
+    /// assert_eq!(Interval::from(f64::NAN).checked(), Err(IntervalError::NaN));
+    /// assert!(Interval::<f64>::empty().checked().is_ok());
+    /// ```
+    pub fn checked(self) -> Result<Self, IntervalError> {
+        if self.start.partial_cmp(&self.start).is_none() ||
+           self.end.partial_cmp(&self.end).is_none() {
+            Err(IntervalError::NaN)
+        } else {
+            Ok(self)
+        }
+    }
+
+    /// Create degenerate interval containing only `value`
     pub fn exact(value: T) -> Self
         where T: Num + Copy
     {
         Interval::with_epsilon(value, Zero::zero())
     }
+
+    /// Lower bound (infimum) of interval
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    /// let interval = Interval::with_range(1., 2.);
+    ///
+    /// assert_eq!((interval.inf(), interval.sup()), (1., 2.));
+    /// ```
+    pub fn inf(&self) -> T
+        where T: Copy
+    {
+        self.start
+    }
+
+    /// Upper bound (supremum) of interval
+    pub fn sup(&self) -> T
+        where T: Copy
+    {
+        self.end
+    }
+
+    /// Check if interval contains exactly one value
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// assert!(Interval::exact(1.5).is_exact());
+    /// assert!(!Interval::with_range(1., 2.).is_exact());
+    /// ```
+    pub fn is_exact(&self) -> bool {
+        self.start == self.end
+    }
+
+    /// Create interval containing no values
+    ///
+    /// Empty interval is represented with start greater than end, so it is the only way to
+    /// create one. Arithmetic on empty interval results in empty interval.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// let empty = Interval::<f64>::empty();
+    ///
+    /// assert!(empty.is_empty());
+    /// assert!(!empty.contains(0.));
+    /// assert!((empty + Interval::exact(1.)).is_empty());
+    /// ```
+    pub fn empty() -> Self
+        where T: Bounded
+    {
+        Interval {
+            start: T::max_value(),
+            end: T::min_value(),
+        }
+    }
+
+    /// Check if interval contains no values
+    pub fn is_empty(&self) -> bool {
+        // NaN bounds also make interval empty
+        self.start.partial_cmp(&self.end).is_none_or(|order| order == Ordering::Greater)
+    }
 }
 
+/// Additive identity is the degenerate interval `[0, 0]`
+///
+/// `is_zero` is `true` only for that exact interval (see `is_certainly_zero`), so generic code
+/// short-circuiting on `is_zero` never drops an operand that could be non-zero. Use
+/// `contains_zero` to check whether zero is merely possible.
 impl<T> Zero for Interval<T>
-    where T: Num + Copy + PartialOrd
+    where T: Num + RoundedArith
 {
     fn zero() -> Self {
         Interval::exact(Zero::zero())
     }
 
     fn is_zero(&self) -> bool {
+        self.is_certainly_zero()
+    }
+}
+
+/// Default interval is `[0, 0]`, the same as `Zero::zero()`
+impl<T> Default for Interval<T>
+    where T: Num + RoundedArith
+{
+    fn default() -> Self {
+        Zero::zero()
+    }
+}
+
+impl<T> Interval<T>
+    where T: Num + Copy + PartialOrd
+{
+    /// Check if zero is the only value in interval
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// assert!(Interval::exact(0.).is_certainly_zero());
+    /// assert!(!Interval::with_range(-1., 1.).is_certainly_zero());
+    /// ```
+    pub fn is_certainly_zero(&self) -> bool {
         self.start == Zero::zero() && self.end == Zero::zero()
     }
+
+    /// Check if zero is one of values in interval
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// assert!(Interval::exact(0.).contains_zero());
+    /// assert!(Interval::with_range(-1., 1.).contains_zero());
+    /// assert!(!Interval::with_range(1., 2.).contains_zero());
+    /// ```
+    pub fn contains_zero(&self) -> bool {
+        self.contains(Zero::zero())
+    }
+
+    /// Absolute values of every value in interval
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// assert_eq!(Interval::with_range(-3., 2.).abs(), Interval::with_range(0., 3.));
+    /// assert_eq!(Interval::with_range(-3., -2.).abs(), Interval::with_range(2., 3.));
+    /// ```
+    pub fn abs(&self) -> Self
+        where T: Neg<Output = T>
+    {
+        if self.is_empty() {
+            *self
+        } else {
+            Interval {
+                start: self.mig(),
+                end: self.mag(),
+            }
+        }
+    }
+
+    /// Magnitude, the largest absolute value in interval
+    pub fn mag(&self) -> T
+        where T: Neg<Output = T>
+    {
+        partial_max(-self.start, self.end)
+    }
+
+    /// Mignitude, the smallest absolute value in interval
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    /// let interval = Interval::with_range(-3., 2.);
+    ///
+    /// assert_eq!((interval.mig(), interval.mag()), (0., 3.));
+    /// assert_eq!(Interval::with_range(-3., -2.).mig(), 2.);
+    /// ```
+    pub fn mig(&self) -> T
+        where T: Neg<Output = T>
+    {
+        let zero = T::zero();
+
+        if self.start > zero {
+            self.start
+        } else if self.end < zero {
+            -self.end
+        } else {
+            zero
+        }
+    }
 }
 
 impl<T> One for Interval<T>
-    where T: Num + Copy + PartialOrd
+    where T: Num + RoundedArith
 {
     fn one() -> Self {
         Interval::exact(one())
@@ -121,7 +388,10 @@ impl<T> Interval<T>
         self.start <= value && value <= self.end
     }
 
-    /// Width of interval
+    /// Check if all values fit inside range
+    ///
+    /// Every value is checked without early exit, so the loop can be vectorized. For empty
+    /// slice this returns `true`.
     ///
     /// ## Example
     ///
@@ -129,15 +399,19 @@ impl<T> Interval<T>
     /// use inter::Interval;
     /// let interval = Interval::with_range(1., 2.);
     ///
-    /// assert_eq!(interval.width(), 1.);
+    /// assert!(interval.contains_all(&[1., 1.5, 2.]));
+    /// assert!(!interval.contains_all(&[1., 2.5]));
     /// ```
-    pub fn width(&self) -> T
-        where T: Sub<Output = T>
+    pub fn contains_all(&self, values: &[T]) -> bool
+        where T: PartialOrd
     {
-        self.end - self.start
+        values.iter().fold(true, |acc, &v| acc & (self.start <= v) & (v <= self.end))
     }
 
-    /// Central element of interval (mean)
+    /// Check if any value fits inside range
+    ///
+    /// Every value is checked without early exit, so the loop can be vectorized. For empty
+    /// slice this returns `false`.
     ///
     /// ## Example
     ///
@@ -145,306 +419,3960 @@ impl<T> Interval<T>
     /// use inter::Interval;
     /// let interval = Interval::with_range(1., 2.);
     ///
-    /// assert_eq!(interval.center(), 1.5);
+    /// assert!(interval.contains_any(&[0., 1.5, 3.]));
+    /// assert!(!interval.contains_any(&[0., 2.5]));
     /// ```
-    pub fn center(&self) -> T
-        where T: Add<Output = T> + Div<Output = T> + FromPrimitive
+    pub fn contains_any(&self, values: &[T]) -> bool
+        where T: PartialOrd
     {
-        (self.start + self.end) / FromPrimitive::from_usize(2).unwrap()
+        values.iter().fold(false, |acc, &v| acc | ((self.start <= v) & (v <= self.end)))
     }
 
-    /// Calculate intersection of intervals.
+    /// Check if all values yielded by iterator fit inside range, stopping at first that does not
     ///
-    /// ## Returns
+    /// ## Example
     ///
-    /// - `None` if there is no overlap
-    /// - `Some(Interval)` otherwise
+    /// ```rust
+    /// use inter::Interval;
+    /// let interval = Interval::with_range(0., 10.);
+    ///
+    /// assert!(interval.contains_all_iter((0..10).map(|i| i as f64)));
+    /// ```
+    pub fn contains_all_iter<I>(&self, values: I) -> bool
+        where T: PartialOrd,
+              I: IntoIterator<Item = T>
+    {
+        values.into_iter().all(|v| self.contains(v))
+    }
+
+    /// Check if any value yielded by iterator fits inside range, stopping at first that does
     ///
     /// ## Example
     ///
     /// ```rust
     /// use inter::Interval;
-    /// let a = Interval::with_range(1., 2.);
-    /// let b = Interval::with_range(1.5, 2.5);
-    /// let c = Interval::with_range(3., 4.);
+    /// let interval = Interval::with_range(0.5, 0.75);
     ///
-    /// assert_eq!(a.intersection(&b), b.intersection(&a));
-    /// assert_eq!(a.intersection(&b), Some(Interval::with_range(1.5, 2.)));
-    /// assert_eq!(a.intersection(&c), None);
+    /// assert!(!interval.contains_any_iter((0..10).map(|i| i as f64)));
     /// ```
-    pub fn intersection(&self, other: &Interval<T>) -> Option<Interval<T>>
-        where T: PartialOrd
+    pub fn contains_any_iter<I>(&self, values: I) -> bool
+        where T: PartialOrd,
+              I: IntoIterator<Item = T>
     {
-        let low = partial_max(self.start, other.start);
-        let high = partial_min(self.end, other.end);
+        values.into_iter().any(|v| self.contains(v))
+    }
 
-        if low > high {
-            return None;
+    /// Entries of `map` with keys inside interval
+    ///
+    /// Both endpoints are included, and empty interval selects nothing.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use std::collections::BTreeMap;
+    /// use inter::Interval;
+    ///
+    /// // Readings keyed by timestamp in seconds
+    /// let readings: BTreeMap<i64, f64> = (0..100).map(|t| (t * 10, t as f64)).collect();
+    /// let window: Vec<_> = Interval::with_range(95, 120).range_in(&readings).collect();
+    ///
+    /// assert_eq!(window, [(&100, &10.), (&110, &11.), (&120, &12.)]);
+    /// ```
+    pub fn range_in<'a, V>(&self, map: &'a BTreeMap<T, V>) -> btree_map::Range<'a, T, V>
+        where T: Ord
+    {
+        if self.start <= self.end {
+            map.range((Bound::Included(self.start), Bound::Included(self.end)))
+        } else {
+            map.range((Bound::Included(self.start), Bound::Excluded(self.start)))
         }
-
-        Some(Interval {
-            start: low,
-            end: high,
-        })
     }
 
-    /// Return ε (half of interval width)
+    /// Elements of `sorted`, ordered by `key`, with keys inside interval
+    ///
+    /// Both endpoints are included, and empty interval selects nothing. Elements are found by
+    /// binary search, so if they are not sorted by `key` the result is unspecified.
     ///
     /// ## Example
     ///
     /// ```rust
     /// use inter::Interval;
-    /// let interval = Interval::with_range(1., 2.);
     ///
-    /// assert_eq!(interval.epsilon(), 0.5);
+    /// let samples = [(0.5, 'a'), (1., 'b'), (1.5, 'c'), (2., 'd')];
+    /// let inside = Interval::with_range(1., 1.75).sorted_in(&samples, |sample| sample.0);
+    ///
+    /// assert_eq!(inside, [(1., 'b'), (1.5, 'c')]);
     /// ```
-    pub fn epsilon(&self) -> T
-        where T: Sub<Output = T> + Div<Output = T> + FromPrimitive
+    pub fn sorted_in<'a, E, F>(&self, sorted: &'a [E], key: F) -> &'a [E]
+        where T: PartialOrd,
+              F: Fn(&E) -> T
     {
-        self.width() / FromPrimitive::from_usize(2).unwrap()
+        if self.start <= self.end {
+            let start = sorted.partition_point(|x| key(x) < self.start);
+            let end = start + sorted[start..].partition_point(|x| key(x) <= self.end);
+            &sorted[start..end]
+        } else {
+            &sorted[..0]
+        }
     }
-}
 
-impl<T> fmt::Display for Interval<T>
-    where T: fmt::Display + Copy
-{
-    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
-        write!(fmt, "[{}, {}]", self.start, self.end)
-    }
-}
+    /// Check if every element of interval is equal to every element of `other`
+    ///
+    /// `other` can be interval or a single value. This holds only when both are the same point.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// assert!(Interval::exact(1.).certainly_eq(1.));
+    /// assert!(!Interval::with_range(1., 2.).certainly_eq(1.));
+    /// assert!(!Interval::with_range(1., 2.).certainly_eq(Interval::with_range(1., 2.)));
+    /// ```
+    pub fn certainly_eq<I>(&self, other: I) -> bool
+        where T: PartialOrd,
+              I: Into<Interval<T>>
+    {
+        let other = other.into();
 
-impl<T> PartialEq<T> for Interval<T>
-    where T: PartialOrd + Copy
-{
-    fn eq(&self, other: &T) -> bool {
-        self.contains(*other)
+        self.is_exact() && other.is_exact() && self.start == other.start
     }
-}
 
-// impl<T> PartialOrd for Interval<T>
-// where T: Float + Copy {
-//     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-//         if self.end < other.start {
-//             return Some(Ordering::Less)
-//         }
-//         if self.start > other.end {
-//             return Some(Ordering::Greater)
-//         }
-//         if (self.start - other.start).abs() <  &&
-//             (self.end - other.end).abs() < Float::epsilon() {
-//                 return Some(Ordering::Equal)
-//             }
+    /// Check if any element of interval can be equal to some element of `other`
+    ///
+    /// For single value this is the same as `contains`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// assert!(Interval::with_range(1., 2.).possibly_eq(1.));
+    /// assert!(!Interval::with_range(1., 2.).possibly_eq(3.));
+    /// assert!(Interval::with_range(1., 2.).possibly_eq(Interval::with_range(2., 3.)));
+    /// ```
+    pub fn possibly_eq<I>(&self, other: I) -> bool
+        where T: PartialOrd,
+              I: Into<Interval<T>>
+    {
+        let other = other.into();
 
-//         None
-//     }
-// }
+        self.start <= other.end && other.start <= self.end
+    }
 
-impl<T> PartialOrd<T> for Interval<T>
-    where T: PartialOrd + Copy
-{
-    fn partial_cmp(&self, other: &T) -> Option<Ordering> {
-        if *other < self.start {
-            return Some(Ordering::Greater);
-        }
-        if *other > self.end {
-            return Some(Ordering::Less);
-        }
-        if self.contains(*other) {
-            return Some(Ordering::Equal);
-        }
+    /// Check if every element of interval is less than every element of `other`
+    ///
+    /// Holds vacuously when either interval is empty. This is the same as `strictly_precedes`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// let a = Interval::with_range(1., 2.);
+    ///
+    /// assert!(a.certainly_lt(3.));
+    /// assert!(!a.certainly_lt(2.));
+    /// assert!(a.certainly_lt(Interval::with_range(2.5, 3.)));
+    /// ```
+    pub fn certainly_lt<I>(&self, other: I) -> bool
+        where T: PartialOrd,
+              I: Into<Interval<T>>
+    {
+        self.strictly_precedes(other)
+    }
 
-        None
+    /// Check if every element of interval is greater than every element of `other`
+    ///
+    /// Holds vacuously when either interval is empty.
+    pub fn certainly_gt<I>(&self, other: I) -> bool
+        where T: PartialOrd,
+              I: Into<Interval<T>>
+    {
+        other.into().strictly_precedes(*self)
     }
-}
 
-impl<T> Add for Interval<T>
-    where T: Add<Output = T> + Copy
-{
-    type Output = Interval<T>;
+    /// Check if some element of interval can be less than some element of `other`
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// let a = Interval::with_range(1., 2.);
+    ///
+    /// assert!(a.possibly_lt(1.5));
+    /// assert!(!a.possibly_lt(1.));
+    /// assert!(!a.possibly_lt(Interval::<f64>::empty()));
+    /// ```
+    pub fn possibly_lt<I>(&self, other: I) -> bool
+        where T: PartialOrd,
+              I: Into<Interval<T>>
+    {
+        let other = other.into();
+
+        !self.is_empty() && !other.is_empty() && self.start < other.end
+    }
+
+    /// Check if every element of interval is less than or equal to every element of `other`
+    ///
+    /// Holds vacuously when either interval is empty.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// assert!(Interval::with_range(1., 2.).precedes(Interval::with_range(2., 3.)));
+    /// assert!(!Interval::with_range(1., 2.).strictly_precedes(Interval::with_range(2., 3.)));
+    /// ```
+    pub fn precedes<I>(&self, other: I) -> bool
+        where T: PartialOrd,
+              I: Into<Interval<T>>
+    {
+        let other = other.into();
+
+        self.is_empty() || other.is_empty() || self.end <= other.start
+    }
+
+    /// Check if every element of interval is less than every element of `other`
+    ///
+    /// Holds vacuously when either interval is empty.
+    pub fn strictly_precedes<I>(&self, other: I) -> bool
+        where T: PartialOrd,
+              I: Into<Interval<T>>
+    {
+        let other = other.into();
+
+        self.is_empty() || other.is_empty() || self.end < other.start
+    }
+
+    /// Width of interval
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    /// let interval = Interval::with_range(1., 2.);
+    ///
+    /// assert_eq!(interval.width(), 1.);
+    /// ```
+    pub fn width(&self) -> T
+        where T: Sub<Output = T> + Zero + PartialOrd
+    {
+        // Avoid `∞ - ∞` for degenerate intervals at infinity
+        if self.start == self.end {
+            T::zero()
+        } else {
+            self.end - self.start
+        }
+    }
+
+    /// Central element of interval (mean)
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    /// let interval = Interval::with_range(1., 2.);
+    ///
+    /// assert_eq!(interval.center(), 1.5);
+    /// ```
+    ///
+    /// For the whole line `(-∞, ∞)` this is `0`. Divisor `2` is built as `1 + 1`, so this works
+    /// for any numeric type, including fixed-point and decimal ones without primitive conversions.
+    pub fn center(&self) -> T
+        where T: Add<Output = T> + Div<Output = T> + One + Zero + PartialOrd
+    {
+        let center = (self.start + self.end) / (T::one() + T::one());
+
+        // Only `-∞ + ∞` (or NaN bounds) gives value that is not comparable with itself
+        match center.partial_cmp(&center) {
+            Some(_) => center,
+            None => T::zero(),
+        }
+    }
+
+    /// Calculate convex hull of intervals, i.e. smallest interval containing both of them
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    /// let a = Interval::with_range(1., 2.);
+    /// let b = Interval::with_range(3., 4.);
+    ///
+    /// assert_eq!(a.hull(&b), Interval::with_range(1., 4.));
+    /// ```
+    pub fn hull(&self, other: &Interval<T>) -> Interval<T>
+        where T: PartialOrd
+    {
+        if self.is_empty() {
+            return *other;
+        }
+        if other.is_empty() {
+            return *self;
+        }
+
+        Interval {
+            start: partial_min(self.start, other.start),
+            end: partial_max(self.end, other.end),
+        }
+    }
+
+    /// Calculate intersection of intervals.
+    ///
+    /// ## Returns
+    ///
+    /// - `None` if there is no overlap
+    /// - `Some(Interval)` otherwise
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    /// let a = Interval::with_range(1., 2.);
+    /// let b = Interval::with_range(1.5, 2.5);
+    /// let c = Interval::with_range(3., 4.);
+    ///
+    /// assert_eq!(a.intersection(&b), b.intersection(&a));
+    /// assert_eq!(a.intersection(&b), Some(Interval::with_range(1.5, 2.)));
+    /// assert_eq!(a.intersection(&c), None);
+    /// ```
+    pub fn intersection(&self, other: &Interval<T>) -> Option<Interval<T>>
+        where T: PartialOrd
+    {
+        let low = partial_max(self.start, other.start);
+        let high = partial_min(self.end, other.end);
+
+        // NaN bound is not comparable, which is empty as well
+        if low.partial_cmp(&high).is_none_or(|order| order == Ordering::Greater) {
+            return None;
+        }
+
+        Some(Interval {
+            start: low,
+            end: high,
+        })
+    }
+
+    /// Calculate intersection of intervals, which is `Interval::empty()` if they do not overlap
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    /// let a = Interval::with_range(1., 2.);
+    ///
+    /// assert_eq!(a.intersect(&Interval::with_range(1.5, 3.)), Interval::with_range(1.5, 2.));
+    /// assert_eq!(a.intersect(&Interval::with_range(3., 4.)), Interval::empty());
+    /// ```
+    pub fn intersect(&self, other: &Interval<T>) -> Interval<T>
+        where T: PartialOrd + Bounded
+    {
+        self.intersection(other).unwrap_or_else(Interval::empty)
+    }
+
+    /// Check if every value of interval is contained in `other`
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    /// let a = Interval::with_range(1., 2.);
+    ///
+    /// assert!(a.subset(&Interval::with_range(0., 2.)));
+    /// assert!(!a.subset(&Interval::with_range(1.5, 3.)));
+    /// assert!(Interval::empty().subset(&a));
+    /// ```
+    pub fn subset(&self, other: &Interval<T>) -> bool
+        where T: PartialOrd
+    {
+        self.is_empty() || other.start <= self.start && self.end <= other.end
+    }
+
+    /// Check if interval contains every value of `other`
+    pub fn superset(&self, other: &Interval<T>) -> bool
+        where T: PartialOrd
+    {
+        other.subset(self)
+    }
+
+    /// Check if intervals have no common values
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    /// let a = Interval::with_range(1., 2.);
+    ///
+    /// assert!(a.disjoint(&Interval::with_range(3., 4.)));
+    /// assert!(!a.disjoint(&Interval::with_range(2., 4.)));
+    /// ```
+    pub fn disjoint(&self, other: &Interval<T>) -> bool
+        where T: PartialOrd
+    {
+        self.intersection(other).is_none()
+    }
+
+    /// Check if interval is contained in interior of `other`, i.e. does not touch its endpoints
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    /// let a = Interval::with_range(1., 2.);
+    ///
+    /// assert!(a.interior_of(&Interval::with_range(0., 3.)));
+    /// assert!(!a.interior_of(&Interval::with_range(1., 3.)));
+    /// ```
+    pub fn interior_of(&self, other: &Interval<T>) -> bool
+        where T: PartialOrd
+    {
+        self.is_empty() || other.start < self.start && self.end < other.end
+    }
+
+    /// Return ε (half of interval width)
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    /// let interval = Interval::with_range(1., 2.);
+    ///
+    /// assert_eq!(interval.epsilon(), 0.5);
+    /// ```
+    pub fn epsilon(&self) -> T
+        where T: Sub<Output = T> + Div<Output = T> + One + Zero + PartialOrd
+    {
+        self.width() / (T::one() + T::one())
+    }
+
+    /// Evaluate polynomial with point coefficients at interval using Horner scheme
+    ///
+    /// Coefficients are given in ascending order, so `coeffs[k]` is the coefficient of `x^k`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    /// let x = Interval::with_range(1., 2.);
+    ///
+    /// // 1 + 2x + 3x²
+    /// assert_eq!(x.eval_poly(&[1., 2., 3.]), Interval::with_range(6., 17.));
+    /// ```
+    pub fn eval_poly(&self, coeffs: &[T]) -> Interval<T>
+        where T: Num + RoundedArith
+    {
+        coeffs.iter()
+            .rev()
+            .fold(Interval::exact(Zero::zero()), |acc, &c| acc * *self + Interval::exact(c))
+    }
+
+    /// Lift binary operation on endpoints to intervals
+    ///
+    /// `down` is evaluated with downward rounding and `up` with upward rounding on all four
+    /// pairs of endpoints, and the result spans the smallest and largest of them. This is valid
+    /// for every operation that is monotone in each argument separately (like `*` or `/` away
+    /// from zero).
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    /// let a = Interval::with_range(-1., 2.);
+    /// let b = Interval::with_range(3., 4.);
+    ///
+    /// assert_eq!(a.map2(&b, |x, y| x * y, |x, y| x * y), Interval::with_range(-4., 8.));
+    /// ```
+    ///
+    /// Switching rounding mode needs the `libc` feature, and it is skipped for integers.
+    #[cfg(feature = "libc")]
+    pub fn map2<F, G>(&self, other: &Interval<T>, down: F, up: G) -> Interval<T>
+        where T: RoundedArith,
+              F: Fn(T, T) -> T,
+              G: Fn(T, T) -> T
+    {
+        Interval {
+            start: T::with_rounding(Rounding::Downward, || self.corners(other, down, partial_min)),
+            end: T::with_rounding(Rounding::Upward, || self.corners(other, up, partial_max)),
+        }
+    }
+
+    /// Combine results of `f` on all four pairs of endpoints with `pick`
+    fn corners<F>(&self, other: &Interval<T>, f: F, pick: fn(T, T) -> T) -> T
+        where T: PartialOrd,
+              F: Fn(T, T) -> T
+    {
+        let (a, b, c, d) = (self.start, self.end, other.start, other.end);
+        let values = [f(a, c), f(a, d), f(b, c), f(b, d)];
+
+        // Quotient of infinities is NaN, but then other corners already reach both limits
+        values.iter()
+            .cloned()
+            .filter(|x| x.partial_cmp(x).is_some())
+            .fold(None, |acc, x| Some(acc.map_or(x, |acc| pick(acc, x))))
+            .unwrap_or(values[0])
+    }
+
+    /// Lift binary operation that is non-decreasing in both arguments to intervals
+    ///
+    /// Skips corner analysis of `map2`, `down` is evaluated only on the lower endpoints (with
+    /// downward rounding) and `up` only on the upper endpoints (with upward rounding).
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    /// let a = Interval::with_range(1., 2.);
+    /// let b = Interval::with_range(3., 5.);
+    ///
+    /// assert_eq!(a.map2_increasing(&b, f64::max, f64::max), Interval::with_range(3., 5.));
+    /// ```
+    ///
+    /// Switching rounding mode needs the `libc` feature.
+    #[cfg(feature = "libc")]
+    pub fn map2_increasing<F, G>(&self, other: &Interval<T>, down: F, up: G) -> Interval<T>
+        where T: RoundedArith,
+              F: FnOnce(T, T) -> T,
+              G: FnOnce(T, T) -> T
+    {
+        Interval {
+            start: T::with_rounding(Rounding::Downward, || down(self.start, other.start)),
+            end: T::with_rounding(Rounding::Upward, || up(self.end, other.end)),
+        }
+    }
+
+    /// Evaluate function defined by cases over subdomains
+    ///
+    /// Every case whose subdomain overlaps the interval is evaluated on the common part, and the
+    /// result is hull of all of them. Subdomains should cover the domain of function, values
+    /// outside of every subdomain are ignored, so when there is no overlap the result is empty.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use std::f64;
+    /// use inter::Interval;
+    ///
+    /// let negative = Interval::with_range(f64::NEG_INFINITY, 0.);
+    /// let positive = Interval::with_range(0., f64::INFINITY);
+    /// let relu: [(_, &dyn Fn(Interval<f64>) -> Interval<f64>); 2] =
+    ///     [(negative, &|_| Interval::exact(0.)), (positive, &|x| x)];
+    ///
+    /// assert_eq!(Interval::with_range(-1., 2.).piecewise(&relu), Interval::with_range(0., 2.));
+    /// assert_eq!(Interval::with_range(1., 2.).piecewise(&relu), Interval::with_range(1., 2.));
+    /// ```
+    pub fn piecewise<F>(&self, cases: &[(Interval<T>, F)]) -> Interval<T>
+        where T: PartialOrd + Bounded,
+              F: Fn(Interval<T>) -> Interval<T>
+    {
+        cases.iter()
+            .map(|(domain, f)| (self.intersect(domain), f))
+            .filter(|(x, _)| !x.is_empty())
+            .fold(Interval::empty(), |acc, (x, f)| acc.hull(&f(x)))
+    }
+}
+
+impl<T> fmt::Display for Interval<T>
+    where T: fmt::Display + Num + FromPrimitive + RoundedArith
+{
+    /// Formatter precision is applied to both endpoints and width to the whole interval
+    ///
+    /// Alternate flag (`{:#}`) writes `center ± radius` instead, or the endpoints when radius
+    /// cannot be computed (for half-bounded intervals). Radius is rounded upward, but decimal
+    /// digits are rounded to nearest, see `display_outward` for printing valid enclosures.
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let number = |x: T| match fmt.precision() {
+            Some(precision) => format!("{:.*}", precision, x),
+            None => format!("{}", x),
+        };
+
+        let s = if self.is_empty() {
+            "[empty]".to_string()
+        } else {
+            let center = self.center();
+            let (above, below) = (self.end.sub_up(center), center.sub_up(self.start));
+            // Distance between infinities is NaN, which is not comparable
+            let bounded = above.partial_cmp(&below).is_some();
+
+            if fmt.alternate() && bounded {
+                format!("{} ± {}", number(center), number(partial_max(above, below)))
+            } else {
+                format!("[{}, {}]", number(self.start), number(self.end))
+            }
+        };
+
+        pad(fmt, &s)
+    }
+}
+
+/// Interval displayed with outward-rounded decimal endpoints, see `Interval::display_outward`
+pub struct Outward<'a, T: 'a> {
+    interval: &'a Interval<T>,
+    digits: usize,
+}
+
+impl<'a, T> fmt::Display for Outward<'a, T>
+    where T: Float + fmt::Display
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        pad(fmt,
+            &format!("[{}, {}]",
+                     format_directed(self.interval.start, self.digits, false),
+                     format_directed(self.interval.end, self.digits, true)))
+    }
+}
+
+/// Interval displayed with hexadecimal endpoints, see `Interval::display_hex`
+pub struct Hex<'a, T: 'a> {
+    interval: &'a Interval<T>,
+}
+
+impl<'a, T> fmt::Display for Hex<'a, T>
+    where T: Float + fmt::Display
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        if self.interval.is_empty() {
+            return pad(fmt, "[empty]");
+        }
+
+        pad(fmt,
+            &format!("[{}, {}]",
+                     format_hex(self.interval.start),
+                     format_hex(self.interval.end)))
+    }
+}
+
+impl<T> Interval<T>
+    where T: Float + fmt::Display
+{
+    /// Display interval with `digits` fractional digits, rounding lower endpoint toward −∞ and
+    /// upper toward +∞
+    ///
+    /// Printed interval is therefore a valid (slightly wider) enclosure of the original one.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    /// let interval = Interval::with_range(1. / 3., 2. / 3.);
+    ///
+    /// assert_eq!(interval.display_outward(3).to_string(), "[0.333, 0.667]");
+    /// assert_eq!((-interval).display_outward(3).to_string(), "[-0.667, -0.333]");
+    /// assert_eq!(Interval::exact(0.5).display_outward(2).to_string(), "[0.50, 0.50]");
+    /// ```
+    pub fn display_outward(&self, digits: usize) -> Outward<'_, T> {
+        Outward {
+            interval: self,
+            digits,
+        }
+    }
+
+    /// Display interval with endpoints written exactly as hexadecimal floats
+    ///
+    /// Parsing the result gives back the same interval.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    /// let interval = Interval::with_range(0.1, 3.);
+    /// let hex = interval.display_hex().to_string();
+    ///
+    /// assert_eq!(hex, "[0x1.999999999999ap-4, 0x1.8p+1]");
+    /// assert_eq!(hex.parse(), Ok(interval));
+    /// ```
+    pub fn display_hex(&self) -> Hex<'_, T> {
+        Hex { interval: self }
+    }
+
+    /// Format interval in concise uncertainty notation, like `1.31(7)` for `1.31 ± 0.07`
+    ///
+    /// Uncertainty is written in units of the last digit with one or two digits, chosen so that
+    /// `center ± uncertainty` covers the whole interval. Exact intervals are written as exact
+    /// decimal value, and intervals that are unbounded or empty as by `Display`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// assert_eq!(Interval::with_range(1.25, 1.375).fmt_uncertainty(), "1.31(7)");
+    /// assert_eq!(Interval::with_range(1230., 1290.).fmt_uncertainty(), "1260(30)");
+    /// assert_eq!(Interval::exact(0.25).fmt_uncertainty(), "0.25");
+    /// ```
+    pub fn fmt_uncertainty(&self) -> String {
+        if self.is_empty() {
+            return "[empty]".to_string();
+        }
+
+        format_uncertainty(self.start, self.end)
+            .unwrap_or_else(|| format!("[{}, {}]", self.start, self.end))
+    }
+}
+
+impl<T> Interval<T>
+    where T: Float
+{
+    /// Hull of all `samples`, which is empty when there are none
+    ///
+    /// Returns error as soon as some sample is NaN, as measurement with unknown value cannot be
+    /// enclosed.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use std::f64;
+    /// use inter::Interval;
+    /// use inter::interval::IntervalError;
+    ///
+    /// let readings = vec![20.1, 19.8, 20.4, 20.0];
+    ///
+    /// assert_eq!(Interval::from_samples(readings), Ok(Interval::with_range(19.8, 20.4)));
+    /// assert_eq!(Interval::from_samples(vec![1., f64::NAN]), Err(IntervalError::NaN));
+    /// assert!(Interval::<f64>::from_samples(vec![]).unwrap().is_empty());
+    /// ```
+    pub fn from_samples<I>(samples: I) -> Result<Self, IntervalError>
+        where I: IntoIterator<Item = T>
+    {
+        let mut hull = Interval {
+            start: T::max_value(),
+            end: T::min_value(),
+        };
+        for x in samples {
+            if x.is_nan() {
+                return Err(IntervalError::NaN);
+            }
+            hull.start = hull.start.min(x);
+            hull.end = hull.end.max(x);
+        }
+
+        Ok(hull)
+    }
+
+    /// Interval from `n` representable values below `value` to `n` above it
+    ///
+    /// Useful for values known up to the accuracy of computation that produced them, like
+    /// results of library functions with documented error in ulps.
+    ///
+    /// # Panics
+    ///
+    /// This will panic if `value` is NaN.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// let x = Interval::from_ulps(1., 2);
+    ///
+    /// assert_eq!(x.inf(), 1. - 2. * f64::EPSILON / 2.);
+    /// assert_eq!(x.sup(), 1. + 2. * f64::EPSILON);
+    /// assert_eq!(Interval::from_ulps(0.1, 0), Interval::exact(0.1));
+    /// ```
+    pub fn from_ulps(value: T, n: u32) -> Self {
+        assert!(!value.is_nan(), "invalid interval: {}", IntervalError::NaN);
+
+        let (mut start, mut end) = (value, value);
+        for _ in 0..n {
+            start = next_down(start);
+            end = next_up(end);
+        }
+
+        Interval { start, end }
+    }
+
+    /// How far interval is from the tightest enclosure representable in `T`
+    ///
+    /// When no value of `T` lies strictly inside the interval only the endpoints could still
+    /// move, so further refinement is pointless. Zero of both signs counts as one value.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// let x = Interval::with_range(1., 1. + 4. * f64::EPSILON);
+    /// let tightness = x.tightness_report();
+    ///
+    /// assert_eq!(tightness.interior, 3);
+    /// assert_eq!((tightness.start_ulp, tightness.end_ulp), (f64::EPSILON, f64::EPSILON));
+    /// assert!(Interval::exact(0.1).tightness_report().is_tight());
+    /// ```
+    pub fn tightness_report(&self) -> Tightness<T> {
+        if self.is_empty() {
+            return Tightness {
+                interior: 0,
+                start_ulp: T::zero(),
+                end_ulp: T::zero(),
+            };
+        }
+
+        let between = ordinal(self.end) - ordinal(self.start) - 1;
+        let ulp = |x: T| if x.is_finite() { next_up(x.abs()) - x.abs() } else { T::infinity() };
+
+        Tightness {
+            interior: between.max(0).min(u64::MAX as i128) as u64,
+            start_ulp: ulp(self.start),
+            end_ulp: ulp(self.end),
+        }
+    }
+}
+
+/// Result of `Interval::tightness_report`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Tightness<T> {
+    /// Number of values of `T` strictly between the endpoints
+    pub interior: u64,
+    /// Distance from lower endpoint to the next value of larger magnitude
+    pub start_ulp: T,
+    /// Distance from upper endpoint to the next value of larger magnitude
+    pub end_ulp: T,
+}
+
+impl<T> Tightness<T> {
+    /// Check if no value of `T` lies strictly inside interval
+    pub fn is_tight(&self) -> bool {
+        self.interior == 0
+    }
+}
+
+#[cfg(feature = "legacy-cmp")]
+impl<T> PartialEq<T> for Interval<T>
+    where T: PartialOrd + Copy
+{
+    fn eq(&self, other: &T) -> bool {
+        self.contains(*other)
+    }
+}
+
+/// Degenerate interval containing only `value`
+impl<T> From<T> for Interval<T>
+    where T: Copy
+{
+    fn from(value: T) -> Self {
+        Interval {
+            start: value,
+            end: value,
+        }
+    }
+}
+
+/// Interval from `(start, end)` pair
+///
+/// # Panics
+///
+/// This will panic on bounds rejected by `with_range`.
+impl<T> From<(T, T)> for Interval<T>
+    where T: PartialOrd
+{
+    fn from((start, end): (T, T)) -> Self {
+        Interval::with_range(start, end)
+    }
+}
+
+/// Bounds of interval as `(start, end)` pair
+///
+/// ## Example
+///
+/// ```rust
+/// use inter::Interval;
+///
+/// let interval = Interval::from((1., 2.));
+/// let (start, end) = interval.into();
+///
+/// assert_eq!((start, end), (1., 2.));
+/// ```
+impl<T> From<Interval<T>> for (T, T) {
+    fn from(interval: Interval<T>) -> Self {
+        (interval.start, interval.end)
+    }
+}
+
+/// Interval of `f64` with the same endpoints, which are all exactly representable
+///
+/// ## Example
+///
+/// ```rust
+/// use inter::Interval;
+///
+/// let x = Interval::<f32>::from_f64_outward(0.1);
+///
+/// assert!(Interval::<f64>::from(x).contains(0.1));
+/// ```
+impl From<Interval<f32>> for Interval<f64> {
+    fn from(interval: Interval<f32>) -> Self {
+        Interval {
+            start: f64::from(interval.start),
+            end: f64::from(interval.end),
+        }
+    }
+}
+
+impl Interval<f64> {
+    /// The tightest interval of `f32` containing this interval
+    ///
+    /// Lower endpoint is rounded down and upper endpoint up, values beyond range of `f32` go to
+    /// infinity. Empty interval stays empty.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// let x = Interval::with_range(0.1, 0.3);
+    ///
+    /// assert!(Interval::<f64>::from(x.to_f32_enclosure()).superset(&x));
+    /// assert_eq!(Interval::exact(0.5).to_f32_enclosure(), Interval::exact(0.5f32));
+    /// ```
+    pub fn to_f32_enclosure(&self) -> Interval<f32> {
+        if self.is_empty() {
+            return Interval::empty();
+        }
+
+        Interval {
+            start: Interval::<f32>::from_f64_outward(self.start).start,
+            end: Interval::<f32>::from_f64_outward(self.end).end,
+        }
+    }
+}
+
+/// Intervals are ordered only when one strictly precedes the other
+///
+/// Equal intervals (with the same bounds) compare as `Equal`, overlapping ones and empty
+/// intervals are not comparable. Use `certainly_lt` and `possibly_lt` when it matters whether
+/// relation holds for all or for some values.
+///
+/// ## Example
+///
+/// ```rust
+/// use inter::Interval;
+///
+/// assert!(Interval::with_range(1., 2.) < Interval::with_range(3., 4.));
+/// assert!(!(Interval::with_range(1., 2.) < Interval::with_range(2., 3.)));
+/// assert!(!(Interval::with_range(1., 2.) >= Interval::with_range(2., 3.)));
+/// ```
+impl<T> PartialOrd for Interval<T>
+    where T: PartialOrd + Copy
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self == other {
+            Some(Ordering::Equal)
+        } else if self.is_empty() || other.is_empty() {
+            None
+        } else if self.end < other.start {
+            Some(Ordering::Less)
+        } else if other.end < self.start {
+            Some(Ordering::Greater)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(feature = "legacy-cmp")]
+impl<T> PartialOrd<T> for Interval<T>
+    where T: PartialOrd + Copy
+{
+    fn partial_cmp(&self, other: &T) -> Option<Ordering> {
+        if *other < self.start {
+            return Some(Ordering::Greater);
+        }
+        if *other > self.end {
+            return Some(Ordering::Less);
+        }
+        if self.contains(*other) {
+            return Some(Ordering::Equal);
+        }
+
+        None
+    }
+}
+
+impl<T> Interval<T>
+    where T: PartialOrd + Copy
+{
+    /// Empty one of two operands, if any
+    pub(crate) fn either_empty(&self, other: &Self) -> Option<Self> {
+        if self.is_empty() {
+            Some(*self)
+        } else if other.is_empty() {
+            Some(*other)
+        } else {
+            None
+        }
+    }
+}
+
+impl<T> Interval<T>
+    where T: Float
+{
+    /// Sum with bounds rounded by backend `B` instead of `RoundedArith` of `T`
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    /// use inter::rounding::{Native, Ulp};
+    ///
+    /// let (a, b) = (Interval::exact(0.1), Interval::exact(0.2));
+    ///
+    /// assert_eq!(a.add_with::<Native>(b), a + b);
+    /// assert!(a.add_with::<Ulp>(b).contains(0.1 + 0.2));
+    /// assert!((a + b).subset(&a.add_with::<Ulp>(b)));
+    /// ```
+    pub fn add_with<B: RoundedOps<T>>(self, other: Self) -> Self {
+        if let Some(empty) = self.either_empty(&other) {
+            return empty;
+        }
+
+        Interval {
+            start: B::add_down(self.start, other.start),
+            end: B::add_up(self.end, other.end),
+        }
+    }
+
+    /// Difference with bounds rounded by backend `B`
+    pub fn sub_with<B: RoundedOps<T>>(self, other: Self) -> Self {
+        if let Some(empty) = self.either_empty(&other) {
+            return empty;
+        }
+
+        Interval {
+            start: B::sub_down(self.start, other.end),
+            end: B::sub_up(self.end, other.start),
+        }
+    }
+
+    /// Product with bounds rounded by backend `B`
+    pub fn mul_with<B: RoundedOps<T>>(self, other: Self) -> Self {
+        if let Some(empty) = self.either_empty(&other) {
+            return empty;
+        }
+
+        Interval {
+            start: self.corners(&other, B::mul_down, partial_min),
+            end: self.corners(&other, B::mul_up, partial_max),
+        }
+    }
+
+    /// Quotient with bounds rounded by backend `B`, or `None` when `other` contains zero
+    ///
+    /// Division by zero is left to `div_split`, see `checked_div`.
+    pub fn div_with<B: RoundedOps<T>>(self, other: Self) -> Option<Self> {
+        if let Some(empty) = self.either_empty(&other) {
+            return Some(empty);
+        }
+        if other.contains(T::zero()) {
+            return None;
+        }
+
+        Some(Interval {
+            start: self.corners(&other, B::div_down, partial_min),
+            end: self.corners(&other, B::div_up, partial_max),
+        })
+    }
+}
+
+impl<T> Add for Interval<T>
+    where T: RoundedArith
+{
+    type Output = Interval<T>;
+
+    fn add(self, other: Self) -> Self {
+        if let Some(empty) = self.either_empty(&other) {
+            return empty;
+        }
+
+        let start = self.start.add_down(other.start);
+        let end = self.end.add_up(other.end);
+        let result = Interval {
+            start,
+            end,
+        };
+
+        #[cfg(feature = "validate")]
+        validate::binary("+", self, other, result, T::add_down, T::add_up, |_, _| false);
+        result
+    }
+}
+
+impl<T> Sub for Interval<T>
+    where T: RoundedArith
+{
+    type Output = Interval<T>;
+
+    fn sub(self, other: Self) -> Self {
+        if let Some(empty) = self.either_empty(&other) {
+            return empty;
+        }
+
+        let start = self.start.sub_down(other.end);
+        let end = self.end.sub_up(other.start);
+        let result = Interval {
+            start,
+            end,
+        };
+
+        #[cfg(feature = "validate")]
+        validate::binary("-", self, other, result, T::sub_down, T::sub_up, |_, _| false);
+        result
+    }
+}
+
+impl<T> Mul for Interval<T>
+    where T: RoundedArith + Zero
+{
+    type Output = Interval<T>;
+
+    fn mul(self, other: Self) -> Self {
+        if let Some(empty) = self.either_empty(&other) {
+            return empty;
+        }
+
+        // In interval arithmetic zero times infinity is zero, as only finite values are multiplied
+        let zero = T::zero();
+        let down = |a: T, b: T| if a == zero || b == zero { zero } else { a.mul_down(b) };
+        let up = |a: T, b: T| if a == zero || b == zero { zero } else { a.mul_up(b) };
+
+        let result = Interval {
+            start: self.corners(&other, &down, partial_min),
+            end: self.corners(&other, &up, partial_max),
+        };
+
+        #[cfg(feature = "validate")]
+        validate::binary("*", self, other, result, down, up, |_, _| false);
+        result
+    }
+}
+
+impl<T> Div for Interval<T>
+    where T: Float + RoundedArith
+{
+    type Output = Interval<T>;
+
+    /// Divide intervals, returning hull of `div_split`
+    ///
+    /// Division by `[0, 0]` results in empty interval.
+    fn div(self, other: Self) -> Self {
+        if let Some(empty) = self.either_empty(&other) {
+            return empty;
+        }
+
+        let result = match self.div_split(other) {
+            Some(result) => result.hull(),
+            None => Interval::empty(),
+        };
+        let result = policy::check("/", !other.contains(T::zero()), result);
+
+        #[cfg(feature = "validate")]
+        validate::binary("/", self, other, result, T::div_down, T::div_up, |_, b| b.is_zero());
+        result
+    }
+}
+
+impl<T> Rem for Interval<T>
+    where T: Float + RoundedArith
+{
+    type Output = Interval<T>;
+
+    /// Enclosure of remainders `x % y` of truncated division, with the sign of `x`
+    ///
+    /// Remainder by `[0, 0]` results in empty interval.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// let three = Interval::exact(3.);
+    /// assert_eq!(Interval::with_range(7., 8.) % three, Interval::with_range(1., 2.));
+    /// let x = Interval::with_range(-1., 2.);
+    /// assert_eq!(x % Interval::exact(5.), x);
+    /// assert_eq!(Interval::with_range(5., 7.) % three, Interval::with_range(0., 3.));
+    /// ```
+    fn rem(self, other: Self) -> Self {
+        if let Some(empty) = self.either_empty(&other) {
+            return empty;
+        }
+
+        // Remainder has the sign of `x` and is smaller than both `|x|` and `|y|`
+        let bound = other.mag();
+        let sign = Interval {
+            start: partial_max(partial_min(self.start, T::zero()), -bound),
+            end: partial_min(partial_max(self.end, T::zero()), bound),
+        };
+        let valid = !other.contains(T::zero());
+        let result = if valid {
+            // `x - trunc(x / y) y` evaluated for independent `x` and `y`
+            (self - (self / other).trunc() * other).intersect(&sign)
+        } else if other.is_certainly_zero() {
+            Interval::empty()
+        } else {
+            sign
+        };
+
+        policy::check("%", valid, result)
+    }
+}
+
+impl<T> Neg for Interval<T>
+    where T: Neg<Output = T> + Copy
+{
+    type Output = Interval<T>;
+
+    fn neg(self) -> Self {
+        Interval {
+            start: -self.end,
+            end: -self.start,
+        }
+    }
+}
+
+impl<T> Sum for Interval<T>
+    where T: RoundedArith + Zero
+{
+    /// Sum with all lower bounds added in one pass rounded downward and all upper bounds in one
+    /// pass rounded upward, which switches rounding mode only twice with `fesetround`
+    fn sum<I: Iterator<Item = Interval<T>>>(iter: I) -> Self {
+        let (mut starts, mut ends) = (Vec::new(), Vec::new());
+        for x in iter {
+            if x.is_empty() {
+                return x;
+            }
+            starts.push(x.start);
+            ends.push(x.end);
+        }
+
+        Interval {
+            start: T::zero().sum_down(&starts),
+            end: T::zero().sum_up(&ends),
+        }
+    }
+}
+
+impl<'a, T> Sum<&'a Interval<T>> for Interval<T>
+    where T: RoundedArith + Zero + 'a
+{
+    fn sum<I: Iterator<Item = &'a Interval<T>>>(iter: I) -> Self {
+        iter.cloned().sum()
+    }
+}
+
+impl<T> Product for Interval<T>
+    where T: RoundedArith + Zero + One
+{
+    /// Product of intervals multiplied one by one, starting from `[1, 1]`
+    fn product<I: Iterator<Item = Interval<T>>>(iter: I) -> Self {
+        let one = Interval {
+            start: T::one(),
+            end: T::one(),
+        };
+
+        iter.fold(one, Mul::mul)
+    }
+}
+
+impl<'a, T> Product<&'a Interval<T>> for Interval<T>
+    where T: RoundedArith + Zero + One + 'a
+{
+    fn product<I: Iterator<Item = &'a Interval<T>>>(iter: I) -> Self {
+        iter.cloned().product()
+    }
+}
+
+/// Implement operator with scalar on either side and compound assignment by treating scalar as
+/// degenerate interval, so rounding is the same as for interval operands
+macro_rules! scalar_ops {
+    ($($imp:ident, $method:ident, $assign:ident, $assign_method:ident;)*) => {
+        $(
+            impl<T> $imp<T> for Interval<T>
+                where Interval<T>: $imp<Output = Interval<T>>,
+                      T: Copy
+            {
+                type Output = Interval<T>;
+
+                fn $method(self, other: T) -> Self {
+                    self.$method(Interval::from(other))
+                }
+            }
+
+            impl<T> $assign for Interval<T>
+                where Interval<T>: $imp<Output = Interval<T>> + Copy
+            {
+                fn $assign_method(&mut self, other: Self) {
+                    *self = self.$method(other);
+                }
+            }
+
+            impl<T> $assign<T> for Interval<T>
+                where Interval<T>: $imp<Output = Interval<T>> + Copy,
+                      T: Copy
+            {
+                fn $assign_method(&mut self, other: T) {
+                    *self = self.$method(Interval::from(other));
+                }
+            }
+
+            impl $imp<Interval<f32>> for f32 {
+                type Output = Interval<f32>;
+
+                fn $method(self, other: Interval<f32>) -> Interval<f32> {
+                    Interval::from(self).$method(other)
+                }
+            }
+
+            impl $imp<Interval<f64>> for f64 {
+                type Output = Interval<f64>;
+
+                fn $method(self, other: Interval<f64>) -> Interval<f64> {
+                    Interval::from(self).$method(other)
+                }
+            }
+        )*
+    };
+}
+
+scalar_ops! {
+    Add, add, AddAssign, add_assign;
+    Sub, sub, SubAssign, sub_assign;
+    Mul, mul, MulAssign, mul_assign;
+    Div, div, DivAssign, div_assign;
+    Rem, rem, RemAssign, rem_assign;
+}
+
+impl<T> Interval<T>
+    where T: Float + RoundedArith
+{
+    /// Compute `self * a + b` with fused multiply-add
+    ///
+    /// Every bound is a product of endpoints with an endpoint of `b` added, rounded only once, so
+    /// the result is never wider than `self * a + b` and is often tighter, for example in Horner
+    /// scheme.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// let (x, ten, one) = (Interval::exact(0.1), Interval::exact(10.), Interval::exact(1.));
+    ///
+    /// assert!((x * ten - one).width() > 0.);
+    /// assert_eq!(x.mul_add(ten, -one), Interval::exact(2f64.powi(-54)));
+    /// ```
+    pub fn mul_add(self, a: Self, b: Self) -> Self {
+        if let Some(empty) = self.either_empty(&a).or_else(|| self.either_empty(&b)) {
+            return empty;
+        }
+        if !(self.is_bounded() && a.is_bounded() && b.is_bounded()) {
+            return self * a + b;
+        }
+
+        let zero = T::zero();
+        // Zero times bounded value is exactly zero, so only `b` is left
+        let down = |x: T, y: T| {
+            if x == zero || y == zero { b.start } else { x.mul_add_down(y, b.start) }
+        };
+        let up = |x: T, y: T| if x == zero || y == zero { b.end } else { x.mul_add_up(y, b.end) };
+
+        Interval {
+            start: self.corners(&a, down, partial_min),
+            end: self.corners(&a, up, partial_max),
+        }
+    }
+
+    /// Divide by interval not containing zero
+    ///
+    /// Returns `None` when divisor contains zero, and empty interval when either operand is empty.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// let a = Interval::with_range(1., 2.);
+    ///
+    /// assert_eq!(a.checked_div(Interval::with_range(2., 4.)),
+    ///            Some(Interval::with_range(0.25, 1.)));
+    /// assert_eq!(a.checked_div(Interval::with_range(0., 4.)), None);
+    /// ```
+    pub fn checked_div(self, other: Self) -> Option<Self> {
+        if let Some(empty) = self.either_empty(&other) {
+            Some(empty)
+        } else if other.contains(T::zero()) {
+            None
+        } else {
+            Some(Interval {
+                start: self.corners(&other, T::div_down, partial_min),
+                end: self.corners(&other, T::div_up, partial_max),
+            })
+        }
+    }
+
+    /// Extended division, following IEEE 1788
+    ///
+    /// Result contains quotients of all values from `self` by all non-zero values from `other`.
+    /// Dividing by interval that contains zero in the interior can result in two disjoint pieces.
+    /// Returns `None` when the result is empty, that is when dividing by `[0, 0]` or when either
+    /// operand is empty.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::{Interval, OneOrTwo};
+    /// use std::f64;
+    ///
+    /// let a = Interval::with_range(1., 2.);
+    ///
+    /// assert_eq!(a.div_split(Interval::with_range(-1., 1.)),
+    ///            Some(OneOrTwo::Two(Interval::with_range(f64::NEG_INFINITY, -1.),
+    ///                               Interval::with_range(1., f64::INFINITY))));
+    /// assert_eq!(a.div_split(Interval::with_range(0., 4.)),
+    ///            Some(OneOrTwo::One(Interval::with_range(0.25, f64::INFINITY))));
+    /// assert_eq!(a.div_split(Interval::exact(0.)), None);
+    /// ```
+    pub fn div_split(self, other: Self) -> Option<OneOrTwo<T>> {
+        let zero = T::zero();
+        let (inf, neg_inf) = (T::infinity(), T::neg_infinity());
+
+        if self.is_empty() || other.is_empty() {
+            return None;
+        }
+        if let Some(result) = self.checked_div(other) {
+            return Some(OneOrTwo::One(result));
+        }
+        if self.contains(zero) {
+            return Some(OneOrTwo::One(Interval::with_range(neg_inf, inf)));
+        }
+        if other.start == zero && other.end == zero {
+            return None;
+        }
+
+        let (down, up) = (T::div_down, T::div_up);
+        // Quotients by negative and positive part of divisor, from the endpoint closest to zero
+        let (negative, positive) = if self.end < zero {
+            (Interval::with_range(down(self.end, other.start), inf),
+             Interval::with_range(neg_inf, up(self.end, other.end)))
+        } else {
+            (Interval::with_range(neg_inf, up(self.start, other.start)),
+             Interval::with_range(down(self.start, other.end), inf))
+        };
+
+        Some(match (other.start < zero, other.end > zero) {
+            (true, true) if self.end < zero => OneOrTwo::Two(positive, negative),
+            (true, true) => OneOrTwo::Two(negative, positive),
+            (true, false) => OneOrTwo::One(negative),
+            _ => OneOrTwo::One(positive),
+        })
+    }
+
+    /// Create interval containing the whole real line, `(-∞, ∞)`
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// let x = Interval::<f64>::entire();
+    ///
+    /// assert!(x.contains(1e308) && !x.is_bounded());
+    /// assert_eq!(x * Interval::exact(0.), Interval::exact(0.));
+    /// assert_eq!(format!("{}", x), "[-inf, inf]");
+    /// ```
+    pub fn entire() -> Self {
+        Interval {
+            start: T::neg_infinity(),
+            end: T::infinity(),
+        }
+    }
+
+    /// Check if both endpoints are finite, empty interval is bounded as well
+    pub fn is_bounded(&self) -> bool {
+        self.is_empty() || self.start.is_finite() && self.end.is_finite()
+    }
+
+    /// Check if interval is non-empty and bounded
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use std::f64;
+    /// use inter::Interval;
+    ///
+    /// assert!(Interval::with_range(1., 2.).is_common_interval());
+    /// assert!(!Interval::with_range(0., f64::INFINITY).is_common_interval());
+    /// assert!(!Interval::<f64>::empty().is_common_interval());
+    /// ```
+    pub fn is_common_interval(&self) -> bool {
+        !self.is_empty() && self.is_bounded()
+    }
+
+    /// Finite point inside the interval, as close to the center as possible
+    ///
+    /// Unlike `center` this never overflows: for `[-MAX, MAX]` it returns `0`, for half-bounded
+    /// intervals it returns the finite endpoint closest to infinity (`±MAX`), and the result is
+    /// always clamped into the interval so it can be used as a split point. Only `[∞, ∞]` and
+    /// `[-∞, -∞]` have no finite point, and give their endpoint.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use std::f64;
+    /// use inter::Interval;
+    ///
+    /// assert_eq!(Interval::with_range(1., 2.).mid(), 1.5);
+    /// assert_eq!(Interval::with_range(f64::MAX / 2., f64::MAX).mid(), 0.75 * f64::MAX);
+    /// assert_eq!(Interval::with_range(-f64::MAX, f64::MAX).mid(), 0.);
+    /// assert_eq!(Interval::with_range(0., f64::INFINITY).mid(), f64::MAX);
+    /// ```
+    pub fn mid(&self) -> T {
+        let zero = T::zero();
+        let half = T::one() / (T::one() + T::one());
+
+        match (self.start.is_infinite(), self.end.is_infinite()) {
+            (true, true) if self.start == self.end => self.start,
+            (true, true) => zero,
+            (true, false) => partial_min(self.end, -<T as Float>::max_value()),
+            (false, true) => partial_max(self.start, <T as Float>::max_value()),
+            (false, false) if self.start == -self.end => zero,
+            (false, false) => {
+                let mid = self.start * half + self.end * half;
+                partial_min(partial_max(mid, self.start), self.end)
+            }
+        }
+    }
+
+    /// Point at fraction `t` of interval, from `inf()` at `0` to `sup()` at `1`
+    ///
+    /// Result is always in interval, so with `t` uniform in `[0, 1)` it samples interval
+    /// uniformly, for example to check enclosures by Monte Carlo. Unbounded intervals have no
+    /// uniform distribution, and give `mid()` apart from endpoints.
+    ///
+    /// # Panics
+    ///
+    /// This will panic if interval is empty.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// let x = Interval::with_range(1., 3.);
+    ///
+    /// assert_eq!((x.point_at(0.), x.point_at(0.25), x.point_at(1.)), (1., 1.5, 3.));
+    /// ```
+    pub fn point_at(&self, t: T) -> T {
+        assert!(!self.is_empty(), "empty interval has no points");
+
+        if t <= T::zero() {
+            self.start
+        } else if t >= T::one() {
+            self.end
+        } else {
+            let width = self.end - self.start;
+            let point = if width.is_finite() {
+                self.start + t * width
+            } else {
+                // Width of bounded interval overflows only when its endpoints have opposite signs
+                self.start * (T::one() - t) + self.end * t
+            };
+
+            if point.is_finite() {
+                partial_min(partial_max(point, self.start), self.end)
+            } else {
+                self.mid()
+            }
+        }
+    }
+
+    /// Hausdorff distance to `other`, rounded up
+    ///
+    /// For intervals it is the larger of distances between their starts and between their ends.
+    /// Distance between empty intervals is zero, and between empty and non-empty is infinite.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// let x = Interval::with_range(1., 2.);
+    ///
+    /// assert_eq!(x.distance(&Interval::with_range(1.5, 4.)), 2.);
+    /// assert_eq!(x.distance(&x), 0.);
+    /// ```
+    pub fn distance(&self, other: &Self) -> T {
+        let zero = T::zero();
+        let dist = |a: T, b: T| if a == b { zero } else { partial_max(a.sub_up(b), b.sub_up(a)) };
+
+        match (self.is_empty(), other.is_empty()) {
+            (true, true) => zero,
+            (true, false) | (false, true) => T::infinity(),
+            (false, false) => {
+                partial_max(dist(self.start, other.start), dist(self.end, other.end))
+            }
+        }
+    }
+
+    /// Width relative to magnitude, rounded up
+    ///
+    /// It does not depend on scale, so `x.rel_width() < tolerance` can stop iterations with
+    /// enclosures of any magnitude. It is zero for single points and empty interval, between
+    /// `0` and `1` for intervals not containing zero, at most `2` for bounded intervals and
+    /// infinite for unbounded ones.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// assert_eq!(Interval::with_range(1.5e10, 2e10).rel_width(), 0.25);
+    /// assert_eq!(Interval::with_range(-1., 1.).rel_width(), 2.);
+    /// ```
+    pub fn rel_width(&self) -> T {
+        if self.is_empty() || self.start == self.end {
+            T::zero()
+        } else if !self.is_bounded() {
+            T::infinity()
+        } else {
+            self.end.sub_up(self.start).div_up(self.mag())
+        }
+    }
+
+    /// Part of the hull of both intervals covered by their intersection
+    ///
+    /// Measure is `1` for equal intervals, and `0` for disjoint ones or ones sharing a single
+    /// point only. When the hull is unbounded it is `1` if the intersection is unbounded too,
+    /// and `0` otherwise.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// let x = Interval::with_range(0., 2.);
+    ///
+    /// assert_eq!(x.overlap_measure(&Interval::with_range(1., 4.)), 0.25);
+    /// assert_eq!(x.overlap_measure(&x), 1.);
+    /// assert_eq!(x.overlap_measure(&Interval::with_range(2., 3.)), 0.);
+    /// ```
+    pub fn overlap_measure(&self, other: &Self) -> T {
+        let (common, hull) = (self.intersect(other), self.hull(other));
+
+        if common.is_empty() {
+            T::zero()
+        } else if common == hull {
+            T::one()
+        } else if !hull.is_bounded() {
+            if common.is_bounded() { T::zero() } else { T::one() }
+        } else {
+            common.width() / hull.width()
+        }
+    }
+
+    /// Split interval at `mid` into two halves
+    ///
+    /// Halves share the split point, so together they cover the original interval.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// assert_eq!(Interval::with_range(1., 2.).bisect(),
+    ///            (Interval::with_range(1., 1.5), Interval::with_range(1.5, 2.)));
+    /// ```
+    pub fn bisect(&self) -> (Self, Self) {
+        self.split_at(self.mid())
+    }
+
+    /// Split interval into `[start, point]` and `[point, end]`
+    ///
+    /// # Panics
+    ///
+    /// This will panic if `point` is not contained in interval.
+    pub fn split_at(&self, point: T) -> (Self, Self) {
+        assert!(self.contains(point));
+
+        (Interval {
+            start: self.start,
+            end: point,
+        },
+         Interval {
+            start: point,
+            end: self.end,
+        })
+    }
+
+    /// Split interval into its negative and positive parts
+    ///
+    /// Parts are `[start, 0]` and `[0, end]`, clipped to the interval, and each exists only when
+    /// the interval has values of that sign. Whether zero itself is contained is reported
+    /// separately, so `[0, 0]` has no parts, but `zero` is set.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// let signs = Interval::with_range(-1., 2.).split_at_zeros();
+    /// assert_eq!(signs.negative, Some(Interval::with_range(-1., 0.)));
+    /// assert_eq!(signs.positive, Some(Interval::with_range(0., 2.)));
+    /// assert!(signs.zero);
+    ///
+    /// let signs = Interval::with_range(0., 2.).split_at_zeros();
+    /// assert_eq!((signs.negative, signs.zero), (None, true));
+    /// ```
+    pub fn split_at_zeros(&self) -> Signs<T> {
+        let pieces = self.split_at_points(&[T::zero()]);
+
+        Signs {
+            negative: pieces[0],
+            zero: self.contains(T::zero()),
+            positive: pieces[1],
+        }
+    }
+
+    /// Split interval at increasing `points`
+    ///
+    /// Piece `i` is the part of the interval between `points[i - 1]` and `points[i]`, where the
+    /// first piece starts at `-∞` and the last ends at `∞`. It is `None` when no value of the
+    /// interval lies strictly between these points, so an interval that is a single point from
+    /// `points` has no pieces. Neighbouring pieces share the split point.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// let pieces = Interval::with_range(0.5, 2.5).split_at_points(&[0., 1., 2.]);
+    ///
+    /// assert_eq!(pieces, vec![None,
+    ///                         Some(Interval::with_range(0.5, 1.)),
+    ///                         Some(Interval::with_range(1., 2.)),
+    ///                         Some(Interval::with_range(2., 2.5))]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This will panic if `points` are not strictly increasing or some of them is NaN.
+    pub fn split_at_points(&self, points: &[T]) -> Vec<Option<Self>> {
+        assert!(points.iter().all(|x| !x.is_nan()) && points.windows(2).all(|w| w[0] < w[1]),
+                "split points are not increasing");
+
+        let bounds = |i: usize| {
+            let low = if i == 0 { T::neg_infinity() } else { points[i - 1] };
+            let high = if i == points.len() { T::infinity() } else { points[i] };
+            (low, high)
+        };
+
+        (0..points.len() + 1)
+            .map(|i| {
+                let (low, high) = bounds(i);
+                if self.is_empty() || self.start >= high || self.end <= low {
+                    return None;
+                }
+                Some(Interval {
+                    start: partial_max(self.start, low),
+                    end: partial_min(self.end, high),
+                })
+            })
+            .collect()
+    }
+
+    /// Iterate over `n` consecutive pieces of (nearly) equal width
+    ///
+    /// Neighbouring pieces share endpoints, the first starts at `start` and the last ends at
+    /// `end`, so the pieces always cover the original interval even if split points are rounded.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// let pieces: Vec<_> = Interval::with_range(0., 3.).subdivide(3).collect();
+    ///
+    /// assert_eq!(pieces, vec![Interval::with_range(0., 1.),
+    ///                         Interval::with_range(1., 2.),
+    ///                         Interval::with_range(2., 3.)]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This will panic if `n` is zero.
+    pub fn subdivide(&self, n: usize) -> Subdivide<T> {
+        assert!(n > 0);
+
+        Subdivide {
+            interval: *self,
+            pieces: n,
+            next: 0,
+            last: self.start,
+        }
+    }
+
+    /// Expand interval outward to the nearest multiples of `step`
+    ///
+    /// Result is exact when `step` is a power of two. For other steps the endpoints are computed
+    /// with directed rounding, so they may land slightly outside the grid point, but the result
+    /// always contains the original interval.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    /// let interval = Interval::with_range(0.3, 1.1);
+    ///
+    /// assert_eq!(interval.snap_to_grid(0.25), Interval::with_range(0.25, 1.25));
+    /// assert_eq!(interval.snap_to_grid(1.), Interval::with_range(0., 2.));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This will panic if `step` is not positive and finite.
+    pub fn snap_to_grid(&self, step: T) -> Self {
+        assert!(step > T::zero() && step.is_finite());
+
+        Interval {
+            start: self.start.div_down(step).floor().mul_down(step),
+            end: self.end.div_up(step).ceil().mul_up(step),
+        }
+    }
+
+    /// Round both endpoints toward −∞
+    ///
+    /// Results are integers, so no outward rounding is needed.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// assert_eq!(Interval::with_range(-1.5, 2.5).floor(), Interval::with_range(-2., 2.));
+    /// ```
+    pub fn floor(&self) -> Self {
+        Interval {
+            start: self.start.floor(),
+            end: self.end.floor(),
+        }
+    }
+
+    /// Round both endpoints toward +∞
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// assert_eq!(Interval::with_range(-1.5, 2.5).ceil(), Interval::with_range(-1., 3.));
+    /// ```
+    pub fn ceil(&self) -> Self {
+        Interval {
+            start: self.start.ceil(),
+            end: self.end.ceil(),
+        }
+    }
+
+    /// Round both endpoints toward zero
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// assert_eq!(Interval::with_range(-1.5, 2.5).trunc(), Interval::with_range(-1., 2.));
+    /// ```
+    pub fn trunc(&self) -> Self {
+        Interval {
+            start: self.start.trunc(),
+            end: self.end.trunc(),
+        }
+    }
+
+    /// Round both endpoints to the nearest integer, with halves rounded away from zero
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// assert_eq!(Interval::with_range(-1.5, 2.4).round(), Interval::with_range(-2., 2.));
+    /// ```
+    pub fn round(&self) -> Self {
+        Interval {
+            start: self.start.round(),
+            end: self.end.round(),
+        }
+    }
+
+    /// Enclosure of fractional parts `x - ⌊x⌋`
+    ///
+    /// Fractional part jumps from nearly 1 back to 0 at every integer, so when interval spans an
+    /// integer (or is not bounded) the result is the whole `[0, 1]`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// assert_eq!(Interval::with_range(2.25, 2.5).fract(), Interval::with_range(0.25, 0.5));
+    /// assert_eq!(Interval::with_range(-0.5, 0.5).fract(), Interval::with_range(0., 1.));
+    /// ```
+    pub fn fract(&self) -> Self {
+        if self.start > self.end {
+            return *self;
+        }
+
+        let (start, end) = (self.start.floor(), self.end.floor());
+        if start != end || !self.is_bounded() {
+            return Interval::with_range(T::zero(), T::one());
+        }
+
+        let unit = Interval::with_range(T::zero(), T::one());
+        Interval {
+            start: self.start.sub_down(start),
+            end: self.end.sub_up(end),
+        }
+        .intersect(&unit)
+    }
+
+    /// Widen result of plain floating-point computation by bound on its rounding error
+    ///
+    /// Uses the standard model `fl(x ∘ y) = (x ∘ y)(1 + ε) + δ` with `|ε| ≤ u` (unit roundoff)
+    /// and `|δ| ≤ η` (half of the smallest subnormal). When value was computed in round to
+    /// nearest by chain of `ops` operations whose errors accumulate as in sums and products
+    /// (e.g. `Σ xᵢ`, `Π xᵢ`, Horner scheme with exact data), its error is bounded by
+    /// `γ |x| + 2 ops η`, where `γ = ops u / (1 - 2 ops u)`, so the result contains exact value.
+    ///
+    /// This is a cheap a-posteriori bound: no interval arithmetic is needed during computation,
+    /// but the result is usually much wider than enclosure computed with intervals. `ops` so
+    /// large that `2 ops u ≥ 1` gives the whole real line.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// let sum = (0..10).fold(0., |acc, _| acc + 0.1);
+    /// assert!(sum != 1.);
+    ///
+    /// let enclosure = Interval::exact(sum).widen_rounding(10);
+    /// assert!(enclosure.contains(1.) && enclosure.width() < 1e-14);
+    /// ```
+    pub fn widen_rounding(&self, ops: usize) -> Self {
+        let (zero, one) = (T::zero(), T::one());
+        let two = one + one;
+        let n = T::from(ops).unwrap_or_else(T::infinity);
+        let unit = T::epsilon() / two;
+        // Smallest subnormal, i.e. `2η`
+        let subnormal = T::min_positive_value() * T::epsilon();
+
+        let nu = n.mul_up(unit);
+        if nu.mul_up(two) >= one {
+            return Interval::entire();
+        }
+        let gamma = nu.div_up(one.sub_down(nu.mul_up(two)));
+        let underflow = n.mul_up(subnormal);
+        let error = |x: T| if x.is_finite() {
+            gamma.mul_up(x.abs()).add_up(underflow)
+        } else {
+            zero
+        };
+
+        Interval {
+            start: self.start.sub_down(error(self.start)),
+            end: self.end.add_up(error(self.end)),
+        }
+    }
+
+    /// Create interval containing `mantissa * 2^exp`
+    ///
+    /// The result is exact whenever the value is representable in `T`, which is always the case
+    /// when `mantissa` fits in the precision of `T` and the result neither overflows nor
+    /// underflows. Otherwise it is the tightest enclosure of the value.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// assert_eq!(Interval::<f64>::from_dyadic(3, -2), Interval::exact(0.75));
+    ///
+    /// let wide = Interval::<f32>::from_dyadic((1 << 40) + 1, 0);
+    /// assert!(!wide.is_exact());
+    /// assert!(wide.contains(1099511627776.));
+    /// ```
+    pub fn from_dyadic(mantissa: i64, exp: i32) -> Self
+        where T: FromPrimitive
+    {
+        let bits = 64 - mantissa.unsigned_abs().leading_zeros() as i32;
+        let shift = partial_max(bits - precision::<T>(), 0);
+
+        let quotient = mantissa >> shift;
+        let exact = quotient << shift == mantissa;
+        let start: T = FromPrimitive::from_i64(quotient).unwrap();
+        let end: T = if exact {
+            start
+        } else {
+            FromPrimitive::from_i64(quotient + 1).unwrap()
+        };
+
+        Interval {
+            start: scale_pow2(start, exp.saturating_add(shift)).0,
+            end: scale_pow2(end, exp.saturating_add(shift)).1,
+        }
+    }
+
+    /// Create the tightest interval containing `value`
+    ///
+    /// Converting to type with lower precision (like `f32`) rounds the value to nearest, which
+    /// can move it outside of interval built with `exact`. This instead returns two neighbouring
+    /// values of `T` around `value` whenever it is not representable.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// let x = Interval::<f32>::from_f64_outward(0.1);
+    /// assert!(!x.is_exact());
+    /// assert!(x.contains(0.1f32));
+    ///
+    /// assert!(Interval::<f32>::from_f64_outward(0.5).is_exact());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This will panic if `value` is NaN.
+    pub fn from_f64_outward(value: f64) -> Self
+        where T: FromPrimitive
+    {
+        assert!(!value.is_nan());
+
+        let rounded: T = FromPrimitive::from_f64(value).unwrap_or_else(|| if value > 0. {
+            T::infinity()
+        } else {
+            T::neg_infinity()
+        });
+        let back = rounded.to_f64().unwrap();
+
+        Interval::enclose_rounded(rounded, back.partial_cmp(&value).unwrap())
+    }
+
+    /// Create the tightest interval containing integer `value`
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// let x = Interval::<f64>::from_i64_outward((1 << 53) + 1);
+    /// assert_eq!(x, Interval::with_range(9007199254740992., 9007199254740994.));
+    /// ```
+    pub fn from_i64_outward(value: i64) -> Self
+        where T: FromPrimitive
+    {
+        let rounded: T = FromPrimitive::from_i64(value).unwrap();
+        let back = rounded.to_i128().unwrap();
+
+        Interval::enclose_rounded(rounded, back.cmp(&(value as i128)))
+    }
+
+    /// Create the tightest interval containing unsigned integer `value`
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// assert!(!Interval::<f32>::from_u64_outward(u64::MAX).is_exact());
+    /// assert!(Interval::<f32>::from_u64_outward(1 << 40).is_exact());
+    /// ```
+    pub fn from_u64_outward(value: u64) -> Self {
+        let rounded = T::from(value).unwrap();
+        let back = rounded.to_u128().unwrap();
+
+        Interval::enclose_rounded(rounded, back.cmp(&(value as u128)))
+    }
+
+    /// Create the tightest interval containing 128-bit integer `value`
+    ///
+    /// Large counters are usually converted with `as`, which rounds to nearest, so
+    /// `exact(n as f64)` can miss `n`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// let events = (1i128 << 80) + 1;
+    /// assert_eq!(events as f64 as i128, events - 1);
+    ///
+    /// let x = Interval::<f64>::from_i128_outward(events);
+    /// assert_eq!(x.inf(), (1u128 << 80) as f64);
+    /// assert!(x.sup() > x.inf());
+    /// assert!(Interval::<f32>::from_i128_outward(i128::MAX).contains(1.7014118e38));
+    /// ```
+    pub fn from_i128_outward(value: i128) -> Self {
+        let rounded = T::from(value).unwrap_or_else(|| if value > 0 {
+            T::infinity()
+        } else {
+            T::neg_infinity()
+        });
+        // Only values rounded up to `2^127` do not fit back
+        let direction = rounded.to_i128().map_or(Ordering::Greater, |back| back.cmp(&value));
+
+        Interval::enclose_rounded(rounded, direction)
+    }
+
+    /// Create the tightest interval containing unsigned 128-bit integer `value`
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    /// use std::f32;
+    ///
+    /// assert!(Interval::<f64>::from_u128_outward(1 << 100).is_exact());
+    /// assert_eq!(Interval::<f32>::from_u128_outward(u128::MAX),
+    ///            Interval::with_range(f32::MAX, f32::INFINITY));
+    /// ```
+    pub fn from_u128_outward(value: u128) -> Self {
+        let rounded = T::from(value).unwrap_or_else(T::infinity);
+        // Values rounded up to `2^128` or overflowing do not fit back
+        let direction = rounded.to_u128().map_or(Ordering::Greater, |back| back.cmp(&value));
+
+        Interval::enclose_rounded(rounded, direction)
+    }
+
+    fn enclose_rounded(rounded: T, direction: Ordering) -> Self {
+        match direction {
+            Ordering::Less => Interval { start: rounded, end: next_up(rounded) },
+            Ordering::Equal => Interval { start: rounded, end: rounded },
+            Ordering::Greater => Interval { start: next_down(rounded), end: rounded },
+        }
+    }
+
+    /// Multiply interval by `2^exp`
+    ///
+    /// Endpoints are scaled exactly, without touching the rounding mode. Only results that
+    /// overflow or underflow into subnormals are widened to stay valid enclosures.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    /// let interval = Interval::with_range(1., 3.);
+    ///
+    /// assert_eq!(interval.mul_pow2(2), Interval::with_range(4., 12.));
+    /// ```
+    pub fn mul_pow2(&self, exp: i32) -> Self {
+        Interval {
+            start: scale_pow2(self.start, exp).0,
+            end: scale_pow2(self.end, exp).1,
+        }
+    }
+
+    /// Divide interval by `2^exp`
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    /// let interval = Interval::with_range(1., 3.);
+    ///
+    /// assert_eq!(interval.div_pow2(1), Interval::with_range(0.5, 1.5));
+    /// ```
+    pub fn div_pow2(&self, exp: i32) -> Self {
+        // i32::MIN has no negation, but it underflows any float anyway
+        self.mul_pow2(exp.checked_neg().unwrap_or(i32::MAX))
+    }
+
+    /// Multiply interval by integer `n`, rounded outward
+    ///
+    /// Unlike multiplication by `Interval::exact(n)`, this needs no conversion from primitive
+    /// types, and stays valid even when `n` is not representable in `T`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// assert_eq!(Interval::with_range(1., 3.).mul_by_uint(3), Interval::with_range(3., 9.));
+    /// assert!(Interval::<f32>::exact(1.).mul_by_uint((1 << 40) + 1).contains(1099511627777.));
+    /// ```
+    pub fn mul_by_uint(&self, n: u64) -> Self {
+        *self * Interval::from_u64_outward(n)
+    }
+
+    /// Divide interval by integer `n`, rounded outward
+    ///
+    /// Dividing by `0` is the same as dividing by `Interval::exact(0.)`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// let third = Interval::exact(1.).div_by_uint(3);
+    /// assert!(third.contains(1. / 3.) && !third.is_exact());
+    /// assert!(Interval::exact(1.).div_by_uint(0).is_empty());
+    /// ```
+    pub fn div_by_uint(&self, n: u64) -> Self {
+        *self / Interval::from_u64_outward(n)
+    }
+
+    /// Enclosure of `x * 2^e` for all `x` in interval and `e` in `min_exp..=max_exp`
+    ///
+    /// This is the inverse of `frexp`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    /// let mantissa = Interval::with_range(0.5, 0.75);
+    ///
+    /// assert_eq!(mantissa.ldexp((1, 2)), Interval::with_range(1., 3.));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This will panic if `min_exp` is greater than `max_exp`.
+    pub fn ldexp(&self, (min_exp, max_exp): (i32, i32)) -> Self {
+        assert!(min_exp <= max_exp);
+
+        Interval {
+            start: partial_min(scale_pow2(self.start, min_exp).0,
+                               scale_pow2(self.start, max_exp).0),
+            end: partial_max(scale_pow2(self.end, min_exp).1, scale_pow2(self.end, max_exp).1),
+        }
+    }
+
+    /// Split interval into enclosure of mantissas and range of binary exponents
+    ///
+    /// Every non-zero `x` in a finite interval can be written as `m * 2^e` where
+    /// `0.5 <= |m| < 1`, `m` is in the returned mantissa interval and `e` is in the returned
+    /// (inclusive) exponent range. When the interval spans several binades the mantissa is the
+    /// whole `[0.5, 1]` (or its negation), and when it contains zero the exponent range extends
+    /// down to the exponent of the smallest subnormal.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// let (mantissa, exp) = Interval::with_range(3., 3.5).frexp();
+    /// assert_eq!(mantissa, Interval::with_range(0.75, 0.875));
+    /// assert_eq!(exp, (2, 2));
+    ///
+    /// let (mantissa, exp) = Interval::with_range(1., 5.).frexp();
+    /// assert_eq!(mantissa, Interval::with_range(0.5, 1.));
+    /// assert_eq!(exp, (1, 3));
+    /// ```
+    pub fn frexp(&self) -> (Self, (i32, i32)) {
+        let zero = T::zero();
+        let one = T::one();
+        let half = one / (one + one);
+
+        let (start, start_exp) = frexp(self.start);
+        let (end, end_exp) = frexp(self.end);
+
+        if self.start > zero {
+            if start_exp == end_exp {
+                (Interval { start, end }, (start_exp, end_exp))
+            } else {
+                (Interval::with_range(half, one), (start_exp, end_exp))
+            }
+        } else if self.end < zero {
+            if start_exp == end_exp {
+                (Interval { start, end }, (end_exp, start_exp))
+            } else {
+                (Interval::with_range(-one, -half), (end_exp, start_exp))
+            }
+        } else {
+            let min_exp = frexp(T::min_positive_value() * T::epsilon()).1;
+            let max_exp = partial_max(start_exp, end_exp);
+            let mantissa = Interval {
+                start: if self.start < zero { -one } else { zero },
+                end: if self.end > zero { one } else { zero },
+            };
+
+            (mantissa, (min_exp, partial_max(min_exp, max_exp)))
+        }
+    }
+}
+
+/// Raise non-negative `x` to positive power `n` by squaring, so that every product is rounded
+/// in the same direction
+fn pow_rounded<T, F>(x: T, n: i32, mul: F) -> T
+    where T: Float,
+          F: Fn(T, T) -> T
+{
+    let (mut base, mut n, mut acc) = (x, n, T::one());
+    while n > 0 {
+        if n & 1 == 1 {
+            acc = mul(acc, base);
+        }
+        n >>= 1;
+        if n > 0 {
+            base = mul(base, base);
+        }
+    }
+
+    acc
+}
+
+/// Largest float whose `n`-th power is at most non-negative `x`, searched from `approx`
+fn root_down<T>(x: T, n: i32, approx: T) -> T
+    where T: Float + RoundedArith
+{
+    search_root(approx, |root| pow_rounded(root, n, T::mul_up) > x).0
+}
+
+/// Smallest float whose `n`-th power is at least non-negative `x`, searched from `approx`
+fn root_up<T>(x: T, n: i32, approx: T) -> T
+    where T: Float + RoundedArith
+{
+    search_root(approx, |root| pow_rounded(root, n, T::mul_down) >= x).1
+}
+
+/// Steps of one ulp taken from approximation of root before `search_root` bisects
+const ROOT_STEPS: usize = 8;
+
+/// Neighbouring non-negative floats `(below, above)` where increasing `rises` changes from
+/// `false` to `true`, searched from `approx`
+///
+/// Directed rounding of powers below the underflow limit can put the boundary many ulps away from
+/// `approx`, so after `ROOT_STEPS` steps the rest is bisected, which bounds the number of
+/// evaluations by the number of bits of `T`.
+fn search_root<T, F>(approx: T, rises: F) -> (T, T)
+    where T: Float,
+          F: Fn(T) -> bool
+{
+    let zero = T::zero();
+    let approx = partial_max(approx, zero);
+    let (mut below, mut above) = if rises(approx) {
+        let mut above = approx;
+        for _ in 0..ROOT_STEPS {
+            if above <= zero {
+                return (zero, zero);
+            }
+            let below = partial_max(next_down(above), zero);
+            if !rises(below) {
+                return (below, above);
+            }
+            above = below;
+        }
+        if rises(zero) {
+            return (zero, zero);
+        }
+
+        (zero, above)
+    } else {
+        let mut below = approx;
+        for _ in 0..ROOT_STEPS {
+            if below == T::infinity() {
+                return (below, below);
+            }
+            let above = next_up(below);
+            if rises(above) {
+                return (below, above);
+            }
+            below = above;
+        }
+        let max = T::max_value();
+        if !rises(max) {
+            return (max, T::infinity());
+        }
+
+        (below, max)
+    };
+
+    loop {
+        let mid = below + (above - below) / (T::one() + T::one());
+        if mid <= below || mid >= above {
+            return (below, above);
+        }
+        if rises(mid) {
+            above = mid;
+        } else {
+            below = mid;
+        }
+    }
+}
+
+/// Iterator over pieces of interval, see `Interval::subdivide`
+pub struct Subdivide<T> {
+    interval: Interval<T>,
+    pieces: usize,
+    next: usize,
+    last: T,
+}
+
+impl<T> Iterator for Subdivide<T>
+    where T: Float
+{
+    type Item = Interval<T>;
+
+    fn next(&mut self) -> Option<Interval<T>> {
+        if self.next == self.pieces {
+            return None;
+        }
+        self.next += 1;
+
+        let Interval { start, end } = self.interval;
+        let point = if self.next == self.pieces {
+            end
+        } else {
+            // Convex combination of endpoints does not overflow for wide intervals
+            let n = T::from(self.pieces).unwrap();
+            let k = T::from(self.next).unwrap();
+            let point = start / n * (n - k) + end / n * k;
+
+            // Keeps points in order even when rounded, NaN (from infinite bounds) is skipped
+            if point > self.last { partial_min(point, end) } else { self.last }
+        };
+        let piece = Interval {
+            start: self.last,
+            end: point,
+        };
+        self.last = point;
+
+        Some(piece)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let left = self.pieces - self.next;
+
+        (left, Some(left))
+    }
+}
+
+/// Parts of interval by sign, see `Interval::split_at_zeros`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Signs<T> {
+    /// Part with negative values, ending at zero if the interval continues past it
+    pub negative: Option<Interval<T>>,
+    /// Whether interval contains zero
+    pub zero: bool,
+    /// Part with positive values, starting at zero if the interval starts before it
+    pub positive: Option<Interval<T>>,
+}
+
+/// Maximal error of `sin`, `cos` and `tan` from the standard library in ulps
+pub(crate) const LIBM_ULPS: usize = 2;
+
+/// Widen `x` by `ulps` in both directions
+pub(crate) fn widen<T: Float>(x: T, ulps: usize) -> (T, T) {
+    (0..ulps).fold((x, x), |(lo, hi), _| (next_down(lo), next_up(hi)))
+}
+
+impl<T> Interval<T>
+    where T: Float + FloatConst + RoundedArith
+{
+    /// Interval containing π
+    pub fn pi() -> Self {
+        let pi = T::PI();
+
+        Interval {
+            start: next_down(pi),
+            end: next_up(pi),
+        }
+    }
+
+    /// Integers `n` for which `(n + shift)π` can lie inside interval, as `(first, last)`
+    ///
+    /// Returns `None` when there is no upper bound on the number of such integers.
+    fn multiples_of_pi(self, shift: T) -> Option<(T, T)> {
+        if !self.start.is_finite() || !self.end.is_finite() {
+            return None;
+        }
+
+        let turns = self / Interval::pi() - Interval::exact(shift);
+
+        Some((turns.start.ceil(), turns.end.floor()))
+    }
+
+    /// Range of function that is monotone between extrema at `(n + shift)π`, which are `1` for
+    /// even `n` and `-1` for odd `n`
+    fn periodic<F>(self, shift: T, f: F) -> Self
+        where F: Fn(T) -> T
+    {
+        let one = T::one();
+        let two = one + one;
+        let full = Interval::with_range(-one, one);
+
+        if self.is_empty() {
+            return self;
+        }
+
+        let (first, last) = match self.multiples_of_pi(shift) {
+            Some((first, last)) if last - first < one => (first, last),
+            _ => return full,
+        };
+
+        let (a, b) = (widen(f(self.start), LIBM_ULPS), widen(f(self.end), LIBM_ULPS));
+        let mut start = partial_min(a.0, b.0);
+        let mut end = partial_max(a.1, b.1);
+        if first == last {
+            if (first / two).floor() * two == first {
+                end = one;
+            } else {
+                start = -one;
+            }
+        }
+
+        Interval {
+            start: start.max(-one),
+            end: end.min(one),
+        }
+    }
+
+    /// Sine of every value in interval
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// let x = Interval::with_range(1., 2.);
+    ///
+    /// assert!(x.sin().contains(1f64.sin()));
+    /// assert!(x.sin().contains(1.));
+    /// assert!(x.sin().width() < 0.16);
+    /// ```
+    pub fn sin(self) -> Self {
+        let half = T::one() / (T::one() + T::one());
+
+        self.periodic(half, T::sin)
+    }
+
+    /// Cosine of every value in interval
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// let x = Interval::with_range(3., 7.);
+    ///
+    /// assert!(x.cos().contains(-1.) && x.cos().contains(1.));
+    /// assert!(Interval::with_range(0.5, 1.).cos().width() < 0.34);
+    /// ```
+    pub fn cos(self) -> Self {
+        self.periodic(T::zero(), T::cos)
+    }
+
+    /// Tangent of every value in interval
+    ///
+    /// Interval that can contain a pole results in `(-∞, ∞)`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    /// use std::f64;
+    ///
+    /// assert!(Interval::with_range(-1., 1.).tan().contains(1f64.tan()));
+    /// assert_eq!(Interval::with_range(1., 2.).tan(),
+    ///            Interval::with_range(f64::NEG_INFINITY, f64::INFINITY));
+    /// ```
+    pub fn tan(self) -> Self {
+        let half = T::one() / (T::one() + T::one());
+
+        self.increasing_between_poles(half, LIBM_ULPS, T::tan)
+    }
+
+    /// Cotangent of every value in interval
+    ///
+    /// Interval that can contain a pole results in `(-∞, ∞)`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// assert!(Interval::with_range(1., 2.).cot().contains(1. / 1f64.tan()));
+    /// assert!(Interval::with_range(-1., 1.).cot().contains(1e100));
+    /// ```
+    pub fn cot(self) -> Self {
+        // Cotangent is decreasing, so `-cot(x)` is increasing. Reciprocal adds one rounding.
+        -self.increasing_between_poles(T::zero(), LIBM_ULPS + 1, |x| -x.tan().recip())
+    }
+
+    /// Arcsine of every value in interval
+    ///
+    /// Values outside of `[-1, 1]` are ignored, interval lying entirely outside of it gives empty
+    /// interval.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    /// use std::f64::consts::PI;
+    ///
+    /// let x = Interval::with_range(0., 2.).asin();
+    ///
+    /// assert!(x.contains_all(&[0., PI / 2.]) && x.sup() < 1.5708);
+    /// assert!(Interval::with_range(1.5, 2.).asin().is_empty());
+    /// ```
+    pub fn asin(self) -> Self {
+        let x = self.clamp_unit();
+        if x.is_empty() {
+            let empty = Interval::empty();
+            return policy::check("asin", self.is_empty(), empty);
+        }
+
+        let result = x.increasing(T::asin).intersect(&Self::half_turn());
+        policy::check("asin", x == self, result)
+    }
+
+    /// Arccosine of every value in interval, with domain handled as in `asin`
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    /// use std::f64::consts::PI;
+    ///
+    /// assert!(Interval::with_range(-1., 0.5).acos().contains_all(&[PI / 3., PI]));
+    /// ```
+    pub fn acos(self) -> Self {
+        let x = self.clamp_unit();
+        if x.is_empty() {
+            let empty = Interval::empty();
+            return policy::check("acos", self.is_empty(), empty);
+        }
+        let range = Interval {
+            start: T::zero(),
+            end: Interval::pi().end,
+        };
+
+        let result = range.intersect(&Interval {
+            start: widen(x.end.acos(), LIBM_ULPS).0,
+            end: widen(x.start.acos(), LIBM_ULPS).1,
+        });
+        policy::check("acos", x == self, result)
+    }
+
+    /// Arctangent of every value in interval
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    /// use std::f64::{self, consts::PI};
+    ///
+    /// assert!(Interval::with_range(-1., f64::INFINITY).atan().contains_all(&[-PI / 4., PI / 2.]));
+    /// ```
+    pub fn atan(self) -> Self {
+        if self.is_empty() {
+            return self;
+        }
+
+        self.increasing(T::atan).intersect(&Self::half_turn())
+    }
+
+    /// Range of `atan2(y, x)` for `y` from `self` and `x` from `other`, within `[-π, π]`
+    ///
+    /// Angle is discontinuous on the negative part of `x` axis, where it is both `π` and `-π`, so
+    /// when `self` contains zero and `other` reaches it (or the origin, where direction is not
+    /// defined) the result is the whole `[-π, π]`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    /// use std::f64::consts::PI;
+    ///
+    /// let y = Interval::with_range(0., 1.);
+    ///
+    /// assert!(y.atan2(Interval::with_range(-1., -0.5)).contains_all(&[3. * PI / 4., PI]));
+    /// assert!(y.atan2(Interval::with_range(1., 2.)).contains_all(&[0., PI / 4.]));
+    /// assert!(y.atan2(Interval::with_range(1., 2.)).sup() < 0.786);
+    /// assert!((-y).atan2(Interval::exact(-1.)).contains_all(&[-PI, -3. * PI / 4.]));
+    /// ```
+    pub fn atan2(self, other: Self) -> Self {
+        let (y, x) = (self, other);
+        let zero = T::zero();
+        let pi: Self = Interval::pi();
+        let whole = Interval {
+            start: -pi.end,
+            end: pi.end,
+        };
+
+        if y.is_empty() || x.is_empty() {
+            return Interval::empty();
+        }
+        if y.contains(zero) && x.start <= zero {
+            return whole;
+        }
+
+        // Box does not meet the cut, so extreme angles are attained at its vertices
+        let corners = [(y.start, x.start), (y.start, x.end), (y.end, x.start), (y.end, x.end)];
+        let (start, end) = corners.iter()
+            .map(|&(y, x)| widen(y.atan2(x), LIBM_ULPS))
+            .fold((T::infinity(), T::neg_infinity()),
+                  |(start, end), (lo, hi)| (partial_min(start, lo), partial_max(end, hi)));
+
+        whole.intersect(&Interval { start, end })
+    }
+
+    /// Intersection with `[-1, 1]`
+    fn clamp_unit(self) -> Self {
+        self.intersect(&Interval::with_range(-T::one(), T::one()))
+    }
+
+    /// Enclosure of `[-π/2, π/2]`
+    fn half_turn() -> Self {
+        let half_pi = Interval::pi() / Interval::exact(T::one() + T::one());
+
+        Interval::with_range(-half_pi.end, half_pi.end)
+    }
+
+    /// Range of function increasing between poles at `(n + shift)π`, computed with error of at
+    /// most `ulps`
+    fn increasing_between_poles<F>(self, shift: T, ulps: usize, f: F) -> Self
+        where F: Fn(T) -> T
+    {
+        if self.is_empty() {
+            return self;
+        }
+
+        match self.multiples_of_pi(shift) {
+            Some((first, last)) if first > last => {
+                let (start, _) = widen(f(self.start), ulps);
+                let (_, end) = widen(f(self.end), ulps);
+
+                Interval { start, end }
+            }
+            _ => Interval::with_range(T::neg_infinity(), T::infinity()),
+        }
+    }
+}
+
+impl<T> Interval<T>
+    where T: Float + RoundedArith
+{
+    /// Apply increasing function `f` with error of at most `LIBM_ULPS` to both endpoints
+    fn increasing<F>(self, f: F) -> Self
+        where F: Fn(T) -> T
+    {
+        Interval {
+            start: widen(f(self.start), LIBM_ULPS).0,
+            end: widen(f(self.end), LIBM_ULPS).1,
+        }
+    }
+
+    /// Check if interval has no negative values, which are outside of domain of `sqrt` and `ln`
+    fn is_positive_domain(&self) -> bool {
+        self.is_empty() || self.start >= T::zero()
+    }
+
+    /// Apply increasing function `f` defined for non-negative values, ignoring negative part
+    fn increasing_positive<F>(self, f: F) -> Self
+        where F: Fn(T) -> T
+    {
+        let zero = T::zero();
+        if self.is_empty() || self.end < zero {
+            return Interval::empty();
+        }
+        let start = if self.start > zero { widen(f(self.start), LIBM_ULPS).0 } else { f(zero) };
+
+        Interval {
+            start,
+            end: widen(f(self.end), LIBM_ULPS).1,
+        }
+    }
+
+    /// Exponential of every value in interval
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// let x = Interval::with_range(0., 1.).exp();
+    ///
+    /// assert!(x.contains(1.) && x.contains(1f64.exp()));
+    /// assert!(!Interval::with_range(-1000., 0.).exp().contains(-1e-300));
+    /// ```
+    pub fn exp(self) -> Self {
+        let mut ret = self.increasing(T::exp);
+        ret.start = ret.start.max(T::zero());
+
+        ret
+    }
+
+    /// Power of two of every value in interval
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// assert!(Interval::with_range(-1., 3.).exp2().contains_all(&[0.5, 8.]));
+    /// ```
+    pub fn exp2(self) -> Self {
+        let mut ret = self.increasing(T::exp2);
+        ret.start = ret.start.max(T::zero());
+
+        ret
+    }
+
+    /// `eˣ - 1` of every value in interval, accurate also for values near zero
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// let x = Interval::exact(1e-20).exp_m1();
+    ///
+    /// assert!(x.contains(1e-20) && x.rel_width() < 1e-15);
+    /// ```
+    pub fn exp_m1(self) -> Self {
+        let mut ret = self.increasing(T::exp_m1);
+        ret.start = ret.start.max(-T::one());
+
+        ret
+    }
+
+    /// Natural logarithm of every value in interval
+    ///
+    /// Values below zero are outside of domain and are ignored, so interval containing zero
+    /// starts at `-∞`. Interval lying entirely below zero gives empty interval.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    /// use std::f64;
+    ///
+    /// let x = Interval::with_range(1., 2.).ln();
+    /// assert!(x.contains(0.) && x.contains(2f64.ln()));
+    ///
+    /// assert!(Interval::with_range(-1., 1.).ln().contains(f64::NEG_INFINITY));
+    /// assert!(Interval::with_range(-2., -1.).ln().is_empty());
+    /// ```
+    pub fn ln(self) -> Self {
+        policy::check("ln", self.is_positive_domain(), self.increasing_positive(T::ln))
+    }
+
+    /// Base 2 logarithm of every value in interval
+    ///
+    /// Domain is handled as in `ln`.
+    pub fn log2(self) -> Self {
+        policy::check("log2", self.is_positive_domain(), self.increasing_positive(T::log2))
+    }
+
+    /// Base 10 logarithm of every value in interval
+    ///
+    /// Domain is handled as in `ln`.
+    pub fn log10(self) -> Self {
+        policy::check("log10", self.is_positive_domain(), self.increasing_positive(T::log10))
+    }
+
+    /// `ln(1 + x)` of every value in interval, accurate also for values near zero
+    ///
+    /// Values below `-1` are outside of domain and are ignored, so interval containing `-1`
+    /// starts at `-∞`. Interval lying entirely below `-1` gives empty interval, as in `ln`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    /// use std::f64;
+    ///
+    /// let x = Interval::exact(1e-20).ln_1p();
+    /// assert!(x.contains(1e-20) && x.rel_width() < 1e-15);
+    ///
+    /// assert_eq!(Interval::with_range(-2., 0.).ln_1p().inf(), f64::NEG_INFINITY);
+    /// ```
+    pub fn ln_1p(self) -> Self {
+        let one = T::one();
+        if self.is_empty() || self.end < -one {
+            let empty = Interval::empty();
+            return policy::check("ln_1p", self.is_empty(), empty);
+        }
+        let start = if self.start > -one {
+            widen(self.start.ln_1p(), LIBM_ULPS).0
+        } else {
+            (-one).ln_1p()
+        };
+
+        let result = Interval {
+            start,
+            end: widen(self.end.ln_1p(), LIBM_ULPS).1,
+        };
+        policy::check("ln_1p", self.is_empty() || self.start >= -one, result)
+    }
+
+    /// Hyperbolic sine of every value in interval
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// assert!(Interval::with_range(-1., 2.).sinh().contains_all(&[(-1f64).sinh(), 2f64.sinh()]));
+    /// ```
+    pub fn sinh(self) -> Self {
+        self.increasing(T::sinh)
+    }
+
+    /// Hyperbolic cosine of every value in interval
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// let x = Interval::with_range(-1., 2.).cosh();
+    ///
+    /// assert_eq!(x.inf(), 1.);
+    /// assert!(x.contains(2f64.cosh()));
+    /// ```
+    pub fn cosh(self) -> Self {
+        let (zero, one) = (T::zero(), T::one());
+        if self.is_empty() {
+            return self;
+        }
+
+        // Even function with minimum at zero
+        let (near, far) = if self.start >= zero {
+            (self.start, self.end)
+        } else if self.end <= zero {
+            (-self.end, -self.start)
+        } else {
+            (zero, partial_max(-self.start, self.end))
+        };
+        let start = if near == zero { one } else { widen(near.cosh(), LIBM_ULPS).0 };
+
+        Interval {
+            start: partial_max(start, one),
+            end: widen(far.cosh(), LIBM_ULPS).1,
+        }
+    }
+
+    /// Hyperbolic tangent of every value in interval
+    pub fn tanh(self) -> Self {
+        self.increasing(T::tanh).intersect(&Interval::with_range(-T::one(), T::one()))
+    }
+
+    /// Inverse hyperbolic sine of every value in interval
+    pub fn asinh(self) -> Self {
+        self.increasing(T::asinh)
+    }
+
+    /// Inverse hyperbolic cosine of every value in interval
+    ///
+    /// Values below one are outside of domain and are ignored, interval lying entirely below
+    /// one gives empty interval.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// assert_eq!(Interval::with_range(0., 1.).acosh().inf(), 0.);
+    /// assert!(Interval::with_range(-2., 0.5).acosh().is_empty());
+    /// ```
+    pub fn acosh(self) -> Self {
+        let x = self.intersect(&Interval::with_range(T::one(), T::infinity()));
+        if x.is_empty() {
+            let empty = Interval::empty();
+            return policy::check("acosh", self.is_empty(), empty);
+        }
+
+        let mut ret = x.increasing(T::acosh);
+        ret.start = partial_max(ret.start, T::zero());
+
+        policy::check("acosh", x == self, ret)
+    }
+
+    /// Inverse hyperbolic tangent of every value in interval
+    ///
+    /// Values outside of `[-1, 1]` are ignored, and `±1` has infinite image.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    /// use std::f64;
+    ///
+    /// assert!(Interval::with_range(0., 0.5).atanh().contains(0.5f64.atanh()));
+    /// assert_eq!(Interval::with_range(0.5, 3.).atanh().sup(), f64::INFINITY);
+    /// ```
+    pub fn atanh(self) -> Self {
+        let x = self.intersect(&Interval::with_range(-T::one(), T::one()));
+        if x.is_empty() {
+            let empty = Interval::empty();
+            return policy::check("atanh", self.is_empty(), empty);
+        }
+
+        policy::check("atanh", x == self, x.increasing(T::atanh))
+    }
+
+    /// Square of every value in interval
+    ///
+    /// Unlike `x * x`, which treats both factors as independent, the result is never negative.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// let x = Interval::with_range(-2., 1.);
+    ///
+    /// assert_eq!(x * x, Interval::with_range(-2., 4.));
+    /// assert_eq!(x.sqr(), Interval::with_range(0., 4.));
+    /// ```
+    pub fn sqr(self) -> Self {
+        if self.is_empty() {
+            return self;
+        }
+
+        let (mig, mag) = (self.mig(), self.mag());
+        Interval {
+            start: mig.mul_down(mig),
+            end: mag.mul_up(mag),
+        }
+    }
+
+    /// Raise every value in interval to integer power `n`
+    ///
+    /// Every value is raised separately, so even powers are never negative and are tight also
+    /// for intervals containing zero. Empty interval stays empty, even for `n = 0`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// let x = Interval::with_range(-2., 1.);
+    ///
+    /// assert_eq!(x.powi(2), Interval::with_range(0., 4.));
+    /// assert_eq!(x.powi(3), Interval::with_range(-8., 1.));
+    /// assert_eq!(Interval::with_range(2., 4.).powi(-1), Interval::with_range(0.25, 0.5));
+    /// ```
+    pub fn powi(self, n: i32) -> Self {
+        let zero = T::zero();
+
+        if self.is_empty() {
+            return self;
+        }
+        if n == 0 {
+            return Interval::exact(T::one());
+        }
+        if n == i32::MIN {
+            // Negation would overflow, but the power is even
+            return self.powi(n / 2).sqr();
+        }
+        if n < 0 {
+            return Interval::exact(T::one()) / self.powi(-n);
+        }
+
+        let down = |x: T| pow_rounded(x.abs(), n, T::mul_down);
+        let up = |x: T| pow_rounded(x.abs(), n, T::mul_up);
+
+        if n % 2 == 1 {
+            Interval {
+                start: if self.start < zero { -up(self.start) } else { down(self.start) },
+                end: if self.end < zero { -down(self.end) } else { up(self.end) },
+            }
+        } else if self.start >= zero {
+            Interval {
+                start: down(self.start),
+                end: up(self.end),
+            }
+        } else if self.end <= zero {
+            Interval {
+                start: down(self.end),
+                end: up(self.start),
+            }
+        } else {
+            Interval {
+                start: zero,
+                end: partial_max(up(self.start), up(self.end)),
+            }
+        }
+    }
+
+    /// Square root of every value in interval
+    ///
+    /// Negative values are outside of domain and are ignored, interval lying entirely below zero
+    /// gives empty interval.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// assert_eq!(Interval::with_range(-1., 4.).sqrt(), Interval::with_range(0., 2.));
+    /// assert!(Interval::exact(2.).sqrt().contains(2f64.sqrt()));
+    /// assert!(Interval::with_range(-2., -1.).sqrt().is_empty());
+    /// ```
+    pub fn sqrt(self) -> Self {
+        let zero = T::zero();
+        let result = if self.is_empty() || self.end < zero {
+            Interval::empty()
+        } else {
+            // Square root of `T` is correctly rounded, so one ulp away from it is already outward
+            let (start, end) = (partial_max(self.start, zero), self.end);
+            let (low, high) = (start.sqrt(), end.sqrt());
+            let low = if low.mul_up(low) <= start { low } else { next_down(low) };
+            Interval {
+                start: partial_max(low, zero),
+                end: if high.mul_down(high) >= end { high } else { next_up(high) },
+            }
+        };
+
+        policy::check("sqrt", self.is_positive_domain(), result)
+    }
+
+    /// Cube root of every value in interval
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// assert_eq!(Interval::with_range(-8., 27.).cbrt(), Interval::with_range(-2., 3.));
+    /// ```
+    pub fn cbrt(self) -> Self {
+        self.nth_root_with(3, T::cbrt)
+    }
+
+    /// Real `n`-th root of every value in interval
+    ///
+    /// For even `n` domain is handled as in `sqrt`, for odd `n` negative values have negative
+    /// roots. Every endpoint is checked by raising it back to power `n` with directed rounding,
+    /// so for `n > 2` it can be a few ulps away from exact root.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// assert_eq!(Interval::with_range(16., 81.).nth_root(4), Interval::with_range(2., 3.));
+    /// assert_eq!(Interval::with_range(-32., 1.).nth_root(5), Interval::with_range(-2., 1.));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This will panic if `n` is not positive.
+    pub fn nth_root(self, n: i32) -> Self {
+        assert!(n > 0);
+        if n == 1 {
+            return self;
+        }
+
+        let exp = T::one() / T::from(n).unwrap();
+        self.nth_root_with(n, |x| x.powf(exp))
+    }
+
+    /// `n`-th root with `approx` giving approximation of root of non-negative value
+    fn nth_root_with<F>(self, n: i32, approx: F) -> Self
+        where F: Fn(T) -> T
+    {
+        let zero = T::zero();
+        let down = |x: T| if x < zero {
+            -root_up(-x, n, approx(-x))
+        } else {
+            root_down(x, n, approx(x))
+        };
+        let up = |x: T| if x < zero {
+            -root_down(-x, n, approx(-x))
+        } else {
+            root_up(x, n, approx(x))
+        };
+
+        if self.is_empty() || n % 2 == 0 && self.end < zero {
+            return Interval::empty();
+        }
+        let start = if n % 2 == 0 { partial_max(self.start, zero) } else { self.start };
+
+        Interval {
+            start: down(start),
+            end: up(self.end),
+        }
+    }
+
+    /// Raise every value in interval to every power from `exp`
+    ///
+    /// Negative base is outside of domain and is ignored. Base lying entirely below zero gives
+    /// empty interval.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// let x = Interval::with_range(2., 4.).powf(Interval::with_range(-1., 0.5));
+    ///
+    /// assert!(x.contains(0.25) && x.contains(2.));
+    /// assert!(!x.contains(0.24) && !x.contains(2.01));
+    /// ```
+    pub fn powf(self, exp: Self) -> Self {
+        let zero = T::zero();
+        if self.is_empty() || exp.is_empty() || self.end < zero {
+            return Interval::empty();
+        }
+
+        let base = Interval {
+            start: partial_max(self.start, zero),
+            end: self.end,
+        };
+
+        // Power of non-negative base is monotone in both arguments, so extremes are in corners
+        let corners = [base.start.powf(exp.start),
+                       base.start.powf(exp.end),
+                       base.end.powf(exp.start),
+                       base.end.powf(exp.end)];
+        let start = corners.iter().cloned().fold(T::infinity(), partial_min);
+        let end = corners.iter().cloned().fold(T::neg_infinity(), partial_max);
 
-    fn add(self, other: Self) -> Self {
-        let start = Rounding::Downward.execute(|| self.start + other.start);
-        let end = Rounding::Upward.execute(|| self.end + other.end);
         Interval {
-            start: start,
-            end: end,
+            start: partial_max(widen(start, LIBM_ULPS).0, zero),
+            end: widen(end, LIBM_ULPS).1,
+        }
+    }
+
+    /// Raise every value in interval to every power from `exponent`, following `pow` of IEEE 1788
+    ///
+    /// Result encloses `exp(y ln(x))`, so negative bases are outside of domain and are ignored,
+    /// as is zero base for non-positive exponents. Exponent that is a single integer is handled
+    /// as in `powi` instead, so negative bases are allowed for it. Unlike `powf`, values entirely
+    /// outside of domain give empty interval.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// let x = Interval::with_range(0., 4.).pow(Interval::with_range(0.5, 2.));
+    /// assert!(x.contains_all(&[0., 2., 16.]) && x.sup() < 16.01);
+    ///
+    /// assert_eq!(Interval::exact(-2.).pow(Interval::exact(3.)), Interval::exact(-8.));
+    /// assert!(Interval::with_range(-2., -1.).pow(Interval::exact(0.5)).is_empty());
+    /// assert!(Interval::exact(0.).pow(Interval::with_range(-1., 0.)).is_empty());
+    /// ```
+    pub fn pow(self, exponent: Self) -> Self {
+        let zero = T::zero();
+        let empty = Interval::empty();
+        if self.is_empty() || exponent.is_empty() {
+            return empty;
+        }
+        if exponent.is_exact() && exponent.start.fract() == zero {
+            if let Some(n) = exponent.start.to_i32() {
+                return self.powi(n);
+            }
+        }
+        if self.end < zero || self.end == zero && exponent.end <= zero {
+            return empty;
+        }
+        if self.end == zero {
+            return Interval::exact(zero);
+        }
+
+        let base = Interval {
+            start: partial_max(self.start, zero),
+            end: self.end,
+        };
+
+        (exponent * base.ln()).exp()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeMap;
+
+    use super::{Interval, IntervalError};
+
+    fn setup() -> (Interval<f64>, Interval<f64>) {
+        (Interval::with_range(1., 2.), Interval::with_range(3., 4.))
+    }
+
+    #[test]
+    fn contains() {
+        let (a, _) = setup();
+        assert!(a.contains(1.5));
+        assert!(!a.contains(2.1));
+        assert!(a.possibly_eq(1.5));
+        assert!(!a.certainly_eq(1.5));
+        assert!(Interval::exact(2.1).certainly_eq(2.1));
+    }
+
+    #[test]
+    fn from_measurements() {
+        let samples = [3.5, -1., 2., f64::INFINITY, -0.];
+        assert_eq!(Interval::from_samples(samples[..3].iter().cloned()),
+                   Ok(Interval::with_range(-1., 3.5)));
+        assert_eq!(Interval::from_samples(samples.iter().cloned()),
+                   Ok(Interval::with_range(-1., f64::INFINITY)));
+        assert_eq!(Interval::from_samples(Some(0.25)), Ok(Interval::exact(0.25)));
+        assert_eq!(Interval::from_samples(vec![f64::NAN]), Err(IntervalError::NaN));
+
+        let x = Interval::from_ulps(0.1, 3);
+        assert_eq!(x.inf(), 0.1f64.next_down().next_down().next_down());
+        assert_eq!(x.sup(), 0.1f64.next_up().next_up().next_up());
+        assert_eq!(Interval::from_ulps(0., 1), Interval::with_range(-5e-324, 5e-324));
+        assert_eq!(Interval::from_ulps(f64::MAX, 1).sup(), f64::INFINITY);
+        assert_eq!(Interval::from_ulps(f64::INFINITY, 1).sup(), f64::INFINITY);
+        assert!(::std::panic::catch_unwind(|| Interval::from_ulps(f64::NAN, 1)).is_err());
+
+        let tightness = Interval::with_range(-5e-324, f64::MIN_POSITIVE).tightness_report();
+        // Zero and all positive subnormals
+        let tiny = f64::MIN_POSITIVE.to_bits();
+        assert_eq!((tightness.interior, tightness.start_ulp), (tiny, 5e-324));
+        assert!(Interval::with_range(1., 1f64.next_up()).tightness_report().is_tight());
+        assert!(Interval::with_range(-0., 0.).tightness_report().is_tight());
+        let entire = Interval::<f64>::entire().tightness_report();
+        assert_eq!((entire.interior, entire.end_ulp), (u64::MAX - (1 << 53), f64::INFINITY));
+        assert_eq!(Interval::<f32>::with_range(-1., 1.).tightness_report().interior,
+                   2 * 1f32.to_bits() as u64 - 1);
+        assert!(Interval::<f64>::empty().tightness_report().is_tight());
+    }
+
+    #[test]
+    fn contains_many() {
+        use std::f64;
+
+        let (a, _) = setup();
+        assert!(a.contains_all(&[]));
+        assert!(!a.contains_any(&[]));
+        assert!(a.contains_all(&[1., 2., 1.25]));
+        assert!(!a.contains_all(&[1., f64::NAN]));
+        assert!(a.contains_any(&[f64::NAN, 2.]));
+        assert!(a.contains_all_iter(vec![1., 1.5]));
+        assert!(!a.contains_any_iter(vec![0., 3.]));
+    }
+
+    #[test]
+    #[cfg(feature = "legacy-cmp")]
+    fn legacy_contains() {
+        let (a, _) = setup();
+        assert!(a == 1.5);
+        assert!(a != 2.1);
+    }
+
+    #[test]
+    #[cfg(feature = "legacy-cmp")]
+    fn ordering() {
+        let (a, _) = setup();
+        assert!(a > 0.);
+        assert!(a < 3.);
+        assert!(a <= 1.5);
+        assert!(a >= 1.5);
+    }
+
+    #[test]
+    fn width() {
+        let (a, _) = setup();
+        assert_eq!(a.width(), 1.);
+    }
+
+    #[test]
+    fn display() {
+        let (a, _) = setup();
+        assert_eq!(format!("{}", a), "[1, 2]".to_string());
+        assert_eq!(format!("{:.2}", a), "[1.00, 2.00]");
+        assert_eq!(format!("{:>8}|{:<8}|{:^9}", a, a, a), "  [1, 2]|[1, 2]  | [1, 2]  ");
+        assert_eq!(format!("{:#}", a), "1.5 ± 0.5");
+        assert_eq!(format!("{:#.1}", Interval::with_range(-1., 1.)), "0.0 ± 1.0");
+        assert_eq!(format!("{:#}", Interval::with_range(0., f64::INFINITY)), "[0, inf]");
+        assert_eq!(format!("{:#}", Interval::with_range(1, 4)), "2 ± 2");
+        assert_eq!(format!("{:8}|", Interval::<f64>::empty()), "[empty] |");
+        assert_eq!(format!("{:>12}", a.display_outward(1)), "  [1.0, 2.0]");
+        assert_eq!(a.display_hex().to_string(), "[0x1p+0, 0x1p+1]");
+        assert_eq!(Interval::with_range(0., f64::INFINITY).fmt_uncertainty(), "[0, inf]");
+    }
+
+    #[test]
+    fn addition() {
+        let (a, b) = setup();
+        assert_eq!(a + b, Interval::with_range(4., 6.));
+    }
+
+    #[test]
+    fn substraction() {
+        let (a, b) = setup();
+        assert_eq!(b - a, Interval::with_range(1., 3.));
+    }
+
+    #[test]
+    fn multiply() {
+        let (a, b) = setup();
+        assert_eq!(a * b, Interval::with_range(3., 8.));
+    }
+
+    #[test]
+    fn divide() {
+        let (a, b) = setup();
+        assert_eq!(b / a, Interval::with_range(1.5, 4.));
+    }
+
+    #[test]
+    fn extended_division() {
+        use one_or_two::OneOrTwo::{One, Two};
+
+        const INF: f64 = f64::INFINITY;
+        let i = Interval::with_range;
+        let (pos, neg) = (i(1., 2.), i(-2., -1.));
+
+        assert_eq!(pos / i(-1., 1.), i(-INF, INF));
+        assert_eq!(pos / i(0., 1.), i(1., INF));
+        assert_eq!(neg / i(0., 1.), i(-INF, -1.));
+        assert_eq!(pos / i(-1., 0.), i(-INF, -1.));
+        assert_eq!(neg / i(-1., 0.), i(1., INF));
+        assert!((pos / Interval::exact(0.)).is_empty());
+        assert_eq!(i(-1., 1.).div_split(i(-1., 1.)), Some(One(i(-INF, INF))));
+        assert_eq!(neg.div_split(i(-4., 2.)),
+                   Some(Two(i(-INF, -0.5), i(0.25, INF))));
+        assert_eq!(pos.div_split(i(-4., 2.)),
+                   Some(Two(i(-INF, -0.25), i(0.5, INF))));
+        assert_eq!(pos.checked_div(neg), Some(i(-2., -0.5)));
+
+        // Empty operand gives empty quotient, also with divisor containing zero
+        let empty = Interval::empty();
+        for &x in &[pos, i(-1., 1.), Interval::exact(0.)] {
+            assert!(x.checked_div(empty).unwrap().is_empty());
+            assert!(empty.checked_div(x).unwrap().is_empty());
+            assert_eq!(x.div_split(empty), None);
+            assert_eq!(empty.div_split(x), None);
+        }
+    }
+
+    #[test]
+    fn eval_poly() {
+        let (a, _) = setup();
+        assert_eq!(a.eval_poly(&[]), Interval::exact(0.));
+        assert_eq!(a.eval_poly(&[2.]), Interval::exact(2.));
+        assert_eq!(a.eval_poly(&[-1., 0., 1.]), Interval::with_range(0., 3.));
+    }
+
+    #[test]
+    fn scale_pow2() {
+        use std::f64;
+
+        let (a, _) = setup();
+        assert_eq!(a.mul_pow2(3), Interval::with_range(8., 16.));
+        assert_eq!(a.div_pow2(3), Interval::with_range(0.125, 0.25));
+        assert_eq!(a.mul_pow2(-3), a.div_pow2(3));
+        assert_eq!(a.mul_pow2(1100), Interval::with_range(f64::MAX, f64::INFINITY));
+
+        let tiny = a.div_pow2(1074);
+        assert!(tiny.contains(f64::MIN_POSITIVE * f64::EPSILON));
+        assert!(tiny.contains(2. * f64::MIN_POSITIVE * f64::EPSILON));
+        assert!(a.div_pow2(2000).contains(0.));
+    }
+
+    #[test]
+    fn frexp_ldexp() {
+        let intervals = [Interval::with_range(1., 1.),
+                         Interval::with_range(0.3, 0.4),
+                         Interval::with_range(-6., -5.),
+                         Interval::with_range(-0.1, 100.),
+                         Interval::with_range(0., 0.)];
+
+        for x in &intervals {
+            let (mantissa, exp) = x.frexp();
+            assert!(exp.0 <= exp.1);
+            let back = mantissa.ldexp(exp);
+            assert!(back.contains(x.start) && back.contains(x.end), "{}", x);
         }
+
+        let (mantissa, exp) = Interval::with_range(-6., -5.).frexp();
+        assert_eq!(mantissa, Interval::with_range(-0.75, -0.625));
+        assert_eq!(exp, (3, 3));
     }
-}
 
-impl<T> Sub for Interval<T>
-    where T: Sub<Output = T> + Copy
-{
-    type Output = Interval<T>;
+    #[test]
+    fn from_dyadic() {
+        assert_eq!(Interval::<f64>::from_dyadic(5, 3), Interval::exact(40.));
+        assert_eq!(Interval::<f64>::from_dyadic(-1, -1), Interval::exact(-0.5));
+        assert!(Interval::<f64>::from_dyadic(0, 100).is_exact());
 
-    fn sub(self, other: Self) -> Self {
-        let start = Rounding::Downward.execute(|| self.start - other.start);
-        let end = Rounding::Upward.execute(|| self.end - other.end);
-        Interval {
-            start: start,
-            end: end,
-        }
+        let m = (1i64 << 60) + 3;
+        let x = Interval::<f64>::from_dyadic(m, -60);
+        assert!(!x.is_exact());
+        assert!(x.start < x.end && x.contains(1.));
+
+        let x = Interval::<f64>::from_dyadic(-m, 0);
+        assert!(x.start < -((1u64 << 60) as f64) && x.end >= -((1u64 << 60) as f64));
+
+        let x = Interval::<f64>::from_dyadic(i64::MIN, 0);
+        assert!(x.is_exact());
     }
-}
 
-impl<T> Mul for Interval<T>
-    where T: Mul<Output = T> + Copy + PartialOrd
-{
-    type Output = Interval<T>;
+    #[test]
+    fn mid() {
+        use std::f64;
 
-    fn mul(self, other: Self) -> Self {
-        let (a, b, c, d) = (self.start, self.end, other.start, other.end);
-        let min = Rounding::Downward.execute(|| {
-            vec![a * d, b * c, b * d].into_iter().fold(a * c, |acc, i| partial_min(acc, i))
-        });
-        let max = Rounding::Upward.execute(|| {
-            vec![a * d, b * c, b * d].into_iter().fold(a * c, |acc, i| partial_max(acc, i))
-        });
+        let samples = [(1., 2.),
+                       (-f64::MAX, f64::MAX),
+                       (f64::MAX, f64::MAX),
+                       (-f64::MAX, -f64::MAX / 2.),
+                       (f64::MIN_POSITIVE * f64::EPSILON, 2. * f64::MIN_POSITIVE * f64::EPSILON),
+                       (-1e300, 1e308),
+                       (f64::NEG_INFINITY, -1.),
+                       (f64::NEG_INFINITY, f64::INFINITY)];
 
-        Interval {
-            start: min,
-            end: max,
+        for &(start, end) in &samples {
+            let interval = Interval::with_range(start, end);
+            let mid = interval.mid();
+            assert!(mid.is_finite(), "{}", interval);
+            assert!(interval.contains(mid), "{}", interval);
+        }
+
+        // Infinite point is the only value of its interval
+        for &inf in &[f64::INFINITY, f64::NEG_INFINITY] {
+            let point = Interval::exact(inf);
+            assert_eq!(point.mid(), inf);
+            assert_eq!(point.bisect(), (point, point));
         }
     }
-}
 
-impl<T> Div for Interval<T>
-    where T: Div<Output = T> + Copy + PartialOrd
-{
-    type Output = Interval<T>;
+    #[test]
+    fn zero() {
+        use num::Zero;
 
-    fn div(self, other: Self) -> Self {
-        let (a, b, c, d) = (self.start, self.end, other.start, other.end);
-        let min = Rounding::Downward.execute(|| {
-            vec![a / d, b / c, b / d].into_iter().fold(a / c, |acc, i| partial_min(acc, i))
-        });
-        let max = Rounding::Upward.execute(|| {
-            vec![a / d, b / c, b / d].into_iter().fold(a / c, |acc, i| partial_max(acc, i))
-        });
+        let (a, _) = setup();
+        assert!(Interval::<f64>::zero().is_zero());
+        assert_eq!(Interval::<f64>::default(), Interval::zero());
+        assert!(!Interval::with_range(-1., 1.).is_zero());
+        assert!(Interval::with_range(-1., 1.).contains_zero());
+        assert!(!a.contains_zero());
+    }
 
-        Interval {
-            start: min,
-            end: max,
-        }
+    #[test]
+    #[cfg(feature = "libc")]
+    fn map2() {
+        let (a, b) = setup();
+        assert_eq!(a.map2(&b, |x, y| x - y, |x, y| x - y), a - b);
+        assert_eq!(a.map2(&b, f64::powf, f64::powf), Interval::with_range(1., 16.));
+        assert_eq!(a.map2_increasing(&b, |x, y| x + y, |x, y| x + y), a + b);
     }
-}
 
-impl<T> Neg for Interval<T>
-    where T: Neg<Output = T> + Copy
-{
-    type Output = Interval<T>;
+    #[test]
+    fn snap_to_grid() {
+        let (a, b) = setup();
+        assert_eq!(a.snap_to_grid(0.5), a);
+        assert_eq!(b.snap_to_grid(3.), Interval::with_range(3., 6.));
+        assert_eq!((-a).snap_to_grid(0.75), Interval::with_range(-2.25, -0.75));
 
-    fn neg(self) -> Self {
-        Interval {
-            start: -self.end,
-            end: -self.start,
-        }
+        let x = Interval::with_range(0.15, 0.75);
+        let snapped = x.snap_to_grid(0.1);
+        assert!(snapped.start <= x.start && x.end <= snapped.end);
+        assert!(snapped.width() < 0.7 + 1e-9);
     }
-}
 
-impl<T> Interval<T>
-    where T: Float + Num + FromPrimitive
-{
-    pub fn sin(self) -> Self {
-        let x2 = self * self;
-
-        let mut ret = (1u64..500_000).fold(self, |acc, i| {
-            let mul: T = FromPrimitive::from_u64(2 * i * (2 * i + 1)).unwrap();
-            let int = Interval::exact(mul);
-            let mul = x2 / int;
-            if i % 2 == 0 {
-                acc * mul + acc
-            } else {
-                acc * mul - acc
+    #[test]
+    fn integer_rounding() {
+        let x = Interval::with_range(-2.5, 3.5);
+        assert_eq!(x.floor(), Interval::with_range(-3., 3.));
+        assert_eq!(x.ceil(), Interval::with_range(-2., 4.));
+        assert_eq!(x.trunc(), Interval::with_range(-2., 3.));
+        assert_eq!(x.round(), Interval::with_range(-3., 4.));
+        assert!(Interval::<f64>::empty().floor().is_empty());
+        assert_eq!(Interval::with_range(f64::NEG_INFINITY, 0.5).ceil(),
+                   Interval::with_range(f64::NEG_INFINITY, 1.));
+
+        assert_eq!(Interval::with_range(-1.75, -1.5).fract(), Interval::with_range(0.25, 0.5));
+        assert_eq!(Interval::with_range(0.5, 1.).fract(), Interval::with_range(0., 1.));
+        assert_eq!(Interval::exact(3.).fract(), Interval::exact(0.));
+        assert_eq!(Interval::with_range(1., f64::INFINITY).fract(), Interval::with_range(0., 1.));
+        assert!(Interval::<f64>::empty().fract().is_empty());
+
+        // Fractional part of tiny negative number is rounded up to 1, which is still enclosed
+        let tiny = Interval::exact(-1e-20).fract();
+        assert!(tiny.sup() == 1. && tiny.inf() < 1.);
+    }
+
+    #[test]
+    fn hull() {
+        let (a, b) = setup();
+        assert_eq!(a.hull(&b), Interval::with_range(1., 4.));
+        assert_eq!(a.hull(&b), b.hull(&a));
+        assert_eq!(a.hull(&a), a);
+        assert_eq!(a.hull(&Interval::empty()), a);
+        assert_eq!(Interval::empty().hull(&b), b);
+    }
+
+    #[test]
+    fn ranges_of_containers() {
+        let map: BTreeMap<i32, char> = [(1, 'a'), (3, 'b'), (5, 'c')].iter().cloned().collect();
+        let keys = |x: Interval<i32>| x.range_in(&map).map(|(&k, _)| k).collect::<Vec<_>>();
+        assert_eq!(keys(Interval::with_range(1, 5)), [1, 3, 5]);
+        assert_eq!(keys(Interval::with_range(2, 4)), [3]);
+        assert_eq!(keys(Interval::exact(4)), []);
+        assert_eq!(keys(Interval::empty()), []);
+
+        let sorted = [-1., 0., 0., 2., f64::INFINITY];
+        let select = |x: Interval<f64>| x.sorted_in(&sorted, |&x| x).to_vec();
+        assert_eq!(select(Interval::with_range(-0., 2.)), [0., 0., 2.]);
+        assert_eq!(select(Interval::with_range(3., f64::INFINITY)), [f64::INFINITY]);
+        assert_eq!(select(Interval::with_range(-3., -2.)), []);
+        assert_eq!(select(Interval::empty()), []);
+        assert_eq!(select(Interval { start: f64::NAN, end: 1. }), []);
+    }
+
+    #[test]
+    fn subdivision() {
+        let i = Interval::with_range;
+
+        let (left, right) = i(-f64::MAX, f64::MAX).bisect();
+        assert_eq!((left.end, right.start), (0., 0.));
+        assert_eq!(i(1., 1.).bisect(), (i(1., 1.), i(1., 1.)));
+        assert_eq!(i(0., 4.).split_at(1.), (i(0., 1.), i(1., 4.)));
+
+        for &x in &[i(0.1, 0.7), i(-f64::MAX, f64::MAX), i(1e-310, 3e-310), i(0., f64::INFINITY)] {
+            let pieces: Vec<_> = x.subdivide(7).collect();
+            assert_eq!(pieces.len(), 7);
+            assert_eq!((pieces[0].start, pieces[6].end), (x.start, x.end));
+            for w in pieces.windows(2) {
+                assert!(w[0].start <= w[0].end && w[0].end == w[1].start, "{} {}", w[0], w[1]);
             }
-        });
+        }
+        assert_eq!(i(0., 1.).subdivide(1).collect::<Vec<_>>(), vec![i(0., 1.)]);
+    }
+
+    #[test]
+    fn sign_pieces() {
+        let i = Interval::with_range;
 
-        let min = FromPrimitive::from_isize(-1).unwrap();
-        let max = one();
+        let signs = i(-0., 3.).split_at_zeros();
+        assert_eq!((signs.negative, signs.zero, signs.positive), (None, true, Some(i(-0., 3.))));
+        let signs = i(-2., -1.).split_at_zeros();
+        assert_eq!((signs.negative, signs.zero, signs.positive), (Some(i(-2., -1.)), false, None));
+        let signs = i(0., 0.).split_at_zeros();
+        assert_eq!((signs.negative, signs.zero, signs.positive), (None, true, None));
+        let signs = Interval::<f64>::empty().split_at_zeros();
+        assert_eq!((signs.negative, signs.zero, signs.positive), (None, false, None));
+        let signs = Interval::<f64>::entire().split_at_zeros();
+        assert_eq!(signs.negative, Some(i(f64::NEG_INFINITY, 0.)));
 
-        ret.start = ret.start.max(min).min(max);
-        ret.end = ret.end.max(min).min(max);
+        assert_eq!(i(1., 2.).split_at_points(&[]), vec![Some(i(1., 2.))]);
+        assert_eq!(i(1., 1.).split_at_points(&[1., 2.]), vec![None, None, None]);
+        assert_eq!(i(1., 2.).split_at_points(&[1., 2.]), vec![None, Some(i(1., 2.)), None]);
+    }
 
-        ret
+    #[test]
+    #[should_panic(expected = "split points are not increasing")]
+    fn unsorted_split_points() {
+        Interval::with_range(0., 1.).split_at_points(&[0.5, 0.5]);
     }
 
-    // pub fn cos(self) -> Self {
-    //     let x2 = self * self;
+    #[test]
+    #[should_panic]
+    fn split_outside() {
+        Interval::with_range(0., 1.).split_at(2.);
+    }
 
-    //     let mut ret = (1..500_000).fold(one(), |acc, i| {
-    //         let mul: T = cast(2*i * (2*i + 1)).unwrap();
-    //         let int = Interval::exact(mul);
-    //         let mul = x2 / int;
-    //         if i % 2 == 0 { acc * mul + acc } else { acc * mul - acc }
-    //     });
+    #[test]
+    fn magnitudes() {
+        let i = Interval::with_range;
 
-    //     ret.start = ret.start.max(cast(-1).unwrap()).min(one());
-    //     ret.end = ret.end.max(cast(-1).unwrap()).min(one());
+        assert_eq!(i(1, 4).abs(), i(1, 4));
+        assert_eq!(i(-5, 4).abs(), i(0, 5));
+        assert_eq!(Interval::with_range(-5., f64::INFINITY).mag(), f64::INFINITY);
+        assert_eq!(Interval::with_range(-0.5, -0.25).mig(), 0.25);
+        assert!(Interval::<f64>::empty().abs().is_empty());
+        assert_eq!(<(i32, i32)>::from(i(-1, 2)), (-1, 2));
+    }
 
-    //     ret
-    // }
-}
+    #[test]
+    fn roots() {
+        let i = Interval::with_range;
 
-#[cfg(test)]
-mod test {
-    use super::Interval;
+        for &x in &[2., 3., 0.1, 1e-310, 7e300, f64::MAX] {
+            for n in 1..8 {
+                // Powers are rounded once per multiplication, so the check of candidate root
+                // can reject a few closest floats (and more when powers underflow)
+                let root = Interval::exact(x).nth_root(n);
+                let limit = (0..n).fold(root.start, |hi, _| hi.next_up());
+                assert!(root.end <= limit || x < f64::MIN_POSITIVE, "{} {} {}", x, n, root);
+                assert!(root.powi(n).contains(x), "{} {}", x, n);
+            }
+            let root = Interval::exact(x).sqrt();
+            assert!(root.start <= x.sqrt() && x.sqrt() <= root.end);
+            assert!(root.start == root.end || root.start.next_up() == root.end || x < 1e-300);
+            assert!(Interval::exact(-x).cbrt().contains(-x.cbrt()));
+        }
 
-    fn setup() -> (Interval<f64>, Interval<f64>) {
-        (Interval::with_range(1., 2.), Interval::with_range(3., 4.))
+        assert_eq!(i(0., f64::INFINITY).sqrt(), i(0., f64::INFINITY));
+        assert_eq!(i(-f64::INFINITY, -8.).cbrt(), i(-f64::INFINITY, -2.));
+        assert_eq!(i(2., 2.).nth_root(1), i(2., 2.));
+        assert!(Interval::<f64>::empty().cbrt().is_empty());
     }
 
     #[test]
-    fn contains() {
-        let (a, _) = setup();
-        assert!(a.contains(1.5));
-        assert!(!a.contains(2.1));
-        assert!(a == 1.5);
-        assert!(a != 2.1);
+    fn outside_domain_is_empty() {
+        let x = Interval::with_range(-3f64, -2.);
+        let results = [x.ln(), x.log2(), x.log10(), x.ln_1p(), x.powf(Interval::exact(2.)),
+                       x.sqrt(), x.asin(), x.acosh(), x.nth_root(4)];
+        for (i, result) in results.iter().enumerate() {
+            assert_eq!(*result, Interval::empty(), "{}", i);
+            assert_eq!(result.hull(&Interval::exact(1.)), Interval::exact(1.));
+        }
+        assert!(Interval::<f64>::empty().ln().is_empty());
+        assert!(Interval::exact(2.).powf(Interval::empty()).is_empty());
     }
 
     #[test]
-    fn ordering() {
-        let (a, _) = setup();
-        assert!(a > 0.);
-        assert!(a < 3.);
-        assert!(a <= 1.5);
-        assert!(a >= 1.5);
+    fn subnormal_roots() {
+        let tiny = 5e-324f64;
+        for &x in &[tiny, 1e-320, -tiny, 3e-310] {
+            for n in 2..8 {
+                let root = Interval::exact(x).nth_root(n);
+                assert_eq!(root.is_empty(), n % 2 == 0 && x < 0., "{} {}", x, n);
+                assert!(root.is_empty() || root.powi(n).contains(x), "{} {} {}", x, n, root);
+            }
+            assert!(Interval::exact(x).cbrt().contains(x.cbrt()));
+        }
+
+        let root = Interval::with_range(0., 1e-320).sqrt();
+        assert!(root.start == 0. && root.end >= 1e-320f64.sqrt() && root.end < 1e-159);
+        assert!(Interval::exact(tiny).sqrt().contains(tiny.sqrt()));
+        assert!(Interval::exact(tiny).sqrt().width() < 1e-170);
     }
 
     #[test]
-    fn width() {
-        let (a, _) = setup();
-        assert_eq!(a.width(), 1.);
+    fn rounding_error_model() {
+        let exact = Interval::exact;
+
+        // Exact product is `1e-400`, which underflows to zero
+        let product = exact(1e-200 * 1e-200).widen_rounding(1);
+        assert!(product.start < 0. && product.end > 0. && product.end < 1e-320);
+
+        let sum = (1..=100).fold(0., |acc, k| acc + 1. / k as f64);
+        let harmonic = (1..=100).fold(exact(0.), |acc, k| acc + exact(1.) / exact(k as f64));
+        assert!(harmonic.subset(&exact(sum).widen_rounding(200)));
+
+        assert_eq!(Interval::with_range(0., f64::INFINITY).widen_rounding(3).end, f64::INFINITY);
+        assert_eq!(exact(1.).widen_rounding(0), exact(1.));
+        assert_eq!(exact(1.).widen_rounding(usize::MAX), Interval::entire());
     }
 
     #[test]
-    fn display() {
+    fn scalar_operands() {
         let (a, _) = setup();
-        assert_eq!(format!("{}", a), "[1, 2]".to_string());
+        let tenth = Interval::with_range(0.1f64.next_down(), 0.1f64.next_up());
+
+        assert_eq!(a + 1., Interval::with_range(2., 3.));
+        assert_eq!(1. - a, Interval::with_range(-1., 0.));
+        assert_eq!(a * -2., Interval::with_range(-4., -2.));
+        assert_eq!(2. / a, Interval::with_range(1., 2.));
+        assert!((a * 0.1).subset(&(a * tenth)));
+        assert!((a + 1e-300).contains(1. + 1e-300) && (a + 1e-300).contains(1f64.next_up()));
+        assert!((a / 0.).is_empty());
+        assert_eq!(Interval::with_range(1, 2) * 3, Interval::with_range(3, 6));
+        assert_eq!(1f32 + Interval::exact(1f32), Interval::exact(2f32));
+
+        let mut x = a;
+        x += 1.;
+        x *= Interval::with_range(-1., 1.);
+        x -= a;
+        x /= 2.;
+        assert_eq!(x, Interval::with_range(-2.5, 1.));
     }
 
     #[test]
-    fn addition() {
+    fn comparisons() {
         let (a, b) = setup();
-        assert_eq!(a + b, Interval::with_range(4., 6.));
+        let c = Interval::with_range(2., 3.);
+        let empty = Interval::<f64>::empty();
+
+        assert!(a.certainly_lt(b) && b.certainly_gt(a) && !a.certainly_lt(c));
+        assert!(a.precedes(c) && !a.strictly_precedes(c) && !c.precedes(a));
+        assert!(a.possibly_lt(c) && !c.possibly_lt(a) && c.possibly_lt(b));
+        assert!(a.possibly_eq(c) && !a.possibly_eq(b) && !a.possibly_eq(empty));
+        assert!(empty.precedes(a) && a.strictly_precedes(empty));
+        assert!(Interval::exact(2.).certainly_eq(Interval::exact(2.)));
+        assert!(Interval::with_range(1, 2).certainly_lt(3));
+
+        assert!(a < b && c > Interval::exact(0.));
+        assert_eq!(a.partial_cmp(&a), Some(::std::cmp::Ordering::Equal));
+        assert_eq!(a.partial_cmp(&c), None);
+        assert_eq!(empty.partial_cmp(&a), None);
+        assert_eq!(empty.partial_cmp(&empty), Some(::std::cmp::Ordering::Equal));
     }
 
     #[test]
-    fn substraction() {
-        let (a, b) = setup();
-        assert_eq!(b - a, Interval::with_range(2., 2.));
+    fn fallible_constructors() {
+        use std::f64;
+
+        assert_eq!(Interval::try_with_epsilon(1., 0.5), Ok(Interval::with_range(0.5, 1.5)));
+        assert_eq!(Interval::try_with_epsilon(1., -0.5), Err(IntervalError::Reversed));
+        assert_eq!(Interval::try_with_epsilon(1., f64::NAN), Err(IntervalError::NaN));
+        assert_eq!(Interval::try_with_range(f64::NAN, f64::NAN), Err(IntervalError::NaN));
+        assert_eq!(Interval::try_with_range(3, 3), Ok(Interval::exact(3)));
+        assert_eq!(Interval::try_with_range(3, 2), Err(IntervalError::Reversed));
     }
 
     #[test]
-    fn multiply() {
-        let (a, b) = setup();
-        assert_eq!(a * b, Interval::with_range(3., 8.));
+    #[should_panic(expected = "lower bound is greater than upper bound")]
+    fn reversed_range() {
+        Interval::with_range(2., 1.);
     }
 
     #[test]
-    fn divide() {
+    fn without_primitive_conversions() {
+        use std::ops::{Add, Div, Mul, Sub};
+
+        use num::{One, Zero};
+
+        // Decimal with two fraction digits, which has no conversions from primitive types
+        #[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+        struct Cents(i64);
+
+        impl Add for Cents {
+            type Output = Cents;
+            fn add(self, other: Cents) -> Cents { Cents(self.0 + other.0) }
+        }
+        impl Sub for Cents {
+            type Output = Cents;
+            fn sub(self, other: Cents) -> Cents { Cents(self.0 - other.0) }
+        }
+        impl Mul for Cents {
+            type Output = Cents;
+            fn mul(self, other: Cents) -> Cents { Cents(self.0 * other.0 / 100) }
+        }
+        impl Div for Cents {
+            type Output = Cents;
+            fn div(self, other: Cents) -> Cents { Cents(self.0 * 100 / other.0) }
+        }
+        impl Zero for Cents {
+            fn zero() -> Cents { Cents(0) }
+            fn is_zero(&self) -> bool { self.0 == 0 }
+        }
+        impl One for Cents {
+            fn one() -> Cents { Cents(100) }
+        }
+
+        let x = Interval::with_range(Cents(125), Cents(350));
+        assert_eq!((x.center(), x.epsilon()), (Cents(237), Cents(112)));
+        assert_eq!(Interval::with_range(Cents(-5), Cents(-5)).center(), Cents(-5));
+    }
+
+    #[test]
+    fn unbounded() {
+        use std::f64;
+
+        let i = Interval::with_range;
+        let inf = f64::INFINITY;
+        let positive = i(0., inf);
+        let entire = Interval::<f64>::entire();
+
+        assert_eq!(positive.width(), inf);
+        assert_eq!(entire.center(), 0.);
+        assert_eq!(Interval::exact(inf).width(), 0.);
+        assert_eq!(positive + i(-1., 1.), i(-1., inf));
+        assert_eq!(positive - positive, entire);
+        assert_eq!(positive * i(-2., -1.), i(-inf, 0.));
+        assert_eq!(positive * Interval::exact(0.), Interval::exact(0.));
+        assert_eq!(entire * i(1., 2.), entire);
+        assert_eq!(i(1., inf) / i(1., inf), positive);
+        assert_eq!(i(1., 2.) / i(1., inf), i(0., 2.));
+        assert_eq!(i(-inf, -1.) / i(2., inf), i(-inf, 0.));
+        assert_eq!(entire / i(1., 2.), entire);
+        assert!(positive.contains(inf) && !positive.is_bounded());
+        assert!(Interval::<f64>::empty().is_bounded());
+    }
+
+    #[test]
+    fn piecewise() {
+        let i = Interval::with_range;
+        // Tariff: 1 per unit up to 10 units, 2 per unit above
+        let cases = [(i(0., 10.), (|x| x) as fn(Interval<f64>) -> Interval<f64>),
+                     (i(10., 100.), |x| Interval::exact(2.) * x - Interval::exact(10.))];
+
+        assert_eq!(i(2., 5.).piecewise(&cases), i(2., 5.));
+        assert_eq!(i(5., 20.).piecewise(&cases), i(5., 30.));
+        assert_eq!(i(20., 30.).piecewise(&cases), i(30., 50.));
+        assert!(i(200., 300.).piecewise(&cases).is_empty());
+    }
+
+    #[test]
+    fn empty() {
+        use std::f64;
+
         let (a, b) = setup();
-        assert_eq!(b / a, Interval::with_range(1.5, 4.));
+        let empty = Interval::<f64>::empty();
+
+        assert_eq!(a.intersect(&b), empty);
+        assert_eq!(b.intersect(&a), empty);
+        assert_eq!(a.intersection(&b), None);
+        let nan = Interval {
+            start: f64::NAN,
+            end: f64::NAN,
+        };
+        assert!(nan.is_empty());
+        assert!(!Interval::exact(f64::INFINITY).is_empty());
+
+        // NaN bound poisons results whichever side it comes from
+        let half = Interval { start: 0., end: f64::NAN };
+        assert!(half.intersect(&a) == empty && a.intersect(&half) == empty);
+        assert!(half.hull(&a) == a && a.intersection(&half).is_none());
+
+        assert!(empty.subset(&a) && a.superset(&empty) && empty.interior_of(&a));
+        assert!(empty.disjoint(&empty) && a.disjoint(&b));
+        assert!(!a.subset(&empty));
+
+        for x in &[empty + a, a - empty, empty * a, a / empty, -empty, a / Interval::exact(0.)] {
+            assert!(x.is_empty());
+        }
+        assert_eq!(format!("{}", empty), "[empty]");
+        assert!(Interval::<u8>::empty().is_empty());
+    }
+
+    #[test]
+    fn from_outward() {
+        use std::f32;
+
+        let x = Interval::<f32>::from_f64_outward(0.1);
+        assert!((x.start as f64) < 0.1 && 0.1 < (x.end as f64));
+        assert_eq!(x.start.next_up(), x.end);
+
+        let x = Interval::<f32>::from_f64_outward(-1e300);
+        assert_eq!(x, Interval::with_range(f32::NEG_INFINITY, -f32::MAX));
+
+        let x = Interval::<f32>::from_f64_outward(1e-50);
+        assert_eq!(x, Interval::with_range(0., f32::MIN_POSITIVE * f32::EPSILON));
+
+        assert!(Interval::<f64>::from_f64_outward(0.1).is_exact());
+        assert!(Interval::<f32>::from_i64_outward(i64::MIN).is_exact());
+
+        let x = Interval::<f32>::from_i64_outward(-16777217);
+        assert_eq!(x, Interval::with_range(-16777218., -16777216.));
+
+        for &value in &[0, 1, -1, (1 << 53) + 1, -(1 << 90) - 3, i128::MAX, i128::MIN, 1 << 126] {
+            let (x, y) = (Interval::<f64>::from_i128_outward(value),
+                          Interval::<f32>::from_i128_outward(value));
+            for &(start, end) in &[(x.start, x.end), (y.start as f64, y.end as f64)] {
+                let (start, end) = (start as i128, end as i128);
+                assert!(start <= value && value <= end, "{}", value);
+            }
+            assert_eq!(x.is_exact(), value as f64 as i128 == value && value != i128::MAX);
+        }
+        for &value in &[0, u64::MAX as u128 + 1, u128::MAX - (1 << 100), u128::MAX] {
+            let x = Interval::<f64>::from_u128_outward(value);
+            let fits = x.end < 2f64.powi(128);
+            assert!(x.start as u128 <= value && (!fits || value <= x.end as u128));
+        }
+        assert_eq!(Interval::<f64>::from_i128_outward(-(1 << 53) - 1),
+                   Interval::from_i64_outward(-(1 << 53) - 1));
+
+        // Scaling by integers not representable in `T` keeps the exact product
+        let x = Interval::<f32>::with_range(-1., 2.).mul_by_uint(u64::MAX);
+        assert!(x.contains(-18446744073709551615.) && !x.is_exact());
+        assert_eq!(Interval::<f32>::exact(3.).div_by_uint(3), Interval::exact(1.));
+        let x = Interval::<f32>::exact(1.).div_by_uint(16777217);
+        assert!(x.inf() < 1. / 16777216. && x.sup() > 1. / 16777218.);
+        assert_eq!(Interval::<f64>::entire().mul_by_uint(0), Interval::exact(0.));
+        assert!(Interval::<f64>::empty().mul_by_uint(2).is_empty());
     }
 
     #[test]
@@ -452,4 +4380,355 @@ mod test {
         let (a, _) = setup();
         assert_eq!(-a, Interval::with_range(-2., -1.));
     }
+
+    fn check_samples<F, G>(x: Interval<f64>, f: F, g: G)
+        where F: Fn(Interval<f64>) -> Interval<f64>,
+              G: Fn(f64) -> f64
+    {
+        let y = f(x);
+        for k in 0..=100 {
+            let t = x.start + (x.end - x.start) * k as f64 / 100.;
+            let t = t.min(x.end);
+            assert!(y.contains(g(t)), "{} at {}: {}", g(t), t, y);
+        }
+    }
+
+    #[test]
+    fn trigonometry() {
+        use std::f64;
+
+        let inputs = [(0., 0.), (0.1, 0.2), (-1., 1.), (1., 2.), (3., 7.), (-10., -9.5),
+                      (1e6, 1e6 + 0.5), (-1e15, -1e15 + 1.), (1.5, 1.6)];
+        for &(start, end) in &inputs {
+            let x = Interval::with_range(start, end);
+            check_samples(x, Interval::sin, f64::sin);
+            check_samples(x, Interval::cos, f64::cos);
+            check_samples(x, Interval::tan, f64::tan);
+            if !x.contains(0.) {
+                check_samples(x, Interval::cot, |t| 1. / t.tan());
+            }
+        }
+
+        let full = Interval::with_range(-1., 1.);
+        let entire = Interval::with_range(f64::NEG_INFINITY, f64::INFINITY);
+        assert_eq!(Interval::with_range(0., 7.).sin(), full);
+        assert_eq!(Interval::with_range(0., f64::INFINITY).cos(), full);
+        assert_eq!(Interval::with_range(1e300, 1e300).cos(), full);
+        assert_eq!(Interval::with_range(1., 2.).tan(), entire);
+        assert_eq!(Interval::with_range(3., 3.5).cot(), entire);
+
+        let x = Interval::with_range(0.5, 1.).cos();
+        assert!(x.start > 0.54 && x.end < 0.88);
+        assert!(Interval::<f64>::pi().contains(f64::consts::PI));
+    }
+
+    #[test]
+    fn trigonometry_near_extrema() {
+        use std::f64::consts::FRAC_PI_2;
+
+        // Intervals ending just before, at and just after every `kπ/2`, where the function turns
+        let x = Interval::with_range(-1e-3, 1e-3);
+        for k in -40..40 {
+            let point = k as f64 * FRAC_PI_2;
+            for &(a, b) in &[(-1e-3, 0.), (0., 1e-3), (-1e-9, 1e-9), (-2., 1e-12), (0., 0.)] {
+                let x = Interval::with_range(point + a, point + b);
+                check_samples(x, Interval::sin, f64::sin);
+                check_samples(x, Interval::cos, f64::cos);
+            }
+
+            // Extremum inside the interval is attained exactly, otherwise bound stays tight
+            let sin = (Interval::exact(point) + x).sin();
+            if k % 2 != 0 {
+                assert!(sin.sup() == 1. || sin.inf() == -1.);
+            }
+            assert!(sin.width() < 2.1e-3);
+        }
+
+        // Pseudo-random intervals across magnitudes
+        let mut state = 0x2545_f491_4f6c_dd1du64;
+        let mut random = || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state >> 11) as f64 / (1u64 << 53) as f64
+        };
+        for &scale in &[1., 10., 1e3, 1e8] {
+            for _ in 0..50 {
+                let start = (random() - 0.5) * scale;
+                let x = Interval::with_range(start, start + random() * 4.);
+                check_samples(x, Interval::sin, f64::sin);
+                check_samples(x, Interval::cos, f64::cos);
+            }
+        }
+
+        let empty = Interval::<f64>::empty();
+        assert!(empty.sin().is_empty() && empty.cos().is_empty());
+        assert!(empty.tan().is_empty() && empty.cot().is_empty());
+    }
+
+    #[test]
+    fn inverse_trigonometry() {
+        use std::f64::{self, consts::PI};
+
+        let inputs = [(0., 0.), (0.1, 0.2), (-1., 1.), (-0.99, -0.5), (0.999, 1.)];
+        for &(start, end) in &inputs {
+            let x = Interval::with_range(start, end);
+            check_samples(x, Interval::asin, f64::asin);
+            check_samples(x, Interval::acos, f64::acos);
+            check_samples(x * Interval::exact(1e3), Interval::atan, f64::atan);
+        }
+        let half_pi = Interval::exact(PI / 2.).hull(&Interval::exact((PI / 2.).next_up()));
+        assert!(Interval::with_range(-1., 1.).asin().subset(&half_pi.hull(&-half_pi)));
+        assert!(Interval::with_range(-2., 1.).acos().contains(PI));
+        assert_eq!(Interval::exact(1.).acos().start, 0.);
+        assert!(Interval::with_range(1.5, 2.).acos().is_empty());
+        let entire = Interval::<f64>::entire().atan();
+        assert!(entire.contains_all(&[-PI / 2., PI / 2.]));
+        assert!(entire.subset(&half_pi.hull(&-half_pi)));
+
+        // Quadrants, axes and the branch cut
+        let pi = Interval::<f64>::pi();
+        let whole = Interval::with_range(-pi.end, pi.end);
+        let corners = [(1., 1.), (1., -1.), (-1., -1.), (-1., 1.), (0., -1.), (0., 1.), (1., 0.)];
+        for &(y, x) in &corners {
+            let (sy, sx) = (Interval::with_range(y, y + 0.5), Interval::with_range(x, x + 0.5));
+            for &(dy, dx) in &[(0., 0.), (0., 0.5), (0.5, 0.), (0.25, 0.25)] {
+                let angle = f64::atan2(y + dy, x + dx);
+                assert!(sy.atan2(sx).contains(angle), "{} {}", sy, sx);
+            }
+        }
+        let cut = Interval::with_range(-1., -0.5);
+        assert_eq!(Interval::with_range(-0.1, 0.1).atan2(cut), whole);
+        assert!(Interval::with_range(-0.1, 0.).atan2(cut).contains_all(&[-PI, -PI + 0.1]));
+        // Both -π and π are angles of the negative part of x axis
+        assert!(Interval::with_range(-0.1, 0.).atan2(cut).contains_all(&[-PI, PI]));
+        assert!(Interval::with_range(0., 0.1).atan2(cut).contains_all(&[-PI, PI]));
+        assert_eq!(Interval::exact(0.).atan2(cut), whole);
+        assert_eq!(Interval::exact(0.).atan2(Interval::exact(0.)), whole);
+        assert!(Interval::with_range(-1., 1.).atan2(Interval::with_range(1., 2.)).width() < 1.58);
+        assert!(Interval::<f64>::empty().atan2(cut).is_empty());
+    }
+
+    #[test]
+    fn hyperbolic() {
+        use std::f64;
+
+        let inputs = [(0., 0.), (0.1, 0.2), (-1., 1.), (-3., -2.), (-0.5, 2.), (-700., 10.)];
+        for &(start, end) in &inputs {
+            let x = Interval::with_range(start, end);
+            check_samples(x, Interval::sinh, f64::sinh);
+            check_samples(x, Interval::cosh, f64::cosh);
+            check_samples(x, Interval::tanh, f64::tanh);
+            check_samples(x, Interval::asinh, f64::asinh);
+            check_samples(x.abs() + Interval::exact(1.), Interval::acosh, f64::acosh);
+            check_samples(x.tanh(), Interval::atanh, f64::atanh);
+        }
+
+        assert_eq!(Interval::with_range(-1., 1.).cosh().inf(), 1.);
+        assert!(Interval::with_range(-3., -2.).cosh().inf() > 3.7);
+        assert_eq!(Interval::<f64>::entire().tanh(), Interval::with_range(-1., 1.));
+        assert_eq!(Interval::with_range(-1., 1.).atanh(), Interval::entire());
+        assert!(Interval::with_range(1.5, 2.).atanh().is_empty());
+        assert!(Interval::with_range(-2., 0.9).acosh().is_empty());
+    }
+
+    #[test]
+    fn elementary() {
+        use std::f64;
+
+        let inputs = [(0.5, 0.5), (0.1, 0.2), (1., 2.), (3., 7.), (1e-300, 1.), (0.9, 700.)];
+        for &(start, end) in &inputs {
+            let x = Interval::with_range(start, end);
+            check_samples(x, Interval::exp, f64::exp);
+            check_samples(x, Interval::ln, f64::ln);
+            check_samples(x, Interval::log2, f64::log2);
+            check_samples(x, Interval::log10, f64::log10);
+            for &n in &[-3, -2, 1, 2, 5] {
+                check_samples(x, |x| x.powi(n), |t| t.powi(n));
+                check_samples(-x, |x| x.powi(n), |t| t.powi(n));
+            }
+            check_samples(x, |x| x.powf(Interval::exact(1.5)), |t| t.powf(1.5));
+            check_samples(x, |x| Interval::exact(2.).powf(x), |t| 2f64.powf(t));
+        }
+
+        let x = Interval::with_range(-3., 2.);
+        assert_eq!(x.powi(4), Interval::with_range(0., 81.));
+        assert_eq!(x.powi(0), Interval::exact(1.));
+        assert_eq!(x.ln(), Interval::with_range(f64::NEG_INFINITY, 2f64.ln().next_up().next_up()));
+        assert_eq!(Interval::with_range(f64::NEG_INFINITY, 0.).exp().start, 0.);
+        assert_eq!(x.powf(Interval::exact(2.)).start, 0.);
+        assert_eq!(Interval::with_range(-2f64, -1.).powf(Interval::exact(2.)), Interval::empty());
+
+        let inputs = [(-1e-10, 1e-10), (-0.5, 0.3), (-3., 2.), (1e-300, 1.), (-700., 10.)];
+        for &(start, end) in &inputs {
+            let x = Interval::with_range(start, end);
+            check_samples(x, Interval::exp2, f64::exp2);
+            check_samples(x, Interval::exp_m1, f64::exp_m1);
+            check_samples(x.abs(), Interval::ln_1p, f64::ln_1p);
+            check_samples(x, |x| Interval::exact(1.5).pow(x), |t| 1.5f64.powf(t));
+            check_samples(x.abs(), |x| x.pow(Interval::with_range(-0.5, 3.)), |t| t.powf(-0.5));
+            check_samples(x.abs(), |x| x.pow(Interval::with_range(-0.5, 3.)), |t| t.powf(3.));
+            check_samples(x, |x| x.pow(Interval::exact(3.)), |t| t.powi(3));
+        }
+        assert!(Interval::with_range(f64::NEG_INFINITY, 0.).exp_m1().inf() >= -1.);
+        assert_eq!(Interval::with_range(-3f64, -2.).ln_1p(), Interval::empty());
+        assert_eq!(x.pow(Interval::exact(-2.)), x.powi(-2));
+        assert_eq!(Interval::exact(0.).pow(Interval::with_range(-1., 2.)), Interval::exact(0.));
+        assert_eq!(x.pow(Interval::exact(0.5)).inf(), 0.);
+        assert!(Interval::with_range(0., 1.).pow(Interval::exact(-1.)).contains(f64::INFINITY));
+        assert!(x.pow(Interval::empty()).is_empty());
+    }
+
+    #[test]
+    fn powers() {
+        let mixed = Interval::with_range(-2., 1.);
+        for &x in &[mixed, -mixed, Interval::with_range(0.1, 3.), Interval::with_range(-5., -0.5)] {
+            check_samples(x, Interval::sqr, |t| t * t);
+            assert_eq!(x.sqr(), x.powi(2));
+            for n in -4..6 {
+                check_samples(x, |x| x.powi(n), |t| t.powi(n));
+            }
+        }
+
+        // Dependency of factors is kept
+        assert_eq!(mixed.sqr(), Interval::with_range(0., 4.));
+        assert_eq!(mixed.powi(4), Interval::with_range(0., 16.));
+        assert_eq!(mixed.powi(-2), Interval::with_range(0.25, f64::INFINITY));
+        assert!(Interval::exact(0.1).sqr().contains(0.1 * 0.1));
+
+        let empty = Interval::<f64>::empty();
+        for &n in &[i32::MIN, -3, -2, 0, 2, 3] {
+            assert!(empty.powi(n).is_empty());
+        }
+        assert!(empty.sqr().is_empty());
+        assert_eq!(Interval::exact(1.).powi(i32::MIN), Interval::exact(1.));
+        assert!(Interval::exact(2.).powi(i32::MIN).subset(&Interval::with_range(0., 1e-300)));
+    }
+
+    #[test]
+    fn metrics() {
+        let (a, b) = (Interval::with_range(1., 3.), Interval::with_range(2., 7.));
+        let empty = Interval::<f64>::empty();
+        let entire = Interval::<f64>::entire();
+
+        assert_eq!(a.distance(&b), 4.);
+        assert_eq!(b.distance(&a), 4.);
+        assert_eq!(a.distance(&Interval::with_range(1.5, 2.5)), 0.5);
+        assert!(Interval::exact(0.1).distance(&Interval::exact(0.3)) >= 0.3 - 0.1);
+        assert_eq!(empty.distance(&empty), 0.);
+        assert_eq!(a.distance(&empty), f64::INFINITY);
+        assert_eq!(entire.distance(&entire), 0.);
+        assert_eq!(entire.distance(&Interval::with_range(0., f64::INFINITY)), f64::INFINITY);
+
+        let close_above = |x: f64, y: f64| x >= y && x - y < 1e-15;
+        assert!(close_above(a.rel_width(), 2. / 3.));
+        assert_eq!(a.rel_width(), (-a).rel_width());
+        assert_eq!(Interval::exact(5.).rel_width(), 0.);
+        assert_eq!(Interval::exact(0.).rel_width(), 0.);
+        assert!(close_above(Interval::with_range(-1., 3.).rel_width(), 4. / 3.));
+        assert!(Interval::with_range(1., 1. + 1e-15).rel_width() < 2e-15);
+        assert_eq!(empty.rel_width(), 0.);
+        assert_eq!(Interval::with_range(1., f64::INFINITY).rel_width(), f64::INFINITY);
+
+        assert_eq!(a.overlap_measure(&b), 1. / 6.);
+        assert_eq!(b.overlap_measure(&a), a.overlap_measure(&b));
+        assert_eq!(a.overlap_measure(&Interval::with_range(1.5, 2.5)), 0.5);
+        assert_eq!(Interval::exact(2.).overlap_measure(&Interval::exact(2.)), 1.);
+        assert_eq!(Interval::exact(2.).overlap_measure(&a), 0.);
+        assert_eq!(a.overlap_measure(&empty), 0.);
+        let half = Interval::with_range(0., f64::INFINITY);
+        assert_eq!(half.overlap_measure(&Interval::with_range(1., f64::INFINITY)), 1.);
+        assert_eq!(half.overlap_measure(&Interval::with_range(f64::NEG_INFINITY, 1.)), 0.);
+        assert_eq!(entire.overlap_measure(&entire), 1.);
+    }
+
+    #[test]
+    fn points_at() {
+        let x = Interval::with_range(-1., 3.);
+        assert_eq!((x.point_at(-1.), x.point_at(0.5), x.point_at(2.)), (-1., 1., 3.));
+        for i in 0..100 {
+            assert!(x.contains(x.point_at(i as f64 / 100.)));
+        }
+
+        let wide = Interval::with_range(-f64::MAX, f64::MAX);
+        assert_eq!(wide.point_at(0.5), 0.);
+        assert!((wide.point_at(0.75) / f64::MAX - 0.5).abs() < 1e-15);
+        let half = Interval::with_range(0., f64::INFINITY);
+        assert_eq!((half.point_at(0.), half.point_at(0.5)), (0., f64::MAX));
+        assert_eq!(Interval::exact(2.).point_at(0.3), 2.);
+    }
+
+    #[test]
+    #[should_panic(expected = "empty interval has no points")]
+    fn point_of_empty() {
+        Interval::<f64>::empty().point_at(0.5);
+    }
+
+    #[test]
+    fn precision_conversions() {
+        let x = Interval::with_range(0.1, 1e300);
+        let narrow = x.to_f32_enclosure();
+        assert_eq!(narrow.sup(), f32::INFINITY);
+        assert!(Interval::<f64>::from(narrow).superset(&x));
+        assert!(Interval::with_range(-1e300, -1e-300).to_f32_enclosure()
+            .contains_all(&[f32::MIN, 0.]));
+
+        // Exact values round trip
+        let y = Interval::with_range(-0.75f32, 3.5);
+        assert_eq!(Interval::<f64>::from(y).to_f32_enclosure(), y);
+        for &v in &[0.1, 1. / 3., -2e-40, 1e38, f64::INFINITY] {
+            let tight = Interval::exact(v).to_f32_enclosure();
+            assert!(Interval::<f64>::from(tight).contains(v));
+            assert!(tight.is_exact() || tight.sup() == tight.inf().next_up());
+        }
+        assert!(Interval::<f64>::empty().to_f32_enclosure().is_empty());
+        assert_eq!(Interval::<f64>::entire().to_f32_enclosure(), Interval::entire());
+    }
+
+    #[test]
+    fn fused_multiply_add() {
+        let values = [-3., -1., -0.1, 0., 0.1, 1. / 3., 0.7, 2., 1e10];
+        for &(a, b) in &[(-3f64, 2.), (0.1, 0.7), (-1., -0.1), (0., 1. / 3.)] {
+            for &(c, d) in &[(-1., 1.), (0.7, 2.), (1e10, 1e10), (-3., -0.1)] {
+                let (x, y) = (Interval::with_range(a, b), Interval::with_range(c, d));
+                for &z in &values {
+                    let z = Interval::with_range(z, z + 0.5);
+                    let fused = x.mul_add(y, z);
+                    assert!(fused.subset(&(x * y + z)), "{:?} {:?} {:?}", x, y, z);
+                    let corners = [b.mul_add(d, z.sup()), a.mul_add(c, z.inf())];
+                    assert!(fused.contains_all(&corners));
+                }
+            }
+        }
+
+        let one = Interval::exact(1.);
+        assert_eq!(Interval::exact(3.).mul_add(Interval::exact(4.), one), Interval::exact(13.));
+        assert!(Interval::<f64>::empty().mul_add(one, one).is_empty());
+        assert!(one.mul_add(one, Interval::empty()).is_empty());
+        let unbounded = Interval::with_range(1., f64::INFINITY);
+        assert_eq!(unbounded.mul_add(Interval::exact(0.), one), one);
+        assert_eq!(unbounded.mul_add(one, -one), Interval::with_range(0., f64::INFINITY));
+    }
+
+    #[test]
+    fn sum_and_product() {
+        let xs = [Interval::with_range(0.1, 0.2),
+                  Interval::exact(1. / 3.),
+                  Interval::exact(-1e-20)];
+
+        let sum: Interval<f64> = xs.iter().sum();
+        assert_eq!(sum, xs[0] + xs[1] + xs[2]);
+        assert!(sum.contains_all(&[0.1 + 1. / 3., 0.2 + 1. / 3.]));
+        let tenths: Interval<f64> = vec![Interval::exact(0.1); 10].into_iter().sum();
+        assert!(tenths.contains(1.) && tenths.width() > 0.);
+        assert_eq!(xs.iter().cloned().product::<Interval<f64>>(), xs[0] * xs[1] * xs[2]);
+
+        let none: [Interval<f64>; 0] = [];
+        assert_eq!((none.iter().sum::<Interval<f64>>(), none.iter().product::<Interval<f64>>()),
+                   (Interval::exact(0.), Interval::exact(1.)));
+        let with_empty = [Interval::exact(1.), Interval::empty(), Interval::exact(2.)];
+        assert!(with_empty.iter().sum::<Interval<f64>>().is_empty());
+        assert!(with_empty.iter().product::<Interval<f64>>().is_empty());
+        assert_eq!((1..5).map(Interval::exact).product::<Interval<i32>>(), Interval::exact(24));
+    }
 }
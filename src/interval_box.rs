@@ -0,0 +1,515 @@
+//! Boxes: Cartesian products of intervals.
+
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::iter::FromIterator;
+use std::ops::{Add, Deref, DerefMut, Mul, Neg, Sub};
+use std::slice;
+
+use num::{Float, FromPrimitive, Num};
+
+use interval::Interval;
+use linalg::dot;
+use rounding::RoundedArith;
+use table::write_table;
+use utils::{partial_min, partial_max};
+
+/// Box in `n`-dimensional space, one interval per coordinate
+///
+/// Box dereferences to slice of its intervals, so all slice methods (indexing, `len`, `iter`)
+/// are available directly.
+///
+/// ## Example
+///
+/// ```rust
+/// use inter::{Interval, IntervalBox};
+///
+/// let b = IntervalBox::new(vec![Interval::with_range(0., 1.), Interval::with_range(2., 3.)]);
+///
+/// assert_eq!(b.dimension(), 2);
+/// assert_eq!(b[1], Interval::with_range(2., 3.));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct IntervalBox<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T> IntervalBox<T> {
+    /// Create box from intervals of subsequent coordinates
+    pub fn new(intervals: Vec<Interval<T>>) -> Self {
+        IntervalBox { intervals }
+    }
+
+    /// Number of coordinates
+    pub fn dimension(&self) -> usize {
+        self.intervals.len()
+    }
+
+    /// Unwrap intervals of subsequent coordinates
+    pub fn into_inner(self) -> Vec<Interval<T>> {
+        self.intervals
+    }
+}
+
+impl<T> IntervalBox<T>
+    where T: Copy
+{
+    /// Iterate over all `2^n` vertices of the box
+    ///
+    /// The first coordinate changes the fastest. Degenerate coordinates still count twice, so
+    /// vertices may repeat.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::{Interval, IntervalBox};
+    ///
+    /// let b = IntervalBox::new(vec![Interval::with_range(0., 1.), Interval::with_range(2., 3.)]);
+    /// let corners: Vec<_> = b.corners().collect();
+    ///
+    /// assert_eq!(corners, vec![vec![0., 2.], vec![1., 2.], vec![0., 3.], vec![1., 3.]]);
+    /// ```
+    pub fn corners(&self) -> Corners<'_, T> {
+        Corners {
+            intervals: &self.intervals,
+            state: Some(vec![false; self.intervals.len()]),
+        }
+    }
+
+    /// Iterate over centers of the `k^n` sub-boxes obtained by splitting every coordinate into
+    /// `k` equal parts
+    ///
+    /// The first coordinate changes the fastest. Centers are computed in plain floating point
+    /// (they are sample points, not enclosures), but they always lie inside the box.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::{Interval, IntervalBox};
+    ///
+    /// let b = IntervalBox::new(vec![Interval::with_range(0., 1.), Interval::with_range(0., 2.)]);
+    /// let centers: Vec<_> = b.sample_centers(2).collect();
+    ///
+    /// assert_eq!(centers,
+    ///            vec![vec![0.25, 0.5], vec![0.75, 0.5], vec![0.25, 1.5], vec![0.75, 1.5]]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This will panic if `k` is zero.
+    pub fn sample_centers(&self, k: usize) -> SampleCenters<'_, T>
+        where T: Float + FromPrimitive
+    {
+        assert!(k > 0);
+
+        SampleCenters {
+            intervals: &self.intervals,
+            parts: k,
+            state: Some(vec![0; self.intervals.len()]),
+        }
+    }
+}
+
+impl<T> IntervalBox<T>
+    where T: Num + RoundedArith
+{
+    /// Lower bound of box volume (product of widths rounded downward)
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::{Interval, IntervalBox};
+    ///
+    /// let b = IntervalBox::new(vec![Interval::with_range(0., 0.1), Interval::with_range(0., 3.)]);
+    ///
+    /// assert!(b.volume_lower() <= 0.1 * 3.);
+    /// assert!(b.volume_lower() < b.volume_upper());
+    /// ```
+    pub fn volume_lower(&self) -> T {
+        self.intervals.iter().fold(T::one(), |acc, i| acc.mul_down(i.end.sub_down(i.start)))
+    }
+
+    /// Upper bound of box volume (product of widths rounded upward)
+    pub fn volume_upper(&self) -> T {
+        self.intervals.iter().fold(T::one(), |acc, i| acc.mul_up(i.end.sub_up(i.start)))
+    }
+}
+
+impl<T> IntervalBox<T>
+    where T: Num + RoundedArith
+{
+    /// Enclosure of dot product `Σ xᵢ yᵢ` of boxes treated as vectors
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::{Interval, IntervalBox};
+    ///
+    /// let x = IntervalBox::new(vec![Interval::with_range(0., 1.), Interval::exact(2.)]);
+    ///
+    /// assert_eq!(x.dot(&x), Interval::with_range(4., 5.));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This will panic if boxes have different dimensions.
+    pub fn dot(&self, other: &IntervalBox<T>) -> Interval<T> {
+        assert_eq!(self.dimension(), other.dimension());
+
+        dot(self, other)
+    }
+}
+
+/// Box is printed as a column, one coordinate per line, with endpoints aligned
+///
+/// Precision flag is applied to every endpoint, alternate flag adds column with widths.
+///
+/// ## Example
+///
+/// ```rust
+/// use inter::{Interval, IntervalBox};
+///
+/// let b = IntervalBox::new(vec![Interval::with_range(1., 2.), Interval::with_range(-10., 0.5)]);
+///
+/// assert_eq!(format!("{:.1}", b), "[  1.0, 2.0]\n[-10.0, 0.5]");
+/// assert_eq!(format!("{:#.1}", b), "[  1.0, 2.0]  1.0\n[-10.0, 0.5] 10.5");
+/// ```
+impl<T> fmt::Display for IntervalBox<T>
+    where T: fmt::Display + Sub<Output = T> + Copy
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let rows: Vec<_> = self.intervals.iter().map(slice::from_ref).collect();
+        write_table(fmt, &rows)
+    }
+}
+
+impl<T> IntervalBox<T>
+    where T: Float
+{
+    /// Hash of box that is the same in every run and on every platform
+    ///
+    /// `Hash` of the box feeds the same values, but `HashMap` seeds its hasher randomly in
+    /// every run. This instead runs 64-bit FNV-1a over little-endian encoding of them, so memo
+    /// tables and caches keyed by fingerprints can be stored and compared between runs.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::{Interval, IntervalBox};
+    ///
+    /// let a = IntervalBox::new(vec![Interval::with_range(0., 1.)]);
+    /// let b = IntervalBox::new(vec![Interval::with_range(-0., 1.)]);
+    ///
+    /// assert_eq!(a.fingerprint(), b.fingerprint());
+    /// assert_ne!(a.fingerprint(), IntervalBox::new(vec![Interval::exact(0.)]).fingerprint());
+    /// ```
+    pub fn fingerprint(&self) -> u64 {
+        let mut hasher = Fnv(0xcbf2_9ce4_8422_2325);
+        self.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+impl<T> Hash for IntervalBox<T>
+    where T: Float
+{
+    /// Hash dimension and bit patterns of all bounds
+    ///
+    /// Both zeros hash the same, as they compare equal.
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.intervals.len() as u64);
+
+        for x in &self.intervals {
+            for &bound in &[x.start, x.end] {
+                let (mantissa, exp, sign) = (bound + T::zero()).integer_decode();
+                state.write_u64(mantissa);
+                state.write_i16(exp);
+                state.write_i8(sign);
+            }
+        }
+    }
+}
+
+/// 64-bit FNV-1a, with integers encoded independently of platform
+struct Fnv(u64);
+
+impl Hasher for Fnv {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = (self.0 ^ byte as u64).wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn write_u64(&mut self, n: u64) {
+        self.write(&n.to_le_bytes());
+    }
+
+    fn write_i16(&mut self, n: i16) {
+        self.write(&n.to_le_bytes());
+    }
+}
+
+impl<T> Deref for IntervalBox<T> {
+    type Target = [Interval<T>];
+
+    fn deref(&self) -> &[Interval<T>] {
+        &self.intervals
+    }
+}
+
+impl<T> DerefMut for IntervalBox<T> {
+    fn deref_mut(&mut self) -> &mut [Interval<T>] {
+        &mut self.intervals
+    }
+}
+
+impl<T> From<Vec<Interval<T>>> for IntervalBox<T> {
+    fn from(intervals: Vec<Interval<T>>) -> Self {
+        IntervalBox::new(intervals)
+    }
+}
+
+impl<T> FromIterator<Interval<T>> for IntervalBox<T> {
+    fn from_iter<I>(iter: I) -> Self
+        where I: IntoIterator<Item = Interval<T>>
+    {
+        IntervalBox::new(iter.into_iter().collect())
+    }
+}
+
+macro_rules! elementwise_ops {
+    ($($imp:ident, $method:ident;)*) => {
+        $(
+            /// Elementwise operation on boxes treated as vectors
+            ///
+            /// # Panics
+            ///
+            /// This will panic if boxes have different dimensions.
+            impl<T> $imp for IntervalBox<T>
+                where Interval<T>: $imp<Output = Interval<T>>,
+                      T: Copy
+            {
+                type Output = IntervalBox<T>;
+
+                fn $method(self, other: Self) -> Self {
+                    assert_eq!(self.dimension(), other.dimension());
+
+                    self.iter().zip(other.iter()).map(|(&a, &b)| a.$method(b)).collect()
+                }
+            }
+        )*
+    };
+}
+
+elementwise_ops! {
+    Add, add;
+    Sub, sub;
+}
+
+/// Scale every coordinate by the same interval
+impl<T> Mul<Interval<T>> for IntervalBox<T>
+    where Interval<T>: Mul<Output = Interval<T>>,
+          T: Copy
+{
+    type Output = IntervalBox<T>;
+
+    fn mul(self, k: Interval<T>) -> Self {
+        self.iter().map(|&x| x * k).collect()
+    }
+}
+
+impl<T> Neg for IntervalBox<T>
+    where T: Neg<Output = T> + Copy
+{
+    type Output = IntervalBox<T>;
+
+    fn neg(self) -> Self {
+        self.iter().map(|&x| -x).collect()
+    }
+}
+
+/// Iterator over vertices of a box, see `IntervalBox::corners`
+pub struct Corners<'a, T: 'a> {
+    intervals: &'a [Interval<T>],
+    state: Option<Vec<bool>>,
+}
+
+impl<'a, T> Iterator for Corners<'a, T>
+    where T: Copy
+{
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        let ret = {
+            let state = self.state.as_ref()?;
+            self.intervals
+                .iter()
+                .zip(state)
+                .map(|(i, &upper)| if upper { i.end } else { i.start })
+                .collect()
+        };
+
+        self.state = self.state.take().and_then(|mut state| {
+            let carry = state.iter_mut().all(|upper| {
+                *upper = !*upper;
+                !*upper
+            });
+            if carry { None } else { Some(state) }
+        });
+
+        Some(ret)
+    }
+}
+
+/// Iterator over centers of sub-boxes, see `IntervalBox::sample_centers`
+pub struct SampleCenters<'a, T: 'a> {
+    intervals: &'a [Interval<T>],
+    parts: usize,
+    state: Option<Vec<usize>>,
+}
+
+impl<'a, T> Iterator for SampleCenters<'a, T>
+    where T: Float + FromPrimitive
+{
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        let parts: T = FromPrimitive::from_usize(self.parts).unwrap();
+        let half = T::one() / (T::one() + T::one());
+
+        let ret = {
+            let state = self.state.as_ref()?;
+            self.intervals
+                .iter()
+                .zip(state)
+                .map(|(i, &k)| {
+                    let k: T = FromPrimitive::from_usize(k).unwrap();
+                    let point = i.start + (k + half) * (i.end - i.start) / parts;
+                    partial_min(partial_max(point, i.start), i.end)
+                })
+                .collect()
+        };
+
+        let max = self.parts - 1;
+        self.state = self.state.take().and_then(|mut state| {
+            let carry = state.iter_mut().all(|k| {
+                if *k == max {
+                    *k = 0;
+                    true
+                } else {
+                    *k += 1;
+                    false
+                }
+            });
+            if carry { None } else { Some(state) }
+        });
+
+        Some(ret)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::IntervalBox;
+    use interval::Interval;
+
+    fn setup() -> IntervalBox<f64> {
+        IntervalBox::new(vec![Interval::with_range(0., 1.),
+                              Interval::with_range(2., 3.),
+                              Interval::with_range(-1., 1.)])
+    }
+
+    #[test]
+    fn fingerprints() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+
+        let hash = |b: &IntervalBox<f64>| {
+            let mut hasher = DefaultHasher::new();
+            b.hash(&mut hasher);
+            hasher.finish()
+        };
+
+        // Recorded once, so any change of the encoding breaks stored caches
+        assert_eq!(setup().fingerprint(), 0x43ce_e2d9_5187_d757);
+        assert_eq!(IntervalBox::<f64>::new(vec![]).fingerprint(), 0xa8c7_f832_281a_39c5);
+
+        let zeros = IntervalBox::new(vec![Interval::with_range(-0., -0.)]);
+        assert_eq!(zeros.fingerprint(), IntervalBox::new(vec![Interval::exact(0.)]).fingerprint());
+        assert_eq!(hash(&zeros), hash(&IntervalBox::new(vec![Interval::exact(0.)])));
+        let mut other = setup();
+        other[2] = Interval::with_range(-1., 1f64.next_up());
+        assert_ne!(other.fingerprint(), setup().fingerprint());
+        assert_ne!(hash(&other), hash(&setup()));
+        let swapped = IntervalBox::new(vec![Interval::exact(1.), Interval::exact(2.)]);
+        let flat = IntervalBox::new(vec![Interval::with_range(1., 2.)]);
+        assert_ne!(swapped.fingerprint(), flat.fingerprint());
+    }
+
+    #[test]
+    fn corners() {
+        let b = setup();
+        let corners: Vec<_> = b.corners().collect();
+
+        assert_eq!(corners.len(), 8);
+        assert_eq!(corners[0], vec![0., 2., -1.]);
+        assert_eq!(corners[7], vec![1., 3., 1.]);
+        for corner in &corners {
+            assert!(corner.iter().zip(b.iter()).all(|(&x, i)| i.contains(x)));
+        }
+    }
+
+    #[test]
+    fn corners_of_empty_box() {
+        let b = IntervalBox::<f64>::new(vec![]);
+        assert_eq!(b.corners().count(), 1);
+    }
+
+    #[test]
+    fn volume() {
+        let b = setup();
+        assert_eq!(b.volume_lower(), 2.);
+        assert_eq!(b.volume_upper(), 2.);
+
+        let b = IntervalBox::new(vec![Interval::with_range(0., 0.1); 3]);
+        assert!(b.volume_lower() < b.volume_upper());
+        assert!(b.volume_lower() <= 0.001 && 0.001 <= b.volume_upper());
+    }
+
+    #[test]
+    fn display() {
+        let b = setup();
+        assert_eq!(format!("{}", b), "[ 0, 1]\n[ 2, 3]\n[-1, 1]");
+        assert_eq!(format!("{:#.2}", b),
+                   "[ 0.00, 1.00] 1.00\n[ 2.00, 3.00] 1.00\n[-1.00, 1.00] 2.00");
+        assert_eq!(format!("{}", IntervalBox::<f64>::new(vec![])), "");
+    }
+
+    #[test]
+    fn vector_ops() {
+        let b = setup();
+        let ones = IntervalBox::new(vec![Interval::exact(1.); 3]);
+
+        assert_eq!(b.dot(&ones), Interval::with_range(1., 5.));
+        assert_eq!((b.clone() + ones.clone())[1], Interval::with_range(3., 4.));
+        assert_eq!((b.clone() - ones)[2], Interval::with_range(-2., 0.));
+        assert_eq!((b.clone() * Interval::exact(-2.))[0], Interval::with_range(-2., 0.));
+        assert_eq!((-b)[1], Interval::with_range(-3., -2.));
+    }
+
+    #[test]
+    fn sample_centers() {
+        let b = setup();
+        let centers: Vec<_> = b.sample_centers(4).collect();
+
+        assert_eq!(centers.len(), 64);
+        assert_eq!(centers[0], vec![0.125, 2.125, -0.75]);
+        for center in &centers {
+            assert!(center.iter().zip(b.iter()).all(|(&x, i)| i.contains(x)));
+        }
+    }
+}
@@ -0,0 +1,270 @@
+//! Reading and writing columns of intervals in CSV format.
+//!
+//! Available with the `io` feature. Intervals can be stored either as pairs of bounds (`lo,hi`)
+//! or as value and error (`value,err`, meaning `value ± err`). Numbers are parsed with outward
+//! rounding, so decimal values that are not representable (like `0.1`) are enclosed by the
+//! resulting interval.
+//!
+//! Writer emits bounds in the shortest representation that rounds back to the same `f64`, which
+//! is what most other tools expect. Reading such file back can widen each endpoint by one ulp.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use inter::Interval;
+//! use inter::io::{self, Column};
+//!
+//! let data = "name,value,err\nfoo,1.5,0.25\nbar,-2,0.5\n";
+//! let rows = io::read_csv(data.as_bytes(), &[Column::Uncertain(1, 2)], true).unwrap();
+//!
+//! assert_eq!(rows, vec![vec![Interval::with_range(1.25, 1.75)],
+//!                       vec![Interval::with_range(-2.5, -1.5)]]);
+//! ```
+
+use std::error;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+
+use decimal::parse_enclosure;
+use interval::Interval;
+
+/// Position of interval in CSV record
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Column {
+    /// Interval stored as lower and upper bound in fields with given indices
+    Bounds(usize, usize),
+    /// Interval stored as value and (non-negative) error in fields with given indices
+    Uncertain(usize, usize),
+}
+
+/// Error while reading CSV data
+#[derive(Debug)]
+pub enum Error {
+    /// Underlying reader failed
+    Io(io::Error),
+    /// Record in `line` has no field with index `field`
+    MissingField { line: usize, field: usize },
+    /// Field is not a number
+    InvalidNumber { line: usize, field: usize },
+    /// Interval in `column` has lower bound greater than upper bound or negative error
+    InvalidInterval { line: usize, column: usize },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Io(ref err) => write!(fmt, "I/O error: {}", err),
+            Error::MissingField { line, field } => {
+                write!(fmt, "line {}: missing field {}", line, field)
+            }
+            Error::InvalidNumber { line, field } => {
+                write!(fmt, "line {}: field {} is not a number", line, field)
+            }
+            Error::InvalidInterval { line, column } => {
+                write!(fmt, "line {}: column {} is not a valid interval", line, column)
+            }
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            Error::Io(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+/// Read intervals described by `columns` from every non-empty record
+///
+/// When `header` is `true` the first line is skipped. Result contains one vector per record,
+/// with intervals in the same order as `columns`.
+pub fn read_csv<R>(reader: R,
+                   columns: &[Column],
+                   header: bool)
+                   -> Result<Vec<Vec<Interval<f64>>>, Error>
+    where R: BufRead
+{
+    let mut rows = Vec::new();
+
+    for (i, line) in reader.lines().enumerate().skip(if header { 1 } else { 0 }) {
+        let line = line?;
+        let line_no = i + 1;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let fields = split_fields(&line);
+        let bounds = |field: usize| -> Result<(f64, f64), Error> {
+            let value = fields.get(field).ok_or(Error::MissingField {
+                line: line_no,
+                field,
+            })?;
+            parse_bounds(value).ok_or(Error::InvalidNumber {
+                line: line_no,
+                field,
+            })
+        };
+
+        let row = columns.iter()
+            .enumerate()
+            .map(|(column, &spec)| {
+                let invalid = Error::InvalidInterval {
+                    line: line_no,
+                    column,
+                };
+                match spec {
+                    Column::Bounds(lo, hi) => {
+                        let (start, start_up) = bounds(lo)?;
+                        let (end_down, end) = bounds(hi)?;
+                        if start <= end {
+                            Ok(Interval::with_range(start, end))
+                        } else if start_up == f64::MAX && end_down == f64::MIN {
+                            // Bounds of empty interval, as written by `write_csv`
+                            Ok(Interval::empty())
+                        } else {
+                            Err(invalid)
+                        }
+                    }
+                    Column::Uncertain(value, err) => {
+                        let (start, end) = bounds(value)?;
+                        let (_, err) = bounds(err)?;
+                        if err >= 0. {
+                            Ok(Interval::with_range(start, end) + Interval::with_range(-err, err))
+                        } else {
+                            Err(invalid)
+                        }
+                    }
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        rows.push(row);
+    }
+
+    Ok(rows)
+}
+
+/// Write rows of intervals as `lo,hi` pairs, optionally preceded by `header` line
+///
+/// Empty interval is written as its bounds `MAX,MIN`, which `read_csv` reads back as empty.
+pub fn write_csv<W>(mut writer: W,
+                    header: Option<&[&str]>,
+                    rows: &[Vec<Interval<f64>>])
+                    -> io::Result<()>
+    where W: Write
+{
+    if let Some(header) = header {
+        let names: Vec<_> = header.iter().map(|name| quote(name)).collect();
+        writeln!(writer, "{}", names.join(","))?;
+    }
+
+    for row in rows {
+        let fields: Vec<_> =
+            row.iter().map(|i| format!("{:?},{:?}", i.start, i.end)).collect();
+        writeln!(writer, "{}", fields.join(","))?;
+    }
+
+    Ok(())
+}
+
+fn parse_bounds(s: &str) -> Option<(f64, f64)> {
+    match s.trim() {
+        "inf" | "+inf" => Some((f64::INFINITY, f64::INFINITY)),
+        "-inf" => Some((f64::NEG_INFINITY, f64::NEG_INFINITY)),
+        s => parse_enclosure(s).map(|(start, end, _)| (start, end)),
+    }
+}
+
+fn split_fields(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    fields.push(field);
+
+    fields
+}
+
+fn quote(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn split() {
+        assert_eq!(split_fields("a,\"b,c\",\"d\"\"e\","),
+                   vec!["a", "b,c", "d\"e", ""]);
+    }
+
+    #[test]
+    fn read_bounds() {
+        let data = "0.1,0.2\n\n1,inf\n";
+        let rows = read_csv(data.as_bytes(), &[Column::Bounds(0, 1)], false).unwrap();
+
+        assert_eq!(rows.len(), 2);
+        assert!(rows[0][0].start < 0.1 && rows[0][0].end == 0.2);
+        assert_eq!(rows[1][0], Interval::with_range(1., f64::INFINITY));
+    }
+
+    #[test]
+    fn read_errors() {
+        let columns = [Column::Bounds(0, 1)];
+        match read_csv("1,x\n".as_bytes(), &columns, false) {
+            Err(Error::InvalidNumber { line: 1, field: 1 }) => (),
+            other => panic!("{:?}", other),
+        }
+        match read_csv("h\n1\n".as_bytes(), &columns, true) {
+            Err(Error::MissingField { line: 2, field: 1 }) => (),
+            other => panic!("{:?}", other),
+        }
+        match read_csv("2,1\n".as_bytes(), &columns, false) {
+            Err(Error::InvalidInterval { line: 1, column: 0 }) => (),
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let rows = vec![vec![Interval::with_range(0.1, 0.3), Interval::with_range(-1e300, 2.)],
+                        vec![Interval::empty(), Interval::entire()]];
+        let mut out = Vec::new();
+        write_csv(&mut out, Some(&["a lo", "a hi", "b,lo", "b hi"]), &rows).unwrap();
+
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.starts_with("a lo,a hi,\"b,lo\",b hi\n"));
+
+        let columns = [Column::Bounds(0, 1), Column::Bounds(2, 3)];
+        let back = read_csv(text.as_bytes(), &columns, true).unwrap();
+        for (original, read) in rows[0].iter().zip(&back[0]) {
+            assert!(read.start <= original.start && original.end <= read.end);
+        }
+        assert_eq!(back[1], rows[1]);
+    }
+}
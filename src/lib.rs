@@ -3,10 +3,77 @@
 //! Created as part of Numerical Analysis at Computer Engineering classes at PUT
 
 extern crate num_traits as num;
+#[cfg(feature = "libc")]
 extern crate libc;
 
+mod decimal;
+mod integer;
+mod modular;
+mod reverse;
+mod table;
 mod utils;
+#[cfg(feature = "validate")]
+mod validate;
+pub mod accumulator;
+pub mod activation;
+pub mod affine;
+pub mod batch;
+pub mod bench;
+pub mod calibration;
+pub mod complex;
+pub mod confidence;
+pub mod config;
+pub mod conformance;
+pub mod continuation;
+pub mod continued_fraction;
+pub mod decorated;
+pub mod domain;
+#[cfg(feature = "exhaustive")]
+pub mod exhaustive;
+pub mod expr;
+pub mod factorial;
+pub mod falsify;
+pub mod geodesy;
+pub mod grad;
+pub mod ibp;
+pub mod integrate;
 pub mod interval;
+pub mod interval_box;
+#[cfg(feature = "io")]
+pub mod io;
+pub mod linalg;
+pub mod midrad;
+pub mod one_or_two;
+pub mod optimize;
+pub mod order;
+pub mod parallel;
+pub mod parse;
+pub mod plot;
+pub mod policy;
+pub mod polynomial;
+pub mod predicates;
+#[cfg(feature = "provenance")]
+pub mod provenance;
+pub mod quaternion;
+pub mod real;
+pub mod rolling;
 pub mod rounding;
+pub mod rpn;
+pub mod running_error;
+pub mod semantics;
+#[cfg(feature = "io")]
+pub mod snapshot;
+pub mod solve;
+pub mod strategy;
+#[macro_use]
+pub mod tagged;
+pub mod testfns;
+pub mod tribool;
+pub mod uniform;
 
 pub use interval::Interval;
+pub use interval_box::IntervalBox;
+pub use linalg::{IntervalMatrix, IntervalVector};
+pub use one_or_two::OneOrTwo;
+pub use tribool::Tribool;
+pub use utils::{partial_max, partial_min};
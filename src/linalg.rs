@@ -0,0 +1,453 @@
+//! Interval vectors and matrices with verified linear solvers.
+//!
+//! Vectors are `IntervalBox`es, which already support elementwise operations and dot product.
+//! All sums and products are evaluated in interval arithmetic, so results of matrix operations
+//! enclose the exact result for every choice of point matrices and vectors inside the operands.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use inter::{Interval, IntervalBox, IntervalMatrix};
+//!
+//! let a = IntervalMatrix::from_rows(vec![vec![Interval::exact(4.), Interval::exact(1.)],
+//!                                        vec![Interval::exact(1.), Interval::exact(3.)]]);
+//! let b = IntervalBox::new(vec![Interval::exact(1.), Interval::exact(2.)]);
+//!
+//! let x = a.krawczyk(&b, 10).unwrap();
+//! let ax = &a * &x;
+//!
+//! assert!(ax[0].contains(1.) && ax[1].contains(2.));
+//! assert!(x[0].width() < 1e-12 && x[1].width() < 1e-12);
+//! ```
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Sub, Mul, Index, IndexMut};
+
+use num::{Float, Num, Zero};
+
+use interval::Interval;
+use interval_box::IntervalBox;
+use rounding::RoundedArith;
+use table::write_table;
+use utils::partial_max;
+
+/// Vector of intervals, the same as box
+pub type IntervalVector<T> = IntervalBox<T>;
+
+/// Matrix of intervals stored row by row
+///
+/// ## Example
+///
+/// ```rust
+/// use inter::{Interval, IntervalMatrix};
+///
+/// let a = IntervalMatrix::new(2, 2, vec![Interval::with_range(1., 2.); 4]);
+/// let square = &a * &a;
+///
+/// assert_eq!(square[(0, 1)], Interval::with_range(2., 8.));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct IntervalMatrix<T> {
+    rows: usize,
+    cols: usize,
+    data: Vec<Interval<T>>,
+}
+
+impl<T> IntervalMatrix<T> {
+    /// Create matrix with `rows` rows and `cols` columns from intervals stored row by row
+    ///
+    /// # Panics
+    ///
+    /// This will panic if length of `data` is not `rows * cols`.
+    pub fn new(rows: usize, cols: usize, data: Vec<Interval<T>>) -> Self {
+        assert_eq!(data.len(), rows * cols);
+
+        IntervalMatrix { rows, cols, data }
+    }
+
+    /// Create matrix from its rows
+    ///
+    /// # Panics
+    ///
+    /// This will panic if rows have different lengths.
+    pub fn from_rows(rows: Vec<Vec<Interval<T>>>) -> Self {
+        let cols = rows.first().map_or(0, Vec::len);
+        assert!(rows.iter().all(|row| row.len() == cols));
+
+        IntervalMatrix {
+            rows: rows.len(),
+            cols,
+            data: rows.into_iter().flatten().collect(),
+        }
+    }
+
+    /// Number of rows
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Number of columns
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// Intervals in row `i`
+    pub fn row(&self, i: usize) -> &[Interval<T>] {
+        &self.data[i * self.cols..(i + 1) * self.cols]
+    }
+
+    /// Unwrap intervals stored row by row
+    pub fn into_inner(self) -> Vec<Interval<T>> {
+        self.data
+    }
+}
+
+impl<T> IntervalMatrix<T>
+    where T: Copy
+{
+    /// Transposed matrix
+    pub fn transpose(&self) -> Self {
+        let data = (0..self.cols)
+            .flat_map(|j| (0..self.rows).map(move |i| (i, j)))
+            .map(|(i, j)| self[(i, j)])
+            .collect();
+
+        IntervalMatrix::new(self.cols, self.rows, data)
+    }
+}
+
+impl<T> IntervalMatrix<T>
+    where T: Num + PartialOrd + Copy
+{
+    /// Identity matrix of size `n`
+    pub fn identity(n: usize) -> Self {
+        let data = (0..n * n)
+            .map(|k| Interval::exact(if k / n == k % n { T::one() } else { T::zero() }))
+            .collect();
+
+        IntervalMatrix::new(n, n, data)
+    }
+}
+
+impl<T> IntervalMatrix<T>
+    where T: Float + RoundedArith
+{
+    /// Upper bound of row-sum norm `max Σ |aᵢⱼ|` over all matrices inside
+    pub fn norm_inf(&self) -> T {
+        (0..self.rows).fold(T::zero(), |norm, i| {
+            partial_max(norm, self.row(i).iter().fold(T::zero(), |acc, a| acc.add_up(a.mag())))
+        })
+    }
+
+    /// Enclose solutions of `A x = b` for all `A` and `b` inside, by Gaussian elimination
+    ///
+    /// Pivot is chosen in every column as the interval of largest mignitude. Returns `None` if
+    /// some pivot contains zero, which happens for singular matrices, but also for regular ones
+    /// with wide entries. `krawczyk` usually succeeds more often and gives tighter enclosures.
+    ///
+    /// # Panics
+    ///
+    /// This will panic if matrix is not square or dimension of `b` does not match.
+    pub fn gauss(&self, b: &IntervalBox<T>) -> Option<IntervalBox<T>> {
+        let n = self.system_size(b);
+        let mut a = self.data.clone();
+        let mut b = b.to_vec();
+
+        for k in 0..n {
+            let pivot = (k..n)
+                .max_by(|&i, &j| cmp(a[i * n + k].mig(), a[j * n + k].mig()))
+                .unwrap();
+            if a[pivot * n + k].mig().partial_cmp(&T::zero()) != Some(Ordering::Greater) {
+                return None;
+            }
+            swap_rows(&mut a, n, k, pivot);
+            b.swap(k, pivot);
+
+            for i in k + 1..n {
+                let factor = a[i * n + k] / a[k * n + k];
+                for j in k + 1..n {
+                    a[i * n + j] = a[i * n + j] - factor * a[k * n + j];
+                }
+                b[i] = b[i] - factor * b[k];
+            }
+        }
+
+        let mut x = vec![Interval::zero(); n];
+        for k in (0..n).rev() {
+            let sum = (k + 1..n).fold(b[k], |acc, j| acc - a[k * n + j] * x[j]);
+            x[k] = sum / a[k * n + k];
+        }
+
+        Some(x.into())
+    }
+
+    /// Enclose solutions of `A x = b` for all `A` and `b` inside, by Krawczyk method
+    ///
+    /// System is preconditioned with approximate inverse `Y` of the midpoint matrix. When
+    /// `‖I - Y A‖ < 1` every matrix in `A` is proven regular and all solutions lie in a box
+    /// around the origin, which is then refined by `iterations` steps of
+    /// `X ← (Y b + (I - Y A) X) ∩ X`. Returns `None` when the condition cannot be verified.
+    ///
+    /// # Panics
+    ///
+    /// This will panic if matrix is not square or dimension of `b` does not match.
+    pub fn krawczyk(&self, b: &IntervalBox<T>, iterations: usize) -> Option<IntervalBox<T>> {
+        let n = self.system_size(b);
+        let inverse = self.mid_inverse()?;
+        let y = IntervalMatrix::new(n, n, inverse.into_iter().map(Interval::exact).collect());
+        let c = IntervalMatrix::identity(n) - &y * self;
+        let yb = &y * b;
+
+        let norm = c.norm_inf();
+        if norm.partial_cmp(&T::one()) != Some(Ordering::Less) {
+            return None;
+        }
+        let radius = yb.iter()
+            .fold(T::zero(), |acc, i| partial_max(acc, i.mag()))
+            .div_up(T::one().sub_down(norm));
+
+        let mut x: IntervalBox<T> = vec![Interval::with_range(-radius, radius); n].into();
+        for _ in 0..iterations {
+            let next = yb.clone() + &c * &x;
+            x = x.iter().zip(next.iter()).map(|(x, next)| x.intersect(next)).collect();
+        }
+
+        Some(x)
+    }
+
+    /// Size of square system with right-hand side `b`
+    fn system_size(&self, b: &IntervalBox<T>) -> usize {
+        assert_eq!(self.rows, self.cols);
+        assert_eq!(self.rows, b.dimension());
+
+        self.rows
+    }
+
+    /// Approximate inverse of the midpoint matrix by Gauss–Jordan elimination in floating point
+    fn mid_inverse(&self) -> Option<Vec<T>> {
+        let n = self.rows;
+        let mut m: Vec<T> = self.data.iter().map(Interval::mid).collect();
+        let mut inv: Vec<T> = IntervalMatrix::identity(n).data.iter().map(|i| i.start).collect();
+
+        for k in 0..n {
+            let pivot = (k..n)
+                .max_by(|&i, &j| cmp(m[i * n + k].abs(), m[j * n + k].abs()))
+                .unwrap();
+            let p = m[pivot * n + k];
+            if p == T::zero() || !p.is_finite() {
+                return None;
+            }
+            swap_rows(&mut m, n, k, pivot);
+            swap_rows(&mut inv, n, k, pivot);
+
+            for j in 0..n {
+                m[k * n + j] = m[k * n + j] / p;
+                inv[k * n + j] = inv[k * n + j] / p;
+            }
+            for i in (0..n).filter(|&i| i != k) {
+                let factor = m[i * n + k];
+                for j in 0..n {
+                    m[i * n + j] = m[i * n + j] - factor * m[k * n + j];
+                    inv[i * n + j] = inv[i * n + j] - factor * inv[k * n + j];
+                }
+            }
+        }
+
+        Some(inv)
+    }
+}
+
+fn cmp<T: PartialOrd>(a: T, b: T) -> Ordering {
+    a.partial_cmp(&b).unwrap_or(Ordering::Equal)
+}
+
+fn swap_rows<X>(data: &mut [X], n: usize, i: usize, j: usize) {
+    if i != j {
+        for k in 0..n {
+            data.swap(i * n + k, j * n + k);
+        }
+    }
+}
+
+/// Enclosure of dot product `Σ xᵢ yᵢ`
+pub(crate) fn dot<T>(x: &[Interval<T>], y: &[Interval<T>]) -> Interval<T>
+    where T: Num + RoundedArith
+{
+    x.iter().zip(y).fold(Interval::zero(), |acc, (&x, &y)| acc + x * y)
+}
+
+impl<T> Index<(usize, usize)> for IntervalMatrix<T> {
+    type Output = Interval<T>;
+
+    fn index(&self, (i, j): (usize, usize)) -> &Interval<T> {
+        assert!(j < self.cols);
+        &self.data[i * self.cols + j]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for IntervalMatrix<T> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut Interval<T> {
+        assert!(j < self.cols);
+        &mut self.data[i * self.cols + j]
+    }
+}
+
+/// Matrix is printed one row per line, with endpoints aligned in columns
+///
+/// Precision flag is applied to every endpoint, alternate flag adds widths after intervals.
+///
+/// ## Example
+///
+/// ```rust
+/// use inter::{Interval, IntervalMatrix};
+///
+/// let a = IntervalMatrix::from_rows(vec![vec![Interval::exact(1.), Interval::with_range(-2., 2.)],
+///                                        vec![Interval::exact(10.), Interval::exact(0.)]]);
+///
+/// assert_eq!(format!("{}", a), "[ 1,  1]  [-2, 2]\n[10, 10]  [ 0, 0]");
+/// ```
+impl<T> fmt::Display for IntervalMatrix<T>
+    where T: fmt::Display + Sub<Output = T> + Copy
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let rows: Vec<_> = (0..self.rows).map(|i| self.row(i)).collect();
+        write_table(fmt, &rows)
+    }
+}
+
+macro_rules! elementwise_ops {
+    ($($imp:ident, $method:ident;)*) => {
+        $(
+            /// Elementwise operation
+            ///
+            /// # Panics
+            ///
+            /// This will panic if matrices have different shapes.
+            impl<T> $imp for IntervalMatrix<T>
+                where Interval<T>: $imp<Output = Interval<T>>,
+                      T: Copy
+            {
+                type Output = IntervalMatrix<T>;
+
+                fn $method(self, other: Self) -> Self {
+                    assert_eq!((self.rows, self.cols), (other.rows, other.cols));
+
+                    let data = self.data.iter().zip(&other.data).map(|(&a, &b)| a.$method(b));
+                    IntervalMatrix::new(self.rows, self.cols, data.collect())
+                }
+            }
+        )*
+    };
+}
+
+elementwise_ops! {
+    Add, add;
+    Sub, sub;
+}
+
+/// Matrix–vector product
+///
+/// # Panics
+///
+/// This will panic if number of columns is not dimension of vector.
+impl<'a, T> Mul<&'a IntervalBox<T>> for &'a IntervalMatrix<T>
+    where T: Num + RoundedArith
+{
+    type Output = IntervalBox<T>;
+
+    fn mul(self, x: &IntervalBox<T>) -> IntervalBox<T> {
+        assert_eq!(self.cols, x.dimension());
+
+        (0..self.rows).map(|i| dot(self.row(i), x)).collect()
+    }
+}
+
+/// Matrix–matrix product
+///
+/// # Panics
+///
+/// This will panic if number of columns of left matrix is not number of rows of right one.
+impl<T> Mul for &IntervalMatrix<T>
+    where T: Num + RoundedArith
+{
+    type Output = IntervalMatrix<T>;
+
+    fn mul(self, other: &IntervalMatrix<T>) -> IntervalMatrix<T> {
+        assert_eq!(self.cols, other.rows);
+
+        let columns = other.transpose();
+        let data = (0..self.rows)
+            .flat_map(|i| (0..other.cols).map(move |j| (i, j)))
+            .map(|(i, j)| dot(self.row(i), columns.row(j)))
+            .collect();
+
+        IntervalMatrix::new(self.rows, other.cols, data)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn setup() -> (IntervalMatrix<f64>, IntervalBox<f64>) {
+        let e = |x| Interval::with_range(x - 0.01, x + 0.01);
+        let a = IntervalMatrix::from_rows(vec![vec![e(4.), e(1.), e(0.)],
+                                               vec![e(1.), e(5.), e(2.)],
+                                               vec![e(0.), e(2.), e(6.)]]);
+        let b = vec![Interval::exact(1.), Interval::exact(2.), Interval::exact(3.)].into();
+
+        (a, b)
+    }
+
+    #[test]
+    fn products() {
+        let a = IntervalMatrix::from_rows(vec![vec![Interval::exact(1.), Interval::exact(2.)],
+                                               vec![Interval::exact(3.), Interval::exact(4.)]]);
+        let x = IntervalBox::new(vec![Interval::with_range(-1., 1.), Interval::exact(1.)]);
+
+        assert_eq!((&a * &x).into_inner(),
+                   vec![Interval::with_range(1., 3.), Interval::with_range(1., 7.)]);
+        assert_eq!(&a * &IntervalMatrix::identity(2), a);
+        assert_eq!(a.transpose()[(0, 1)], Interval::exact(3.));
+        assert_eq!((a.clone() - a.clone())[(1, 1)], Interval::exact(0.));
+        assert_eq!(a.norm_inf(), 7.);
+    }
+
+    #[test]
+    fn solvers_enclose_point_solutions() {
+        let (a, b) = setup();
+        let gauss = a.gauss(&b).unwrap();
+        let krawczyk = a.krawczyk(&b, 20).unwrap();
+
+        // Solutions of the corner systems must be inside both enclosures
+        for &d in &[-0.01, 0.01] {
+            let data = a.data.iter().map(|i| Interval::exact(i.mid() + d)).collect();
+            let x = IntervalMatrix::new(3, 3, data).krawczyk(&b, 20).unwrap();
+            for ((x, g), k) in x.iter().zip(gauss.iter()).zip(krawczyk.iter()) {
+                assert!(g.contains(x.mid()) && k.contains(x.mid()));
+            }
+        }
+        assert!(krawczyk.iter().all(|x| x.width() < 0.1));
+    }
+
+    #[test]
+    fn singular() {
+        let a = IntervalMatrix::new(2, 2, vec![Interval::exact(1.); 4]);
+        let b = vec![Interval::exact(1.); 2].into();
+
+        assert_eq!(a.gauss(&b), None);
+        assert_eq!(a.krawczyk(&b, 5), None);
+
+        let wide = IntervalMatrix::new(2, 2, vec![Interval::with_range(-1., 1.); 4]);
+        assert_eq!(wide.krawczyk(&b, 5), None);
+    }
+
+    #[test]
+    fn display() {
+        let a = IntervalMatrix::new(1, 2, vec![Interval::with_range(0., 1.), Interval::exact(5.)]);
+
+        assert_eq!(format!("{:#.1}", a), "[0.0, 1.0] 1.0  [5.0, 5.0] 0.0");
+        assert_eq!(format!("{}", IntervalMatrix::<f64>::new(0, 0, vec![])), "");
+    }
+}
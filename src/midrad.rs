@@ -0,0 +1,289 @@
+//! Midpoint-radius representation of intervals.
+//!
+//! Interval `[m - r, m + r]` is stored as its midpoint `m` and radius `r`, like balls of ball
+//! arithmetic. Arithmetic works on midpoints with the default rounding and accounts for their
+//! rounding errors in radii, so it is cheaper than endpoint arithmetic and keeps the exact
+//! center when radii are tiny. Conversions between both forms round outward, so every value of
+//! the source is contained in the result. The same holds for conversions from and to value with
+//! standard uncertainty `value ± σ` of measurement libraries, covered `k` times.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use inter::Interval;
+//! use inter::midrad::MidRad;
+//!
+//! let x = Interval::with_range(1., 3.).to_midrad();
+//! assert_eq!((x.mid(), x.rad()), (2., 1.));
+//!
+//! // Product of balls is wider than [9.5, 31.5] of endpoint arithmetic
+//! let y = x * MidRad::new(10., 0.5);
+//! assert_eq!((y.mid(), y.rad()), (20., 11.5));
+//! assert_eq!(y.to_interval(), Interval::with_range(8.5, 31.5));
+//! ```
+
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use num::Float;
+
+use interval::Interval;
+use rounding::RoundedArith;
+use utils::partial_max;
+
+/// Interval given by midpoint and radius
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MidRad<T> {
+    mid: T,
+    rad: T,
+}
+
+impl<T> MidRad<T>
+    where T: Float + RoundedArith
+{
+    /// Interval `[mid - rad, mid + rad]`
+    ///
+    /// # Panics
+    ///
+    /// This will panic if `rad` is negative or NaN, or `mid` is NaN.
+    pub fn new(mid: T, rad: T) -> Self {
+        assert!(rad >= T::zero() && !mid.is_nan(), "invalid radius or midpoint");
+
+        MidRad { mid, rad }
+    }
+
+    /// Single point
+    pub fn exact(mid: T) -> Self {
+        MidRad::new(mid, T::zero())
+    }
+
+    /// Midpoint
+    pub fn mid(&self) -> T {
+        self.mid
+    }
+
+    /// Radius
+    pub fn rad(&self) -> T {
+        self.rad
+    }
+
+    /// Interval containing every value of ball
+    pub fn to_interval(&self) -> Interval<T> {
+        Interval::with_range(self.mid.sub_down(self.rad), self.mid.add_up(self.rad))
+    }
+
+    /// Ball with midpoint `nearest`, enlarged to contain both `down` and `up`
+    fn enclose(nearest: T, down: T, up: T, rad: T) -> Self {
+        if nearest.is_infinite() {
+            // Midpoint overflowed, nothing but its sign is known
+            let max: T = Float::max_value();
+            return MidRad::new(nearest.max(-max).min(max), T::infinity());
+        }
+
+        let error = partial_max(nearest.sub_up(down), up.sub_up(nearest));
+
+        MidRad::new(nearest, rad.add_up(error))
+    }
+}
+
+impl<T> Interval<T>
+    where T: Float + RoundedArith
+{
+    /// Convert to midpoint-radius form, with radius rounded up
+    ///
+    /// # Panics
+    ///
+    /// This will panic if interval is empty.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// let x = Interval::with_range(0.1, 0.2);
+    /// assert!(x.to_midrad().to_interval().superset(&x));
+    /// ```
+    pub fn to_midrad(&self) -> MidRad<T> {
+        assert!(!self.is_empty(), "empty interval has no midpoint");
+
+        let mid = self.mid();
+        MidRad::new(mid, partial_max(mid.sub_up(self.start), self.end.sub_up(mid)))
+    }
+}
+
+impl<T> Interval<T>
+    where T: Float + RoundedArith
+{
+    /// Interval `value ± k σ` covering `k` standard uncertainties `sigma`, rounded outward
+    ///
+    /// # Panics
+    ///
+    /// This will panic if `sigma` or `k` is negative or any argument is NaN.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// // Coverage factor 2 covers about 95% of normally distributed values
+    /// assert_eq!(Interval::from_sigma(9.81, 0.25, 2.), Interval::with_range(9.31, 10.31));
+    /// assert_eq!(Interval::from_sigma(1., 0.5, 3.).to_sigma(3.), (1., 0.5));
+    /// ```
+    pub fn from_sigma(value: T, sigma: T, k: T) -> Self {
+        assert!(sigma >= T::zero() && k >= T::zero(), "invalid uncertainty or coverage factor");
+
+        MidRad::new(value, sigma.mul_up(k)).to_interval()
+    }
+
+    /// Value and standard uncertainty, such that `value ± k σ` covers the whole interval
+    ///
+    /// Uncertainty is rounded up, so converting back with `from_sigma` gives superset.
+    ///
+    /// # Panics
+    ///
+    /// This will panic if interval is empty or `k` is not positive.
+    pub fn to_sigma(&self, k: T) -> (T, T) {
+        assert!(k > T::zero(), "coverage factor is not positive");
+        let ball = self.to_midrad();
+
+        (ball.mid, ball.rad.div_up(k))
+    }
+}
+
+impl<T> From<Interval<T>> for MidRad<T>
+    where T: Float + RoundedArith
+{
+    fn from(interval: Interval<T>) -> Self {
+        interval.to_midrad()
+    }
+}
+
+impl<T> Add for MidRad<T>
+    where T: Float + RoundedArith
+{
+    type Output = MidRad<T>;
+
+    fn add(self, other: Self) -> Self {
+        let (a, b) = (self.mid, other.mid);
+
+        MidRad::enclose(a + b, a.add_down(b), a.add_up(b), self.rad.add_up(other.rad))
+    }
+}
+
+impl<T> Sub for MidRad<T>
+    where T: Float + RoundedArith
+{
+    type Output = MidRad<T>;
+
+    fn sub(self, other: Self) -> Self {
+        self + -other
+    }
+}
+
+impl<T> Mul for MidRad<T>
+    where T: Float + RoundedArith
+{
+    type Output = MidRad<T>;
+
+    /// Product with radius `|a| r_b + r_a |b| + r_a r_b`, where `a` and `b` are midpoints
+    fn mul(self, other: Self) -> Self {
+        let (a, b) = (self.mid, other.mid);
+        // Zero radius of exact value times infinite radius is zero
+        let zero = T::zero();
+        let mul = |x: T, y: T| if x == zero || y == zero { zero } else { x.mul_up(y) };
+        let rad = mul(a.abs(), other.rad)
+            .add_up(mul(self.rad, b.abs()))
+            .add_up(mul(self.rad, other.rad));
+
+        MidRad::enclose(a * b, a.mul_down(b), a.mul_up(b), rad)
+    }
+}
+
+impl<T> Div for MidRad<T>
+    where T: Float + RoundedArith
+{
+    type Output = MidRad<T>;
+
+    /// Divide through endpoint form
+    ///
+    /// # Panics
+    ///
+    /// This will panic if the result is empty, which happens when dividing by zero.
+    fn div(self, other: Self) -> Self {
+        (self.to_interval() / other.to_interval()).to_midrad()
+    }
+}
+
+impl<T> Neg for MidRad<T>
+    where T: Neg<Output = T> + Copy
+{
+    type Output = MidRad<T>;
+
+    fn neg(self) -> Self {
+        MidRad {
+            mid: -self.mid,
+            rad: self.rad,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn conversions() {
+        let x = Interval::with_range(-1., 3.);
+        assert_eq!(x.to_midrad(), MidRad::new(1., 2.));
+        assert_eq!(MidRad::from(x).to_interval(), x);
+        assert_eq!(MidRad::exact(0.5).to_interval(), Interval::exact(0.5));
+
+        // Midpoint of different magnitudes cannot be exact, but the result contains the interval
+        let y = Interval::with_range(1e-20, 1.);
+        assert!(y.to_midrad().to_interval().superset(&y));
+        let unbounded = Interval::with_range(0., f64::INFINITY).to_midrad();
+        assert_eq!(unbounded.rad(), f64::INFINITY);
+        assert_eq!(Interval::<f64>::entire().to_midrad(), MidRad::new(0., f64::INFINITY));
+
+        // Conversions of uncertain values keep every covered value
+        let x = Interval::from_sigma(0.1, 0.03, 3.);
+        assert!(x.contains_all(&[0.1 - 0.09, 0.1 + 0.09]));
+        let (value, sigma) = x.to_sigma(2.);
+        assert!(Interval::from_sigma(value, sigma, 2.).superset(&x));
+        assert_eq!(Interval::from_sigma(1., 0., 2.), Interval::exact(1.));
+        assert_eq!(Interval::from_sigma(1., f64::INFINITY, 1.).sup(), f64::INFINITY);
+        assert!(::std::panic::catch_unwind(|| Interval::from_sigma(1., -1., 1.)).is_err());
+        assert!(::std::panic::catch_unwind(|| x.to_sigma(0.)).is_err());
+    }
+
+    #[test]
+    fn arithmetic() {
+        let a = MidRad::new(0.1, 1e-3);
+        let b = MidRad::new(-3., 0.2);
+
+        // Results enclose results of endpoint arithmetic
+        let (x, y) = (a.to_interval(), b.to_interval());
+        assert!((a + b).to_interval().superset(&(x + y)));
+        assert!((a - b).to_interval().superset(&(x - y)));
+        assert!((a * b).to_interval().superset(&(x * y)));
+        assert!((a / b).to_interval().superset(&(x / y)));
+        assert_eq!(-(-a), a);
+
+        // Rounding error of midpoint goes to radius
+        let third = MidRad::exact(1.) / MidRad::exact(3.);
+        assert!(third.rad() > 0. && third.to_interval().contains(1. / 3.));
+        let sum = MidRad::exact(0.1) + MidRad::exact(0.2);
+        assert!(sum.rad() > 0. && sum.to_interval().contains_all(&[0.1 + 0.2, 0.3]));
+        assert_eq!(MidRad::exact(2.) * MidRad::exact(3.), MidRad::exact(6.));
+
+        let (max, entire) = (MidRad::exact(f64::MAX), MidRad::new(0., f64::INFINITY));
+        assert_eq!(max + max, MidRad::new(f64::MAX, f64::INFINITY));
+        assert_eq!(entire * MidRad::exact(0.), MidRad::exact(0.));
+        assert_eq!((entire * MidRad::exact(2.)).to_interval(), Interval::entire());
+    }
+
+    #[test]
+    #[should_panic(expected = "empty interval has no midpoint")]
+    fn empty() {
+        Interval::<f64>::empty().to_midrad();
+    }
+}
@@ -0,0 +1,224 @@
+//! Modular arithmetic on integer intervals.
+//!
+//! Every operation returns an interval containing `(a ∘ b) mod m` for all `a`, `b` and `m` from
+//! the operands. Remainders are Euclidean, so they always lie in `[0, m)`. When the unreduced
+//! result can wrap around the modulus, the full range `[0, m - 1]` is returned.
+
+use num::{NumCast, PrimInt};
+
+use interval::Interval;
+
+impl<T> Interval<T>
+    where T: PrimInt
+{
+    /// Enclosure of `(a + b) mod m`
+    ///
+    /// # Panics
+    ///
+    /// This will panic if `modulus` is not positive or bounds do not fit in `i128`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// let a = Interval::with_range(10u32, 12);
+    /// let b = Interval::exact(5u32);
+    ///
+    /// assert_eq!(a.add_mod(b, Interval::exact(32)), Interval::with_range(15, 17));
+    /// // Wraps around, so any remainder is possible
+    /// assert_eq!(a.add_mod(b, Interval::exact(16)), Interval::with_range(0, 15));
+    /// ```
+    pub fn add_mod(self, other: Self, modulus: Self) -> Self {
+        let (a, b) = (wide(self), wide(other));
+        let sum = a.0.checked_add(b.0).and_then(|lo| a.1.checked_add(b.1).map(|hi| (lo, hi)));
+
+        reduce(sum, modulus)
+    }
+
+    /// Enclosure of `(a * b) mod m`
+    ///
+    /// # Panics
+    ///
+    /// This will panic if `modulus` is not positive or bounds do not fit in `i128`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// let a = Interval::with_range(3i64, 4);
+    ///
+    /// assert_eq!(a.mul_mod(a, Interval::exact(100)), Interval::with_range(9, 16));
+    /// assert_eq!(a.mul_mod(-a, Interval::exact(100)), Interval::with_range(84, 91));
+    /// ```
+    pub fn mul_mod(self, other: Self, modulus: Self) -> Self {
+        let (a, b) = (wide(self), wide(other));
+        let products = [a.0.checked_mul(b.0),
+                        a.0.checked_mul(b.1),
+                        a.1.checked_mul(b.0),
+                        a.1.checked_mul(b.1)];
+
+        reduce(hull(&products), modulus)
+    }
+
+    /// Enclosure of `a^e mod m`
+    ///
+    /// # Panics
+    ///
+    /// This will panic if `modulus` is not positive, exponent can be negative or bounds do not fit
+    /// in `i128`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// let m = Interval::exact(1_000_000_007u64);
+    ///
+    /// assert_eq!(Interval::exact(2).pow_mod(Interval::exact(1_000_000_006), m),
+    ///            Interval::exact(1));
+    /// assert_eq!(Interval::with_range(2, 3).pow_mod(Interval::with_range(2, 3), m),
+    ///            Interval::with_range(4, 27));
+    /// ```
+    pub fn pow_mod(self, exp: Self, modulus: Self) -> Self {
+        let (base, exp) = (wide(self), wide(exp));
+        let (m, _) = wide(modulus);
+        assert!(m > 0, "modulus must be positive");
+        assert!(exp.0 >= 0, "exponent must be non-negative");
+
+        if base.0 == base.1 && exp.0 == exp.1 && modulus.start == modulus.end {
+            if let Some(value) = pow_mod_exact(base.0, exp.0, m) {
+                return narrow(value, value);
+            }
+        }
+
+        let pow = |b: i128, e: i128| NumCast::from(e).and_then(|e: u32| b.checked_pow(e));
+        let range = if base.0 >= 0 {
+            hull(&[pow(base.0, exp.0), pow(base.0, exp.1), pow(base.1, exp.0), pow(base.1, exp.1)])
+        } else {
+            let magnitude = pow(base.0.abs().max(base.1.abs()), exp.1);
+            magnitude.map(|x| (-x, x))
+        };
+
+        reduce(range, modulus)
+    }
+}
+
+fn wide<T: PrimInt>(x: Interval<T>) -> (i128, i128) {
+    let cast = |x: T| x.to_i128().expect("bound does not fit in i128");
+
+    (cast(x.start), cast(x.end))
+}
+
+fn narrow<T: PrimInt>(start: i128, end: i128) -> Interval<T> {
+    // Reduced values are below the modulus, so they always fit in `T`
+    Interval::with_range(NumCast::from(start).unwrap(), NumCast::from(end).unwrap())
+}
+
+fn hull(values: &[Option<i128>]) -> Option<(i128, i128)> {
+    values.iter().try_fold((i128::MAX, i128::MIN),
+                           |(lo, hi), x| x.map(|x| (lo.min(x), hi.max(x))))
+}
+
+/// Enclose `x mod m` for every `x` in `range` (unbounded when `None`)
+fn reduce<T: PrimInt>(range: Option<(i128, i128)>, modulus: Interval<T>) -> Interval<T> {
+    let (m_lo, m_hi) = wide(modulus);
+    assert!(m_lo > 0, "modulus must be positive");
+
+    let full = narrow(0, m_hi - 1);
+    let (lo, hi) = match range {
+        Some(range) => range,
+        None => return full,
+    };
+
+    if m_lo == m_hi {
+        let (a, b) = (lo.rem_euclid(m_lo), hi.rem_euclid(m_lo));
+        if hi - lo < m_lo && a <= b {
+            narrow(a, b)
+        } else {
+            full
+        }
+    } else if lo >= 0 && hi < m_lo {
+        narrow(lo, hi)
+    } else if lo >= 0 {
+        // Remainder of non-negative number never exceeds the number itself
+        narrow(0, hi.min(m_hi - 1))
+    } else {
+        full
+    }
+}
+
+fn pow_mod_exact(base: i128, exp: i128, m: i128) -> Option<i128> {
+    let mut base = base.rem_euclid(m);
+    let mut exp = exp;
+    let mut acc = 1 % m;
+
+    while exp > 0 {
+        if exp & 1 == 1 {
+            acc = acc.checked_mul(base)? % m;
+        }
+        base = base.checked_mul(base)? % m;
+        exp >>= 1;
+    }
+
+    Some(acc)
+}
+
+#[cfg(test)]
+mod test {
+    use interval::Interval;
+
+    fn brute(a: Interval<i64>, b: Interval<i64>, m: Interval<i64>, f: fn(i64, i64) -> i64)
+             -> Vec<i64> {
+        let mut values = Vec::new();
+        for x in a.start..=a.end {
+            for y in b.start..=b.end {
+                for n in m.start..=m.end {
+                    values.push(f(x, y).rem_euclid(n));
+                }
+            }
+        }
+
+        values
+    }
+
+    #[test]
+    fn encloses_brute_force() {
+        let ranges = [(-7, -3), (-2, 2), (0, 0), (3, 9), (14, 20)];
+        let moduli = [(1, 1), (5, 5), (7, 12), (16, 16)];
+
+        for &(a0, a1) in &ranges {
+            for &(b0, b1) in &ranges {
+                for &(m0, m1) in &moduli {
+                    let (a, b) = (Interval::with_range(a0, a1), Interval::with_range(b0, b1));
+                    let m = Interval::with_range(m0, m1);
+
+                    assert!(a.add_mod(b, m).contains_all(&brute(a, b, m, |x, y| x + y)));
+                    assert!(a.mul_mod(b, m).contains_all(&brute(a, b, m, |x, y| x * y)));
+                    if b0 >= 0 && b1 < 10 {
+                        let pow = |x: i64, y: i64| x.pow(y as u32);
+                        assert!(a.pow_mod(b, m).contains_all(&brute(a, b, m, pow)));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn tight_results() {
+        let m = Interval::exact(10u8);
+
+        assert_eq!(Interval::with_range(12u8, 14).add_mod(Interval::exact(0), m),
+                   Interval::with_range(2, 4));
+        assert_eq!(Interval::with_range(8u8, 12).add_mod(Interval::exact(0), m),
+                   Interval::with_range(0, 9));
+        assert_eq!(Interval::exact(250u8).mul_mod(Interval::exact(250), m), Interval::exact(0));
+
+        let m = Interval::with_range(8, 20);
+        assert_eq!(Interval::with_range(3u8, 5).add_mod(Interval::exact(1), m),
+                   Interval::with_range(4, 6));
+        assert_eq!(Interval::with_range(3u8, 10).add_mod(Interval::exact(1), m),
+                   Interval::with_range(0, 11));
+    }
+}
@@ -0,0 +1,130 @@
+//! Results of operations that split into at most two intervals.
+
+use std::iter::Chain;
+use std::option;
+
+use interval::Interval;
+
+/// One interval or two disjoint intervals
+///
+/// Operations like extended division or complement can produce two pieces. This type keeps them
+/// on the stack instead of allocating a collection. By convention in `Two(a, b)` the interval `a`
+/// lies entirely before `b`.
+///
+/// ## Example
+///
+/// ```rust
+/// use inter::{Interval, OneOrTwo};
+///
+/// let pieces = OneOrTwo::Two(Interval::with_range(-2., -1.), Interval::with_range(1., 2.));
+///
+/// assert_eq!(pieces.len(), 2);
+/// assert_eq!(pieces.hull(), Interval::with_range(-2., 2.));
+/// assert!(!pieces.contains(0.));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OneOrTwo<T> {
+    /// Single interval
+    One(Interval<T>),
+    /// Two disjoint intervals, first before second
+    Two(Interval<T>, Interval<T>),
+}
+
+impl<T> OneOrTwo<T>
+    where T: Copy + PartialOrd
+{
+    /// Number of intervals, either 1 or 2
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        match *self {
+            OneOrTwo::One(_) => 1,
+            OneOrTwo::Two(..) => 2,
+        }
+    }
+
+    /// First (or only) interval
+    pub fn first(&self) -> Interval<T> {
+        match *self {
+            OneOrTwo::One(a) | OneOrTwo::Two(a, _) => a,
+        }
+    }
+
+    /// Second interval if there is one
+    pub fn second(&self) -> Option<Interval<T>> {
+        match *self {
+            OneOrTwo::One(_) => None,
+            OneOrTwo::Two(_, b) => Some(b),
+        }
+    }
+
+    /// Smallest interval containing all pieces
+    pub fn hull(&self) -> Interval<T> {
+        match *self {
+            OneOrTwo::One(a) => a,
+            OneOrTwo::Two(a, b) => a.hull(&b),
+        }
+    }
+
+    /// Check if value fits inside any of pieces
+    pub fn contains(&self, value: T) -> bool {
+        self.iter().any(|i| i.contains(value))
+    }
+
+    /// Iterate over pieces
+    pub fn iter(&self) -> IntoIter<T> {
+        self.into_iter()
+    }
+}
+
+impl<T> From<Interval<T>> for OneOrTwo<T> {
+    fn from(interval: Interval<T>) -> Self {
+        OneOrTwo::One(interval)
+    }
+}
+
+/// Iterator over pieces of `OneOrTwo`
+pub type IntoIter<T> = Chain<option::IntoIter<Interval<T>>, option::IntoIter<Interval<T>>>;
+
+impl<T> IntoIterator for OneOrTwo<T> {
+    type Item = Interval<T>;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        let (a, b) = match self {
+            OneOrTwo::One(a) => (a, None),
+            OneOrTwo::Two(a, b) => (a, Some(b)),
+        };
+
+        Some(a).into_iter().chain(b)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::OneOrTwo;
+    use interval::Interval;
+
+    #[test]
+    fn one() {
+        let a = Interval::with_range(1., 2.);
+        let one = OneOrTwo::from(a);
+
+        assert_eq!(one.len(), 1);
+        assert_eq!(one.hull(), a);
+        assert_eq!(one.second(), None);
+        assert_eq!(one.iter().collect::<Vec<_>>(), vec![a]);
+    }
+
+    #[test]
+    fn two() {
+        let a = Interval::with_range(1., 2.);
+        let b = Interval::with_range(3., 4.);
+        let two = OneOrTwo::Two(a, b);
+
+        assert_eq!(two.first(), a);
+        assert_eq!(two.second(), Some(b));
+        assert_eq!(two.hull(), Interval::with_range(1., 4.));
+        assert!(two.contains(3.5) && !two.contains(2.5));
+        assert_eq!(two.into_iter().collect::<Vec<_>>(), vec![a, b]);
+    }
+}
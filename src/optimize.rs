@@ -0,0 +1,593 @@
+//! Rigorous global minimization with interval branch-and-bound.
+//!
+//! Enclosure of `f` over a box bounds the minimum from below, and value at any point of it
+//! bounds the minimum from above. Boxes whose lower bound exceeds the best upper bound found so
+//! far cannot contain the global minimizer, so they are discarded, while the rest are bisected
+//! until they are small enough. The global minimum and all its minimizers are always contained
+//! in the result, even when the search was stopped early. Inequality constraints are handled
+//! by discarding boxes that certainly violate them.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use inter::Interval;
+//! use inter::optimize::{self, Config};
+//!
+//! let one = Interval::exact(1.);
+//! let domain = vec![Interval::with_range(-4., 4.)].into_iter().collect();
+//! let minimum = optimize::minimize(|x| (x[0] - one).powi(2) - one, &domain, &Config::default());
+//!
+//! assert!(minimum.complete && minimum.value.contains(-1.));
+//! assert!(minimum.minimizers.iter().any(|b| b[0].contains(1.)));
+//! ```
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, VecDeque};
+
+use interval::Interval;
+use interval_box::IntervalBox;
+use polynomial::Polynomial;
+use tribool::Tribool;
+
+/// Order in which boxes are processed
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Queue {
+    /// Box with the lowest lower bound first, which tightens the result the fastest
+    #[default]
+    BestFirst,
+    /// The most recently created box first, which uses the least memory
+    DepthFirst,
+    /// The oldest box first, which explores the whole domain uniformly
+    BreadthFirst,
+}
+
+/// Parameters of `minimize`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Config {
+    /// Boxes with all coordinates narrower than that are not bisected any more
+    pub tolerance: f64,
+    /// Maximal number of bisections
+    pub max_boxes: usize,
+    /// Order of processing boxes
+    pub queue: Queue,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            tolerance: 1e-6,
+            max_boxes: 100_000,
+            queue: Queue::default(),
+        }
+    }
+}
+
+/// Result of `minimize`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Minimum {
+    /// Enclosure of the global minimum
+    pub value: Interval<f64>,
+    /// Boxes that can contain global minimizers, sorted by lower bound of `f` over them
+    pub minimizers: Vec<IntervalBox<f64>>,
+    /// All boxes were narrowed down to `tolerance` before running out of `max_boxes`
+    pub complete: bool,
+}
+
+/// Enclose the global minimum of `f` over `domain`
+///
+/// `f` has to enclose the range of function over every box it is called with, in particular
+/// over degenerate ones used for upper bounds. Returned minimizer boxes together contain every
+/// global minimizer from the `domain`, but some of them may contain none.
+pub fn minimize<F>(f: F, domain: &IntervalBox<f64>, config: &Config) -> Minimum
+    where F: Fn(&[Interval<f64>]) -> Interval<f64>
+{
+    search(f, |_| Tribool::True, domain, config)
+}
+
+/// Enclose the minimum of `f` over points of `domain` satisfying `g(x) ≤ 0` for every `g` from
+/// `constraints`
+///
+/// Every box is tested for feasibility with enclosures of constraints over it. Certainly
+/// infeasible boxes are discarded, and only points that are certainly feasible are used for
+/// upper bounds, so the result encloses the constrained minimum even when no feasible point was
+/// found (then it is unbounded above). Empty result proves there are no feasible points at all.
+/// Minimizer boxes may be undecided, in which case they can also contain infeasible points.
+///
+/// ## Example
+///
+/// ```rust
+/// use inter::{Interval, IntervalBox};
+/// use inter::optimize::{self, Config};
+///
+/// // Minimum of x + y in the unit disc is -√2
+/// let domain = IntervalBox::new(vec![Interval::with_range(-2., 2.); 2]);
+/// let disc = |x: &[Interval<f64>]| x[0].powi(2) + x[1].powi(2) - Interval::exact(1.);
+/// let minimum =
+///     optimize::minimize_constrained(|x| x[0] + x[1], &[disc], &domain, &Config::default());
+///
+/// assert!(minimum.value.contains(-2f64.sqrt()) && minimum.value.width() < 1e-3);
+/// ```
+pub fn minimize_constrained<F, G>(f: F,
+                                  constraints: &[G],
+                                  domain: &IntervalBox<f64>,
+                                  config: &Config)
+                                  -> Minimum
+    where F: Fn(&[Interval<f64>]) -> Interval<f64>,
+          G: Fn(&[Interval<f64>]) -> Interval<f64>
+{
+    let zero = Interval::exact(0.);
+    let feasibility = |x: &[Interval<f64>]| {
+        constraints.iter().fold(Tribool::True, |acc, g| acc & g(x).less_or_equal(&zero))
+    };
+
+    search(f, feasibility, domain, config)
+}
+
+/// Branch-and-bound over boxes that are not certainly infeasible, see `minimize_constrained`
+fn search<F, C>(f: F, feasibility: C, domain: &IntervalBox<f64>, config: &Config) -> Minimum
+    where F: Fn(&[Interval<f64>]) -> Interval<f64>,
+          C: Fn(&[Interval<f64>]) -> Tribool
+{
+    let mut upper = f64::INFINITY;
+    let mut work = Work::new(config.queue);
+    let mut done = Vec::new();
+    let mut bisections = 0;
+    let mut complete = true;
+
+    let status = feasibility(domain);
+    if domain.iter().all(|x| !x.is_empty()) && status.possibly() {
+        work.push(Candidate {
+            lower: f(domain).start,
+            x: domain.clone(),
+            feasible: status.is_true(),
+        });
+    }
+
+    while let Some(candidate) = work.pop() {
+        if candidate.lower > upper {
+            continue;
+        }
+        let x = &candidate.x;
+        let mid: Vec<_> = x.iter().map(|xi| Interval::exact(xi.mid())).collect();
+        if candidate.feasible || feasibility(&mid).is_true() {
+            // Midpoint outside of the domain of `f` gives no upper bound
+            let y = f(&mid);
+            if !y.is_empty() {
+                upper = upper.min(y.sup());
+            }
+        }
+
+        let (i, widest) = x.iter()
+            .enumerate()
+            .fold((0, 0.), |acc, (i, xi)| if xi.width() > acc.1 { (i, xi.width()) } else { acc });
+        if widest <= config.tolerance || x[i].mid() == x[i].start || x[i].mid() == x[i].end {
+            done.push(candidate);
+            continue;
+        }
+        if bisections >= config.max_boxes {
+            done.push(candidate);
+            complete = false;
+            break;
+        }
+        bisections += 1;
+
+        let (a, b) = x[i].bisect();
+        let mut halves = Vec::with_capacity(2);
+        for half in [a, b] {
+            let mut y = x.clone();
+            y[i] = half;
+            // Subboxes of feasible box are feasible
+            let status = if candidate.feasible { Tribool::True } else { feasibility(&y) };
+            if status.possibly() {
+                halves.push(Candidate {
+                    lower: f(&y).start,
+                    x: y,
+                    feasible: status.is_true(),
+                });
+            }
+        }
+        // Depth-first search continues with the more promising half
+        halves.sort();
+        for half in halves {
+            if half.lower <= upper {
+                work.push(half);
+            }
+        }
+    }
+
+    done.extend(work.drain());
+    // Comparison keeps boxes with NaN bounds
+    done.retain(|c| c.lower.partial_cmp(&upper) != Some(Ordering::Greater));
+    done.sort_by(|a, b| a.lower.total_cmp(&b.lower));
+
+    Minimum {
+        value: match done.first() {
+            Some(c) => Interval::with_range(c.lower, upper),
+            None => Interval::empty(),
+        },
+        minimizers: done.into_iter().map(|c| c.x).collect(),
+        complete,
+    }
+}
+
+/// Enclose `max |f(x) - p(x)|` over `domain`, the error of polynomial approximation `p` of `f`
+///
+/// `f` has to enclose the approximated function over every interval it is called with. Upper
+/// bound of the result is a certified error constant of `p`, but it does not include rounding
+/// errors of evaluating `p` in floating-point, which have to be bounded separately. Overestimation
+/// of `f - p` over narrow intervals is proportional to the difference of their derivatives,
+/// so the bound is tight when `tolerance` is small compared to the error itself.
+///
+/// ## Example
+///
+/// ```rust
+/// use inter::Interval;
+/// use inter::optimize::{self, Config};
+/// use inter::polynomial::Polynomial;
+///
+/// // Taylor polynomial of exp, with the largest error e - 5/2 at 1
+/// let p = Polynomial::from_coeffs(&[1., 1., 0.5]);
+/// let error = optimize::max_error(|x| x.exp(), &p, Interval::with_range(0., 1.),
+///                                 &Config::default());
+///
+/// assert!(error.contains(1f64.exp() - 2.5) && error.sup() < 0.21829);
+/// ```
+pub fn max_error<F>(f: F, p: &Polynomial<f64>, domain: Interval<f64>, config: &Config)
+                    -> Interval<f64>
+    where F: Fn(Interval<f64>) -> Interval<f64>
+{
+    let domain = IntervalBox::new(vec![domain]);
+
+    -minimize(|x| -(f(x[0]) - p.eval(x[0])).abs(), &domain, config).value
+}
+
+/// Result of `pareto`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Pareto {
+    /// Boxes that can contain Pareto optimal points
+    pub boxes: Vec<IntervalBox<f64>>,
+    /// All boxes were narrowed down to `tolerance` before running out of `max_boxes`
+    pub complete: bool,
+}
+
+/// Enclose Pareto optimal points of `domain` for minimizing every objective returned by `f`
+///
+/// `f` has to enclose the ranges of all objectives over every box it is called with. Values at
+/// midpoints of boxes give points whose objectives are known from above, and a box is
+/// discarded when one of them is certainly better in every objective (and strictly better in
+/// one) than all points of the box. Remaining boxes together contain the whole Pareto set.
+///
+/// Pareto set is usually a curve or a surface, so the number of boxes grows quickly with
+/// decreasing `tolerance`, and every box is compared with all known points. That makes the
+/// search much more expensive than `minimize` with the same `tolerance`.
+///
+/// ## Example
+///
+/// ```rust
+/// use inter::{Interval, IntervalBox};
+/// use inter::optimize::{self, Config};
+///
+/// // Trade-off between distance from 0 and from 2
+/// let two = Interval::exact(2.);
+/// let f = |x: &[Interval<f64>]| vec![x[0].powi(2), (x[0] - two).powi(2)];
+/// let domain = IntervalBox::new(vec![Interval::with_range(-5., 5.)]);
+/// let config = Config { tolerance: 1e-2, ..Config::default() };
+/// let pareto = optimize::pareto(f, &domain, &config);
+///
+/// let hull = pareto.boxes.iter().fold(Interval::empty(), |acc, b| acc.hull(&b[0]));
+/// assert!(hull.contains_all(&[0., 2.]) && hull.subset(&Interval::with_range(-0.02, 2.02)));
+/// ```
+pub fn pareto<F>(f: F, domain: &IntervalBox<f64>, config: &Config) -> Pareto
+    where F: Fn(&[Interval<f64>]) -> Vec<Interval<f64>>
+{
+    // Objectives of known points, kept mutually non-dominated
+    let mut uppers: Vec<Vec<f64>> = Vec::new();
+    let mut work = Work::new(config.queue);
+    let mut done = Vec::new();
+    let mut bisections = 0;
+    let mut complete = true;
+
+    // Best-first order uses sum of lower bounds of objectives
+    let enclose = |x: IntervalBox<f64>| {
+        let lower = f(&x).iter().map(|y| y.start).sum();
+        Candidate {
+            lower,
+            x,
+            feasible: true,
+        }
+    };
+    let dominated = |x: &[Interval<f64>], uppers: &[Vec<f64>]| {
+        let lower: Vec<_> = f(x).iter().map(|y| y.start).collect();
+        uppers.iter().any(|u| dominates(u, &lower))
+    };
+
+    if domain.iter().all(|x| !x.is_empty()) {
+        work.push(enclose(domain.clone()));
+    }
+
+    while let Some(candidate) = work.pop() {
+        let x = &candidate.x;
+        let mid: Vec<_> = x.iter().map(|xi| Interval::exact(xi.mid())).collect();
+        let values = f(&mid);
+        // Midpoint outside of the domain of some objective is not a known point
+        if values.iter().all(|y| !y.is_empty()) {
+            let upper: Vec<_> = values.iter().map(|y| y.sup()).collect();
+            if !uppers.iter().any(|u| dominates(u, &upper) || *u == upper) {
+                uppers.retain(|u| !dominates(&upper, u));
+                uppers.push(upper);
+            }
+        }
+        if dominated(x, &uppers) {
+            continue;
+        }
+
+        let (i, widest) = x.iter()
+            .enumerate()
+            .fold((0, 0.), |acc, (i, xi)| if xi.width() > acc.1 { (i, xi.width()) } else { acc });
+        if widest <= config.tolerance || x[i].mid() == x[i].start || x[i].mid() == x[i].end {
+            done.push(candidate);
+            continue;
+        }
+        if bisections >= config.max_boxes {
+            done.push(candidate);
+            complete = false;
+            break;
+        }
+        bisections += 1;
+
+        let (a, b) = x[i].bisect();
+        for half in [a, b] {
+            let mut y = x.clone();
+            y[i] = half;
+            work.push(enclose(y));
+        }
+    }
+
+    done.extend(work.drain());
+    let boxes = done.into_iter()
+        .map(|c| c.x)
+        .filter(|x| !dominated(x, &uppers))
+        .collect();
+
+    Pareto { boxes, complete }
+}
+
+/// Check if objectives bounded from above by `a` are better than ones bounded from below by `b`
+/// in every component, and strictly in at least one
+fn dominates(a: &[f64], b: &[f64]) -> bool {
+    a.iter().zip(b).all(|(a, b)| a <= b) && a.iter().zip(b).any(|(a, b)| a < b)
+}
+
+/// Box with lower bound of `f` over it
+struct Candidate {
+    lower: f64,
+    x: IntervalBox<f64>,
+    /// Every point of the box is feasible
+    feasible: bool,
+}
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    /// Reversed, so that `BinaryHeap` pops the lowest bound first
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.lower.total_cmp(&self.lower)
+    }
+}
+
+/// Boxes waiting for processing
+enum Work {
+    Heap(BinaryHeap<Candidate>),
+    Deque(VecDeque<Candidate>, Queue),
+}
+
+impl Work {
+    fn new(order: Queue) -> Self {
+        match order {
+            Queue::BestFirst => Work::Heap(BinaryHeap::new()),
+            _ => Work::Deque(VecDeque::new(), order),
+        }
+    }
+
+    fn push(&mut self, candidate: Candidate) {
+        match *self {
+            Work::Heap(ref mut heap) => heap.push(candidate),
+            Work::Deque(ref mut deque, _) => deque.push_back(candidate),
+        }
+    }
+
+    fn pop(&mut self) -> Option<Candidate> {
+        match *self {
+            Work::Heap(ref mut heap) => heap.pop(),
+            Work::Deque(ref mut deque, Queue::DepthFirst) => deque.pop_back(),
+            Work::Deque(ref mut deque, _) => deque.pop_front(),
+        }
+    }
+
+    fn drain(self) -> Vec<Candidate> {
+        match self {
+            Work::Heap(heap) => heap.into_vec(),
+            Work::Deque(deque, _) => deque.into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use testfns;
+
+    type Constraint = dyn Fn(&[Interval<f64>]) -> Interval<f64>;
+
+    #[test]
+    fn test_functions() {
+        let config = Config {
+            tolerance: 1e-4,
+            max_boxes: 5000,
+            ..Config::default()
+        };
+
+        // Result encloses the minimum even if search was not completed
+        for function in testfns::all() {
+            let minimum = minimize(|x| function.eval(x), &function.domain(), &config);
+
+            assert!(minimum.value.intersection(&function.minimum()).is_some(),
+                    "{}",
+                    function.name());
+            for point in function.minimizers() {
+                assert!(minimum.minimizers
+                            .iter()
+                            .any(|b| b.iter().zip(point.iter()).all(|(x, &p)| x.contains(p))),
+                        "{}",
+                        function.name());
+            }
+        }
+    }
+
+    #[test]
+    fn queue_strategies() {
+        let f = |x: &[Interval<f64>]| x[0].sin() + x[1].powi(2);
+        let domain = IntervalBox::new(vec![Interval::with_range(0., 6.),
+                                           Interval::with_range(-1., 1.)]);
+
+        for &queue in &[Queue::BestFirst, Queue::DepthFirst, Queue::BreadthFirst] {
+            let config = Config {
+                tolerance: 1e-5,
+                max_boxes: 10_000,
+                queue,
+            };
+            let minimum = minimize(f, &domain, &config);
+
+            assert!(minimum.complete);
+            assert!(minimum.value.contains(-1.) && minimum.value.width() < 1e-6);
+            assert!(minimum.minimizers.iter().all(|b| (b[0].mid() - 4.712).abs() < 1e-2));
+        }
+    }
+
+    #[test]
+    fn early_stop() {
+        let f = |x: &[Interval<f64>]| x[0].powi(2);
+        let domain = IntervalBox::new(vec![Interval::with_range(-1., 1.)]);
+        let config = Config {
+            max_boxes: 2,
+            ..Config::default()
+        };
+        let minimum = minimize(f, &domain, &config);
+
+        assert!(!minimum.complete && minimum.value.contains(0.));
+        assert!(minimum.minimizers.iter().any(|b| b[0].contains(0.)));
+
+        let empty = minimize(f, &IntervalBox::new(vec![Interval::empty()]), &config);
+        assert!(empty.value.is_empty() && empty.minimizers.is_empty());
+    }
+
+    #[test]
+    fn partial_domain() {
+        // Midpoint of the first box is outside of the domain of sqrt
+        let domain = IntervalBox::new(vec![Interval::with_range(-3., 1.)]);
+        let minimum = minimize(|x| x[0].sqrt(), &domain, &Config::default());
+
+        assert!(minimum.value.contains(0.));
+        assert!(!minimum.minimizers.is_empty());
+        assert!(minimum.minimizers.iter().any(|b| b[0].contains(0.)));
+    }
+
+    #[test]
+    fn approximation_error() {
+        let domain = Interval::with_range(-0.5, 0.5);
+        let p = Polynomial::from_coeffs(&[0., 1., 0., -1. / 6.]);
+        let error = max_error(|x| x.sin(), &p, domain, &Config::default());
+        // Error of Taylor polynomial grows towards the ends
+        let exact = 0.5f64.sin() - (0.5 - 0.125 / 6.);
+
+        assert!(error.contains(exact) && error.width() < 1e-5);
+        assert!(max_error(|x| x.sin(), &p, Interval::exact(0.), &Config::default()).sup() < 1e-300);
+        // Uncertain coefficients are included in the error
+        let uncertain = Polynomial::new(vec![Interval::with_range(-1e-3, 1e-3)]);
+        let error = max_error(|_| Interval::exact(0.), &uncertain, domain, &Config::default());
+        assert_eq!(error.sup(), 1e-3);
+    }
+
+    #[test]
+    fn pareto_set() {
+        let config = Config {
+            tolerance: 1e-2,
+            ..Config::default()
+        };
+        let one = Interval::exact(1.);
+        // Pareto set of distances from (0, 0) and (1, 0) is the segment between them
+        let f = |x: &[Interval<f64>]| {
+            vec![x[0].powi(2) + x[1].powi(2), (x[0] - one).powi(2) + x[1].powi(2)]
+        };
+        let domain = IntervalBox::new(vec![Interval::with_range(-2., 2.); 2]);
+        let result = pareto(f, &domain, &config);
+
+        assert!(result.complete);
+        for k in 0..=10 {
+            let t = k as f64 / 10.;
+            assert!(result.boxes.iter().any(|b| b[0].contains(t) && b[1].contains(0.)));
+        }
+        assert!(result.boxes.iter().all(|b| b[0].mid() > -0.05 && b[0].mid() < 1.05));
+        assert!(result.boxes.iter().all(|b| b[1].mid().abs() < 0.1));
+
+        // Single objective gives minimizers
+        let line = IntervalBox::new(vec![Interval::with_range(-2., 2.)]);
+        let single = pareto(|x| vec![(x[0] - one).powi(2)], &line, &config);
+        assert!(single.boxes.iter().all(|b| (b[0].mid() - 1.).abs() < 0.05));
+        assert!(pareto(f, &IntervalBox::new(vec![Interval::empty(); 2]), &config).boxes.is_empty());
+
+        // Midpoint of the first box is outside of the domain of sqrt
+        let partial = IntervalBox::new(vec![Interval::with_range(-3., 1.)]);
+        let result = pareto(|x| vec![x[0].sqrt()], &partial, &config);
+        assert!(result.boxes.iter().any(|b| b[0].contains(0.)));
+    }
+
+    #[test]
+    fn constraints() {
+        let config = Config {
+            tolerance: 1e-5,
+            ..Config::default()
+        };
+        let f = |x: &[Interval<f64>]| x[0].powi(2) + x[1].powi(2);
+        let domain = IntervalBox::new(vec![Interval::with_range(-2., 2.); 2]);
+
+        // Constraint x + y ≥ 1 moves minimum to (½, ½)
+        let above = |x: &[Interval<f64>]| Interval::exact(1.) - x[0] - x[1];
+        let minimum = minimize_constrained(f, &[above], &domain, &config);
+        assert!(minimum.value.contains(0.5) && minimum.value.width() < 1e-4);
+        assert!(minimum.minimizers.iter().all(|b| (b[0].mid() - 0.5).abs() < 1e-2));
+
+        // Without constraints it is the plain minimization
+        let none: &[&Constraint] = &[];
+        assert_eq!(minimize_constrained(f, none, &domain, &config),
+                   minimize(f, &domain, &config));
+
+        // Constraints of different types, where x ≥ 3 cannot be satisfied
+        let right = |x: &[Interval<f64>]| Interval::exact(3.) - x[0];
+        let constraints: [&Constraint; 2] = [&above, &right];
+        let infeasible = minimize_constrained(f, &constraints, &domain, &config);
+        assert!(infeasible.value.is_empty() && infeasible.minimizers.is_empty());
+
+        // Feasible set of a single point has no feasible box nor midpoint, so there is no
+        // upper bound
+        let point = |x: &[Interval<f64>]| (x[0] - Interval::exact(1. / 3.)).powi(2);
+        let config = Config {
+            max_boxes: 1000,
+            ..config
+        };
+        let minimum = minimize_constrained(f, &[point], &domain, &config);
+        assert!(minimum.value.contains(1. / 9.) && minimum.value.sup() == f64::INFINITY);
+    }
+}
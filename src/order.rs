@@ -0,0 +1,442 @@
+//! Checking whether intervals can be ordered with certainty.
+//!
+//! Ranking uncertain values is safe only when every two of them are certainly ordered, that is
+//! their intervals are disjoint. Otherwise some pair may be swapped in reality. Exact relation of
+//! two intervals, like whether they meet or one contains the other, is given by
+//! `Interval::overlap`. `OrderedInterval` orders intervals totally by their bounds instead, so
+//! they can be keys of heaps, maps and sets.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use inter::Interval;
+//! use inter::order::certain_order;
+//!
+//! let scores = [Interval::with_range(2., 3.), Interval::with_range(0., 1.),
+//!               Interval::with_range(5., 6.)];
+//! assert_eq!(certain_order(&scores), Ok(vec![1, 0, 2]));
+//!
+//! let scores = [Interval::with_range(2., 3.), Interval::with_range(0., 1.),
+//!               Interval::with_range(0.5, 1.5)];
+//! assert_eq!(certain_order(&scores), Err((1, 2)));
+//! ```
+
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+
+use num::Float;
+
+use interval::Interval;
+
+/// Indices of `intervals` in certainly increasing order, or pair that cannot be ordered
+///
+/// Every interval has to strictly precede the next one. Otherwise returns indices (in increasing
+/// order) of the first two neighbouring intervals, in order of lower bounds, that overlap.
+/// Intervals with NaN bounds are never ordered.
+pub fn certain_order<T>(intervals: &[Interval<T>]) -> Result<Vec<usize>, (usize, usize)>
+    where T: PartialOrd + Copy
+{
+    let mut order: Vec<usize> = (0..intervals.len()).collect();
+    order.sort_by(|&i, &j| {
+        intervals[i].start.partial_cmp(&intervals[j].start).unwrap_or(Ordering::Equal)
+    });
+
+    for pair in order.windows(2) {
+        let (i, j) = (pair[0], pair[1]);
+        if !precedes(&intervals[i], &intervals[j]) {
+            return Err((i.min(j), i.max(j)));
+        }
+    }
+
+    Ok(order)
+}
+
+/// Check if `intervals` are already in certainly increasing order
+///
+/// Returns index of the first interval that does not strictly precede the next one.
+///
+/// ## Example
+///
+/// ```rust
+/// use inter::Interval;
+/// use inter::order::certainly_sorted;
+///
+/// let a = [Interval::with_range(0., 1.), Interval::with_range(1., 2.)];
+///
+/// assert_eq!(certainly_sorted(&a[..1]), Ok(()));
+/// assert_eq!(certainly_sorted(&a), Err(0));
+/// ```
+pub fn certainly_sorted<T>(intervals: &[Interval<T>]) -> Result<(), usize>
+    where T: PartialOrd + Copy
+{
+    match intervals.windows(2).position(|pair| !precedes(&pair[0], &pair[1])) {
+        Some(i) => Err(i),
+        None => Ok(()),
+    }
+}
+
+/// Partition of items by whether they belong to the `k` largest
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TopK {
+    /// Indices of items that are among `k` largest whatever the exact values are
+    pub selected: Vec<usize>,
+    /// Indices of items that have at least `k` items certainly greater
+    pub rejected: Vec<usize>,
+    /// Indices of all other items
+    pub undecided: Vec<usize>,
+}
+
+/// Find which of `scores` are certainly, possibly or certainly not among `k` largest
+///
+/// Item is selected when fewer than `k` other items can be greater or equal to it, so it is in
+/// top `k` even if ties are broken against it. All lists are in increasing order of indices.
+///
+/// ## Example
+///
+/// ```rust
+/// use inter::Interval;
+/// use inter::order::select_topk;
+///
+/// let scores = [Interval::with_range(9., 10.), Interval::with_range(5., 8.),
+///               Interval::with_range(6., 7.), Interval::with_range(1., 2.)];
+/// let top = select_topk(&scores, 2);
+///
+/// assert_eq!(top.selected, vec![0]);
+/// assert_eq!(top.undecided, vec![1, 2]);
+/// assert_eq!(top.rejected, vec![3]);
+/// ```
+pub fn select_topk<T>(scores: &[Interval<T>], k: usize) -> TopK
+    where T: PartialOrd + Copy
+{
+    let sorted = |bound: fn(&Interval<T>) -> T| {
+        let mut bounds: Vec<T> = scores.iter().map(bound).collect();
+        bounds.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+        bounds
+    };
+    let starts = sorted(|x| x.start);
+    let ends = sorted(|x| x.end);
+    let n = scores.len();
+
+    let mut top = TopK::default();
+    for (i, score) in scores.iter().enumerate() {
+        // Item itself is always counted among those that can be greater or equal
+        let possibly_greater = n - ends.partition_point(|&end| end < score.start) - 1;
+        let certainly_greater = n - starts.partition_point(|&start| start <= score.end);
+
+        if possibly_greater < k {
+            top.selected.push(i);
+        } else if certainly_greater >= k {
+            top.rejected.push(i);
+        } else {
+            top.undecided.push(i);
+        }
+    }
+
+    top
+}
+
+/// Relation of two intervals, after the `overlap` operation of IEEE 1788
+///
+/// For non-empty intervals these are the thirteen relations of Allen's interval algebra, with
+/// degenerate intervals assigned as in the standard, so exactly one of them holds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Overlap {
+    /// Both intervals are empty
+    BothEmpty,
+    /// Only the first interval is empty
+    FirstEmpty,
+    /// Only the second interval is empty
+    SecondEmpty,
+    /// `a₂ < b₁`
+    Before,
+    /// `a₁ < a₂ = b₁ < b₂`
+    Meets,
+    /// `a₁ < b₁ < a₂ < b₂`
+    Overlaps,
+    /// `a₁ = b₁` and `a₂ < b₂`
+    Starts,
+    /// `b₁ < a₁` and `a₂ < b₂`
+    ContainedBy,
+    /// `b₁ < a₁` and `a₂ = b₂`
+    Finishes,
+    /// `a₁ = b₁` and `a₂ = b₂`
+    Equals,
+    /// `a₁ < b₁` and `a₂ = b₂`
+    FinishedBy,
+    /// `a₁ < b₁` and `b₂ < a₂`
+    Contains,
+    /// `a₁ = b₁` and `b₂ < a₂`
+    StartedBy,
+    /// `b₁ < a₁ < b₂ < a₂`
+    OverlappedBy,
+    /// `b₁ < b₂ = a₁ < a₂`
+    MetBy,
+    /// `b₂ < a₁`
+    After,
+}
+
+impl Overlap {
+    /// Relation with intervals swapped, so `b.overlap(&a) == a.overlap(&b).converse()`
+    pub fn converse(self) -> Overlap {
+        use self::Overlap::*;
+
+        match self {
+            BothEmpty => BothEmpty,
+            FirstEmpty => SecondEmpty,
+            SecondEmpty => FirstEmpty,
+            Before => After,
+            Meets => MetBy,
+            Overlaps => OverlappedBy,
+            Starts => StartedBy,
+            ContainedBy => Contains,
+            Finishes => FinishedBy,
+            Equals => Equals,
+            FinishedBy => Finishes,
+            Contains => ContainedBy,
+            StartedBy => Starts,
+            OverlappedBy => Overlaps,
+            MetBy => Meets,
+            After => Before,
+        }
+    }
+}
+
+impl<T> Interval<T>
+    where T: PartialOrd
+{
+    /// Relation of interval `a` to `b`
+    ///
+    /// Intervals with NaN bounds are empty.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    /// use inter::order::Overlap;
+    ///
+    /// let a = Interval::with_range(1., 2.);
+    ///
+    /// assert_eq!(a.overlap(&Interval::with_range(2., 3.)), Overlap::Meets);
+    /// assert_eq!(a.overlap(&Interval::with_range(0., 2.)), Overlap::Finishes);
+    /// assert_eq!(a.overlap(&Interval::exact(1.)), Overlap::StartedBy);
+    /// assert_eq!(a.overlap(&Interval::empty()), Overlap::SecondEmpty);
+    /// ```
+    pub fn overlap(&self, other: &Interval<T>) -> Overlap {
+        use self::Ordering::*;
+
+        match (self.is_empty(), other.is_empty()) {
+            (true, true) => return Overlap::BothEmpty,
+            (true, false) => return Overlap::FirstEmpty,
+            (false, true) => return Overlap::SecondEmpty,
+            (false, false) => {}
+        }
+
+        // Bounds of non-empty intervals are comparable
+        let cmp = |x: &T, y: &T| x.partial_cmp(y).unwrap();
+        match (cmp(&self.start, &other.start), cmp(&self.end, &other.end)) {
+            (Equal, Equal) => Overlap::Equals,
+            (Equal, Less) => Overlap::Starts,
+            (Equal, Greater) => Overlap::StartedBy,
+            (Greater, Equal) => Overlap::Finishes,
+            (Less, Equal) => Overlap::FinishedBy,
+            (Greater, Less) => Overlap::ContainedBy,
+            (Less, Greater) => Overlap::Contains,
+            (Less, Less) => {
+                match cmp(&self.end, &other.start) {
+                    Less => Overlap::Before,
+                    Equal => Overlap::Meets,
+                    Greater => Overlap::Overlaps,
+                }
+            }
+            (Greater, Greater) => {
+                match cmp(&self.start, &other.end) {
+                    Greater => Overlap::After,
+                    Equal => Overlap::MetBy,
+                    Less => Overlap::OverlappedBy,
+                }
+            }
+        }
+    }
+}
+
+/// Interval without NaN bounds, ordered lexicographically by lower and then upper bound
+///
+/// Order says nothing about values of intervals, it only makes intervals usable as keys of
+/// `BinaryHeap`, `BTreeMap` or `HashMap`. Bounds `0` and `-0` are equal, as they are for intervals.
+///
+/// ## Example
+///
+/// ```rust
+/// use std::collections::BTreeSet;
+/// use inter::Interval;
+/// use inter::order::OrderedInterval;
+///
+/// let boxes: BTreeSet<_> = [(2., 3.), (0., 5.), (0., 1.), (2., 3.)]
+///     .iter()
+///     .map(|&x| OrderedInterval::new(Interval::from(x)).unwrap())
+///     .collect();
+/// let boxes: Vec<Interval<f64>> = boxes.into_iter().map(Interval::from).collect();
+///
+/// assert_eq!(boxes, [Interval::with_range(0., 1.), Interval::with_range(0., 5.),
+///                    Interval::with_range(2., 3.)]);
+/// assert_eq!(OrderedInterval::new(Interval::from(f64::NAN)), None);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct OrderedInterval<T>(Interval<T>);
+
+impl<T> OrderedInterval<T>
+    where T: PartialOrd + Copy
+{
+    /// Wrap `x`, or `None` when some of its bounds is NaN
+    pub fn new(x: Interval<T>) -> Option<Self> {
+        let comparable = |x: &T| x.partial_cmp(x).is_some();
+        if comparable(&x.start) && comparable(&x.end) {
+            Some(OrderedInterval(x))
+        } else {
+            None
+        }
+    }
+
+    /// Wrapped interval
+    pub fn interval(&self) -> Interval<T> {
+        self.0
+    }
+}
+
+impl<T> From<OrderedInterval<T>> for Interval<T> {
+    fn from(x: OrderedInterval<T>) -> Self {
+        x.0
+    }
+}
+
+impl<T> PartialEq for OrderedInterval<T>
+    where T: PartialOrd
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T> Eq for OrderedInterval<T> where T: PartialOrd {}
+
+impl<T> PartialOrd for OrderedInterval<T>
+    where T: PartialOrd
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for OrderedInterval<T>
+    where T: PartialOrd
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Bounds are never NaN
+        let cmp = |x: &T, y: &T| x.partial_cmp(y).unwrap();
+
+        cmp(&self.0.start, &other.0.start).then_with(|| cmp(&self.0.end, &other.0.end))
+    }
+}
+
+impl<T> Hash for OrderedInterval<T>
+    where T: Float
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // Both zeros are equal, so they have to hash the same
+        let bits = |x: T| if x == T::zero() { T::zero() } else { x }.integer_decode();
+
+        bits(self.0.start).hash(state);
+        bits(self.0.end).hash(state);
+    }
+}
+
+/// Check if every value of `a` is less than every value of `b`, false for NaN bounds
+fn precedes<T: PartialOrd>(a: &Interval<T>, b: &Interval<T>) -> bool {
+    a.end.partial_cmp(&b.start) == Some(Ordering::Less)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn ordering() {
+        let i = Interval::with_range;
+
+        assert_eq!(certain_order::<f64>(&[]), Ok(vec![]));
+        assert_eq!(certain_order(&[i(3, 4), i(1, 2), i(5, 5), i(-1, 0)]), Ok(vec![3, 1, 0, 2]));
+        assert_eq!(certain_order(&[i(3, 4), i(1, 2), i(4, 5)]), Err((0, 2)));
+        let nan = Interval::from(f64::NAN);
+        assert_eq!(certain_order(&[Interval::with_range(0., 1.), nan]), Err((0, 1)));
+        assert_eq!(certainly_sorted(&[i(0, 1), i(2, 3), i(3, 4)]), Err(1));
+        assert_eq!(certainly_sorted(&[i(0, 1), i(2, 3)]), Ok(()));
+    }
+
+    #[test]
+    fn top_k() {
+        let i = Interval::with_range;
+        let scores = [i(3, 3), i(3, 3), i(1, 2), i(0, 5)];
+
+        let top = select_topk(&scores, 1);
+        assert_eq!((top.selected, top.rejected), (vec![], vec![2]));
+
+        let top = select_topk(&scores, 3);
+        assert_eq!((top.selected, top.undecided), (vec![0, 1], vec![2, 3]));
+
+        assert_eq!(select_topk(&scores, 4).selected, vec![0, 1, 2, 3]);
+        assert_eq!(select_topk(&scores, 0).rejected, vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn overlap() {
+        let i = Interval::with_range;
+        let b = i(2, 4);
+        let cases = [(i(0, 1), Overlap::Before),
+                     (i(0, 2), Overlap::Meets),
+                     (i(0, 3), Overlap::Overlaps),
+                     (i(2, 3), Overlap::Starts),
+                     (i(3, 3), Overlap::ContainedBy),
+                     (i(3, 4), Overlap::Finishes),
+                     (i(2, 4), Overlap::Equals),
+                     (i(0, 4), Overlap::FinishedBy),
+                     (i(0, 5), Overlap::Contains),
+                     (i(2, 5), Overlap::StartedBy),
+                     (i(3, 5), Overlap::OverlappedBy),
+                     (i(4, 5), Overlap::MetBy),
+                     (i(5, 6), Overlap::After),
+                     // Points at bounds start or finish, they never meet
+                     (i(2, 2), Overlap::Starts),
+                     (i(4, 4), Overlap::Finishes)];
+
+        for &(a, relation) in &cases {
+            assert_eq!(a.overlap(&b), relation, "{:?}", a);
+            assert_eq!(b.overlap(&a), relation.converse(), "{:?}", a);
+        }
+
+        let empty = Interval::<f64>::empty();
+        assert_eq!(empty.overlap(&empty), Overlap::BothEmpty);
+        assert_eq!(empty.overlap(&Interval::exact(0.)), Overlap::FirstEmpty);
+        assert_eq!(Interval::from(f64::NAN).overlap(&empty), Overlap::BothEmpty);
+        assert_eq!(Interval::exact(1.).overlap(&Interval::exact(1.)), Overlap::Equals);
+    }
+
+    #[test]
+    fn ordered() {
+        let i = |a: f64, b: f64| OrderedInterval::new(Interval::with_range(a, b)).unwrap();
+        let mut intervals = vec![i(1., 2.), i(0., 3.), i(1., 1.5), i(-0., 1.), i(0., 1.)];
+        intervals.sort();
+        assert_eq!(intervals, [i(0., 1.), i(0., 1.), i(0., 3.), i(1., 1.5), i(1., 2.)]);
+
+        let mut set = ::std::collections::HashSet::new();
+        assert!(set.insert(i(0., 1.)) && !set.insert(i(-0., 1.)) && set.insert(i(0., 2.)));
+
+        // Heap pops the greatest lower bound first
+        let mut heap: ::std::collections::BinaryHeap<_> = intervals.into_iter().collect();
+        assert_eq!(heap.pop().map(Interval::from), Some(Interval::with_range(1., 2.)));
+
+        let empty = OrderedInterval::new(Interval::<f64>::empty()).unwrap();
+        assert_eq!(empty.interval(), Interval::empty());
+        assert!(empty > i(0., 1.));
+        assert_eq!(OrderedInterval::new(Interval { start: 0., end: f64::NAN }), None);
+    }
+}
@@ -0,0 +1,111 @@
+//! Evaluation of inclusion functions over many boxes in parallel.
+//!
+//! Boxes are split into contiguous chunks, one per available core, and evaluated on scoped
+//! threads. New threads inherit the rounding mode of the spawning thread on some platforms and
+//! start with the default strategy of evaluation, so every worker switches to rounding to
+//! nearest, which `RoundedArith` expects, and to the strategy in effect on calling thread.
+//! Results are returned in the order of boxes.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use inter::Interval;
+//! use inter::parallel;
+//!
+//! let boxes: Vec<Vec<Interval<f64>>> = (0..100)
+//!     .map(|i| vec![Interval::with_range(i as f64, i as f64 + 1.)])
+//!     .collect();
+//! let squares = parallel::eval_many(|x: &[Interval<f64>]| x[0].sqr(), &boxes);
+//!
+//! assert_eq!(squares[3], Interval::with_range(9., 16.));
+//! ```
+
+use std::thread;
+
+#[cfg(feature = "libc")]
+use rounding::Rounding;
+use interval::Interval;
+use strategy::Strategy;
+
+/// Evaluate `f` over every box of `boxes`, using all available cores
+///
+/// # Panics
+///
+/// This will panic if `f` panics for any box.
+pub fn eval_many<T, F, B>(f: F, boxes: &[B]) -> Vec<Interval<T>>
+    where T: Send,
+          F: Fn(&[Interval<T>]) -> Interval<T> + Sync,
+          B: AsRef<[Interval<T>]> + Sync
+{
+    let threads = thread::available_parallelism().map_or(1, |n| n.get());
+    let chunk = boxes.len().div_ceil(threads).max(1);
+    let strategy = Strategy::current();
+    let f = &f;
+
+    thread::scope(|scope| {
+        let workers: Vec<_> = boxes.chunks(chunk)
+            .map(|chunk| {
+                scope.spawn(move || {
+                    #[cfg(feature = "libc")]
+                    let _nearest = Rounding::ToNearest.scoped();
+
+                    strategy.execute(|| chunk.iter().map(|b| f(b.as_ref())).collect::<Vec<_>>())
+                })
+            })
+            .collect();
+
+        workers.into_iter()
+            .flat_map(|worker| worker.join().expect("evaluation panicked"))
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use strategy::Value;
+
+    fn boxes() -> Vec<Vec<Interval<f64>>> {
+        (0..1000)
+            .map(|i| {
+                let t = i as f64 / 1000.;
+                vec![Interval::with_range(t - 0.1, t), Interval::with_range(0.1, 0.2 + t)]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn keeps_order() {
+        let boxes = boxes();
+        let f = |x: &[Interval<f64>]| (x[0] * x[1]).exp() - x[1].sin();
+
+        let sequential: Vec<_> = boxes.iter().map(|b| f(b)).collect();
+        assert_eq!(eval_many(f, &boxes), sequential);
+        assert!(eval_many(f, &Vec::<Vec<Interval<f64>>>::new()).is_empty());
+    }
+
+    #[test]
+    fn uses_strategy_of_caller() {
+        let boxes = boxes();
+        let f = |b: &[Interval<f64>]| {
+            let x = Value::variable(b[0]);
+            (x.clone() * (Value::constant(Interval::exact(1.)) - x)).enclosure()
+        };
+
+        let affine = Strategy::Affine.execute(|| eval_many(f, &boxes));
+        let sequential: Vec<_> = Strategy::Affine.execute(|| boxes.iter().map(|b| f(b)).collect());
+        assert_eq!(affine, sequential);
+        assert_ne!(eval_many(f, &boxes), affine);
+    }
+
+    #[test]
+    #[cfg(feature = "libc")]
+    fn rounds_to_nearest() {
+        let boxes = boxes();
+        let f = |x: &[Interval<f64>]| x[0] / Interval::exact(3.) + x[1] * Interval::exact(0.1);
+
+        let expected: Vec<_> = boxes.iter().map(|b| f(b)).collect();
+        let upward = Rounding::Upward.execute(|| eval_many(f, &boxes));
+        assert_eq!(upward, expected);
+    }
+}
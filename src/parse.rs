@@ -0,0 +1,276 @@
+//! Parsing intervals from strings.
+
+use std::error;
+use std::fmt::{self, LowerExp};
+use std::str::FromStr;
+
+use num::Float;
+
+use decimal::{parse_enclosure, parse_hex};
+use interval::Interval;
+use rounding::RoundedArith;
+
+/// Error returned when parsing interval from string fails
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ParseIntervalError {
+    /// String does not have the form of an interval
+    Format,
+    /// Endpoint is not a decimal number
+    Number,
+    /// Lower endpoint is greater than upper endpoint
+    Reversed,
+    /// Radius of `center +- radius` is negative
+    Radius,
+}
+
+impl fmt::Display for ParseIntervalError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let msg = match *self {
+            ParseIntervalError::Format => "invalid interval syntax",
+            ParseIntervalError::Number => "invalid number in interval",
+            ParseIntervalError::Reversed => "lower endpoint is greater than upper endpoint",
+            ParseIntervalError::Radius => "radius of interval is negative",
+        };
+
+        fmt.write_str(msg)
+    }
+}
+
+impl error::Error for ParseIntervalError {}
+
+/// Information about rounding applied while parsing interval
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ParseInfo {
+    /// Lower endpoint was exactly representable
+    pub start_exact: bool,
+    /// Upper endpoint was exactly representable
+    pub end_exact: bool,
+}
+
+impl ParseInfo {
+    /// Check if neither endpoint was widened
+    pub fn is_exact(&self) -> bool {
+        self.start_exact && self.end_exact
+    }
+}
+
+impl<T> Interval<T>
+    where T: Float + FromStr + LowerExp
+{
+    /// Parse interval written as `[start, end]` and report which endpoints were widened
+    ///
+    /// Endpoints are decimal numbers, hexadecimal floats (like `0x1.8p+1`) or `inf`, `-inf`.
+    /// When an endpoint is not representable in `T` it is rounded outward, so the result always
+    /// encloses the written interval. Literals `[empty]` and `[entire]` of IEEE 1788 are
+    /// accepted as well, so every interval written by `Display` can be read back.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// let (interval, info) = Interval::<f64>::parse_with_info("[0.1, 0.5]").unwrap();
+    ///
+    /// assert!(interval.contains(0.1) && interval.contains(0.5));
+    /// assert!(!info.start_exact);
+    /// assert!(info.end_exact);
+    /// ```
+    pub fn parse_with_info(s: &str) -> Result<(Self, ParseInfo), ParseIntervalError> {
+        let s = s.trim();
+        if !s.starts_with('[') || !s.ends_with(']') {
+            return Err(ParseIntervalError::Format);
+        }
+        let exact = ParseInfo {
+            start_exact: true,
+            end_exact: true,
+        };
+        let inner = s[1..s.len() - 1].trim();
+        if inner.eq_ignore_ascii_case("empty") {
+            let empty = Interval {
+                start: T::max_value(),
+                end: T::min_value(),
+            };
+            return Ok((empty, exact));
+        }
+        if inner.eq_ignore_ascii_case("entire") {
+            let entire = Interval {
+                start: T::neg_infinity(),
+                end: T::infinity(),
+            };
+            return Ok((entire, exact));
+        }
+
+        let mut parts = inner.split(',');
+        let (start, end) = match (parts.next(), parts.next(), parts.next()) {
+            (Some(start), Some(end), None) => (start, end),
+            _ => return Err(ParseIntervalError::Format),
+        };
+
+        let (start, _, start_exact): (T, T, _) = parse_endpoint(start)?;
+        let (_, end, end_exact) = parse_endpoint(end)?;
+        if start > end {
+            return Err(ParseIntervalError::Reversed);
+        }
+
+        let info = ParseInfo {
+            start_exact,
+            end_exact,
+        };
+
+        Ok((Interval { start, end }, info))
+    }
+}
+
+impl<T> FromStr for Interval<T>
+    where T: Float + FromStr + LowerExp + RoundedArith
+{
+    type Err = ParseIntervalError;
+
+    /// Parse interval written as `[start, end]`, `center +- radius` or a single number
+    ///
+    /// Decimal numbers that are not representable in `T` are rounded outward, so a single number
+    /// gives the tightest interval containing its value, and the other forms give enclosures of
+    /// the written interval. Radius can be also separated by `±`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// let a: Interval<f64> = "[1.5, 2.5]".parse().unwrap();
+    /// let b: Interval<f64> = "2 +- 0.5".parse().unwrap();
+    /// let c: Interval<f64> = "0.1".parse().unwrap();
+    ///
+    /// assert_eq!(a, b);
+    /// assert_eq!(c, Interval::with_range(0.1f64.next_down(), 0.1));
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.starts_with('[') {
+            return Interval::parse_with_info(s).map(|(interval, _)| interval);
+        }
+
+        let radius = s.find("+-")
+            .map(|i| (i, 2))
+            .or_else(|| s.find('±').map(|i| (i, '±'.len_utf8())));
+        match radius {
+            Some((i, len)) => {
+                let (start, end, _) = parse_endpoint::<T>(&s[..i])?;
+                let (_, radius, _) = parse_endpoint::<T>(&s[i + len..])?;
+                if radius < T::zero() {
+                    return Err(ParseIntervalError::Radius);
+                }
+
+                Ok(Interval {
+                    start: start.sub_down(radius),
+                    end: end.add_up(radius),
+                })
+            }
+            None => {
+                let (start, end, _) = parse_endpoint(s)?;
+
+                Ok(Interval { start, end })
+            }
+        }
+    }
+}
+
+fn parse_endpoint<T>(s: &str) -> Result<(T, T, bool), ParseIntervalError>
+    where T: Float + FromStr + LowerExp
+{
+    match s.trim() {
+        "inf" | "+inf" => Ok((T::infinity(), T::infinity(), true)),
+        "-inf" => Ok((T::neg_infinity(), T::neg_infinity(), true)),
+        s if is_hex(s) => parse_hex(s).ok_or(ParseIntervalError::Number),
+        s => parse_enclosure(s).ok_or(ParseIntervalError::Number),
+    }
+}
+
+fn is_hex(s: &str) -> bool {
+    let s = s.strip_prefix(['+', '-']).unwrap_or(s);
+
+    s.get(..2).is_some_and(|prefix| prefix.eq_ignore_ascii_case("0x"))
+}
+
+#[cfg(test)]
+mod test {
+    use std::f64;
+
+    use super::{ParseInfo, ParseIntervalError};
+    use interval::Interval;
+
+    #[test]
+    fn exact() {
+        let (interval, info) = Interval::<f64>::parse_with_info(" [ -1.5 , 2 ] ").unwrap();
+
+        assert_eq!(interval, Interval::with_range(-1.5, 2.));
+        assert!(info.is_exact());
+    }
+
+    #[test]
+    fn widened() {
+        let (interval, info) = Interval::<f32>::parse_with_info("[0.1, 0.2]").unwrap();
+
+        assert!((interval.start as f64) < 0.1 && 0.2 < interval.end as f64);
+        assert_eq!(info,
+                   ParseInfo {
+                       start_exact: false,
+                       end_exact: false,
+                   });
+    }
+
+    #[test]
+    fn infinite() {
+        let (interval, info) = Interval::<f64>::parse_with_info("[-inf, 1e400]").unwrap();
+
+        assert_eq!(interval, Interval::with_range(f64::NEG_INFINITY, f64::INFINITY));
+        assert!(info.start_exact && !info.end_exact);
+    }
+
+    #[test]
+    fn errors() {
+        let parse = Interval::<f64>::parse_with_info;
+
+        assert_eq!(parse("1, 2").unwrap_err(), ParseIntervalError::Format);
+        assert_eq!(parse("[1, 2, 3]").unwrap_err(), ParseIntervalError::Format);
+        assert_eq!(parse("[1, x]").unwrap_err(), ParseIntervalError::Number);
+        assert_eq!(parse("[2, 1]").unwrap_err(), ParseIntervalError::Reversed);
+        assert_eq!(parse("[nothing]").unwrap_err(), ParseIntervalError::Format);
+    }
+
+    #[test]
+    fn literals() {
+        let (empty, info) = Interval::<f64>::parse_with_info("[empty]").unwrap();
+        assert!(empty.is_empty() && info.is_exact());
+        assert_eq!(empty, Interval::empty());
+        assert_eq!(" [ Entire ] ".parse(), Ok(Interval::<f32>::entire()));
+
+        for x in &[Interval::empty(), Interval::entire(), Interval::with_range(-1.5, 0.25)] {
+            assert_eq!(x.to_string().parse::<Interval<f64>>(), Ok(*x));
+        }
+    }
+
+    #[test]
+    fn from_str() {
+        let parse = |s: &str| s.parse::<Interval<f64>>();
+
+        assert_eq!(parse(" [-1, 2] "), Ok(Interval::with_range(-1., 2.)));
+        assert_eq!(parse("1.5 +- 0.25"), Ok(Interval::with_range(1.25, 1.75)));
+        assert_eq!(parse("-1±1"), Ok(Interval::with_range(-2., 0.)));
+        assert_eq!(parse("inf"), Ok(Interval::exact(f64::INFINITY)));
+        assert_eq!(parse("-0.1"), Ok(Interval::with_range(-0.1, (-0.1f64).next_up())));
+
+        let x = parse("0.1 +- 0.1").unwrap();
+        assert!(x.start < 0. && 0.2 <= x.end && x.width() < 0.2 + 1e-15);
+
+        let x = Interval::with_range(-0.1, 1e-310);
+        assert_eq!(parse(&x.display_hex().to_string()), Ok(x));
+        assert_eq!(parse("-0x1p-1 +- 0x1p-2"), Ok(Interval::with_range(-0.75, -0.25)));
+
+        assert_eq!(parse("1 +- -1"), Err(ParseIntervalError::Radius));
+        assert_eq!(parse("0x1.g"), Err(ParseIntervalError::Number));
+        assert_eq!(parse("1 +- "), Err(ParseIntervalError::Number));
+        assert_eq!(parse("1, 2"), Err(ParseIntervalError::Number));
+        assert_eq!(parse("[1, 2"), Err(ParseIntervalError::Format));
+    }
+}
@@ -0,0 +1,222 @@
+//! Plot data for visualizing enclosures of functions.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use inter::Interval;
+//! use inter::plot;
+//!
+//! let envelope = plot::envelope(|x| x * x, Interval::with_range(-1., 1.), 8, 0.1);
+//!
+//! for (&(x, lo), &(_, hi)) in envelope.lower.iter().zip(&envelope.upper) {
+//!     assert!(lo <= x * x && x * x <= hi);
+//! }
+//! ```
+//!
+//! Functions of two variables are evaluated over a grid of boxes, and the bound matrices can be
+//! drawn as heatmaps or contoured. Every region where the lower bound is positive is then
+//! certainly above zero, and likewise for other levels.
+//!
+//! ```rust
+//! use inter::Interval;
+//! use inter::plot;
+//!
+//! let unit = Interval::with_range(-1., 1.);
+//! let grid = plot::grid(|x, y| x.sqr() + y.sqr(), unit, unit, (4, 4));
+//!
+//! assert_eq!(grid.x, vec![-1., -0.5, 0., 0.5, 1.]);
+//! assert_eq!((grid.lower[0][0], grid.upper[0][0]), (0.5, 2.));
+//! assert_eq!((grid.lower[1][2], grid.upper[1][2]), (0., 0.5));
+//! ```
+
+use interval::Interval;
+use parallel;
+
+/// How many times a single piece can be bisected while refining envelope
+pub const MAX_DEPTH: usize = 12;
+
+/// Lower and upper envelope of function over domain as polylines
+///
+/// Both polylines share the same `x` coordinates. They are step functions: every piece of the
+/// domain contributes two points with the same `y`, so the area between them contains the graph
+/// of the function.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Envelope {
+    /// Points `(x, y)` of lower envelope
+    pub lower: Vec<(f64, f64)>,
+    /// Points `(x, y)` of upper envelope
+    pub upper: Vec<(f64, f64)>,
+}
+
+/// Compute envelope of inclusion function `f` over `domain`
+///
+/// Domain is split into `pieces` equal parts, then every piece whose enclosure is wider than
+/// `tolerance` is bisected (at most `MAX_DEPTH` times).
+///
+/// # Panics
+///
+/// This will panic if `pieces` is zero.
+pub fn envelope<F>(f: F, domain: Interval<f64>, pieces: usize, tolerance: f64) -> Envelope
+    where F: Fn(Interval<f64>) -> Interval<f64>
+{
+    assert!(pieces > 0);
+
+    let mut envelope = Envelope {
+        lower: Vec::new(),
+        upper: Vec::new(),
+    };
+
+    for piece in domain.subdivide(pieces) {
+        refine(&f, piece, tolerance, 0, &mut envelope);
+    }
+
+    envelope
+}
+
+fn refine<F>(f: &F, x: Interval<f64>, tolerance: f64, depth: usize, envelope: &mut Envelope)
+    where F: Fn(Interval<f64>) -> Interval<f64>
+{
+    let y = f(x);
+
+    if y.width() > tolerance && depth < MAX_DEPTH && !x.is_exact() {
+        let (left, right) = x.bisect();
+        refine(f, left, tolerance, depth + 1, envelope);
+        refine(f, right, tolerance, depth + 1, envelope);
+    } else {
+        envelope.lower.push((x.start, y.start));
+        envelope.lower.push((x.end, y.start));
+        envelope.upper.push((x.start, y.end));
+        envelope.upper.push((x.end, y.end));
+    }
+}
+
+/// Bounds of function of two variables over grid of boxes
+///
+/// Box in row `j` and column `i` is `[x[i], x[i + 1]] × [y[j], y[j + 1]]`, and bounds of the
+/// function over it are `lower[j][i]` and `upper[j][i]`. Both bounds are NaN where the enclosure
+/// is empty, e.g. outside of domain of the function.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Grid {
+    /// Edges of columns
+    pub x: Vec<f64>,
+    /// Edges of rows
+    pub y: Vec<f64>,
+    /// Lower bounds, one row per row of grid
+    pub lower: Vec<Vec<f64>>,
+    /// Upper bounds, one row per row of grid
+    pub upper: Vec<Vec<f64>>,
+}
+
+/// Evaluate inclusion function `f` over `x × y` split into `columns × rows` boxes
+///
+/// # Panics
+///
+/// This will panic if there are no columns or no rows.
+pub fn grid<F>(f: F, x: Interval<f64>, y: Interval<f64>, (columns, rows): (usize, usize)) -> Grid
+    where F: Fn(Interval<f64>, Interval<f64>) -> Interval<f64>
+{
+    let (x, y) = (edges(x, columns), edges(y, rows));
+    let values = boxes(&x, &y).map(|(x, y)| f(x, y)).collect();
+
+    bounds(x, y, values)
+}
+
+/// Same as `grid`, but boxes are evaluated on all available cores
+///
+/// See `parallel::eval_many` for how rounding and strategy of evaluation are set up on workers.
+///
+/// # Panics
+///
+/// This will panic if there are no columns or no rows, or if `f` panics for any box.
+pub fn grid_parallel<F>(f: F,
+                        x: Interval<f64>,
+                        y: Interval<f64>,
+                        (columns, rows): (usize, usize))
+                        -> Grid
+    where F: Fn(Interval<f64>, Interval<f64>) -> Interval<f64> + Sync
+{
+    let (x, y) = (edges(x, columns), edges(y, rows));
+    let boxes: Vec<_> = boxes(&x, &y).map(|(x, y)| [x, y]).collect();
+    let values = parallel::eval_many(|b: &[Interval<f64>]| f(b[0], b[1]), &boxes);
+
+    bounds(x, y, values)
+}
+
+/// Edges of `n` pieces of `x`
+fn edges(x: Interval<f64>, n: usize) -> Vec<f64> {
+    let mut edges: Vec<_> = x.subdivide(n).map(|piece| piece.start).collect();
+    edges.push(x.end);
+    edges
+}
+
+/// Boxes between edges, row by row
+fn boxes<'a>(x: &'a [f64],
+             y: &'a [f64])
+             -> impl Iterator<Item = (Interval<f64>, Interval<f64>)> + 'a {
+    let piece = |w: &[f64]| Interval { start: w[0], end: w[1] };
+
+    y.windows(2).flat_map(move |y| x.windows(2).map(move |x| (piece(x), piece(y))))
+}
+
+fn bounds(x: Vec<f64>, y: Vec<f64>, values: Vec<Interval<f64>>) -> Grid {
+    let rows = |bound: fn(&Interval<f64>) -> f64| {
+        values.chunks(x.len() - 1)
+            .map(|row| row.iter().map(|v| if v.is_empty() { f64::NAN } else { bound(v) }).collect())
+            .collect()
+    };
+
+    Grid {
+        lower: rows(|v| v.start),
+        upper: rows(|v| v.end),
+        x,
+        y,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn covers_domain() {
+        let domain = Interval::with_range(-1., 2.);
+        let envelope = envelope(|x| x, domain, 3, 1.);
+
+        assert_eq!(envelope.lower.first().unwrap().0, -1.);
+        assert_eq!(envelope.lower.last().unwrap().0, 2.);
+        assert_eq!(envelope.lower.len(), 6);
+        for w in envelope.lower.windows(2) {
+            assert!(w[0].0 <= w[1].0);
+        }
+    }
+
+    #[test]
+    fn refines_wide_pieces() {
+        let domain = Interval::with_range(0., 1.);
+        let coarse = envelope(|x| x * x, domain, 1, 10.);
+        let fine = envelope(|x| x * x, domain, 1, 0.01);
+
+        assert_eq!(coarse.upper, vec![(0., 1.), (1., 1.)]);
+        assert!(fine.upper.len() > coarse.upper.len());
+        for (lo, hi) in fine.lower.iter().zip(&fine.upper) {
+            assert!(hi.1 - lo.1 <= 0.01);
+        }
+    }
+
+    #[test]
+    fn grid_bounds() {
+        let f = |x: Interval<f64>, y: Interval<f64>| (x * y).sqrt();
+        let (x, y) = (Interval::with_range(-2., 1.), Interval::with_range(1., 2.));
+        let grid = grid(f, x, y, (3, 2));
+
+        assert_eq!((grid.x, grid.y), (vec![-2., -1., 0., 1.], vec![1., 1.5, 2.]));
+        assert_eq!((grid.lower.len(), grid.upper[0].len()), (2, 3));
+        assert!(grid.lower[1][0].is_nan() && grid.upper[1][0].is_nan());
+        assert_eq!((grid.lower[1][2], grid.upper[1][2]), (0., 2f64.sqrt()));
+        assert_eq!((grid.lower[0][1], grid.upper[0][1]), (0., 0.));
+
+        // NaN bounds are not equal to themselves
+        let parallel = grid_parallel(f, x, y, (3, 2));
+        assert_eq!(format!("{:?}", parallel), format!("{:?}", super::grid(f, x, y, (3, 2))));
+    }
+}
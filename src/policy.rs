@@ -0,0 +1,203 @@
+//! Handling of arguments outside of domains of functions.
+//!
+//! Functions of intervals follow IEEE 1788 and ignore values outside of their domain, so
+//! `sqrt([-1, 4])` is `[0, 2]` and `ln([-2, -1])` is empty. That is what library code usually
+//! wants, but it also hides bugs, and exploratory code would rather fail loudly. `DomainPolicy`
+//! chooses what happens when some value is outside of domain of `sqrt`, logarithms, inverse
+//! trigonometric and hyperbolic functions or when divisor contains zero:
+//!
+//! - `DomainPolicy::Ignore` keeps the result over the rest of the domain, which is the default,
+//! - `DomainPolicy::Entire` gives the whole line, so the result is valid even if the violation
+//!   was caused by overestimation and the true values are within domain,
+//! - `DomainPolicy::Panic` panics with the name of function.
+//!
+//! `checked` instead returns an error when any violation occurred during a computation, with
+//! whatever policy is in effect. Policy in effect is stored per thread, and applies also to
+//! operations inside of other functions of this crate.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use inter::Interval;
+//! use inter::policy::{self, DomainPolicy};
+//!
+//! let x = Interval::with_range(-1., 4.);
+//!
+//! assert_eq!(x.sqrt(), Interval::with_range(0., 2.));
+//! assert!(Interval::with_range(-2., -1.).ln().is_empty());
+//! assert_eq!(DomainPolicy::Entire.execute(|| x.sqrt()), Interval::entire());
+//! assert_eq!(policy::checked(|| x.sqrt()).unwrap_err().function(), "sqrt");
+//! assert!(policy::checked(|| Interval::with_range(1., 4.).sqrt()).is_ok());
+//! ```
+
+use std::cell::Cell;
+use std::error;
+use std::fmt;
+
+use num::Float;
+
+use interval::Interval;
+use rounding::RoundedArith;
+
+thread_local! {
+    static CURRENT: Cell<DomainPolicy> = const { Cell::new(DomainPolicy::Ignore) };
+    static VIOLATION: Cell<Option<DomainError>> = const { Cell::new(None) };
+}
+
+/// What to do when argument of function is partly or entirely outside of its domain
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum DomainPolicy {
+    /// Ignore values outside of domain, as in IEEE 1788
+    #[default]
+    Ignore,
+    /// Return the whole line
+    Entire,
+    /// Panic
+    Panic,
+}
+
+impl DomainPolicy {
+    /// Policy in effect on current thread
+    pub fn current() -> DomainPolicy {
+        CURRENT.with(Cell::get)
+    }
+
+    /// Run `func` with this policy in effect, restoring previous one afterwards
+    pub fn execute<R, F: FnOnce() -> R>(self, func: F) -> R {
+        struct Restore(DomainPolicy);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                CURRENT.with(|current| current.set(self.0));
+            }
+        }
+
+        let _restore = Restore(self.replace());
+        func()
+    }
+
+    /// Put this policy in effect until it is replaced, returning the previous one
+    pub(crate) fn replace(self) -> DomainPolicy {
+        CURRENT.with(|current| current.replace(self))
+    }
+}
+
+/// Argument outside of domain of function, reported by `checked`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DomainError {
+    function: &'static str,
+}
+
+impl DomainError {
+    /// Name of the function, like `sqrt` or `/`
+    pub fn function(&self) -> &'static str {
+        self.function
+    }
+}
+
+impl fmt::Display for DomainError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "argument of `{}` is outside of its domain", self.function)
+    }
+}
+
+impl error::Error for DomainError {}
+
+/// Run `func`, failing with the first domain violation that occurred in it
+///
+/// Nested calls report violations to the outer ones as well.
+pub fn checked<R, F: FnOnce() -> R>(func: F) -> Result<R, DomainError> {
+    struct Restore(Option<DomainError>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            VIOLATION.with(|violation| violation.set(self.0.or(violation.get())));
+        }
+    }
+
+    let restore = Restore(VIOLATION.with(|violation| violation.replace(None)));
+    let result = func();
+    let violation = VIOLATION.with(Cell::get);
+    drop(restore);
+
+    match violation {
+        Some(err) => Err(err),
+        None => Ok(result),
+    }
+}
+
+/// Result of `function` following the policy in effect, when its argument was in domain only
+/// if `valid`, with `ignored` being the result over the rest of domain
+pub(crate) fn check<T>(function: &'static str, valid: bool, ignored: Interval<T>) -> Interval<T>
+    where T: Float + RoundedArith
+{
+    if valid {
+        return ignored;
+    }
+
+    VIOLATION.with(|violation| if violation.get().is_none() {
+        violation.set(Some(DomainError { function }));
+    });
+    match DomainPolicy::current() {
+        DomainPolicy::Ignore => ignored,
+        DomainPolicy::Entire => Interval::entire(),
+        DomainPolicy::Panic => panic!("argument of `{}` is outside of its domain", function),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn policies() {
+        let (x, y) = (Interval::with_range(-2., -1.), Interval::with_range(0., 1.));
+        let all = |x: Interval<f64>| [x.sqrt(), x.ln(), (x + 1.5).asin(), Interval::exact(1.) / x];
+
+        for &(arg, valid) in &[(x, [false, false, true, true]), (y, [true, true, false, false])] {
+            let ignored = all(arg);
+            let entire = DomainPolicy::Entire.execute(|| all(arg));
+            for i in 0..4 {
+                assert_eq!(entire[i] == ignored[i], valid[i], "{} {}", arg, i);
+                assert!(valid[i] || entire[i] == Interval::entire());
+            }
+        }
+        assert!(x.sqrt().is_empty() && y.ln().contains(f64::NEG_INFINITY));
+        assert_eq!(x.ln(), Interval::empty());
+        assert_eq!(DomainPolicy::current(), DomainPolicy::Ignore);
+
+        let panicked = ::std::panic::catch_unwind(|| DomainPolicy::Panic.execute(|| x.ln()));
+        assert!(panicked.is_err());
+        assert_eq!(DomainPolicy::current(), DomainPolicy::Ignore);
+        assert_eq!(DomainPolicy::Panic.execute(|| y.sqrt()), Interval::with_range(0., 1.));
+    }
+
+    #[test]
+    fn errors() {
+        let x = Interval::with_range(-1., 1.);
+        let err = checked(|| (x.sqrt(), Interval::exact(1.) / x)).unwrap_err();
+        assert_eq!(err.function(), "sqrt");
+        assert_eq!(err.to_string(), "argument of `sqrt` is outside of its domain");
+
+        assert_eq!(checked(|| x.asin().sup()), Ok(x.asin().sup()));
+        let outer = checked(|| {
+            assert!(checked(|| x.acosh()).is_err());
+            checked(|| x.tan()).is_ok()
+        });
+        assert_eq!(outer.unwrap_err().function(), "acosh");
+        assert_eq!(checked(|| DomainPolicy::Entire.execute(|| x.ln())).unwrap_err().function(),
+                   "ln");
+        assert!(checked(|| ()).is_ok());
+
+        // Violation of outer computation survives panic in the inner one
+        let outer = checked(|| {
+            let _ = x.sqrt();
+            let inner = ::std::panic::catch_unwind(|| {
+                checked(|| DomainPolicy::Panic.execute(|| x.ln()))
+            });
+            assert!(inner.is_err());
+        });
+        assert_eq!(outer.unwrap_err().function(), "sqrt");
+        assert!(checked(|| ()).is_ok());
+    }
+}
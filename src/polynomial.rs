@@ -0,0 +1,348 @@
+//! Polynomials and rational functions with interval coefficients.
+//!
+//! Coefficients are enclosures, so a polynomial stands for the whole family of polynomials
+//! with coefficients from them, for example a filter designed with tolerances. Evaluation gives
+//! an enclosure of values of every member of the family, either with Horner scheme or in centered
+//! form, which is tighter over narrow arguments. Rational functions detect when the
+//! denominator can vanish and return the pieces on both sides of the pole instead of a
+//! meaningless bound.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use std::f64;
+//! use inter::{Interval, OneOrTwo};
+//! use inter::polynomial::{Polynomial, RationalFn};
+//!
+//! // (x + 1) / (x - 1)
+//! let one = Interval::exact(1.);
+//! let f = RationalFn::new(Polynomial::new(vec![one, one]), Polynomial::new(vec![-one, one]));
+//!
+//! assert_eq!(f.eval(Interval::with_range(2., 3.)),
+//!            Some(OneOrTwo::One(Interval::with_range(1.5, 4.))));
+//! assert_eq!(f.eval(Interval::with_range(0., 2.)),
+//!            Some(OneOrTwo::Two(Interval::with_range(f64::NEG_INFINITY, -1.),
+//!                               Interval::with_range(1., f64::INFINITY))));
+//! ```
+
+use std::ops::{Add, Mul, Neg, Sub};
+
+use num::{Bounded, Float};
+
+use interval::Interval;
+use one_or_two::OneOrTwo;
+use rounding::RoundedArith;
+
+/// Polynomial with interval coefficients
+#[derive(Clone, Debug, PartialEq)]
+pub struct Polynomial<T> {
+    coeffs: Vec<Interval<T>>,
+}
+
+impl<T> Polynomial<T> {
+    /// Polynomial with coefficients in ascending order, so `coeffs[k]` is coefficient of `x^k`
+    pub fn new(coeffs: Vec<Interval<T>>) -> Self {
+        Polynomial { coeffs }
+    }
+
+    /// Coefficients in ascending order
+    pub fn coeffs(&self) -> &[Interval<T>] {
+        &self.coeffs
+    }
+}
+
+impl<T> Polynomial<T>
+    where T: Float + RoundedArith
+{
+    /// Polynomial with exact coefficients in ascending order
+    pub fn from_coeffs(coeffs: &[T]) -> Self {
+        Polynomial::new(coeffs.iter().map(|&c| Interval::exact(c)).collect())
+    }
+
+    /// Index of the highest coefficient that is not exactly zero, `None` for zero polynomial
+    pub fn degree(&self) -> Option<usize> {
+        self.coeffs.iter().rposition(|c| c.start != T::zero() || c.end != T::zero())
+    }
+
+    /// Enclosure of values of all polynomials from the family over `x`, using Horner scheme with
+    /// fused multiply-add
+    pub fn eval(&self, x: Interval<T>) -> Interval<T> {
+        eval_horner(&self.coeffs, x)
+    }
+
+    /// Enclosure of values over `x` in centered form, see `eval_centered`
+    pub fn eval_centered(&self, x: Interval<T>) -> Interval<T> {
+        eval_centered(&self.coeffs, x)
+    }
+
+    /// Enclosure of derivative
+    pub fn derivative(&self) -> Self {
+        let coeffs = self.coeffs
+            .iter()
+            .enumerate()
+            .skip(1)
+            .map(|(k, &c)| c.mul_by_uint(k as u64))
+            .collect();
+
+        Polynomial::new(coeffs)
+    }
+}
+
+/// Enclosure of values of polynomial with coefficients `coeffs` in ascending order over `x`,
+/// using Horner scheme with fused multiply-add
+///
+/// ## Example
+///
+/// ```rust
+/// use inter::Interval;
+/// use inter::polynomial::eval_horner;
+///
+/// // x² - x
+/// let coeffs = [Interval::exact(0.), Interval::exact(-1.), Interval::exact(1.)];
+///
+/// assert_eq!(eval_horner(&coeffs, Interval::exact(3.)), Interval::exact(6.));
+/// assert_eq!(eval_horner(&coeffs, Interval::with_range(0., 1.)), Interval::with_range(-1., 0.));
+/// ```
+pub fn eval_horner<T>(coeffs: &[Interval<T>], x: Interval<T>) -> Interval<T>
+    where T: Float + RoundedArith
+{
+    coeffs.iter().rev().fold(Interval::exact(T::zero()), |acc, &c| acc.mul_add(x, c))
+}
+
+/// Enclosure of values of polynomial with coefficients `coeffs` in ascending order over `x`, in
+/// centered form `p(c) + p'(x) (x - c)` with `c` the midpoint of `x`
+///
+/// Overestimation of centered form shrinks quadratically with width of `x`, while that of Horner
+/// scheme only linearly, so it is much tighter for narrow intervals. Result is intersected with
+/// the one of Horner scheme, so it is never wider. Unbounded `x` is evaluated only with Horner
+/// scheme.
+///
+/// ## Example
+///
+/// ```rust
+/// use inter::Interval;
+/// use inter::polynomial::{eval_centered, eval_horner};
+///
+/// // x² - x
+/// let coeffs = [Interval::exact(0.), Interval::exact(-1.), Interval::exact(1.)];
+/// let x = Interval::with_range(0.4, 0.6);
+///
+/// // Range is [-0.25, -0.24], Horner scheme gives about [-0.36, -0.16]
+/// assert!(eval_horner(&coeffs, x).width() > 0.19);
+/// assert!(eval_centered(&coeffs, x).contains(-0.25) && eval_centered(&coeffs, x).width() < 0.05);
+/// ```
+pub fn eval_centered<T>(coeffs: &[Interval<T>], x: Interval<T>) -> Interval<T>
+    where T: Float + RoundedArith
+{
+    let horner = eval_horner(coeffs, x);
+    if x.is_empty() || !x.is_bounded() {
+        return horner;
+    }
+
+    let c = Interval::exact(x.mid());
+    let derivative = Polynomial::new(coeffs.to_vec()).derivative();
+
+    horner.intersect(&(derivative.eval(x).mul_add(x - c, eval_horner(coeffs, c))))
+}
+
+impl<T> Add for Polynomial<T>
+    where T: Float + RoundedArith
+{
+    type Output = Polynomial<T>;
+
+    fn add(self, other: Self) -> Self {
+        let (mut long, short) = if self.coeffs.len() >= other.coeffs.len() {
+            (self.coeffs, other.coeffs)
+        } else {
+            (other.coeffs, self.coeffs)
+        };
+        for (a, b) in long.iter_mut().zip(short) {
+            *a += b;
+        }
+
+        Polynomial::new(long)
+    }
+}
+
+impl<T> Sub for Polynomial<T>
+    where T: Float + RoundedArith
+{
+    type Output = Polynomial<T>;
+
+    fn sub(self, other: Self) -> Self {
+        self + -other
+    }
+}
+
+impl<T> Mul for Polynomial<T>
+    where T: Float + RoundedArith
+{
+    type Output = Polynomial<T>;
+
+    /// Product enclosing products of all members of both families
+    fn mul(self, other: Self) -> Self {
+        if self.coeffs.is_empty() || other.coeffs.is_empty() {
+            return Polynomial::new(Vec::new());
+        }
+
+        let len = self.coeffs.len() + other.coeffs.len() - 1;
+        let mut coeffs = vec![Interval::exact(T::zero()); len];
+        for (i, &a) in self.coeffs.iter().enumerate() {
+            for (j, &b) in other.coeffs.iter().enumerate() {
+                coeffs[i + j] += a * b;
+            }
+        }
+
+        Polynomial::new(coeffs)
+    }
+}
+
+impl<T> Neg for Polynomial<T>
+    where T: Neg<Output = T> + Copy
+{
+    type Output = Polynomial<T>;
+
+    fn neg(self) -> Self {
+        Polynomial::new(self.coeffs.into_iter().map(|c| -c).collect())
+    }
+}
+
+/// Ratio of two polynomials
+#[derive(Clone, Debug, PartialEq)]
+pub struct RationalFn<T> {
+    numerator: Polynomial<T>,
+    denominator: Polynomial<T>,
+}
+
+impl<T> RationalFn<T> {
+    /// Rational function `numerator / denominator`
+    pub fn new(numerator: Polynomial<T>, denominator: Polynomial<T>) -> Self {
+        RationalFn {
+            numerator,
+            denominator,
+        }
+    }
+
+    /// Numerator polynomial
+    pub fn numerator(&self) -> &Polynomial<T> {
+        &self.numerator
+    }
+
+    /// Denominator polynomial
+    pub fn denominator(&self) -> &Polynomial<T> {
+        &self.denominator
+    }
+}
+
+impl<T> RationalFn<T>
+    where T: Float + Bounded + RoundedArith
+{
+    /// Check if denominator cannot vanish over `x`, so the function has no poles there
+    pub fn is_pole_free(&self, x: Interval<T>) -> bool {
+        !self.denominator.eval(x).contains_zero()
+    }
+
+    /// Enclosure of values over `x`, split in two pieces around possible pole
+    ///
+    /// When denominator can be zero, values are enclosed by extended division of enclosures of
+    /// numerator and denominator (see `Interval::div_split`), which is the whole line if
+    /// numerator can be zero too. Returns `None` when `x` is empty, or when the denominator is zero
+    /// over all of `x` and numerator is not.
+    pub fn eval(&self, x: Interval<T>) -> Option<OneOrTwo<T>> {
+        self.numerator.eval(x).div_split(self.denominator.eval(x))
+    }
+
+    /// Hull of `eval`, empty when it returns `None`
+    pub fn eval_hull(&self, x: Interval<T>) -> Interval<T> {
+        self.eval(x).map_or_else(Interval::empty, |pieces| pieces.hull())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::f64;
+
+    use super::*;
+
+    #[test]
+    fn polynomials() {
+        // 1 - 3x + x³
+        let p = Polynomial::from_coeffs(&[1., -3., 0., 1.]);
+
+        assert_eq!(p.degree(), Some(3));
+        assert_eq!(Polynomial::from_coeffs(&[1., 0.]).degree(), Some(0));
+        assert_eq!(Polynomial::<f64>::new(vec![]).degree(), None);
+        assert_eq!(p.eval(Interval::exact(2.)), Interval::exact(3.));
+        assert!(p.eval(Interval::with_range(-1., 1.)).contains_all(&[-1., 3.]));
+        assert_eq!(p.derivative(), Polynomial::from_coeffs(&[-3., 0., 3.]));
+
+        // Whole family is enclosed
+        let q = Polynomial::new(vec![Interval::with_range(-1., 1.), Interval::exact(1.)]);
+        assert_eq!(q.eval(Interval::exact(2.)), Interval::with_range(1., 3.));
+    }
+
+    #[test]
+    fn centered_and_arithmetic() {
+        let p = Polynomial::from_coeffs(&[1., -3., 0., 1.]);
+        for &(a, b) in &[(-1., 1.), (0.9, 1.1), (1.99, 2.01), (-3., -2.5)] {
+            let x = Interval::with_range(a, b);
+            let (horner, centered) = (p.eval(x), p.eval_centered(x));
+            assert!(centered.subset(&horner));
+            for i in 0..=10 {
+                let t = a + (b - a) * i as f64 / 10.;
+                assert!(centered.contains(1. - 3. * t + t * t * t));
+            }
+        }
+        // Centered form is much tighter around extremum
+        let x = Interval::with_epsilon(1., 1e-3);
+        assert!(p.eval_centered(x).width() < p.eval(x).width() / 100.);
+        assert!(p.eval_centered(Interval::empty()).is_empty());
+        assert_eq!(p.eval_centered(Interval::entire()), p.eval(Interval::entire()));
+
+        let q = Polynomial::new(vec![Interval::with_range(-1., 1.), Interval::exact(1.)]);
+        assert_eq!(p.clone() + q.clone(),
+                   Polynomial::new(vec![Interval::with_range(0., 2.),
+                                        Interval::exact(-2.),
+                                        Interval::exact(0.),
+                                        Interval::exact(1.)]));
+        assert_eq!((q.clone() - q.clone()).coeffs()[1], Interval::exact(0.));
+        let product = p.clone() * q.clone();
+        assert_eq!(product.degree(), Some(4));
+        for &t in &[-2., 0.5, 3.] {
+            let x = Interval::exact(t);
+            assert!(product.eval(x).superset(&(p.eval(x) * q.eval(x))));
+        }
+        assert_eq!(p * Polynomial::new(vec![]), Polynomial::new(vec![]));
+    }
+
+    #[test]
+    fn rational() {
+        let x = Polynomial::from_coeffs(&[0., 1.]);
+        // 1 / (x² + 1) has no real poles
+        let bell = RationalFn::new(Polynomial::from_coeffs(&[1.]),
+                                   Polynomial::from_coeffs(&[1., 0., 1.]));
+        let domain = Interval::with_range(-2., 2.);
+
+        assert!(bell.is_pole_free(Interval::with_range(0., 2.)));
+        let values = bell.eval_hull(Interval::with_range(0., 2.));
+        assert!(values.contains_all(&[0.2, 1.]) && values.subset(&Interval::with_range(0.199, 1.)));
+
+        // x / x is undefined at zero, so nothing is known
+        let ratio = RationalFn::new(x.clone(), x.clone());
+        assert!(!ratio.is_pole_free(domain));
+        assert_eq!(ratio.eval_hull(domain), Interval::entire());
+        assert_eq!(ratio.eval(Interval::with_range(1., 2.)),
+                   Some(OneOrTwo::One(Interval::with_range(0.5, 2.))));
+
+        // Denominator is zero everywhere
+        let zero = RationalFn::new(Polynomial::from_coeffs(&[1.]), Polynomial::from_coeffs(&[0.]));
+        assert_eq!(zero.eval(domain), None);
+        assert!(zero.eval_hull(domain).is_empty());
+
+        // Pole at the endpoint gives a single unbounded piece
+        let inverse = RationalFn::new(Polynomial::from_coeffs(&[1.]),
+                                      Polynomial::from_coeffs(&[0., 1.]));
+        assert_eq!(inverse.eval_hull(Interval::with_range(0., 0.5)),
+                   Interval::with_range(2., f64::INFINITY));
+    }
+}
@@ -0,0 +1,161 @@
+//! Geometric predicates with interval filters.
+//!
+//! Predicates of computational geometry decide the sign of a determinant, and evaluating it in
+//! floating point gives wrong signs for nearly degenerate input, which breaks triangulations and
+//! hulls. These predicates evaluate determinants with interval arithmetic instead and report
+//! the sign only when it is certain. When it is not, they return `None` and the caller falls
+//! back to an exact (and much slower) evaluation, so the filter decides almost every call and
+//! the expensive stage is reached only near degeneracies.
+//!
+//! Differences of coordinates that are exact, which is the case for nearby points, are combined
+//! with `rounding::enclose_dot2`, so `2 × 2` determinants are rounded only once per bound.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use std::cmp::Ordering;
+//! use inter::predicates;
+//!
+//! assert_eq!(predicates::orient2d((0., 0.), (1., 0.), (0., 1.)), Some(Ordering::Greater));
+//! assert_eq!(predicates::orient2d((0., 0.), (1., 1.), (3., 3.)), Some(Ordering::Equal));
+//!
+//! // Point inside of the circle through the first three
+//! let inside = predicates::incircle((0., 0.), (2., 0.), (0., 2.), (1., 1.));
+//! assert_eq!(inside, Some(Ordering::Greater));
+//! ```
+
+use std::cmp::Ordering;
+
+use interval::Interval;
+use rounding::{enclose_dot2, enclose_sub};
+
+/// Point of the plane as `(x, y)`
+pub type Point = (f64, f64);
+
+/// Orientation of triangle `a`, `b`, `c`
+///
+/// This is the sign of `(a - c) × (b - c)`: `Greater` when points are in counterclockwise
+/// order, `Less` when they are clockwise and `Equal` when they are collinear. Returns `None`
+/// when the sign is uncertain.
+pub fn orient2d(a: Point, b: Point, c: Point) -> Option<Ordering> {
+    let (ac, bc) = (difference(a, c), difference(b, c));
+
+    sign(cross(ac, bc))
+}
+
+/// Position of `d` relative to the circle through `a`, `b` and `c`
+///
+/// For counterclockwise `a`, `b`, `c` this is `Greater` when `d` lies inside of the circle,
+/// `Less` when it lies outside and `Equal` when it is on the circle, and the signs are reversed
+/// for clockwise ones. Returns `None` when the sign is uncertain.
+pub fn incircle(a: Point, b: Point, c: Point, d: Point) -> Option<Ordering> {
+    let (ad, bd, cd) = (difference(a, d), difference(b, d), difference(c, d));
+    let lift = |(x, y): (Interval<f64>, Interval<f64>)| x.sqr() + y.sqr();
+
+    sign(lift(ad) * cross(bd, cd) + lift(bd) * cross(cd, ad) + lift(cd) * cross(ad, bd))
+}
+
+/// Enclosure of `a - b` for each coordinate
+fn difference(a: Point, b: Point) -> (Interval<f64>, Interval<f64>) {
+    (enclose_sub(a.0, b.0), enclose_sub(a.1, b.1))
+}
+
+/// Enclosure of `a.x b.y - a.y b.x`
+fn cross(a: (Interval<f64>, Interval<f64>), b: (Interval<f64>, Interval<f64>)) -> Interval<f64> {
+    if [a.0, a.1, b.0, b.1].iter().all(|x| x.is_exact()) {
+        enclose_dot2(a.0.inf(), b.1.inf(), -a.1.inf(), b.0.inf())
+    } else {
+        a.0 * b.1 - a.1 * b.0
+    }
+}
+
+/// Certain sign of `x`, if there is one
+fn sign(x: Interval<f64>) -> Option<Ordering> {
+    if x.is_empty() {
+        None
+    } else if x.inf() > 0. {
+        Some(Ordering::Greater)
+    } else if x.sup() < 0. {
+        Some(Ordering::Less)
+    } else if x.is_certainly_zero() {
+        Some(Ordering::Equal)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Exact orientation of points with coordinates that are integer multiples of `unit`
+    fn exact(a: Point, b: Point, c: Point, unit: f64) -> Ordering {
+        let d = |p: f64, q: f64| (p / unit) as i128 - (q / unit) as i128;
+        (d(a.0, c.0) * d(b.1, c.1) - d(a.1, c.1) * d(b.0, c.0)).cmp(&0)
+    }
+
+    #[test]
+    fn orientation() {
+        // Differences of large coordinates are not exact
+        let big = 2f64.powi(60);
+        let points = [(0., 0.), (1., 3.), (big, big + 256.), (-big, 7.), (3., -big), (big, 1.)];
+        for &a in &points {
+            for &b in &points {
+                for &c in &points {
+                    if let Some(sign) = orient2d(a, b, c) {
+                        assert_eq!(sign, exact(a, b, c, 1.), "{:?} {:?} {:?}", a, b, c);
+                    }
+                }
+            }
+        }
+        assert_eq!(orient2d((0., 0.), (1., 0.), (0., -1.)), Some(Ordering::Less));
+        assert_eq!(orient2d((big, big), (1., 1.), (0., 0.)), Some(Ordering::Equal));
+
+        // Nearly collinear points that plain floating point gets wrong
+        let (b, c) = ((12., 12.), (24., 24.));
+        let (mut wrong, mut uncertain) = (0, 0);
+        for i in 0..64 {
+            let a = (0.5 + i as f64 * f64::EPSILON, 0.5);
+            let plain = (a.0 - c.0) * (b.1 - c.1) - (a.1 - c.1) * (b.0 - c.0);
+            let sign = exact(a, b, c, f64::EPSILON);
+            if plain.partial_cmp(&0.) != Some(sign) {
+                wrong += 1;
+            }
+            match orient2d(a, b, c) {
+                Some(filtered) => assert_eq!(filtered, sign, "{}", i),
+                None => uncertain += 1,
+            }
+        }
+        assert!(wrong > 0 && uncertain < 64);
+        assert_eq!(orient2d(b, c, (f64::NAN, 0.)), None);
+    }
+
+    #[test]
+    fn circle() {
+        let exact = |a: Point, b: Point, c: Point, d: Point| {
+            let m = |p: Point| [p.0 as i128 - d.0 as i128, p.1 as i128 - d.1 as i128];
+            let (a, b, c) = (m(a), m(b), m(c));
+            let lift = |p: [i128; 2]| p[0] * p[0] + p[1] * p[1];
+            let cross = |p: [i128; 2], q: [i128; 2]| p[0] * q[1] - p[1] * q[0];
+            (lift(a) * cross(b, c) + lift(b) * cross(c, a) + lift(c) * cross(a, b)).cmp(&0)
+        };
+
+        let points = [(0., 0.), (1000., 0.), (0., 1000.), (500., 500.), (1000., 1000.),
+                      (-3., 7.), (-1000., 999.)];
+        for &a in &points {
+            for &b in &points {
+                for &c in &points {
+                    for &d in &points {
+                        assert_eq!(incircle(a, b, c, d), Some(exact(a, b, c, d)));
+                    }
+                }
+            }
+        }
+
+        // Cocircular points, with one of them rounded off the circle
+        let s = 0.5f64.sqrt();
+        assert_eq!(incircle((1., 0.), (0., 1.), (-1., 0.), (0., -1.)), Some(Ordering::Equal));
+        assert_eq!(incircle((1., 0.), (0., 1.), (-1., 0.), (s, s)), None);
+        assert_eq!(incircle((1., 0.), (-1., 0.), (0., 1.), (0., 0.)), Some(Ordering::Less));
+    }
+}
@@ -0,0 +1,198 @@
+//! Tracking where intervals grow the most.
+//!
+//! Available with the `provenance` feature. `Traced` wraps interval together with the step of
+//! its computation that increased width the most, compared to the widest operand of that step.
+//! Every arithmetic operation and `apply` records source location of the caller, so after
+//! evaluating big expression the tag points at the line that is worth rewriting.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use inter::Interval;
+//! use inter::provenance::Traced;
+//!
+//! let x = Traced::new(Interval::with_range(1., 2.));
+//! let y = x + Traced::new(Interval::exact(1.));
+//! let z = y * Traced::new(Interval::exact(100.));
+//! let r = z.apply("sqrt", Interval::sqrt);
+//!
+//! let step = r.widest().unwrap();
+//! assert_eq!(step.op, "mul");
+//! assert_eq!(step.growth, 99.);
+//! ```
+
+use std::fmt;
+use std::ops::{Add, Sub, Mul, Div, Neg};
+use std::panic::Location;
+
+use num::Float;
+
+use interval::Interval;
+use rounding::RoundedArith;
+use utils::partial_max;
+
+/// Step of computation that widened interval
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Step<T> {
+    /// Name of operation
+    pub op: &'static str,
+    /// Where operation was called
+    pub location: &'static Location<'static>,
+    /// Width of result minus width of the widest operand
+    pub growth: T,
+}
+
+impl<T> fmt::Display for Step<T>
+    where T: fmt::Display
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "{} at {} widened by {}", self.op, self.location, self.growth)
+    }
+}
+
+/// Interval with the step of its computation that widened it the most
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Traced<T> {
+    interval: Interval<T>,
+    widest: Option<Step<T>>,
+}
+
+impl<T> Traced<T> {
+    /// Input interval, with no history
+    pub fn new(interval: Interval<T>) -> Self {
+        Traced {
+            interval,
+            widest: None,
+        }
+    }
+
+    /// Reference to wrapped interval
+    pub fn interval(&self) -> &Interval<T> {
+        &self.interval
+    }
+
+    /// Step that increased width the most, `None` for inputs and results of exact operations
+    pub fn widest(&self) -> Option<&Step<T>> {
+        self.widest.as_ref()
+    }
+
+    /// Drop history and return wrapped interval
+    pub fn into_inner(self) -> Interval<T> {
+        self.interval
+    }
+}
+
+impl<T> Traced<T>
+    where T: Float + RoundedArith
+{
+    /// Apply unary function `f` named `op`
+    #[track_caller]
+    pub fn apply<F>(self, op: &'static str, f: F) -> Self
+        where F: FnOnce(Interval<T>) -> Interval<T>
+    {
+        let interval = f(self.interval);
+        Traced::record(op, interval, &[self])
+    }
+
+    /// Combine histories of `operands`, adding step `op` which resulted in `interval`
+    #[track_caller]
+    fn record(op: &'static str, interval: Interval<T>, operands: &[Traced<T>]) -> Self {
+        let widest_operand = operands.iter()
+            .fold(T::zero(), |acc, x| partial_max(acc, width(&x.interval)));
+        let step = Step {
+            op,
+            location: Location::caller(),
+            growth: width(&interval) - widest_operand,
+        };
+
+        let widest = operands.iter()
+            .filter_map(|x| x.widest)
+            .chain(if step.growth > T::zero() { Some(step) } else { None })
+            .fold(None, |acc: Option<Step<T>>, step| match acc {
+                Some(acc) if acc.growth >= step.growth => Some(acc),
+                _ => Some(step),
+            });
+
+        Traced { interval, widest }
+    }
+}
+
+/// Width of interval, zero for empty one
+fn width<T: Float>(x: &Interval<T>) -> T {
+    if x.is_empty() { T::zero() } else { x.end - x.start }
+}
+
+macro_rules! traced_ops {
+    ($($imp:ident, $method:ident;)*) => {
+        $(
+            impl<T> $imp for Traced<T>
+                where T: Float + RoundedArith
+            {
+                type Output = Traced<T>;
+
+                #[track_caller]
+                fn $method(self, other: Self) -> Self {
+                    let interval = self.interval.$method(other.interval);
+                    Traced::record(stringify!($method), interval, &[self, other])
+                }
+            }
+        )*
+    };
+}
+
+traced_ops! {
+    Add, add;
+    Sub, sub;
+    Mul, mul;
+    Div, div;
+}
+
+impl<T> Neg for Traced<T>
+    where T: Neg<Output = T> + Copy
+{
+    type Output = Traced<T>;
+
+    fn neg(self) -> Self {
+        Traced {
+            interval: -self.interval,
+            widest: self.widest,
+        }
+    }
+}
+
+impl<T> From<Interval<T>> for Traced<T> {
+    fn from(interval: Interval<T>) -> Self {
+        Traced::new(interval)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn locates_widest_step() {
+        let x = Traced::new(Interval::with_range(-1., 1.));
+        let line = line!() + 1;
+        let y = x.apply("cos", Interval::cos) * Traced::new(Interval::exact(10.));
+        let z = (y - x).apply("exp", Interval::exp);
+
+        let step = z.widest().unwrap();
+        assert_eq!(step.op, "exp");
+        assert!(step.growth > 1e4);
+
+        let step = y.widest().unwrap();
+        assert_eq!((step.op, step.location.line()), ("mul", line));
+        assert_eq!(step.location.file(), file!());
+        assert!(format!("{}", step).starts_with("mul at src/provenance.rs:"));
+    }
+
+    #[test]
+    fn exact_operations() {
+        let x = Traced::new(Interval::with_range(1., 2.));
+        let y = -(x + Traced::new(Interval::exact(1.)));
+
+        assert_eq!(y.widest(), None);
+        assert_eq!(y.into_inner(), Interval::with_range(-3., -2.));
+    }
+}
@@ -0,0 +1,274 @@
+//! Interval quaternions for attitude uncertainty.
+//!
+//! Quaternion with interval components encloses a set of rotations, for example an attitude
+//! estimate with its error bounds. Unit quaternions have every component in `[-1, 1]`, which is
+//! used to tighten products and rotation matrices, so uncertainty does not blow up when
+//! rotations are composed many times.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use std::f64::consts::PI;
+//! use inter::{Interval, IntervalVector};
+//! use inter::quaternion::Quaternion;
+//!
+//! let (zero, one) = (Interval::exact(0.), Interval::exact(1.));
+//! let z = IntervalVector::new(vec![zero, zero, one]);
+//! let angle = Interval::exact(PI / 2.) + Interval::with_range(-1e-3, 1e-3);
+//! let q = Quaternion::from_axis_angle(&z, angle);
+//!
+//! let v = IntervalVector::new(vec![one, zero, zero]);
+//! let r = q.rotate(&v);
+//!
+//! assert!(r[0].contains(0.) && r[1].contains(1.) && r[2].contains(0.));
+//! assert!(r[0].width() < 3e-3);
+//! ```
+
+use std::ops::{Add, Mul, Neg, Sub};
+
+use num::{Bounded, Float, FloatConst};
+
+use interval::Interval;
+use linalg::{IntervalMatrix, IntervalVector};
+use rounding::RoundedArith;
+
+/// Quaternion `w + xi + yj + zk` with interval components
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Quaternion<T> {
+    /// Scalar part
+    pub w: Interval<T>,
+    /// Coefficient of `i`
+    pub x: Interval<T>,
+    /// Coefficient of `j`
+    pub y: Interval<T>,
+    /// Coefficient of `k`
+    pub z: Interval<T>,
+}
+
+impl<T> Quaternion<T> {
+    /// Quaternion with given components
+    pub fn new(w: Interval<T>, x: Interval<T>, y: Interval<T>, z: Interval<T>) -> Self {
+        Quaternion { w, x, y, z }
+    }
+}
+
+impl<T> Quaternion<T>
+    where T: Float + Bounded + RoundedArith
+{
+    /// Identity rotation
+    pub fn identity() -> Self {
+        let (zero, one) = (Interval::exact(T::zero()), Interval::exact(T::one()));
+
+        Quaternion::new(one, zero, zero, zero)
+    }
+
+    /// Conjugate `w - xi - yj - zk`, which is the inverse rotation for unit quaternions
+    pub fn conjugate(self) -> Self {
+        Quaternion::new(self.w, -self.x, -self.y, -self.z)
+    }
+
+    /// Squared norm `w² + x² + y² + z²`
+    pub fn norm_sqr(&self) -> Interval<T> {
+        self.w.powi(2) + self.x.powi(2) + self.y.powi(2) + self.z.powi(2)
+    }
+
+    /// Norm `√(w² + x² + y² + z²)`
+    pub fn norm(&self) -> Interval<T> {
+        self.norm_sqr().sqrt()
+    }
+
+    /// Enclosure of normalized quaternions `q / |q|` for all `q` in `self`
+    ///
+    /// Components of the result are intersected with `[-1, 1]`.
+    pub fn normalize(self) -> Self {
+        let norm = self.norm();
+
+        Quaternion::new(self.w / norm, self.x / norm, self.y / norm, self.z / norm).clamp()
+    }
+
+    /// Product of unit quaternions, contracted to unit quaternions it contains
+    ///
+    /// Product of unit quaternions is a unit quaternion, so every component `c` satisfies
+    /// `c² = 1 - (sum of squares of the others)`. Contracting components with that removes the
+    /// part of overestimation that does not correspond to any rotation.
+    pub fn mul_unit(self, other: Self) -> Self {
+        (self * other).contract_unit()
+    }
+
+    /// Contract every component to values that can be completed to a unit quaternion
+    pub fn contract_unit(self) -> Self {
+        let one = Interval::exact(T::one());
+        let (w, x, y, z) = (self.w.powi(2), self.x.powi(2), self.y.powi(2), self.z.powi(2));
+
+        Quaternion::new(self.w.sqr_rev(one - (x + y + z)),
+                        self.x.sqr_rev(one - (w + y + z)),
+                        self.y.sqr_rev(one - (w + x + z)),
+                        self.z.sqr_rev(one - (w + x + y)))
+    }
+
+    /// Rotation matrix of unit quaternion, with entries intersected with `[-1, 1]`
+    pub fn to_matrix(&self) -> IntervalMatrix<T> {
+        let (w, x, y, z) = (self.w, self.x, self.y, self.z);
+        let one = Interval::exact(T::one());
+        let two = Interval::exact(T::one() + T::one());
+        let range = Interval::with_range(-T::one(), T::one());
+        let entry = |e: Interval<T>| e.intersect(&range);
+
+        IntervalMatrix::from_rows(vec![
+            vec![entry(one - two * (y.powi(2) + z.powi(2))),
+                 entry(two * (x * y - w * z)),
+                 entry(two * (x * z + w * y))],
+            vec![entry(two * (x * y + w * z)),
+                 entry(one - two * (x.powi(2) + z.powi(2))),
+                 entry(two * (y * z - w * x))],
+            vec![entry(two * (x * z - w * y)),
+                 entry(two * (y * z + w * x)),
+                 entry(one - two * (x.powi(2) + y.powi(2)))],
+        ])
+    }
+
+    /// Enclosure of rotations of every vector from `v` by every unit quaternion from `self`
+    ///
+    /// # Panics
+    ///
+    /// This will panic if `v` is not 3-dimensional.
+    pub fn rotate(&self, v: &IntervalVector<T>) -> IntervalVector<T> {
+        assert_eq!(v.dimension(), 3);
+
+        &self.to_matrix() * v
+    }
+
+    /// Intersect every component with `[-1, 1]`
+    fn clamp(self) -> Self {
+        let range = Interval::with_range(-T::one(), T::one());
+
+        Quaternion::new(self.w.intersect(&range),
+                        self.x.intersect(&range),
+                        self.y.intersect(&range),
+                        self.z.intersect(&range))
+    }
+}
+
+impl<T> Quaternion<T>
+    where T: Float + FloatConst + Bounded + RoundedArith
+{
+    /// Enclosure of rotations by every `angle` around every direction of `axis`
+    ///
+    /// Axis does not have to be normalized, but it cannot contain zero vector.
+    ///
+    /// # Panics
+    ///
+    /// This will panic if `axis` is not 3-dimensional.
+    pub fn from_axis_angle(axis: &IntervalVector<T>, angle: Interval<T>) -> Self {
+        assert_eq!(axis.dimension(), 3);
+
+        let half = angle / Interval::exact(T::one() + T::one());
+        let norm = axis.dot(axis).sqrt();
+        let s = half.sin() / norm;
+
+        Quaternion::new(half.cos(), axis[0] * s, axis[1] * s, axis[2] * s).clamp()
+    }
+}
+
+impl<T> Add for Quaternion<T>
+    where T: Float + RoundedArith
+{
+    type Output = Quaternion<T>;
+
+    fn add(self, other: Self) -> Self {
+        Quaternion::new(self.w + other.w, self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl<T> Sub for Quaternion<T>
+    where T: Float + RoundedArith
+{
+    type Output = Quaternion<T>;
+
+    fn sub(self, other: Self) -> Self {
+        Quaternion::new(self.w - other.w, self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl<T> Mul for Quaternion<T>
+    where T: Float + RoundedArith
+{
+    type Output = Quaternion<T>;
+
+    /// Hamilton product, composing rotation `other` followed by `self`
+    fn mul(self, other: Self) -> Self {
+        let (a, b) = (self, other);
+
+        Quaternion::new(a.w * b.w - a.x * b.x - a.y * b.y - a.z * b.z,
+                        a.w * b.x + a.x * b.w + a.y * b.z - a.z * b.y,
+                        a.w * b.y - a.x * b.z + a.y * b.w + a.z * b.x,
+                        a.w * b.z + a.x * b.y - a.y * b.x + a.z * b.w)
+    }
+}
+
+impl<T> Neg for Quaternion<T>
+    where T: Neg<Output = T> + Copy
+{
+    type Output = Quaternion<T>;
+
+    fn neg(self) -> Self {
+        Quaternion::new(-self.w, -self.x, -self.y, -self.z)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::f64::consts::PI;
+
+    use super::*;
+
+    fn vector(x: f64, y: f64, z: f64) -> IntervalVector<f64> {
+        IntervalVector::new(vec![Interval::exact(x), Interval::exact(y), Interval::exact(z)])
+    }
+
+    #[test]
+    fn composition() {
+        let quarter = Quaternion::from_axis_angle(&vector(0., 0., 2.), Interval::exact(PI / 2.));
+        let half = quarter * quarter;
+
+        assert!(half.w.contains(0.) && half.z.contains(1.));
+        assert!(half.x.contains(0.) && half.y.contains(0.));
+        assert!((quarter * quarter.conjugate()).w.contains(1.));
+        assert!(Quaternion::identity().norm().contains(1.));
+
+        let r = half.rotate(&vector(1., 2., 3.));
+        assert!(r[0].contains(-1.) && r[1].contains(-2.) && r[2].contains(3.));
+    }
+
+    #[test]
+    fn normalization() {
+        let err = Interval::with_range(-0.05, 0.05);
+        let q = Quaternion::from_axis_angle(&vector(1., 1., 0.), Interval::exact(3.) + err);
+
+        // Composed rotations stay within `[-1, 1]` and enclose sampled compositions
+        let (mut plain, mut unit) = (q, q);
+        for _ in 0..6 {
+            plain = plain * q;
+            unit = unit.mul_unit(q);
+        }
+        assert!(unit.w.width() < plain.w.width() && unit.x.width() < plain.x.width());
+        for &e in &[-0.05, 0., 0.05] {
+            let p = Quaternion::from_axis_angle(&vector(1., 1., 0.), Interval::exact(3. + e));
+            let product = (0..6).fold(p, |acc, _| acc * p);
+            assert!(product.w.subset(&unit.w) && product.z.subset(&unit.z));
+        }
+    }
+
+    #[test]
+    fn rotation_matrix() {
+        let q = Quaternion::from_axis_angle(&vector(1., 0., 0.), Interval::exact(PI / 2.));
+        let m = q.to_matrix();
+
+        for (i, j, value) in [(0, 0, 1.), (1, 2, -1.), (2, 1, 1.), (1, 1, 0.)] {
+            assert!(m[(i, j)].contains(value) && m[(i, j)].width() < 1e-14);
+        }
+        assert!(q.rotate(&vector(0., 1., 0.))[2].contains(1.));
+        assert_eq!(-(-q), q);
+        assert!((q - q).norm().contains(0.) && (q + q).norm().contains(2.));
+    }
+}
@@ -0,0 +1,380 @@
+//! Traits of `num` for generic numeric code.
+//!
+//! Generic code, like splines, quadrature rules or filters, is usually written against `Float` or
+//! `Real`. Intervals cannot implement them, as both require conversions of a value to a single
+//! primitive number and predicates of a single number, but they implement every part that is
+//! sound, so that code bounded by these parts runs with intervals and its result encloses
+//! results for all values in operands:
+//!
+//! | Trait or methods                         | Status      | Notes                          |
+//! |------------------------------------------|-------------|--------------------------------|
+//! | `Zero`, `One`, `Sum`, `Product`          | implemented | `is_zero` only for `[0, 0]`    |
+//! | `Add`, `Sub`, `Mul`, `Div`, `Rem`, `Neg` | implemented | also with scalars              |
+//! | `Num`                                    | implemented | radix 10 only                  |
+//! | `Signed`                                 | implemented | predicates hold for all values |
+//! | `PartialOrd`                             | implemented | relations hold for all values  |
+//! | `FromPrimitive`                          | implemented | tightest enclosures            |
+//! | `FloatConst`                             | implemented | constants widened by an ulp    |
+//! | `Bounded`                                | implemented | points at bounds of `T`        |
+//! | `sqrt`, `exp`, `ln`, `sin`, `mul_add`, … | methods     | same names as in `Float`       |
+//! | `min`, `max`, `recip`, `hypot`, `log`, … | methods     | in this module                 |
+//! | `ToPrimitive`, `NumCast`                 | missing     | use `inf`, `sup` or `mid`      |
+//! | `is_nan`, `is_finite`, `classify`, …     | missing     | use `is_empty`, `is_bounded`   |
+//! | `is_sign_negative`, `is_sign_positive`   | missing     | use `Signed` or `less_than`    |
+//! | `nan`, `epsilon`, `integer_decode`, …    | missing     | use those of `T`               |
+//! | `Float`, `Real`                          | missing     | require all of the above       |
+//!
+//! Conversions to a primitive number have to choose one value of interval, and predicates of a
+//! single number would answer `false` both for an interval certainly outside and one partly
+//! outside of the set they test, which silently sends generic code down a single branch.
+//! Comparisons and `Signed` have that problem too, but they answer `true` only when relation holds
+//! for every value, like `certainly_lt`, so branches taken on `true` stay valid.
+//!
+//! ## Example
+//!
+//! ```rust
+//! extern crate inter;
+//! extern crate num_traits;
+//!
+//! use inter::Interval;
+//! use num_traits::{FloatConst, FromPrimitive, Num, Zero};
+//!
+//! // Simpson's rule, exact for cubic polynomials
+//! fn simpson<T, F>(f: F, a: T, b: T) -> T
+//!     where T: Num + FromPrimitive + Copy,
+//!           F: Fn(T) -> T
+//! {
+//!     let n = |n| T::from_u8(n).unwrap();
+//!     (b - a) / n(6) * (f(a) + n(4) * f((a + b) / n(2)) + f(b))
+//! }
+//!
+//! # fn main() {
+//! let x = simpson(|x: Interval<f64>| x * x, Interval::zero(), Interval::PI());
+//! assert!(x.contains(std::f64::consts::PI.powi(3) / 3.));
+//! assert!(x.width() < 1e-13);
+//! # }
+//! ```
+
+use std::fmt::LowerExp;
+use std::str::FromStr;
+
+use num::{Bounded, Float, FloatConst, FromPrimitive, Num, Signed};
+
+use interval::Interval;
+use interval::widen;
+use parse::ParseIntervalError;
+use rounding::RoundedArith;
+
+impl<T> Num for Interval<T>
+    where T: Float + FromStr + LowerExp + RoundedArith
+{
+    type FromStrRadixErr = ParseIntervalError;
+
+    /// Parse interval in any form accepted by `from_str`, which has to be decimal
+    fn from_str_radix(s: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+        if radix != 10 {
+            return Err(ParseIntervalError::Number);
+        }
+
+        s.parse()
+    }
+}
+
+impl<T> Signed for Interval<T>
+    where T: Float + FromStr + LowerExp + RoundedArith
+{
+    fn abs(&self) -> Self {
+        Interval::abs(self)
+    }
+
+    fn abs_sub(&self, other: &Self) -> Self {
+        Interval::abs_sub(*self, *other)
+    }
+
+    fn signum(&self) -> Self {
+        Interval::signum(*self)
+    }
+
+    /// Check if all values are positive
+    fn is_positive(&self) -> bool {
+        self.start > T::zero() && !self.is_empty()
+    }
+
+    /// Check if all values are negative
+    fn is_negative(&self) -> bool {
+        self.end < T::zero() && !self.is_empty()
+    }
+}
+
+impl<T> FromPrimitive for Interval<T>
+    where T: Float + FromPrimitive + RoundedArith
+{
+    fn from_i64(n: i64) -> Option<Self> {
+        Some(Interval::from_i64_outward(n))
+    }
+
+    fn from_u64(n: u64) -> Option<Self> {
+        Some(Interval::from_u64_outward(n))
+    }
+
+    fn from_i128(n: i128) -> Option<Self> {
+        Some(Interval::from_i128_outward(n))
+    }
+
+    fn from_u128(n: u128) -> Option<Self> {
+        Some(Interval::from_u128_outward(n))
+    }
+
+    /// Tightest interval containing `n`, or `None` when it is NaN
+    fn from_f64(n: f64) -> Option<Self> {
+        if n.is_nan() {
+            None
+        } else {
+            Some(Interval::from_f64_outward(n))
+        }
+    }
+}
+
+/// Degenerate intervals at bounds of `T`
+impl<T> Bounded for Interval<T>
+    where T: Bounded + Copy
+{
+    fn min_value() -> Self {
+        let min = T::min_value();
+
+        Interval {
+            start: min,
+            end: min,
+        }
+    }
+
+    fn max_value() -> Self {
+        let max = T::max_value();
+
+        Interval {
+            start: max,
+            end: max,
+        }
+    }
+}
+
+macro_rules! float_consts {
+    ($($name:ident)*) => {
+        /// Constants of `T` widened by an ulp in both directions
+        ///
+        /// Constants of `T` have to be correctly rounded, as for `f32` and `f64`.
+        impl<T> FloatConst for Interval<T>
+            where T: Float + FloatConst + RoundedArith
+        {
+            $(
+                fn $name() -> Self {
+                    let (start, end) = widen(T::$name(), 1);
+
+                    Interval { start, end }
+                }
+            )*
+        }
+    };
+}
+
+float_consts! {
+    E FRAC_1_PI FRAC_1_SQRT_2 FRAC_2_PI FRAC_2_SQRT_PI FRAC_PI_2 FRAC_PI_3 FRAC_PI_4 FRAC_PI_6
+    FRAC_PI_8 LN_10 LN_2 LOG10_E LOG2_E PI SQRT_2 TAU LOG10_2 LOG2_10
+}
+
+impl<T> Interval<T>
+    where T: Float + RoundedArith
+{
+    /// Larger of values from `self` and `other`
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// let x = Interval::with_range(-1., 3.);
+    /// assert_eq!(x.max(Interval::with_range(0., 1.)), Interval::with_range(0., 3.));
+    /// ```
+    pub fn max(self, other: Self) -> Self {
+        if let Some(empty) = self.either_empty(&other) {
+            return empty;
+        }
+
+        Interval {
+            start: self.start.max(other.start),
+            end: self.end.max(other.end),
+        }
+    }
+
+    /// Smaller of values from `self` and `other`
+    pub fn min(self, other: Self) -> Self {
+        if let Some(empty) = self.either_empty(&other) {
+            return empty;
+        }
+
+        Interval {
+            start: self.start.min(other.start),
+            end: self.end.min(other.end),
+        }
+    }
+
+    /// Signs of values, between `-1` and `1`
+    ///
+    /// Unlike `Float::signum`, the sign of zero is zero.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// assert_eq!(Interval::with_range(-2., 0.).signum(), Interval::with_range(-1., 0.));
+    /// assert_eq!(Interval::with_range(1., 2.).signum(), Interval::exact(1.));
+    /// ```
+    pub fn signum(self) -> Self {
+        let sign = |x: T| if x > T::zero() {
+            T::one()
+        } else if x < T::zero() {
+            -T::one()
+        } else {
+            T::zero()
+        };
+        if self.is_empty() {
+            return self;
+        }
+
+        Interval {
+            start: sign(self.start),
+            end: sign(self.end),
+        }
+    }
+
+    /// Positive parts of differences, `max(self - other, 0)`
+    pub fn abs_sub(self, other: Self) -> Self {
+        (self - other).max(Interval::exact(T::zero()))
+    }
+
+    /// Reciprocal `1 / self`
+    pub fn recip(self) -> Self {
+        Interval::exact(T::one()) / self
+    }
+
+    /// Length of hypotenuse `√(self² + other²)`
+    pub fn hypot(self, other: Self) -> Self {
+        (self.sqr() + other.sqr()).sqrt()
+    }
+
+    /// Logarithm with respect to `base`
+    pub fn log(self, base: Self) -> Self {
+        self.ln() / base.ln()
+    }
+
+}
+
+impl<T> Interval<T>
+    where T: Float + FloatConst + RoundedArith
+{
+    /// Sine and cosine at once
+    pub fn sin_cos(self) -> (Self, Self) {
+        (self.sin(), self.cos())
+    }
+
+    /// Convert radians to degrees
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// assert!(Interval::pi().to_degrees().contains(180.));
+    /// ```
+    pub fn to_degrees(self) -> Self {
+        self * (Interval::exact(T::from(180).unwrap()) / Interval::pi())
+    }
+
+    /// Convert degrees to radians
+    pub fn to_radians(self) -> Self {
+        self * (Interval::pi() / Interval::exact(T::from(180).unwrap()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use num::{one, Zero};
+    use policy;
+
+    fn generic<T: Num + Signed + FromPrimitive + FloatConst + Bounded + Copy>(x: T) -> T {
+        let half = T::one() / T::from_u8(2).unwrap();
+        if x.is_negative() {
+            T::min_value()
+        } else {
+            (x - half).abs() * T::PI() % (T::one() + one())
+        }
+    }
+
+    #[test]
+    fn traits() {
+        for &x in &[0.5, 1.25, 3., -2.] {
+            let y = generic(Interval::exact(x));
+            assert!(y.contains(generic(x)), "{} {}", x, y);
+        }
+        assert!(generic(Interval::with_range(1., 1.5)).contains(generic(1.2)));
+
+        assert_eq!(Interval::<f64>::from_str_radix("[1, 2]", 10), "[1, 2]".parse());
+        assert!(Interval::<f64>::from_str_radix("10", 2).is_err());
+        assert!(Interval::<f64>::from_f64(f64::NAN).is_none());
+        assert_eq!(Interval::<f32>::from_u8(3), Some(Interval::exact(3.)));
+        assert!(!Interval::<f32>::from_f64(0.1).unwrap().is_exact());
+        for x in &[Interval::<f64>::E(), Interval::LN_2(), Interval::SQRT_2(), Interval::TAU()] {
+            assert!(!x.is_exact() && x.width() < 1e-15 * x.sup());
+        }
+        assert_eq!(Interval::<f64>::PI(), Interval::pi());
+
+        let x = Interval::with_range(-1., 2.);
+        assert!(!x.is_positive() && !x.is_negative() && !Interval::<f64>::empty().is_positive());
+        assert!(Interval::exact(-0.5).is_negative());
+        assert_eq!(x.signum(), Interval::with_range(-1., 1.));
+        assert_eq!(Interval::exact(0.).signum(), Interval::zero());
+        assert_eq!(Signed::abs_sub(&x, &Interval::exact(1.)), Interval::with_range(0., 1.));
+    }
+
+    #[test]
+    fn methods() {
+        let (x, y) = (Interval::with_range(-1., 2.), Interval::with_range(3., 4.));
+        assert_eq!(x.min(y), x);
+        assert_eq!(x.max(y), y);
+        assert!(x.max(Interval::empty()).is_empty());
+        assert_eq!(y.hypot(Interval::exact(0.)), y);
+        assert!(Interval::exact(3.).hypot(Interval::exact(4.)).contains(5.));
+        assert!(y.recip().contains(1. / 3.) && y.recip().inf() == 0.25);
+        assert!(Interval::exact(8.).log(Interval::exact(2.)).contains(3.));
+        assert_eq!(x.sin_cos(), (x.sin(), x.cos()));
+        assert!(Interval::exact(90.).to_radians().contains(::std::f64::consts::FRAC_PI_2));
+        assert!(Interval::exact(1.).to_radians().to_degrees().contains(1.));
+    }
+
+    #[test]
+    fn remainders() {
+        let samples = [(-7.5, -2.), (-3., 4.), (0., 1.), (5., 9.), (1e300, 1e301), (0.1, 0.1)];
+        let divisors = [(3., 3.), (-2.5, -0.5), (0.25, 4.), (-1., 1.), (1e-300, 1e-299)];
+        for &(a, b) in &samples {
+            for &(c, d) in &divisors {
+                let (x, y) = (Interval::with_range(a, b), Interval::with_range(c, d));
+                let r = x % y;
+                for i in 0..=16 {
+                    for j in 0..=16 {
+                        let u = a + (b - a) * i as f64 / 16.;
+                        let v = c + (d - c) * j as f64 / 16.;
+                        assert!(v == 0. || r.contains(u % v), "{} % {} = {}", x, y, r);
+                    }
+                }
+            }
+        }
+        assert_eq!(Interval::with_range(1., 2.) % Interval::exact(0.), Interval::empty());
+        assert_eq!(Interval::with_range(-1., 2.) % Interval::with_range(-3., 0.),
+                   Interval::with_range(-1., 2.));
+        assert!(policy::checked(|| Interval::exact(1.) % Interval::exact(0.))
+            .is_err());
+    }
+}
@@ -0,0 +1,277 @@
+//! Reverse operations for constraint propagation.
+//!
+//! Every method contracts `self`, an operand of some operation, to the values consistent with
+//! the enclosure of its result (and of the other operand). Together with forward evaluation they
+//! form the forward–backward (HC4) contractors of constraint solvers: evaluate expression
+//! bottom-up, intersect the root with the constraint and propagate it back to the variables.
+//! Results are hulls, so they can keep values that are not solutions, but they never lose one.
+//!
+//! ## Example
+//!
+//! Contract box for `x * y = 1`, `x ≥ 0.5`:
+//!
+//! ```rust
+//! use inter::Interval;
+//!
+//! let (x, y) = (Interval::with_range(0.5, 10.), Interval::with_range(0., 1.5));
+//! let z = Interval::exact(1.);
+//!
+//! let x = x.mul_rev(y, z);
+//! let y = y.mul_rev(x, z);
+//!
+//! assert!(x.subset(&Interval::with_range(0.66, 10.)) && x.contains(2. / 3.));
+//! assert!(y.subset(&Interval::with_range(0.099, 1.5)) && y.contains(0.1));
+//! ```
+
+use num::{Bounded, Float, FloatConst};
+
+use interval::Interval;
+use rounding::RoundedArith;
+use utils::partial_min;
+
+impl<T> Interval<T>
+    where T: Float + Bounded + RoundedArith
+{
+    /// Contract `self` to values `x` with `x + y` in `result` for some `y` in `other`
+    pub fn add_rev(self, other: Self, result: Self) -> Self {
+        self.intersect(&(result - other))
+    }
+
+    /// Contract `self` to values `x` with `x - y` in `result` for some `y` in `other`
+    ///
+    /// Right operand `y` is contracted by `y.intersect(&(x - result))`.
+    pub fn sub_rev(self, other: Self, result: Self) -> Self {
+        self.intersect(&(result + other))
+    }
+
+    /// Contract `self` to values `x` with `x * y` in `result` for some `y` in `other`
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// let x = Interval::with_range(-10., 10.);
+    /// let y = Interval::with_range(-1., 1.);
+    ///
+    /// assert_eq!(x.mul_rev(y, Interval::with_range(2., 3.)), x);
+    /// assert_eq!(x.mul_rev(Interval::exact(2.), Interval::with_range(2., 3.)),
+    ///            Interval::with_range(1., 1.5));
+    /// assert!(Interval::with_range(1., 2.).mul_rev(Interval::exact(0.), Interval::exact(1.))
+    ///     .is_empty());
+    /// ```
+    pub fn mul_rev(self, other: Self, result: Self) -> Self {
+        if other.is_empty() || result.is_empty() {
+            return Interval::empty();
+        }
+
+        match result.div_split(other) {
+            Some(quotient) => {
+                quotient.into_iter().fold(Interval::empty(), |acc, q| acc.hull(&self.intersect(&q)))
+            }
+            None => Interval::empty(),
+        }
+    }
+
+    /// Contract `self` to values `x` with `x / y` in `result` for some `y` in `other`
+    pub fn div_rev(self, other: Self, result: Self) -> Self {
+        self.intersect(&(result * other))
+    }
+
+    /// Contract `self` to values `x` with `x²` in `result`
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    ///
+    /// let x = Interval::with_range(-3., 1.);
+    ///
+    /// assert_eq!(x.sqr_rev(Interval::with_range(4., 9.)), Interval::with_range(-3., -2.));
+    /// assert_eq!(x.sqr_rev(Interval::with_range(0., 4.)), Interval::with_range(-2., 1.));
+    /// ```
+    pub fn sqr_rev(self, result: Self) -> Self {
+        let root = result.sqrt();
+        if root.is_empty() {
+            return Interval::empty();
+        }
+
+        self.intersect(&-root).hull(&self.intersect(&root))
+    }
+
+    /// Contract `self` to values `x` with `|x|` in `result`
+    pub fn abs_rev(self, result: Self) -> Self {
+        let result = result.intersect(&Interval::with_range(T::zero(), T::infinity()));
+        if result.is_empty() {
+            return Interval::empty();
+        }
+
+        self.intersect(&-result).hull(&self.intersect(&result))
+    }
+
+    /// Contract `self` to values `x` with `√x` in `result`
+    pub fn sqrt_rev(self, result: Self) -> Self {
+        let result = result.intersect(&Interval::with_range(T::zero(), T::infinity()));
+        if result.is_empty() {
+            return Interval::empty();
+        }
+
+        self.intersect(&(result * result))
+    }
+
+    /// Contract `self` to values `x` with `eˣ` in `result`
+    pub fn exp_rev(self, result: Self) -> Self {
+        let result = result.intersect(&Interval::with_range(T::zero(), T::infinity()));
+        if result.is_empty() {
+            return Interval::empty();
+        }
+
+        self.intersect(&result.ln())
+    }
+
+    /// Contract `self` to values `x` with `ln x` in `result`
+    pub fn ln_rev(self, result: Self) -> Self {
+        self.intersect(&result.exp())
+    }
+}
+
+impl<T> Interval<T>
+    where T: Float + FloatConst + Bounded + RoundedArith
+{
+    /// Contract `self` to values `x` with `sin x` in `result`
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    /// use std::f64::consts::PI;
+    ///
+    /// let x = Interval::with_range(0., 10.).sin_rev(Interval::with_range(0.5, 1.));
+    ///
+    /// assert!(x.contains(PI / 6.) && x.contains(2. * PI + 5. * PI / 6.));
+    /// assert!(x.subset(&Interval::with_range(0.52, 8.91)));
+    /// ```
+    pub fn sin_rev(self, result: Self) -> Self {
+        let result = result.intersect(&Interval::with_range(-T::one(), T::one()));
+        if result.is_empty() {
+            return Interval::empty();
+        }
+
+        // Principal branch of arcsine and its reflection `π - asin`
+        let principal = result.asin();
+        self.periodic_rev([principal, Interval::pi() - principal])
+    }
+
+    /// Contract `self` to values `x` with `cos x` in `result`
+    pub fn cos_rev(self, result: Self) -> Self {
+        let result = result.intersect(&Interval::with_range(-T::one(), T::one()));
+        if result.is_empty() {
+            return Interval::empty();
+        }
+
+        // Principal branch of arccosine and its reflection `-acos`
+        let principal = result.acos();
+        self.periodic_rev([principal, -principal])
+    }
+
+    /// Contract `self` to `branches + 2kπ`, where branches lie in `[-π, 2π]`
+    fn periodic_rev(self, branches: [Self; 2]) -> Self {
+        if self.is_empty() {
+            return self;
+        }
+
+        let two_pi = Interval::pi() * Interval::exact(T::one() + T::one());
+        let hits = |k: T| {
+            let shift = two_pi * Interval::exact(k);
+            branches.iter()
+                .fold(Interval::empty(), |acc, b| acc.hull(&self.intersect(&(*b + shift))))
+        };
+        // Beyond that periods cannot be enumerated reliably, so bound is not contracted
+        let limit = T::one() / T::epsilon();
+        let three = T::one() + T::one() + T::one();
+        let mut ret = self;
+
+        if self.start.abs() < limit {
+            let mut k = (self.start / two_pi.start).floor() - three;
+            loop {
+                let hit = hits(k);
+                if !hit.is_empty() {
+                    ret.start = partial_min(hit.start, hits(k + T::one()).start);
+                    break;
+                }
+                if (-Interval::pi() + two_pi * Interval::exact(k)).start > self.end {
+                    return Interval::empty();
+                }
+                k = k + T::one();
+            }
+        }
+        if self.end.abs() < limit {
+            let mut k = (self.end / two_pi.start).floor() + three;
+            loop {
+                let hit = hits(k);
+                if !hit.is_empty() {
+                    ret.end = hit.hull(&hits(k - T::one())).end;
+                    break;
+                }
+                if (two_pi + two_pi * Interval::exact(k)).end < self.start {
+                    return Interval::empty();
+                }
+                k = k - T::one();
+            }
+        }
+
+        ret
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::f64::consts::PI;
+
+    use interval::Interval;
+
+    #[test]
+    fn arithmetic() {
+        let x = Interval::with_range(0., 10.);
+        let y = Interval::with_range(1., 2.);
+
+        assert_eq!(x.add_rev(y, Interval::with_range(3., 4.)), Interval::with_range(1., 3.));
+        assert_eq!(x.sub_rev(y, Interval::with_range(3., 4.)), Interval::with_range(4., 6.));
+        assert_eq!(x.div_rev(y, Interval::with_range(3., 4.)), Interval::with_range(3., 8.));
+        assert_eq!(x.mul_rev(y, Interval::with_range(-1., 4.)), Interval::with_range(0., 4.));
+        assert!(x.add_rev(y, Interval::with_range(-3., -2.)).is_empty());
+        assert!(x.add_rev(y, Interval::empty()).is_empty());
+    }
+
+    #[test]
+    fn elementary() {
+        let x = Interval::with_range(-10., 10.);
+        let r = Interval::with_range(1., 4.);
+
+        assert_eq!(x.abs_rev(r), x.intersect(&Interval::with_range(-4., 4.)));
+        assert_eq!(x.sqrt_rev(r), Interval::with_range(1., 10.));
+        assert!(x.exp_rev(r).contains_all(&[0., 4f64.ln()]));
+        assert!(x.exp_rev(r).subset(&Interval::with_range(-1e-15, 1.387)));
+        assert!(x.exp_rev(Interval::with_range(-2., -1.)).is_empty());
+        assert!(x.ln_rev(r).contains_all(&[1f64.exp(), 4f64.exp().min(10.)]));
+        assert!(x.sqr_rev(Interval::with_range(-2., -1.)).is_empty());
+    }
+
+    #[test]
+    fn trigonometric() {
+        let x = Interval::with_range(-1., 4.);
+
+        let c = x.cos_rev(Interval::with_range(-1., 0.));
+        assert!(c.contains_all(&[PI / 2., 4.]) && c.subset(&Interval::with_range(1.57, 4.)));
+        assert_eq!(x.cos_rev(Interval::with_range(-2., 2.)), x);
+        assert!(x.sin_rev(Interval::with_range(2., 3.)).is_empty());
+        assert!(Interval::with_range(0.1, 0.2).sin_rev(Interval::with_range(0.5, 1.)).is_empty());
+
+        // Solutions near large arguments and on unbounded intervals
+        let far = Interval::with_range(1000., 1010.).sin_rev(Interval::exact(0.));
+        assert!(far.contains_all(&[319. * PI, 321. * PI]));
+        assert!(far.subset(&Interval::with_range(1002., 1009.)));
+        let half = Interval::with_range(0.5, f64::INFINITY).sin_rev(Interval::exact(0.));
+        assert!(half.contains(PI) && half.start > 3. && half.end == f64::INFINITY);
+    }
+}
@@ -0,0 +1,326 @@
+//! Hulls and intersections over sliding windows of interval streams, and their compression.
+//!
+//! Monitoring keeps the envelope of the last `k` measurements, or the values consistent with all
+//! of them, as new ones arrive. Windows keep bounds in monotone queues: a bound that can no
+//! longer be the extreme one, because a newer one is at least as extreme, is dropped as soon as
+//! it is dominated. Every bound is then queued and dropped once, so a push takes constant time
+//! amortized, whatever the size of window.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use inter::Interval;
+//! use inter::rolling::RollingHull;
+//!
+//! let mut envelope = RollingHull::new(3);
+//! for &(low, high) in &[(0., 1.), (5., 6.), (2., 3.)] {
+//!     envelope.push(Interval::with_range(low, high));
+//! }
+//! assert_eq!(envelope.value(), Interval::with_range(0., 6.));
+//!
+//! // The first reading leaves the window
+//! assert_eq!(envelope.push(Interval::with_range(2.5, 4.)), Interval::with_range(2., 6.));
+//! ```
+//!
+//! Long sequences, like tubes of trajectories, can be stored as runs of consecutive intervals
+//! replaced by their hull. `compress` makes runs as long as the hull widens none of the
+//! intervals by more than a given budget on either side.
+
+use std::collections::VecDeque;
+
+use num::{Bounded, Float};
+
+use interval::Interval;
+use rounding::RoundedArith;
+use utils::{partial_max, partial_min};
+
+/// Bounds of last `window` values, with the extreme one in front
+#[derive(Clone, Debug, PartialEq)]
+struct Extreme<T> {
+    // Pairs of index of value and bound
+    queue: VecDeque<(usize, T)>,
+}
+
+impl<T> Extreme<T>
+    where T: Copy + PartialOrd
+{
+    fn new() -> Self {
+        Extreme { queue: VecDeque::new() }
+    }
+
+    /// Add `bound` of value `index`, dropping the older bounds for which `dominates` holds
+    fn push<F>(&mut self, index: usize, bound: T, window: usize, dominates: F)
+        where F: Fn(T, T) -> bool
+    {
+        while self.queue.back().is_some_and(|&(_, last)| dominates(bound, last)) {
+            self.queue.pop_back();
+        }
+        self.queue.push_back((index, bound));
+        while self.queue.front().is_some_and(|&(first, _)| first + window <= index) {
+            self.queue.pop_front();
+        }
+    }
+
+    fn value(&self) -> Option<T> {
+        self.queue.front().map(|&(_, bound)| bound)
+    }
+}
+
+macro_rules! rolling {
+    ($name:ident, $doc:expr, $start:expr, $end:expr, $identity:ident, $function:ident,
+     $function_doc:expr) => {
+        #[doc = $doc]
+        #[derive(Clone, Debug, PartialEq)]
+        pub struct $name<T> {
+            window: usize,
+            pushed: usize,
+            start: Extreme<T>,
+            end: Extreme<T>,
+        }
+
+        impl<T> $name<T>
+            where T: Float + Bounded + RoundedArith
+        {
+            /// Window over last `window` values
+            ///
+            /// # Panics
+            ///
+            /// This will panic if `window` is zero.
+            pub fn new(window: usize) -> Self {
+                assert!(window > 0, "window cannot be empty");
+
+                $name {
+                    window,
+                    pushed: 0,
+                    start: Extreme::new(),
+                    end: Extreme::new(),
+                }
+            }
+
+            /// Add `x` to the window, dropping the oldest value if it was full, and return the
+            /// new result
+            pub fn push(&mut self, x: Interval<T>) -> Interval<T> {
+                let x = if x.is_empty() { Interval::empty() } else { x };
+                let (index, window) = (self.pushed, self.window);
+                self.start.push(index, x.start, window, $start);
+                self.end.push(index, x.end, window, $end);
+                self.pushed += 1;
+
+                self.value()
+            }
+
+            /// Result over values in the window
+            pub fn value(&self) -> Interval<T> {
+                match (self.start.value(), self.end.value()) {
+                    (Some(start), Some(end)) if start <= end => Interval { start, end },
+                    (Some(_), Some(_)) => Interval::empty(),
+                    _ => Interval::$identity(),
+                }
+            }
+
+            /// Number of values in the window, at most its size
+            pub fn len(&self) -> usize {
+                self.pushed.min(self.window)
+            }
+
+            /// Check if no value was added yet
+            pub fn is_empty(&self) -> bool {
+                self.pushed == 0
+            }
+        }
+
+        #[doc = $function_doc]
+        ///
+        /// Result `i` is over `xs[i + 1 - window..=i]`, so the first `window - 1` results are over
+        /// shorter windows.
+        ///
+        /// # Panics
+        ///
+        /// This will panic if `window` is zero.
+        pub fn $function<T>(xs: &[Interval<T>], window: usize) -> Vec<Interval<T>>
+            where T: Float + Bounded + RoundedArith
+        {
+            let mut rolling = $name::new(window);
+            xs.iter().map(|&x| rolling.push(x)).collect()
+        }
+    }
+}
+
+rolling!(RollingHull,
+         "Convex hull of the last values, which is empty before the first one",
+         |new, old| new <= old,
+         |new, old| new >= old,
+         empty,
+         rolling_hulls,
+         "Hulls over windows ending at every element of `xs`");
+rolling!(RollingIntersection,
+         "Intersection of the last values, which is the whole line before the first one",
+         |new, old| new >= old,
+         |new, old| new <= old,
+         entire,
+         rolling_intersections,
+         "Intersections over windows ending at every element of `xs`");
+
+/// Run of consecutive intervals replaced by their hull, see `compress`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Segment<T> {
+    /// Number of intervals in the run
+    pub len: usize,
+    /// Hull of intervals in the run
+    pub hull: Interval<T>,
+}
+
+/// Split `xs` into the fewest runs whose hulls extend no interval by more than `budget`
+///
+/// Every interval of a run is a subset of its hull, and the hull starts at most `budget` below
+/// the interval and ends at most `budget` above it. Runs are made greedily as long as possible,
+/// which gives the fewest of them. Empty intervals widen nothing and join the current run.
+///
+/// ## Example
+///
+/// ```rust
+/// use inter::Interval;
+/// use inter::rolling;
+///
+/// let tube: Vec<_> = (0..100).map(|i| Interval::with_epsilon(i as f64 * 0.01, 0.5)).collect();
+/// let segments = rolling::compress(&tube, 0.1);
+///
+/// assert_eq!(segments.len(), 10);
+/// assert_eq!(segments[0].len, 11);
+/// assert_eq!(segments[0].hull, Interval::with_range(-0.5, 0.6));
+/// ```
+///
+/// # Panics
+///
+/// This will panic if `budget` is negative or NaN.
+pub fn compress<T>(xs: &[Interval<T>], budget: T) -> Vec<Segment<T>>
+    where T: Float + Bounded + RoundedArith
+{
+    assert!(budget >= T::zero(), "widening budget must not be negative");
+
+    let mut segments = Vec::new();
+    // Current run with largest start and smallest end of its intervals
+    let mut run = Segment {
+        len: 0,
+        hull: Interval::empty(),
+    };
+    let (mut max_start, mut min_end) = (T::neg_infinity(), T::infinity());
+
+    for &x in xs {
+        if x.is_empty() {
+            run.len += 1;
+            continue;
+        }
+
+        let hull = run.hull.hull(&x);
+        let (start, end) = (partial_max(max_start, x.start), partial_min(min_end, x.end));
+        // Rounded so that the budget is kept exactly
+        if run.len == 0 || start.sub_up(budget) <= hull.start && hull.end <= end.add_down(budget) {
+            run = Segment { len: run.len + 1, hull };
+            max_start = start;
+            min_end = end;
+        } else {
+            segments.push(run);
+            run = Segment { len: 1, hull: x };
+            max_start = x.start;
+            min_end = x.end;
+        }
+    }
+    if run.len > 0 {
+        segments.push(run);
+    }
+
+    segments
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn matches_direct_computation() {
+        let xs: Vec<_> = (0..200)
+            .map(|i| {
+                let t = i as f64;
+                if i % 37 == 5 {
+                    Interval::empty()
+                } else {
+                    let (a, b) = ((t * 0.7).sin(), (t * 0.3).cos() + 0.5);
+                    Interval::with_range(a.min(b), a.max(b))
+                }
+            })
+            .collect();
+
+        for &window in &[1, 2, 5, 40, 500] {
+            let hulls = rolling_hulls(&xs, window);
+            let intersections = rolling_intersections(&xs, window);
+            for i in 0..xs.len() {
+                let last = &xs[(i + 1).saturating_sub(window)..=i];
+                let hull = last.iter().fold(Interval::empty(), |acc, x| acc.hull(x));
+                let common = last.iter().fold(Interval::entire(), |acc, x| acc.intersect(x));
+                assert_eq!(hulls[i], hull, "{} {}", window, i);
+                let both_empty = intersections[i].is_empty() && common.is_empty();
+                assert!(intersections[i] == common || both_empty, "{} {}", window, i);
+            }
+        }
+    }
+
+    #[test]
+    fn compression() {
+        let xs: Vec<_> = (0..500)
+            .map(|i| match i % 97 {
+                13 => Interval::empty(),
+                50 => Interval::with_range(f64::NEG_INFINITY, 0.),
+                _ => Interval::with_epsilon((i as f64 * 0.05).sin(), 0.2 + (i % 7) as f64 * 0.01),
+            })
+            .collect();
+
+        for &budget in &[0., 0.05, 0.3, f64::INFINITY] {
+            let segments = compress(&xs, budget);
+            assert_eq!(segments.iter().map(|s| s.len).sum::<usize>(), xs.len());
+
+            let mut offset = 0;
+            for segment in &segments {
+                let members: Vec<_> = xs[offset..offset + segment.len]
+                    .iter()
+                    .filter(|x| !x.is_empty())
+                    .collect();
+                offset += segment.len;
+
+                let hull = segment.hull;
+                for x in &members {
+                    assert!(x.subset(&hull));
+                    assert!(x.start - hull.start <= budget || x.start == hull.start);
+                    assert!(hull.end - x.end <= budget);
+                }
+                assert_eq!(members.iter().fold(Interval::empty(), |acc, x| acc.hull(x)), hull);
+                // Greedy runs could not take the next interval
+                if let Some(next) = xs.get(offset) {
+                    let hull = hull.hull(next);
+                    let widened = members.iter().chain(Some(&next)).any(|x| {
+                        x.start - hull.start > budget || hull.end - x.end > budget
+                    });
+                    assert!(widened, "{} {}", budget, offset);
+                }
+            }
+        }
+        assert_eq!(compress(&xs, f64::INFINITY).len(), 1);
+        assert_eq!(compress(&[Interval::<f64>::empty(); 2], 0.),
+                   [Segment { len: 2, hull: Interval::empty() }]);
+        assert!(compress::<f64>(&[], 1.).is_empty());
+    }
+
+    #[test]
+    fn windows() {
+        let mut rolling = RollingIntersection::new(2);
+        assert!(rolling.is_empty() && rolling.value() == Interval::entire());
+        rolling.push(Interval::with_range(0., 4.));
+        assert_eq!(rolling.push(Interval::with_range(1., 5.)), Interval::with_range(1., 4.));
+        assert_eq!(rolling.push(Interval::with_range(-1., 2.)), Interval::with_range(1., 2.));
+        assert!(rolling.push(Interval { start: f64::NAN, end: 0. }).is_empty());
+        assert_eq!(rolling.len(), 2);
+
+        assert!(RollingHull::<f64>::new(3).value().is_empty());
+        assert!(::std::panic::catch_unwind(|| RollingHull::<f64>::new(0)).is_err());
+    }
+}
@@ -1,27 +1,141 @@
+#[cfg(feature = "libc")]
+use std::cell::Cell;
+#[cfg(feature = "libc")]
+use std::error;
+#[cfg(feature = "libc")]
+use std::fmt;
+#[cfg(feature = "libc")]
+use std::marker::PhantomData;
+#[cfg(feature = "libc")]
+use std::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
+
+#[cfg(feature = "libc")]
 use libc::c_int;
+
+use interval::Interval;
+use num::Bounded;
+#[cfg(feature = "libc")]
 use num::FromPrimitive;
+use num::Float;
+use utils::{next_down, next_up};
+
+/// Access to rounding mode of the floating-point unit
+///
+/// Values of modes differ between platforms, and MSVC does not have `fesetround`, so the mode is
+/// switched with `_controlfp_s` there.
+#[cfg(feature = "libc")]
+mod sys {
+    use libc::c_int;
+    #[cfg(target_env = "msvc")]
+    use libc::c_uint;
+
+    #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), not(target_env = "msvc")))]
+    mod modes {
+        pub const TO_NEAREST: isize = 0x0000;
+        pub const DOWNWARD: isize = 0x0400;
+        pub const UPWARD: isize = 0x0800;
+        pub const TOWARD_ZERO: isize = 0x0C00;
+    }
+
+    // `_RC_*` constants of `_controlfp_s`
+    #[cfg(target_env = "msvc")]
+    mod modes {
+        pub const TO_NEAREST: isize = 0x0000;
+        pub const DOWNWARD: isize = 0x0100;
+        pub const UPWARD: isize = 0x0200;
+        pub const TOWARD_ZERO: isize = 0x0300;
+    }
+
+    // `RMode` field of FPCR (or FPSCR on 32-bit ARM)
+    #[cfg(all(any(target_arch = "aarch64", target_arch = "arm"), not(target_env = "msvc")))]
+    mod modes {
+        pub const TO_NEAREST: isize = 0x000000;
+        pub const UPWARD: isize = 0x400000;
+        pub const DOWNWARD: isize = 0x800000;
+        pub const TOWARD_ZERO: isize = 0xC00000;
+    }
+
+    #[cfg(any(target_arch = "powerpc", target_arch = "powerpc64"))]
+    mod modes {
+        pub const TO_NEAREST: isize = 0;
+        pub const TOWARD_ZERO: isize = 1;
+        pub const UPWARD: isize = 2;
+        pub const DOWNWARD: isize = 3;
+    }
+
+    #[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
+    mod modes {
+        pub const TO_NEAREST: isize = 0;
+        pub const TOWARD_ZERO: isize = 1;
+        pub const DOWNWARD: isize = 2;
+        pub const UPWARD: isize = 3;
+    }
+
+    #[cfg(not(any(target_arch = "x86",
+                  target_arch = "x86_64",
+                  target_arch = "aarch64",
+                  target_arch = "arm",
+                  target_arch = "powerpc",
+                  target_arch = "powerpc64",
+                  target_arch = "riscv32",
+                  target_arch = "riscv64")))]
+    compile_error!("switching rounding mode is not supported on this target, disable the \
+                    `libc` feature");
+
+    pub use self::modes::*;
+
+    #[cfg(not(target_env = "msvc"))]
+    extern "C" {
+        pub fn fesetround(flag: c_int) -> c_int;
+        pub fn fegetround() -> c_int;
+    }
+
+    #[cfg(target_env = "msvc")]
+    extern "C" {
+        fn _controlfp_s(current: *mut c_uint, new: c_uint, mask: c_uint) -> c_int;
+    }
+
+    #[cfg(target_env = "msvc")]
+    const MCW_RC: c_uint = 0x0300;
 
-extern "C" {
-    fn fesetround(flag: c_int) -> c_int;
-    fn fegetround() -> c_int;
+    /// Set rounding mode, returning zero on success like C `fesetround`
+    #[cfg(target_env = "msvc")]
+    pub unsafe fn fesetround(flag: c_int) -> c_int {
+        let mut current = 0;
+        _controlfp_s(&mut current, flag as c_uint, MCW_RC)
+    }
+
+    /// Current rounding mode, like C `fegetround`
+    #[cfg(target_env = "msvc")]
+    pub unsafe fn fegetround() -> c_int {
+        let mut current = 0;
+        _controlfp_s(&mut current, 0, 0);
+
+        (current & MCW_RC) as c_int
+    }
 }
 
+/// Rounding mode of the floating-point unit
+///
+/// Switching mode calls `fesetround` from the C library (or `_controlfp_s` with MSVC), so it is
+/// available only with the `libc` feature (enabled by default). `RoundedArith` does not need it.
+/// Values of variants are the flags of the target platform.
+#[cfg(feature = "libc")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Rounding {
-    ToNearest = 0x0000,
-    Downward = 0x0400,
-    Upward = 0x0800,
-    TowardZero = 0x0C00,
+    ToNearest = sys::TO_NEAREST,
+    Downward = sys::DOWNWARD,
+    Upward = sys::UPWARD,
+    TowardZero = sys::TOWARD_ZERO,
 }
 
+#[cfg(feature = "libc")]
 impl FromPrimitive for Rounding {
     fn from_i64(n: i64) -> Option<Self> {
-        match n {
-            0x0000 => Some(Rounding::ToNearest),
-            0x0400 => Some(Rounding::Downward),
-            0x0800 => Some(Rounding::Upward),
-            0x0C00 => Some(Rounding::TowardZero),
-            _ => None,
-        }
+        [Rounding::ToNearest, Rounding::Downward, Rounding::Upward, Rounding::TowardZero]
+            .iter()
+            .cloned()
+            .find(|&mode| mode as i64 == n)
     }
 
     fn from_u64(n: u64) -> Option<Self> {
@@ -29,13 +143,20 @@ impl FromPrimitive for Rounding {
     }
 }
 
+#[cfg(feature = "libc")]
 impl Rounding {
+    /// Rounding mode of the current thread, `None` if it is not one of the known modes
     pub fn current() -> Option<Self> {
-        FromPrimitive::from_i32(unsafe { fegetround() })
+        FromPrimitive::from_i32(unsafe { sys::fegetround() })
     }
 
+    /// Switch the current thread to this mode until it is switched again
+    ///
+    /// Nothing restores the previous mode, so code called later runs with this one too. Prefer
+    /// `scoped` or `execute`, which compose with other code switching modes.
+    #[allow(clippy::result_unit_err)]
     pub fn set(self) -> Result<(), ()> {
-        let res = unsafe { fesetround(self as c_int) };
+        let res = unsafe { sys::fesetround(self as c_int) };
         if res == 0 {
             Ok(())
         } else {
@@ -43,11 +164,1087 @@ impl Rounding {
         }
     }
 
+    /// Switch the current thread to this mode until the returned guard is dropped
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::rounding::Rounding;
+    ///
+    /// {
+    ///     let _upward = Rounding::Upward.scoped();
+    ///     assert_eq!(Rounding::current(), Some(Rounding::Upward));
+    /// }
+    /// assert_eq!(Rounding::current(), Some(Rounding::ToNearest));
+    /// ```
+    ///
+    /// If the mode cannot be switched, the failure is reported to enclosing `checked` and
+    /// `RoundingGuard::is_switched` returns `false`.
+    pub fn scoped(self) -> RoundingGuard {
+        let old = unsafe { sys::fegetround() };
+        let switched = unsafe { sys::fesetround(self as c_int) } == 0;
+        if !switched {
+            fail();
+        }
+
+        RoundingGuard {
+            old,
+            switched,
+            _thread: PhantomData,
+        }
+    }
+
+    /// Run `func` with this rounding mode, restoring the previous one afterwards
+    ///
+    /// Mode is restored also when `func` panics.
     pub fn execute<R, T: FnOnce() -> R>(self, func: T) -> R {
-        let old = unsafe { fesetround(self as c_int) };
-        let ret = func();
-        unsafe { fesetround(old) };
+        let _guard = self.scoped();
+
+        func()
+    }
+}
+
+/// Guard restoring rounding mode of the current thread when dropped, created by
+/// `Rounding::scoped`
+///
+/// Rounding mode is part of the floating-point environment of a thread, so switching it does
+/// not affect other threads, but threads spawned while it is switched inherit it. Guard is
+/// not `Send`, as dropping it on another thread would restore the mode of the wrong thread.
+/// Nested guards restore modes correctly when dropped in reverse order, which lexical scopes
+/// guarantee.
+#[cfg(feature = "libc")]
+#[must_use = "previous rounding mode is restored as soon as the guard is dropped"]
+#[derive(Debug)]
+pub struct RoundingGuard {
+    old: c_int,
+    switched: bool,
+    _thread: PhantomData<*const ()>,
+}
+
+#[cfg(feature = "libc")]
+impl RoundingGuard {
+    /// Mode that will be restored
+    pub fn previous(&self) -> Option<Rounding> {
+        FromPrimitive::from_i32(self.old)
+    }
+
+    /// Check if the mode was switched, otherwise the thread still runs in the previous one
+    pub fn is_switched(&self) -> bool {
+        self.switched
+    }
+}
+
+#[cfg(feature = "libc")]
+impl Drop for RoundingGuard {
+    fn drop(&mut self) {
+        unsafe { sys::fesetround(self.old) };
+    }
+}
+
+/// Panic unless rounding mode of the current thread is `expected`
+///
+/// Rounding mode is a per-thread state, which other code (C libraries especially) can change
+/// and forget to restore. With the `check-rounding` feature every floating-point operation of
+/// this crate asserts that the mode is `Rounding::ToNearest` in debug builds, so such code is
+/// caught where it breaks the computation.
+///
+/// ## Example
+///
+/// ```rust
+/// use inter::rounding::{self, Rounding};
+///
+/// rounding::assert_mode(Rounding::ToNearest);
+/// Rounding::Upward.execute(|| rounding::assert_mode(Rounding::Upward));
+/// ```
+#[cfg(feature = "libc")]
+#[track_caller]
+pub fn assert_mode(expected: Rounding) {
+    let current = Rounding::current();
+    assert!(current == Some(expected),
+            "rounding mode is {:?}, expected {:?}",
+            current,
+            expected);
+}
+
+/// Switch every worker of a thread pool to rounding `mode`
+///
+/// Workers of pools keep their floating-point environment between tasks, and threads created
+/// while some mode was switched inherit it on some platforms, so applications with their own
+/// pools set the mode of every worker once, before scheduling interval work onto them. Pools
+/// have no common interface for that, so `broadcast` is given a closure and must run it once on
+/// every worker, and return after all of them did. With rayon that is
+/// `|init| { pool.broadcast(|_| init()); }`.
+///
+/// Fails if the mode could not be switched on some worker.
+///
+/// ## Example
+///
+/// ```rust
+/// use std::thread;
+/// use inter::rounding::{self, Rounding};
+///
+/// let workers = rounding::install_on_pool(|init| thread::scope(|scope| {
+///     for _ in 0..4 {
+///         scope.spawn(|| {
+///             init();
+///             // Work of a long-lived worker
+///             assert_eq!(Rounding::current(), Some(Rounding::ToNearest));
+///         });
+///     }
+/// }), Rounding::ToNearest);
+///
+/// assert_eq!(workers, Ok(()));
+/// ```
+#[cfg(feature = "libc")]
+#[allow(clippy::result_unit_err)]
+pub fn install_on_pool<B>(broadcast: B, mode: Rounding) -> Result<(), ()>
+    where B: FnOnce(&(dyn Fn() + Sync))
+{
+    let failed = AtomicBool::new(false);
+    broadcast(&|| if mode.set().is_err() {
+        failed.store(true, AtomicOrdering::Relaxed);
+    });
+
+    if failed.into_inner() { Err(()) } else { Ok(()) }
+}
+
+#[cfg(feature = "libc")]
+thread_local! {
+    static FAILED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Error returned by `checked` when directed rounding could not be guaranteed
+#[cfg(feature = "libc")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RoundingFailure;
+
+#[cfg(feature = "libc")]
+impl fmt::Display for RoundingFailure {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "rounding mode could not be switched or was changed")
+    }
+}
+
+#[cfg(feature = "libc")]
+impl error::Error for RoundingFailure {}
+
+/// Record failure of the rounding backend on the current thread
+#[cfg(feature = "libc")]
+fn fail() {
+    FAILED.with(|failed| failed.set(true));
+}
+
+/// Run `func`, failing if the rounding backend could not guarantee directed rounding in it
+///
+/// Backend failures do not make results wrong, as operations then degrade to wider bounds:
+/// with the `fesetround` feature bounds that could not be rounded in the right direction are
+/// infinite, so intervals become unbounded, and release builds with the `check-rounding`
+/// feature round both ways when they detect that the mode was changed. This tells whether it
+/// happened, so callers can tell such results apart from genuinely wide ones. Failures are
+/// tracked per thread, and nested calls report them to the outer ones as well.
+///
+/// ## Example
+///
+/// ```rust
+/// use inter::Interval;
+/// use inter::rounding;
+///
+/// let x = rounding::checked(|| Interval::with_range(1., 2.) / Interval::exact(3.));
+///
+/// assert!(x.unwrap().contains(0.5));
+/// ```
+#[cfg(feature = "libc")]
+pub fn checked<R, F: FnOnce() -> R>(func: F) -> Result<R, RoundingFailure> {
+    let outer = FAILED.with(|failed| failed.replace(false));
+    let result = func();
+    let failed = FAILED.with(|failed| failed.replace(outer || failed.get()));
+
+    if failed { Err(RoundingFailure) } else { Ok(result) }
+}
+
+/// Check if mode was changed behind the back of `RoundedArith`
+///
+/// With the `check-rounding` feature debug builds panic when it was, and release builds report
+/// the failure to `checked`. Results rounded to nearest are then off by at most one ulp in
+/// either direction, so operations use both neighbours.
+#[cfg(not(feature = "fesetround"))]
+#[inline]
+fn mode_changed() -> bool {
+    #[cfg(feature = "check-rounding")]
+    {
+        if cfg!(debug_assertions) {
+            assert_mode(Rounding::ToNearest);
+        } else if Rounding::current() != Some(Rounding::ToNearest) {
+            fail();
+            return true;
+        }
+    }
+
+    false
+}
+
+/// Arithmetic with results rounded downward or upward
+///
+/// This is what interval operations are built from. For integers every operation except
+/// division is exact, and division rounds towards negative or positive infinity. For floats the
+/// result is computed with the default rounding and then moved by at most one ulp, using
+/// error-free transformations to detect in which direction the exact result lies. Unlike
+/// switching the FPU rounding mode this stays correct under optimizations, as the compiler is
+/// free to assume the default mode.
+///
+/// With the `fesetround` feature floats use `Rounding::execute` instead. Bounds of the type make
+/// the canonical empty interval, see `Interval::empty`.
+pub trait RoundedArith: Copy + PartialOrd + Bounded {
+    /// Sum rounded downward
+    fn add_down(self, other: Self) -> Self;
+    /// Sum rounded upward
+    fn add_up(self, other: Self) -> Self;
+    /// Difference rounded downward
+    fn sub_down(self, other: Self) -> Self;
+    /// Difference rounded upward
+    fn sub_up(self, other: Self) -> Self;
+    /// Product rounded downward
+    fn mul_down(self, other: Self) -> Self;
+    /// Product rounded upward
+    fn mul_up(self, other: Self) -> Self;
+    /// Quotient rounded downward
+    fn div_down(self, other: Self) -> Self;
+    /// Quotient rounded upward
+    fn div_up(self, other: Self) -> Self;
+
+    /// Fused `self * a + b` with single rounding downward
+    ///
+    /// Default implementation rounds product and sum separately.
+    fn mul_add_down(self, a: Self, b: Self) -> Self {
+        self.mul_down(a).add_down(b)
+    }
+    /// Fused `self * a + b` with single rounding upward
+    ///
+    /// Default implementation rounds product and sum separately.
+    fn mul_add_up(self, a: Self, b: Self) -> Self {
+        self.mul_up(a).add_up(b)
+    }
+
+    /// `self * a + b * c` with single rounding downward
+    ///
+    /// Default implementation rounds `b * c` separately.
+    fn dot2_down(self, a: Self, b: Self, c: Self) -> Self {
+        self.mul_add_down(a, b.mul_down(c))
+    }
+    /// `self * a + b * c` with single rounding upward
+    ///
+    /// Default implementation rounds `b * c` separately.
+    fn dot2_up(self, a: Self, b: Self, c: Self) -> Self {
+        self.mul_add_up(a, b.mul_up(c))
+    }
+
+    /// Sum of `self` and all `values`, rounded downward
+    ///
+    /// Default implementation rounds every addition separately.
+    fn sum_down(self, values: &[Self]) -> Self {
+        values.iter().fold(self, |sum, &x| sum.add_down(x))
+    }
+    /// Sum of `self` and all `values`, rounded upward
+    ///
+    /// Default implementation rounds every addition separately.
+    fn sum_up(self, values: &[Self]) -> Self {
+        values.iter().fold(self, |sum, &x| sum.add_up(x))
+    }
+
+    /// Run `func` with rounding `mode`, if arithmetic of this type depends on it
+    ///
+    /// For integers this just calls `func`, without changing mode of the FPU.
+    #[cfg(feature = "libc")]
+    fn with_rounding<R, F: FnOnce() -> R>(mode: Rounding, func: F) -> R {
+        mode.execute(func)
+    }
+}
+
+macro_rules! integer_rounded_arith {
+    ($($t:ty)*) => {
+        $(
+            impl RoundedArith for $t {
+                fn add_down(self, other: Self) -> Self { self + other }
+                fn add_up(self, other: Self) -> Self { self + other }
+                fn sub_down(self, other: Self) -> Self { self - other }
+                fn sub_up(self, other: Self) -> Self { self - other }
+                fn mul_down(self, other: Self) -> Self { self * other }
+                fn mul_up(self, other: Self) -> Self { self * other }
+
+                fn div_down(self, other: Self) -> Self {
+                    let q = self / other;
+                    if self % other != 0 && (self < 0) != (other < 0) { q - 1 } else { q }
+                }
+
+                fn div_up(self, other: Self) -> Self {
+                    let q = self / other;
+                    if self % other != 0 && (self < 0) == (other < 0) { q + 1 } else { q }
+                }
+
+                #[cfg(feature = "libc")]
+                fn with_rounding<R, F: FnOnce() -> R>(_: Rounding, func: F) -> R { func() }
+            }
+        )*
+    };
+}
+
+#[allow(unused_comparisons)]
+mod integer {
+    #[cfg(feature = "libc")]
+    use super::Rounding;
+    use super::RoundedArith;
+
+    integer_rounded_arith!(i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize);
+}
+
+#[cfg(not(feature = "fesetround"))]
+mod float {
+    use std::cmp::Ordering;
+
+    use num::Float;
+
+    use super::{mode_changed, RoundedArith};
+    use utils::{next_up, next_down};
+
+    /// Result rounded to nearest with position of the exact result relative to it
+    ///
+    /// `None` means that the position is unknown and both neighbours have to be used.
+    type Rounded<T> = (T, Option<Ordering>);
+
+    /// Position of exact result of finite operation that overflowed to `rounded`
+    fn overflow<T: Float>(rounded: T) -> Rounded<T> {
+        (rounded, Some(if rounded > T::zero() { Ordering::Less } else { Ordering::Greater }))
+    }
+
+    /// Sign of the rounding error `err = exact - rounded`
+    fn error_sign<T: Float>(err: T) -> Option<Ordering> {
+        // Error of operation on infinities is NaN, but such result is exact
+        Some(err.partial_cmp(&T::zero()).unwrap_or(Ordering::Equal))
+    }
+
+    /// Smallest magnitude for which products and quotients have exact error terms
+    fn underflow_limit<T: Float>() -> T {
+        T::min_positive_value() / T::epsilon()
+    }
+
+    /// TwoSum: `s + err == a + b` exactly, unless the sum overflows
+    fn two_sum<T: Float>(a: T, b: T) -> (T, T) {
+        let s = a + b;
+        let bb = s - a;
+
+        (s, (a - (s - bb)) + (b - bb))
+    }
+
+    fn add<T: Float>(a: T, b: T) -> Rounded<T> {
+        if mode_changed() {
+            return (a + b, None);
+        }
+        let (s, err) = two_sum(a, b);
+        if s.is_infinite() && a.is_finite() && b.is_finite() {
+            return overflow(s);
+        }
+
+        (s, error_sign(err))
+    }
+
+    fn mul<T: Float>(a: T, b: T) -> Rounded<T> {
+        if mode_changed() {
+            return (a * b, None);
+        }
+        let p = a * b;
+        if p.is_infinite() && a.is_finite() && b.is_finite() {
+            return overflow(p);
+        }
+        if a == T::zero() || b == T::zero() || !p.is_finite() {
+            return (p, Some(Ordering::Equal));
+        }
+        if p.abs() < underflow_limit() {
+            return (p, None);
+        }
+
+        (p, error_sign(a.mul_add(b, -p)))
+    }
+
+    fn div<T: Float>(a: T, b: T) -> Rounded<T> {
+        if mode_changed() {
+            return (a / b, None);
+        }
+        let q = a / b;
+        if q.is_infinite() && a.is_finite() && b.is_finite() && b != T::zero() {
+            return overflow(q);
+        }
+        if a == T::zero() || b == T::zero() || !a.is_finite() || !b.is_finite() {
+            return (q, Some(Ordering::Equal));
+        }
+        if q.abs() < underflow_limit() || a.abs() < underflow_limit() {
+            return (q, None);
+        }
+
+        // Remainder `a - q * b` is exactly representable, and `a / b - q` has its sign over `b`
+        let r = (-q).mul_add(b, a);
+        let sign = error_sign(r).map(|sign| if b < T::zero() { sign.reverse() } else { sign });
+
+        (q, sign)
+    }
+
+    fn mul_add<T: Float>(a: T, b: T, c: T) -> Rounded<T> {
+        if mode_changed() {
+            return (a.mul_add(b, c), None);
+        }
+        let r = a.mul_add(b, c);
+        if !r.is_finite() {
+            if a.is_finite() && b.is_finite() && c.is_finite() {
+                return overflow(r);
+            }
+            return (r, Some(Ordering::Equal));
+        }
+
+        let zero = T::zero();
+        let p = a * b;
+        if !p.is_finite() || (a != zero && b != zero && p.abs() < underflow_limit()) {
+            return (r, None);
+        }
+        // `a b + c - r == u + e3 + e2 + e1` exactly, as `a b == p + e1`, `p + c == s + e2` and
+        // `s - r == u + e3`
+        let e1 = a.mul_add(b, -p);
+        let (s, e2) = two_sum(p, c);
+        if !s.is_finite() {
+            return (r, None);
+        }
+        let (u, e3) = two_sum(s, -r);
+
+        (r, sum_sign(&mut [u, e3, e2, e1]))
+    }
+
+    /// Error-free `a * b - p` of product `p` rounded to nearest, `None` if it underflows
+    fn product_error<T: Float>(a: T, b: T, p: T) -> Option<T> {
+        if a == T::zero() || b == T::zero() {
+            return Some(T::zero());
+        }
+        if p.abs() < underflow_limit() {
+            return None;
+        }
+
+        Some(a.mul_add(b, -p))
+    }
+
+    fn dot2<T: Float>(a: T, b: T, c: T, d: T) -> Option<Rounded<T>> {
+        if mode_changed() {
+            return None;
+        }
+        let (p, q) = (a * b, c * d);
+        if !p.is_finite() || !q.is_finite() {
+            return None;
+        }
+        let (e1, e2) = (product_error(a, b, p)?, product_error(c, d, q)?);
+
+        round_sum(&mut [e2, e1, q, p])
+    }
+
+    /// Sum of finite `terms` within one ulp, `None` when it overflows or does not converge
+    ///
+    /// Exact sum is moved into the last term until the rest is smaller than the distance to its
+    /// neighbours, so the exact sum lies strictly between them.
+    fn round_sum<T: Float>(terms: &mut [T]) -> Option<Rounded<T>> {
+        let zero = T::zero();
+
+        for _ in 0..8 {
+            distill(terms);
+
+            let (&mut top, rest) = terms.split_last_mut().unwrap();
+            if !top.is_finite() {
+                return None;
+            }
+            let bound = rest.iter().fold(zero, |acc, &x| up(add(acc, x.abs())));
+            let gap = (next_up(top) - top).min(top - next_down(top));
+            if bound < gap {
+                return Some((top, sum_sign(rest)));
+            }
+        }
+
+        None
+    }
+
+    /// Distillation keeps the exact sum, moving it into the last term
+    fn distill<T: Float>(terms: &mut [T]) {
+        for i in 1..terms.len() {
+            let (s, err) = two_sum(terms[i], terms[i - 1]);
+            terms[i] = s;
+            terms[i - 1] = err;
+        }
+    }
+
+    /// Sign of the exact sum of `terms`, `None` if it was not found after a few passes
+    fn sum_sign<T: Float>(terms: &mut [T]) -> Option<Ordering> {
+        let zero = T::zero();
+        if terms.is_empty() {
+            return Some(Ordering::Equal);
+        }
+
+        for _ in 0..8 {
+            distill(terms);
+
+            let (top, rest) = terms.split_last().unwrap();
+            let bound = rest.iter().fold(zero, |acc, &x| up(add(acc, x.abs())));
+            if top.abs() > bound || bound == zero {
+                return top.partial_cmp(&zero);
+            }
+        }
+
+        None
+    }
+
+    fn down<T: Float>((rounded, exact): Rounded<T>) -> T {
+        match exact {
+            Some(Ordering::Less) | None => next_down(rounded),
+            _ => rounded,
+        }
+    }
+
+    fn up<T: Float>((rounded, exact): Rounded<T>) -> T {
+        match exact {
+            Some(Ordering::Greater) | None => next_up(rounded),
+            _ => rounded,
+        }
+    }
+
+    macro_rules! float_rounded_arith {
+        ($($t:ty)*) => {
+            $(
+                impl RoundedArith for $t {
+                    fn add_down(self, other: Self) -> Self { down(add(self, other)) }
+                    fn add_up(self, other: Self) -> Self { up(add(self, other)) }
+                    fn sub_down(self, other: Self) -> Self { down(add(self, -other)) }
+                    fn sub_up(self, other: Self) -> Self { up(add(self, -other)) }
+                    fn mul_down(self, other: Self) -> Self { down(mul(self, other)) }
+                    fn mul_up(self, other: Self) -> Self { up(mul(self, other)) }
+                    fn div_down(self, other: Self) -> Self { down(div(self, other)) }
+                    fn div_up(self, other: Self) -> Self { up(div(self, other)) }
+                    fn mul_add_down(self, a: Self, b: Self) -> Self { down(mul_add(self, a, b)) }
+                    fn mul_add_up(self, a: Self, b: Self) -> Self { up(mul_add(self, a, b)) }
+                    fn dot2_down(self, a: Self, b: Self, c: Self) -> Self {
+                        match dot2(self, a, b, c) {
+                            Some(result) => down(result),
+                            None => self.mul_add_down(a, b.mul_down(c)),
+                        }
+                    }
+                    fn dot2_up(self, a: Self, b: Self, c: Self) -> Self {
+                        match dot2(self, a, b, c) {
+                            Some(result) => up(result),
+                            None => self.mul_add_up(a, b.mul_up(c)),
+                        }
+                    }
+                }
+            )*
+        };
+    }
+
+    float_rounded_arith!(f32 f64);
+}
+
+#[cfg(feature = "fesetround")]
+mod float {
+    use num::Float;
+
+    use super::{Rounding, RoundedArith};
+
+    /// Evaluate `func` rounded downward, or give `-∞` when the mode could not be switched
+    fn down<T: Float, F: FnOnce() -> T>(func: F) -> T {
+        let guard = Rounding::Downward.scoped();
+        if guard.is_switched() { func() } else { T::neg_infinity() }
+    }
+
+    /// Evaluate `func` rounded upward, or give `∞` when the mode could not be switched
+    fn up<T: Float, F: FnOnce() -> T>(func: F) -> T {
+        let guard = Rounding::Upward.scoped();
+        if guard.is_switched() { func() } else { T::infinity() }
+    }
+
+    macro_rules! float_rounded_arith {
+        ($($t:ty)*) => {
+            $(
+                impl RoundedArith for $t {
+                    fn add_down(self, other: Self) -> Self {
+                        down(|| self + other)
+                    }
+                    fn add_up(self, other: Self) -> Self {
+                        up(|| self + other)
+                    }
+                    fn sub_down(self, other: Self) -> Self {
+                        down(|| self - other)
+                    }
+                    fn sub_up(self, other: Self) -> Self {
+                        up(|| self - other)
+                    }
+                    fn mul_down(self, other: Self) -> Self {
+                        down(|| self * other)
+                    }
+                    fn mul_up(self, other: Self) -> Self {
+                        up(|| self * other)
+                    }
+                    fn div_down(self, other: Self) -> Self {
+                        down(|| self / other)
+                    }
+                    fn div_up(self, other: Self) -> Self {
+                        up(|| self / other)
+                    }
+                    fn mul_add_down(self, a: Self, b: Self) -> Self {
+                        down(|| self.mul_add(a, b))
+                    }
+                    fn mul_add_up(self, a: Self, b: Self) -> Self {
+                        up(|| self.mul_add(a, b))
+                    }
+                    fn dot2_down(self, a: Self, b: Self, c: Self) -> Self {
+                        down(|| self.mul_add(a, b * c))
+                    }
+                    fn dot2_up(self, a: Self, b: Self, c: Self) -> Self {
+                        up(|| self.mul_add(a, b * c))
+                    }
+                    fn sum_down(self, values: &[Self]) -> Self {
+                        down(|| values.iter().fold(self, |sum, &x| sum + x))
+                    }
+                    fn sum_up(self, values: &[Self]) -> Self {
+                        up(|| values.iter().fold(self, |sum, &x| sum + x))
+                    }
+                }
+            )*
+        };
+    }
+
+    float_rounded_arith!(f32 f64);
+}
+
+/// Backend performing directed rounding of operations on `T`
+///
+/// Arithmetic of `Interval` uses `RoundedArith` of its bounds, which is the `Native` backend.
+/// Other backends trade tightness for independence of the floating-point environment, and are
+/// selected per operation with `Interval::add_with` and its siblings.
+pub trait RoundedOps<T> {
+    /// Sum rounded downward
+    fn add_down(a: T, b: T) -> T;
+    /// Sum rounded upward
+    fn add_up(a: T, b: T) -> T;
+    /// Difference rounded downward
+    fn sub_down(a: T, b: T) -> T;
+    /// Difference rounded upward
+    fn sub_up(a: T, b: T) -> T;
+    /// Product rounded downward
+    fn mul_down(a: T, b: T) -> T;
+    /// Product rounded upward
+    fn mul_up(a: T, b: T) -> T;
+    /// Quotient rounded downward
+    fn div_down(a: T, b: T) -> T;
+    /// Quotient rounded upward
+    fn div_up(a: T, b: T) -> T;
+}
+
+/// Backend of `RoundedArith`, the one used by operators of `Interval`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct Native;
+
+impl<T: RoundedArith> RoundedOps<T> for Native {
+    fn add_down(a: T, b: T) -> T { a.add_down(b) }
+    fn add_up(a: T, b: T) -> T { a.add_up(b) }
+    fn sub_down(a: T, b: T) -> T { a.sub_down(b) }
+    fn sub_up(a: T, b: T) -> T { a.sub_up(b) }
+    fn mul_down(a: T, b: T) -> T { a.mul_down(b) }
+    fn mul_up(a: T, b: T) -> T { a.mul_up(b) }
+    fn div_down(a: T, b: T) -> T { a.div_down(b) }
+    fn div_up(a: T, b: T) -> T { a.div_up(b) }
+}
+
+/// Software backend moving results rounded to nearest by one ulp outward
+///
+/// It assumes only that the mode is to nearest, and never switches it or inspects the error, so
+/// results are one ulp wider than they could be, even when they are exact.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct Ulp;
+
+impl<T: Float> RoundedOps<T> for Ulp {
+    fn add_down(a: T, b: T) -> T { next_down(a + b) }
+    fn add_up(a: T, b: T) -> T { next_up(a + b) }
+    fn sub_down(a: T, b: T) -> T { next_down(a - b) }
+    fn sub_up(a: T, b: T) -> T { next_up(a - b) }
+    fn mul_down(a: T, b: T) -> T { next_down(a * b) }
+    fn mul_up(a: T, b: T) -> T { next_up(a * b) }
+    fn div_down(a: T, b: T) -> T { next_down(a / b) }
+    fn div_up(a: T, b: T) -> T { next_up(a / b) }
+}
+
+/// Tightest interval containing the exact sum `a + b`
+///
+/// Functions `enclose_*` bound the error of a single operation on plain numbers, which retrofits
+/// error tracking onto existing floating-point code one operation at a time. Both endpoints are
+/// the neighbours of the exact result (or the result itself when it is representable). Results
+/// that are NaN, like `∞ - ∞`, give interval with NaN bounds, which is empty.
+///
+/// ## Example
+///
+/// ```rust
+/// use inter::rounding::{enclose_add, enclose_div};
+///
+/// let sum = enclose_add(0.1f64, 0.2);
+/// assert!(sum.contains(0.1 + 0.2) && sum.inf().next_up() == sum.sup());
+/// assert!(enclose_add(1., 2.).is_exact() && !enclose_div(1., 3.).is_exact());
+/// ```
+pub fn enclose_add<T: RoundedArith>(a: T, b: T) -> Interval<T> {
+    Interval {
+        start: a.add_down(b),
+        end: a.add_up(b),
+    }
+}
+
+/// Tightest interval containing the exact difference `a - b`
+pub fn enclose_sub<T: RoundedArith>(a: T, b: T) -> Interval<T> {
+    Interval {
+        start: a.sub_down(b),
+        end: a.sub_up(b),
+    }
+}
+
+/// Tightest interval containing the exact product `a * b`
+pub fn enclose_mul<T: RoundedArith>(a: T, b: T) -> Interval<T> {
+    Interval {
+        start: a.mul_down(b),
+        end: a.mul_up(b),
+    }
+}
+
+/// Tightest interval containing the exact quotient `a / b`
+///
+/// Division by zero gives infinity of the corresponding sign, as for plain floats.
+pub fn enclose_div<T: RoundedArith>(a: T, b: T) -> Interval<T> {
+    Interval {
+        start: a.div_down(b),
+        end: a.div_up(b),
+    }
+}
+
+/// Interval containing the exact `a * b + c`
+///
+/// This is the tightest one for floats, and integers get the exact value.
+pub fn enclose_mul_add<T: RoundedArith>(a: T, b: T, c: T) -> Interval<T> {
+    Interval {
+        start: a.mul_add_down(b, c),
+        end: a.mul_add_up(b, c),
+    }
+}
+
+/// Interval containing the exact `a * b + c * d`
+///
+/// Without the `fesetround` feature this is the tightest one for floats, so signs of
+/// determinants like `a d - b c` are certain whenever the result does not contain zero, and it
+/// is exact for integers.
+///
+/// ## Example
+///
+/// ```rust
+/// use inter::rounding::enclose_dot2;
+///
+/// // x² - (x + 1)(x - 1) cancels to zero in floating point
+/// let x = 134217729f64;
+/// assert_eq!(x * x - (x + 1.) * (x - 1.), 0.);
+///
+/// let det = enclose_dot2(x, x, -(x + 1.), x - 1.);
+/// assert!(det.inf() > 0. && det.contains(1.));
+/// assert_eq!(enclose_dot2(3, 4, -2, 5), inter::Interval::exact(2));
+/// ```
+pub fn enclose_dot2<T: RoundedArith>(a: T, b: T, c: T, d: T) -> Interval<T> {
+    Interval {
+        start: a.dot2_down(b, c, d),
+        end: a.dot2_up(b, c, d),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[cfg(feature = "libc")]
+    use std::hint::black_box;
+
+    use super::RoundedArith;
+    #[cfg(feature = "libc")]
+    use super::Rounding;
+
+    #[cfg(feature = "libc")]
+    const VALUES: [f64; 14] = [0., -0., 1., -1., 0.1, -0.3, 3., 7e-300, 1e300, -1.5e308,
+                               f64::MIN_POSITIVE, 5e-324, f64::INFINITY, f64::NEG_INFINITY];
+
+    #[cfg(feature = "libc")]
+    type Op = fn(f64, f64) -> f64;
+
+    #[test]
+    #[cfg(feature = "libc")]
+    fn matches_fesetround() {
+        let tiny = |x: f64| x != 0. && x.abs() < 1e-290;
+        let ops: [(Op, Op, Op); 4] =
+            [(|a, b| a + b, f64::add_down, f64::add_up),
+             (|a, b| a - b, f64::sub_down, f64::sub_up),
+             (|a, b| a * b, f64::mul_down, f64::mul_up),
+             (|a, b| a / b, f64::div_down, f64::div_up)];
+
+        for &a in &VALUES {
+            for &b in &VALUES {
+                for &(op, down, up) in &ops {
+                    let exact_down = Rounding::Downward.execute(|| op(black_box(a), black_box(b)));
+                    let exact_up = Rounding::Upward.execute(|| op(black_box(a), black_box(b)));
+                    if exact_down.is_nan() {
+                        continue;
+                    }
+
+                    // Near underflow results can be one ulp wider than necessary
+                    let (down, up) = (down(a, b), up(a, b));
+                    assert!(down <= exact_down && exact_down <= down.next_up(), "{} {}", a, b);
+                    assert!(exact_up <= up && up.next_down() <= exact_up, "{} {}", a, b);
+                    if ![a, b, exact_down, exact_up].iter().any(|&x| tiny(x)) {
+                        assert_eq!((down, up), (exact_down, exact_up), "{} {}", a, b);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "libc")]
+    fn fused_matches_fesetround() {
+        let tiny = |x: f64| x != 0. && x.abs() < 1e-290;
+        let fma = |mode: Rounding, a: f64, b: f64, c: f64| {
+            mode.execute(|| black_box(a).mul_add(black_box(b), black_box(c)))
+        };
+        let mut values = VALUES.to_vec();
+        values.extend_from_slice(&[10., 0.7, -1e-17, 1. / 3., 2f64.powi(60) + 2048.]);
+
+        for &a in &values {
+            for &b in &values {
+                for &c in &values {
+                    let (exact_down, exact_up) =
+                        (fma(Rounding::Downward, a, b, c), fma(Rounding::Upward, a, b, c));
+                    if exact_down.is_nan() {
+                        continue;
+                    }
+
+                    let (down, up) = (a.mul_add_down(b, c), a.mul_add_up(b, c));
+                    assert!(down <= exact_down && exact_down <= down.next_up(),
+                            "{} {} {}",
+                            a,
+                            b,
+                            c);
+                    assert!(exact_up <= up && up.next_down() <= exact_up, "{} {} {}", a, b, c);
+                    if ![a, b, c, a * b, exact_down].iter().any(|&x| tiny(x)) {
+                        assert_eq!((down, up), (exact_down, exact_up), "{} {} {}", a, b, c);
+                    }
+                }
+            }
+        }
+
+        // Default implementation rounds twice
+        assert_eq!(3i32.mul_add_down(4, 5), 17);
+        assert_eq!(0.1f64.mul_add_down(10., -1.), 2f64.powi(-54));
+    }
+
+    #[test]
+    #[cfg(feature = "libc")]
+    fn switches_mode() {
+        let third = |mode: Rounding| mode.execute(|| black_box(1f64) / black_box(3.));
+
+        assert!(third(Rounding::Downward) < third(Rounding::Upward));
+        assert_eq!(third(Rounding::TowardZero), third(Rounding::Downward));
+        assert_eq!(-third(Rounding::Downward), Rounding::Upward.execute(|| {
+            black_box(-1f64) / black_box(3.)
+        }));
+
+        // Nested modes are restored
+        Rounding::Downward.execute(|| {
+            Rounding::Upward.execute(|| assert_eq!(Rounding::current(), Some(Rounding::Upward)));
+            assert_eq!(Rounding::current(), Some(Rounding::Downward));
+        });
+        assert_eq!(Rounding::current(), Some(Rounding::ToNearest));
+
+        assert_eq!(Rounding::TowardZero.set(), Ok(()));
+        assert_eq!(Rounding::current(), Some(Rounding::TowardZero));
+        assert_eq!(Rounding::ToNearest.set(), Ok(()));
+    }
+
+    #[test]
+    #[cfg(feature = "libc")]
+    fn guards() {
+        use std::panic;
+        use std::thread;
+
+        {
+            let down = Rounding::Downward.scoped();
+            let up = Rounding::Upward.scoped();
+            assert_eq!((down.previous(), up.previous()),
+                       (Some(Rounding::ToNearest), Some(Rounding::Downward)));
+            assert_eq!(Rounding::current(), Some(Rounding::Upward));
+
+            // Other threads keep their own mode
+            let other = thread::spawn(|| {
+                let _zero = Rounding::TowardZero.scoped();
+                Rounding::current()
+            });
+            assert_eq!(other.join().unwrap(), Some(Rounding::TowardZero));
+            assert_eq!(Rounding::current(), Some(Rounding::Upward));
+            // New threads inherit mode of the thread spawning them
+            assert_eq!(thread::spawn(Rounding::current).join().unwrap(),
+                       Some(Rounding::Upward));
+
+            drop(up);
+            assert_eq!(Rounding::current(), Some(Rounding::Downward));
+        }
+        assert_eq!(Rounding::current(), Some(Rounding::ToNearest));
+
+        // Mode is restored while unwinding
+        let result = panic::catch_unwind(|| Rounding::Upward.execute(|| panic!("in upward mode")));
+        assert!(result.is_err());
+        assert_eq!(Rounding::current(), Some(Rounding::ToNearest));
+    }
+
+    #[test]
+    #[cfg(feature = "libc")]
+    #[should_panic(expected = "expected Downward")]
+    fn wrong_mode() {
+        Rounding::Upward.execute(|| super::assert_mode(Rounding::Downward));
+    }
+
+    #[test]
+    #[cfg(all(feature = "check-rounding", not(feature = "fesetround"), debug_assertions))]
+    #[should_panic(expected = "rounding mode is Some(TowardZero)")]
+    fn clobbered_mode() {
+        let x = 0.1f64;
+        Rounding::TowardZero.execute(|| x.add_up(black_box(0.2)));
+    }
+
+    #[test]
+    #[cfg(feature = "libc")]
+    fn failures() {
+        use super::{checked, fail, RoundingFailure};
+
+        assert_eq!(checked(|| 1f64.add_up(1.)), Ok(2.));
+        assert!(Rounding::Upward.scoped().is_switched());
+        assert_eq!(checked(fail), Err(RoundingFailure));
+
+        // Failure of nested call fails the outer one, but not the next one
+        let outer = checked(|| {
+            assert_eq!(checked(|| ()), Ok(()));
+            checked(fail).is_err()
+        });
+        assert_eq!(outer, Err(RoundingFailure));
+        assert_eq!(checked(|| checked(fail)), Err(RoundingFailure));
+        assert_eq!(checked(|| ()), Ok(()));
+        assert!(RoundingFailure.to_string().starts_with("rounding mode could not be switched"));
+    }
+
+    #[test]
+    fn enclosures_of_operations() {
+        use super::{enclose_add, enclose_div, enclose_mul, enclose_mul_add, enclose_sub};
+
+        let third = enclose_div(1f64, 3.);
+        assert!(third.contains(1. / 3.) && third.inf().next_up() == third.sup());
+        assert!(enclose_sub(1., 1e-20).sup() == 1. && enclose_sub(1., 1e-20).inf() < 1.);
+        assert!(enclose_mul(0.1, 0.1).contains(0.1 * 0.1) && !enclose_mul(0.1, 0.1).is_exact());
+        assert!(enclose_mul_add(0.1, 10., -1.).contains(5.551115123125783e-17));
+        assert!(enclose_mul(1e300, 1e300).contains(f64::INFINITY));
+        assert_eq!(enclose_div(1., 0.).inf(), f64::INFINITY);
+        assert!(enclose_add(f64::INFINITY, f64::NEG_INFINITY).is_empty());
+        assert_eq!(enclose_div(7, 2), super::Interval::with_range(3, 4));
+    }
+
+    #[test]
+    fn dot_products_and_sums() {
+        use super::enclose_dot2;
+
+        // Products of 40-bit integers are exact in `i128`
+        let values = [0., 1., -3., 1099511627775., -549755813889., 3. * 2f64.powi(38) + 7.];
+        for &a in &values {
+            for &b in &values {
+                for &(c, d) in &[(values[4], values[5]), (values[5], -values[5]), (0., 1.)] {
+                    let exact = a as i128 * b as i128 + c as i128 * d as i128;
+                    let x = enclose_dot2(a, b, c, d);
+                    assert!(x.inf() as i128 <= exact && exact <= x.sup() as i128, "{}", exact);
+                    if cfg!(not(feature = "fesetround")) {
+                        assert!(x.inf().next_up() >= x.sup(), "{} {}", exact, x);
+                    }
+                }
+            }
+        }
+        assert!(enclose_dot2(f64::MAX, 2., -f64::MAX, 2.).contains(0.));
+        assert!(enclose_dot2(f64::MAX, 2., f64::MAX, 0.).contains(f64::INFINITY));
+        assert!(enclose_dot2(1e-300, 1e-300, 1., 1.).contains(1.));
+        assert!(enclose_dot2(f64::INFINITY, 1., f64::NEG_INFINITY, 1.).is_empty());
+
+    }
+
+    #[test]
+    fn backends() {
+        use interval::Interval;
+        use super::{Native, Ulp};
+
+        let xs = [Interval::with_range(0.1, 0.3), Interval::with_range(-2., 1e300),
+                  Interval::exact(-3.), Interval::with_range(7e-300, f64::INFINITY)];
+        for &a in &xs {
+            for &b in &xs {
+                assert_eq!(a.add_with::<Native>(b), a + b);
+                assert_eq!(a.sub_with::<Native>(b), a - b);
+                assert_eq!(a.mul_with::<Native>(b), a * b);
+                assert_eq!(a.div_with::<Native>(b), a.checked_div(b));
+
+                assert!((a + b).subset(&a.add_with::<Ulp>(b)));
+                assert!((a - b).subset(&a.sub_with::<Ulp>(b)));
+                assert!((a * b).subset(&a.mul_with::<Ulp>(b)));
+                match (a.checked_div(b), a.div_with::<Ulp>(b)) {
+                    (Some(native), Some(ulp)) => assert!(native.subset(&ulp)),
+                    (native, ulp) => assert!(native.is_none() && ulp.is_none()),
+                }
+            }
+        }
+
+        let one = Interval::exact(1.);
+        assert_eq!(one.add_with::<Ulp>(one),
+                   Interval::with_range(2f64.next_down(), 2f64.next_up()));
+        assert!(one.div_with::<Ulp>(Interval::with_range(-1., 1.)).is_none());
+        assert!(one.mul_with::<Ulp>(Interval::empty()).is_empty());
+    }
+
+    #[test]
+    fn integers() {
+        assert_eq!(7i32.div_down(2), 3);
+        assert_eq!(7i32.div_up(2), 4);
+        assert_eq!((-7i32).div_down(2), -4);
+        assert_eq!((-7i32).div_up(2), -3);
+        assert_eq!(7u8.div_up(7), 1);
+        assert_eq!(3u8.add_down(4), 7);
+    }
+
+    #[test]
+    #[cfg(feature = "libc")]
+    fn installs_on_workers() {
+        use std::sync::Mutex;
+        use std::thread;
+        use super::install_on_pool;
+
+        let modes = Mutex::new(Vec::new());
+        let pool = |mode| install_on_pool(|init| thread::scope(|scope| {
+            for _ in 0..3 {
+                scope.spawn(|| {
+                    init();
+                    modes.lock().unwrap().push(Rounding::current());
+                    Rounding::ToNearest.set().unwrap();
+                });
+            }
+        }), mode);
+
+        assert_eq!(pool(Rounding::Upward), Ok(()));
+        assert_eq!(*modes.lock().unwrap(), [Some(Rounding::Upward); 3]);
+        assert_eq!(Rounding::current(), Some(Rounding::ToNearest));
+        assert_eq!(install_on_pool(|_| (), Rounding::Downward), Ok(()));
+    }
 
-        ret
+    #[test]
+    #[cfg(feature = "libc")]
+    fn integers_keep_mode() {
+        assert_eq!(i64::with_rounding(Rounding::Upward, Rounding::current),
+                   Some(Rounding::ToNearest));
+        assert_eq!(f64::with_rounding(Rounding::Upward, Rounding::current),
+                   Some(Rounding::Upward));
     }
 }
@@ -0,0 +1,294 @@
+//! Evaluation of untrusted formulas in reverse Polish notation.
+//!
+//! `Expr` can represent anything, which is too much for formulas coming from configuration
+//! files or requests. `Program` is a flat sequence of whitelisted operations instead, parsed
+//! from tokens separated by whitespace:
+//!
+//! - numbers in any form accepted by `Interval::from_str` without spaces, like `0.1`, `[1,2]` or
+//!   `2+-0.5`, which push their enclosures,
+//! - names of variables given to `Program::parse`, which push their values,
+//! - `+`, `-`, `*` and `/`, which pop two values and push the result,
+//! - `neg`, `sqr`, `abs`, `sqrt`, `exp`, `ln`, `sin`, `cos`, `recip` and integer powers `^n`,
+//!   which replace the value on top of the stack.
+//!
+//! Parsing checks that every operation has its operands and that exactly one value remains, so
+//! evaluation cannot fail, and `Limits` bound the number of operations and the depth of stack,
+//! so it runs in bounded time and memory. Evaluation allocates only the stack, once.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use inter::Interval;
+//! use inter::rpn::{Limits, Program};
+//!
+//! // x² + 2y
+//! let program = Program::parse("x sqr 2 y * +", &["x", "y"], Limits::default()).unwrap();
+//! let value = program.eval(&[Interval::with_range(-1., 2.), Interval::exact(0.5)]);
+//!
+//! assert_eq!(value, Interval::with_range(1., 5.));
+//! assert!(Program::<f64>::parse("x +", &["x"], Limits::default()).is_err());
+//! ```
+
+use std::error;
+use std::fmt::{self, LowerExp};
+use std::str::FromStr;
+
+use num::{Float, FloatConst};
+
+use expr::Function;
+use interval::Interval;
+use rounding::RoundedArith;
+
+/// Bounds on size of `Program`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Limits {
+    /// Maximal number of operations
+    pub max_steps: usize,
+    /// Maximal number of values on stack at once
+    pub max_depth: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Limits {
+            max_steps: 1024,
+            max_depth: 64,
+        }
+    }
+}
+
+/// Error returned when formula is not a valid `Program`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RpnError {
+    /// Token is neither operation, variable nor number, with its index
+    Token(usize),
+    /// Operation has not enough operands on stack, with its index
+    Underflow(usize),
+    /// Formula leaves other number of values than one on stack
+    Unbalanced(usize),
+    /// Formula has more operations than `Limits::max_steps`
+    TooLong,
+    /// Formula needs deeper stack than `Limits::max_depth`
+    TooDeep,
+}
+
+impl fmt::Display for RpnError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            RpnError::Token(i) => write!(fmt, "unknown token at position {}", i),
+            RpnError::Underflow(i) => write!(fmt, "missing operand at position {}", i),
+            RpnError::Unbalanced(n) => write!(fmt, "formula leaves {} values instead of one", n),
+            RpnError::TooLong => fmt.write_str("formula has too many operations"),
+            RpnError::TooDeep => fmt.write_str("formula needs too deep stack"),
+        }
+    }
+}
+
+impl error::Error for RpnError {}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Op<T> {
+    Push(Interval<T>),
+    Var(usize),
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Neg,
+    Sqr,
+    Abs,
+    Apply(Function),
+}
+
+impl<T> Op<T> {
+    /// Number of popped and pushed values
+    fn arity(&self) -> (usize, usize) {
+        match *self {
+            Op::Push(_) | Op::Var(_) => (0, 1),
+            Op::Add | Op::Sub | Op::Mul | Op::Div => (2, 1),
+            Op::Neg | Op::Sqr | Op::Abs | Op::Apply(_) => (1, 1),
+        }
+    }
+}
+
+/// Formula validated against `Limits`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Program<T> {
+    ops: Vec<Op<T>>,
+    arity: usize,
+    depth: usize,
+}
+
+impl<T> Program<T>
+    where T: Float + FromStr + LowerExp + RoundedArith
+{
+    /// Parse `source` with variables named `vars`
+    ///
+    /// Names of operations take precedence over names of variables, and those over numbers.
+    pub fn parse(source: &str, vars: &[&str], limits: Limits) -> Result<Self, RpnError> {
+        let (mut ops, mut depth, mut max_depth) = (Vec::new(), 0, 0);
+        for (i, token) in source.split_whitespace().enumerate() {
+            if i >= limits.max_steps {
+                return Err(RpnError::TooLong);
+            }
+
+            let op = parse_op(token, vars).ok_or(RpnError::Token(i))?;
+            let (popped, pushed) = op.arity();
+            if depth < popped {
+                return Err(RpnError::Underflow(i));
+            }
+            depth = depth - popped + pushed;
+            if depth > limits.max_depth {
+                return Err(RpnError::TooDeep);
+            }
+            max_depth = max_depth.max(depth);
+            ops.push(op);
+        }
+        if depth != 1 {
+            return Err(RpnError::Unbalanced(depth));
+        }
+
+        Ok(Program {
+            ops,
+            arity: vars.len(),
+            depth: max_depth,
+        })
+    }
+}
+
+impl<T> Program<T> {
+    /// Number of operations
+    pub fn len(&self) -> usize {
+        self.ops.len()
+    }
+
+    /// Check if there are no operations, which is never the case for parsed program
+    pub fn is_empty(&self) -> bool {
+        self.ops.is_empty()
+    }
+
+    /// Number of variables
+    pub fn arity(&self) -> usize {
+        self.arity
+    }
+}
+
+impl<T> Program<T>
+    where T: Float + FloatConst + RoundedArith
+{
+    /// Enclosure of the value of formula over box `x`
+    ///
+    /// # Panics
+    ///
+    /// This will panic if `x` has less than `arity()` coordinates.
+    pub fn eval(&self, x: &[Interval<T>]) -> Interval<T> {
+        assert!(x.len() >= self.arity, "box has less coordinates than program variables");
+
+        let mut stack = Vec::with_capacity(self.depth);
+        for op in &self.ops {
+            let value = match *op {
+                Op::Push(value) => value,
+                Op::Var(i) => x[i],
+                Op::Add | Op::Sub | Op::Mul | Op::Div => {
+                    let (b, a) = (pop(&mut stack), pop(&mut stack));
+                    match *op {
+                        Op::Add => a + b,
+                        Op::Sub => a - b,
+                        Op::Mul => a * b,
+                        _ => a / b,
+                    }
+                }
+                Op::Neg => -pop(&mut stack),
+                Op::Sqr => pop(&mut stack).sqr(),
+                Op::Abs => pop(&mut stack).abs(),
+                Op::Apply(f) => f.apply(pop(&mut stack)),
+            };
+            stack.push(value);
+        }
+
+        pop(&mut stack)
+    }
+}
+
+/// Value on top of stack, which `Program::parse` guarantees to be there
+fn pop<T>(stack: &mut Vec<Interval<T>>) -> Interval<T> {
+    stack.pop().expect("stack checked when parsing")
+}
+
+fn parse_op<T>(token: &str, vars: &[&str]) -> Option<Op<T>>
+    where T: Float + FromStr + LowerExp + RoundedArith
+{
+    let op = match token {
+        "+" => Op::Add,
+        "-" => Op::Sub,
+        "*" => Op::Mul,
+        "/" => Op::Div,
+        "neg" => Op::Neg,
+        "sqr" => Op::Sqr,
+        "abs" => Op::Abs,
+        "sqrt" => Op::Apply(Function::Sqrt),
+        "exp" => Op::Apply(Function::Exp),
+        "ln" => Op::Apply(Function::Ln),
+        "sin" => Op::Apply(Function::Sin),
+        "cos" => Op::Apply(Function::Cos),
+        "recip" => Op::Apply(Function::Recip),
+        _ if token.starts_with('^') => Op::Apply(Function::Powi(token[1..].parse().ok()?)),
+        _ => match vars.iter().position(|&var| var == token) {
+            Some(i) => Op::Var(i),
+            None => Op::Push(token.parse().ok()?),
+        },
+    };
+
+    Some(op)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use expr::Expr;
+
+    #[test]
+    fn matches_expressions() {
+        let source = "x 1 x - * y ^3 exp / 2 + sqrt y cos recip -";
+        let program = Program::parse(source, &["x", "y"], Limits::default()).unwrap();
+        assert_eq!((program.len(), program.arity(), program.depth), (16, 2, 3));
+
+        let (x, y) = (Expr::var(0), Expr::var(1));
+        let tree = (x.clone() * (Expr::from(1.) - x) / y.clone().powi(3).exp() + Expr::from(2.))
+            .sqrt() - y.cos().apply(Function::Recip);
+        for &(a, b) in &[(0., 1.), (-1., 0.5), (0.25, 3.)] {
+            let domain = [Interval::with_range(a, b), Interval::with_range(-1., 0.5)];
+            assert_eq!(program.eval(&domain), tree.eval(&domain));
+        }
+
+        let program = Program::parse("x neg abs sqr [1,2] /", &["x"], Limits::default()).unwrap();
+        let x = Interval::with_range(-1., 3.);
+        assert_eq!(program.eval(&[x]), (-x).abs().sqr() / Interval::with_range(1., 2.));
+
+        // Roots of subnormal numbers are found in bounded time
+        let program = Program::parse("x sqrt 1e-320 sqrt +", &["x"], Limits::default()).unwrap();
+        let x = Interval::exact(5e-324);
+        let constant: Interval<f64> = "1e-320".parse().unwrap();
+        assert_eq!(program.eval(&[x]), x.sqrt() + constant.sqrt());
+    }
+
+    #[test]
+    fn rejects_invalid() {
+        let parse = |source| Program::<f64>::parse(source, &["x"], Limits::default());
+        assert_eq!(parse("x y +"), Err(RpnError::Token(1)));
+        assert_eq!(parse("1 +"), Err(RpnError::Underflow(1)));
+        assert_eq!(parse("1 2"), Err(RpnError::Unbalanced(2)));
+        assert_eq!(parse(""), Err(RpnError::Unbalanced(0)));
+        assert_eq!(parse("x ^-2 ^1.5"), Err(RpnError::Token(2)));
+        assert_eq!(parse("[2, 1]"), Err(RpnError::Token(0)));
+
+        let limits = Limits {
+            max_steps: 3,
+            max_depth: 2,
+        };
+        assert_eq!(Program::<f64>::parse("1 2 + 3 +", &[], limits), Err(RpnError::TooLong));
+        assert_eq!(Program::<f64>::parse("1 2 3", &[], limits), Err(RpnError::TooDeep));
+        assert!(Program::<f64>::parse("1 2 +", &[], limits).is_ok());
+        assert_eq!(RpnError::Underflow(3).to_string(), "missing operand at position 3");
+    }
+}
@@ -0,0 +1,212 @@
+//! Running error analysis of plain floating-point computation.
+//!
+//! `RunningError` carries value computed in round to nearest together with bound on its
+//! absolute error, updated after every operation as in Wilkinson's running error analysis. Value
+//! itself is computed exactly as it would be with plain floats, so the result can be compared
+//! with existing float code, and only at the end converted into enclosing `Interval`.
+//!
+//! Bounds are accumulated with upward rounding, so the final interval is rigorous. It is usually
+//! wider than enclosure computed with interval arithmetic, but value is computed only once and
+//! bound needs only a few upward-rounded operations on non-negative numbers.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use inter::Interval;
+//! use inter::running_error::RunningError;
+//!
+//! let tenth = RunningError::new(0.1);
+//! let sum = (0..10).fold(RunningError::new(0.), |acc, _| acc + tenth);
+//!
+//! assert_eq!(sum.value(), (0..10).fold(0., |acc, _| acc + 0.1));
+//! assert!(Interval::from(sum).contains(1.));
+//! ```
+
+use std::ops::{Add, Sub, Mul, Div, Neg};
+
+use num::Float;
+
+use interval::Interval;
+use rounding::RoundedArith;
+
+/// Floating-point value with bound on its absolute rounding error
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RunningError<T> {
+    value: T,
+    error: T,
+}
+
+impl<T> RunningError<T>
+    where T: Float + RoundedArith
+{
+    /// Exact input value
+    pub fn new(value: T) -> Self {
+        RunningError::with_error(value, T::zero())
+    }
+
+    /// Input value known up to absolute `error`
+    ///
+    /// # Panics
+    ///
+    /// This will panic if `error` is negative or NaN.
+    pub fn with_error(value: T, error: T) -> Self {
+        assert!(error >= T::zero());
+
+        RunningError {
+            value,
+            error,
+        }
+    }
+
+    /// Value computed in floating-point arithmetic
+    pub fn value(&self) -> T {
+        self.value
+    }
+
+    /// Bound on absolute difference between `value` and exact result
+    pub fn error(&self) -> T {
+        self.error
+    }
+
+    /// Interval enclosing exact result
+    pub fn to_interval(&self) -> Interval<T> {
+        Interval {
+            start: self.value.sub_down(self.error),
+            end: self.value.add_up(self.error),
+        }
+    }
+
+    /// Add rounding error of operation that produced `value`
+    ///
+    /// Result of every operation is `(x ∘ y)(1 + ε) + δ` with `|ε| ≤ u`, and `δ` non-zero only
+    /// for underflowing products and quotients.
+    fn rounded(value: T, propagated: T, underflow: bool) -> Self {
+        let unit = T::epsilon() / (T::one() + T::one());
+        let mut error = propagated.add_up(value.abs().mul_up(unit));
+        if underflow {
+            error = error.add_up(T::min_positive_value() * T::epsilon());
+        }
+
+        RunningError {
+            value,
+            error,
+        }
+    }
+}
+
+impl<T> From<RunningError<T>> for Interval<T>
+    where T: Float + RoundedArith
+{
+    fn from(x: RunningError<T>) -> Self {
+        x.to_interval()
+    }
+}
+
+impl<T> Add for RunningError<T>
+    where T: Float + RoundedArith
+{
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        RunningError::rounded(self.value + other.value, self.error.add_up(other.error), false)
+    }
+}
+
+impl<T> Sub for RunningError<T>
+    where T: Float + RoundedArith
+{
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        RunningError::rounded(self.value - other.value, self.error.add_up(other.error), false)
+    }
+}
+
+impl<T> Mul for RunningError<T>
+    where T: Float + RoundedArith
+{
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        // |xy - x̂ŷ| ≤ |x̂| e_y + |ŷ| e_x + e_x e_y
+        let propagated = self.value
+            .abs()
+            .mul_up(other.error)
+            .add_up(other.value.abs().mul_up(self.error))
+            .add_up(self.error.mul_up(other.error));
+
+        RunningError::rounded(self.value * other.value, propagated, true)
+    }
+}
+
+impl<T> Div for RunningError<T>
+    where T: Float + RoundedArith
+{
+    type Output = Self;
+
+    /// Divide values, error is infinite when divisor can be zero
+    fn div(self, other: Self) -> Self {
+        let value = self.value / other.value;
+        // |x/y - x̂/ŷ| ≤ (e_x + |x̂/ŷ| e_y) / (|ŷ| - e_y)
+        let quotient = self.value.abs().div_up(other.value.abs());
+        let denominator = other.value.abs().sub_down(other.error);
+        let propagated = if denominator > T::zero() {
+            self.error.add_up(quotient.mul_up(other.error)).div_up(denominator)
+        } else {
+            T::infinity()
+        };
+
+        RunningError::rounded(value, propagated, true)
+    }
+}
+
+impl<T> Neg for RunningError<T>
+    where T: Float + RoundedArith
+{
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        RunningError {
+            value: -self.value,
+            error: self.error,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn horner() {
+        // (x - 1)^5 expanded, evaluated close to its root where cancellation is severe
+        let coefficients = [1., -5., 10., -10., 5., -1.];
+        let x = 1.0001;
+
+        let plain = coefficients.iter().fold(0., |acc, &c| acc * x + c);
+        let running = coefficients.iter().fold(RunningError::new(0.), |acc, &c| {
+            acc * RunningError::new(x) + RunningError::new(c)
+        });
+        let interval = coefficients.iter()
+            .fold(Interval::exact(0.), |acc, &c| acc * Interval::exact(x) + Interval::exact(c));
+
+        assert_eq!(running.value(), plain);
+        assert!(running.to_interval().possibly_eq(interval));
+        assert!(running.error() < 1e-14);
+    }
+
+    #[test]
+    fn underflow_and_division() {
+        let tiny = RunningError::new(1e-200) * RunningError::new(1e-200);
+        assert_eq!(tiny.value(), 0.);
+        assert!(tiny.error() > 0. && tiny.to_interval().contains(-tiny.error()));
+
+        let third = RunningError::new(1.) / RunningError::new(3.);
+        assert!(third.error() > 0. && third.error() < 1e-16);
+        assert!(Interval::from(third * RunningError::new(3.)).contains(1.));
+
+        let unknown = RunningError::new(1.) / RunningError::with_error(0.5, 0.5);
+        assert_eq!(unknown.error(), f64::INFINITY);
+        assert_eq!((-third).to_interval(), -third.to_interval());
+    }
+}
@@ -0,0 +1,325 @@
+//! Conventions for indeterminate forms at infinite bounds.
+//!
+//! Operators on intervals follow the set-based model of IEEE 1788: intervals are sets of real
+//! numbers, infinite bounds only mean that interval is unbounded, so `0 · [1, ∞] = [0, 0]` and
+//! dividing by `[0, 0]` gives empty interval. Other domains need other conventions, and
+//! `Semantics` resolves indeterminate forms by one of them:
+//!
+//! - `Semantics::SetBased` is the model of operators, where intervals without real values like
+//!   `[∞, ∞]` are empty,
+//! - `Semantics::Cset` treats intervals as containment sets over the extended reals, where
+//!   infinities are values and every indeterminate form like `0 · ∞`, `∞ - ∞` or `0 / 0` is the
+//!   whole line, so no operation is undefined,
+//! - `Semantics::Strict` reports an error for indeterminate forms and for division by intervals
+//!   containing zero instead of resolving them, so results never depend on a convention.
+//!
+//! Methods of `Semantics` apply it to single operations. `Semantics::execute` puts it in effect
+//! for a whole computation on `strategy::Value`s instead, which includes evaluation of
+//! expressions, so code written once runs as cset arithmetic, where every operation is total, by
+//! wrapping it in `Semantics::Cset.execute`. Semantics in effect is stored per thread.
+//!
+//! Semantics in effect applies to `Value` operations only. Operators and methods of `Interval`
+//! are always set-based, whatever semantics is in effect, so computations on plain intervals
+//! need methods of `Semantics` to follow another convention.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use std::f64;
+//! use inter::Interval;
+//! use inter::semantics::{Indeterminate, Semantics};
+//!
+//! let (zero, unbounded) = (Interval::exact(0.), Interval::with_range(1., f64::INFINITY));
+//!
+//! assert_eq!(Semantics::SetBased.mul(zero, unbounded), Ok(zero));
+//! assert_eq!(Semantics::Cset.mul(zero, unbounded), Ok(Interval::entire()));
+//! assert_eq!(Semantics::Strict.mul(zero, unbounded), Err(Indeterminate::ZeroTimesInfinity));
+//! ```
+//!
+//! Values of expressions are total in cset scope, while operators on intervals are not:
+//!
+//! ```rust
+//! use inter::Interval;
+//! use inter::expr::Expr;
+//! use inter::semantics::Semantics;
+//!
+//! let f = Expr::var(0) / Expr::var(0);
+//! let zero = [Interval::exact(0.)];
+//!
+//! assert!(f.eval(&zero).is_empty());
+//! assert_eq!(Semantics::Cset.execute(|| f.eval(&zero)), Interval::entire());
+//! assert_eq!(Semantics::Cset.execute(|| zero[0] * Interval::entire()), zero[0]);
+//! ```
+
+use std::cell::Cell;
+use std::error;
+use std::fmt;
+
+use num::Float;
+
+use interval::Interval;
+use rounding::RoundedArith;
+
+thread_local! {
+    static CURRENT: Cell<Semantics> = const { Cell::new(Semantics::SetBased) };
+}
+
+/// Convention for resolving indeterminate forms
+///
+/// Semantics in effect, see `Semantics::current`, is followed by operations on
+/// `strategy::Value`s only, while `Interval` operators are always set-based.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum Semantics {
+    /// Sets of real numbers, as in IEEE 1788 and operators
+    #[default]
+    SetBased,
+    /// Containment sets over the extended reals
+    Cset,
+    /// Errors instead of indeterminate forms
+    Strict,
+}
+
+/// Indeterminate form rejected by `Semantics::Strict`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Indeterminate {
+    /// Product of zero and infinity
+    ZeroTimesInfinity,
+    /// Sum of infinities of opposite signs
+    InfinityMinusInfinity,
+    /// Quotient of infinities
+    InfinityOverInfinity,
+    /// Divisor contains zero
+    DivisionByZero,
+}
+
+impl fmt::Display for Indeterminate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let form = match *self {
+            Indeterminate::ZeroTimesInfinity => "0 · ∞",
+            Indeterminate::InfinityMinusInfinity => "∞ - ∞",
+            Indeterminate::InfinityOverInfinity => "∞ / ∞",
+            Indeterminate::DivisionByZero => "division by zero",
+        };
+
+        write!(f, "indeterminate form {}", form)
+    }
+}
+
+impl error::Error for Indeterminate {}
+
+impl Semantics {
+    /// Semantics in effect on current thread
+    pub fn current() -> Semantics {
+        CURRENT.with(Cell::get)
+    }
+
+    /// Run `func` with this semantics in effect for `Value`s, restoring previous one afterwards
+    ///
+    /// Operations on values whose operands or results are unbounded or empty follow this
+    /// semantics, and their results are plain intervals, as strategies track only real values.
+    /// Elementary functions other than reciprocal follow set-based semantics, as their
+    /// containment sets differ only at infinite bounds where they are unbounded anyway.
+    ///
+    /// # Panics
+    ///
+    /// With `Semantics::Strict`, operations on values panic on indeterminate forms.
+    pub fn execute<R, F: FnOnce() -> R>(self, func: F) -> R {
+        struct Restore(Semantics);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                CURRENT.with(|current| current.set(self.0));
+            }
+        }
+
+        let _restore = Restore(self.replace());
+        func()
+    }
+
+    /// Put this semantics in effect until it is replaced, returning the previous one
+    pub(crate) fn replace(self) -> Semantics {
+        CURRENT.with(|current| current.replace(self))
+    }
+
+    /// Sum of intervals
+    pub fn add<T>(self, a: Interval<T>, b: Interval<T>) -> Result<Interval<T>, Indeterminate>
+        where T: Float + RoundedArith
+    {
+        match self {
+            Semantics::SetBased => Ok(real(a) + real(b)),
+            _ if a.is_empty() || b.is_empty() => Ok(a + b),
+            Semantics::Cset => {
+                let sum = a + b;
+                // Bounds are NaN only for opposite infinities, which sum to anything
+                Ok(Interval {
+                    start: if sum.start.is_nan() { T::neg_infinity() } else { sum.start },
+                    end: if sum.end.is_nan() { T::infinity() } else { sum.end },
+                })
+            }
+            Semantics::Strict => {
+                let sum = a + b;
+                if sum.start.is_nan() || sum.end.is_nan() {
+                    Err(Indeterminate::InfinityMinusInfinity)
+                } else {
+                    Ok(sum)
+                }
+            }
+        }
+    }
+
+    /// Difference of intervals
+    pub fn sub<T>(self, a: Interval<T>, b: Interval<T>) -> Result<Interval<T>, Indeterminate>
+        where T: Float + RoundedArith
+    {
+        self.add(a, -b)
+    }
+
+    /// Product of intervals
+    pub fn mul<T>(self, a: Interval<T>, b: Interval<T>) -> Result<Interval<T>, Indeterminate>
+        where T: Float + RoundedArith
+    {
+        if self == Semantics::SetBased {
+            return Ok(real(a) * real(b));
+        }
+
+        let zero = T::zero();
+        let indeterminate = |a: &Interval<T>, b: &Interval<T>| a.contains(zero) && !b.is_bounded();
+        if a.is_empty() || b.is_empty() || !(indeterminate(&a, &b) || indeterminate(&b, &a)) {
+            return Ok(a * b);
+        }
+
+        match self {
+            Semantics::Strict => Err(Indeterminate::ZeroTimesInfinity),
+            _ => Ok(Interval::entire()),
+        }
+    }
+
+    /// Quotient of intervals
+    ///
+    /// Containment set of `x / 0` is `{-∞, ∞}` for non-zero `x`, so the result is the whole line
+    /// also when dividing by `[0, 0]`.
+    pub fn div<T>(self, a: Interval<T>, b: Interval<T>) -> Result<Interval<T>, Indeterminate>
+        where T: Float + RoundedArith
+    {
+        if self == Semantics::SetBased {
+            return Ok(real(a) / real(b));
+        }
+        if a.is_empty() || b.is_empty() {
+            return Ok(a / b);
+        }
+
+        let zero = T::zero();
+        let infinite = !a.is_bounded() && !b.is_bounded();
+        let zeros = b == Interval::exact(zero) || (a.contains(zero) && b.contains(zero));
+        match self {
+            Semantics::Strict if b.contains(zero) => Err(Indeterminate::DivisionByZero),
+            Semantics::Strict if infinite => Err(Indeterminate::InfinityOverInfinity),
+            _ if infinite || zeros => Ok(Interval::entire()),
+            _ => Ok(a / b),
+        }
+    }
+
+    /// Product `a * b + c`, with single rounding for set-based semantics
+    pub fn mul_add<T>(self,
+                      a: Interval<T>,
+                      b: Interval<T>,
+                      c: Interval<T>)
+                      -> Result<Interval<T>, Indeterminate>
+        where T: Float + RoundedArith
+    {
+        match self {
+            Semantics::SetBased => Ok(real(a).mul_add(real(b), real(c))),
+            _ => self.mul(a, b).and_then(|ab| self.add(ab, c)),
+        }
+    }
+}
+
+/// Real values of interval, so empty when it has only infinite ones
+fn real<T: Float>(x: Interval<T>) -> Interval<T> {
+    if x.start == T::infinity() || x.end == T::neg_infinity() {
+        Interval {
+            start: T::max_value(),
+            end: T::min_value(),
+        }
+    } else {
+        x
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn conventions() {
+        let i = Interval::with_range;
+        let (zero, entire) = (Interval::exact(0.), Interval::entire());
+        let (positive, infinity) = (i(1., f64::INFINITY), Interval::exact(f64::INFINITY));
+        let all = [Semantics::SetBased, Semantics::Cset, Semantics::Strict];
+
+        // Conventions agree away from indeterminate forms
+        for &s in &all {
+            let (a, b) = (i(1., 2.), i(-3., 0.5));
+            assert_eq!(s.add(a, positive), Ok(a + positive));
+            assert_eq!(s.sub(a, b), Ok(a - b));
+            assert_eq!(s.mul(a, b), Ok(a * b));
+            assert_eq!(s.div(a, positive), Ok(a / positive));
+            assert_eq!(s.mul_add(a, b, positive), Ok(a.mul_add(b, positive)));
+            assert!(s.add(Interval::empty(), infinity).unwrap().is_empty());
+            assert!(s.div(zero, Interval::empty()).unwrap().is_empty());
+        }
+
+        let set = Semantics::SetBased;
+        assert_eq!(set.mul(zero, positive), Ok(zero));
+        assert!(set.add(infinity, -infinity).unwrap().is_empty());
+        assert!(set.div(i(1., 2.), zero).unwrap().is_empty());
+        assert_eq!(set.div(positive, positive), Ok(i(0., f64::INFINITY)));
+
+        let cset = Semantics::Cset;
+        assert_eq!(cset.mul(zero, positive), Ok(entire));
+        assert_eq!(cset.mul(i(0., 1.), i(f64::NEG_INFINITY, -1.)), Ok(entire));
+        assert_eq!(cset.add(infinity, -infinity), Ok(entire));
+        assert_eq!(cset.sub(positive, infinity), Ok(entire));
+        assert_eq!(cset.add(infinity, i(1., 2.)), Ok(infinity));
+        assert_eq!(cset.div(i(1., 2.), zero), Ok(entire));
+        assert_eq!(cset.div(i(-1., 2.), i(0., 1.)), Ok(entire));
+        assert_eq!(cset.div(i(1., 2.), i(0., 1.)), Ok(i(1., f64::INFINITY)));
+        assert_eq!(cset.div(positive, positive), Ok(entire));
+        assert_eq!(cset.mul_add(zero, positive, i(1., 2.)), Ok(entire));
+
+        let strict = Semantics::Strict;
+        assert_eq!(strict.mul(positive, i(-1., 0.)), Err(Indeterminate::ZeroTimesInfinity));
+        assert_eq!(strict.sub(infinity, infinity), Err(Indeterminate::InfinityMinusInfinity));
+        assert_eq!(strict.div(i(1., 2.), i(0., 1.)), Err(Indeterminate::DivisionByZero));
+        assert_eq!(strict.div(positive, positive), Err(Indeterminate::InfinityOverInfinity));
+        assert_eq!(strict.mul(zero, i(1., 2.)), Ok(zero));
+        assert_eq!(Indeterminate::ZeroTimesInfinity.to_string(), "indeterminate form 0 · ∞");
+    }
+
+    #[test]
+    fn scoped() {
+        use strategy::{Strategy, Value};
+
+        let x = Value::variable(Interval::with_range(0., 1.));
+        let unbounded = Value::constant(Interval::with_range(1., f64::INFINITY));
+        let product = (x.clone() * unbounded.clone()).enclosure();
+        assert_eq!(product, Interval::with_range(0., f64::INFINITY));
+
+        Semantics::Cset.execute(|| {
+            assert_eq!(Semantics::current(), Semantics::Cset);
+            assert_eq!((x.clone() * unbounded.clone()).enclosure(), Interval::entire());
+            let zero = Value::constant(Interval::exact(0.));
+            assert_eq!((Value::from(1.) / zero).enclosure(), Interval::entire());
+            // Bounded operations are the same and keep strategy
+            let y = Strategy::Affine.execute(|| Value::variable(Interval::with_range(1., 2.)));
+            assert_eq!((y.clone() - y).enclosure(), Interval::exact(0.));
+        });
+        assert_eq!(Semantics::current(), Semantics::SetBased);
+
+        let panicked = ::std::panic::catch_unwind(|| {
+            Semantics::Strict.execute(|| (Value::from(1.) / Value::from(0.)).enclosure())
+        });
+        assert!(panicked.is_err());
+        assert_eq!(Semantics::current(), Semantics::SetBased);
+    }
+}
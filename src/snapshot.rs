@@ -0,0 +1,291 @@
+//! Bit-exact snapshots of interval results for golden-file tests.
+//!
+//! Available with the `io` feature. Snapshot is a list of named intervals, stored one per line
+//! as `name = [start, end]` with endpoints written exactly as hexadecimal floats. Results of a
+//! numeric kernel are recorded once, and every later run compares them with the stored ones
+//! with no tolerance, so any refactoring that changes even the sign of a zero bound is caught.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use inter::Interval;
+//! use inter::snapshot::Snapshot;
+//!
+//! let mut stored = Snapshot::new();
+//! stored.push("third", Interval::with_range(1., 2.) / Interval::exact(3.));
+//! let mut file = Vec::new();
+//! stored.write(&mut file).unwrap();
+//!
+//! let mut current = Snapshot::new();
+//! current.push("third", Interval::with_range(1. / 3., 2. / 3.));
+//! let differences = current.diff(&Snapshot::read(&file[..]).unwrap());
+//!
+//! assert_eq!(differences.len(), 1);
+//! assert_eq!(differences[0].name, "third");
+//! ```
+
+use std::error;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+use interval::Interval;
+
+/// Named intervals, in order they were added
+#[derive(Clone, Debug, Default)]
+pub struct Snapshot {
+    entries: Vec<(String, Interval<f64>)>,
+}
+
+/// Entry that differs between snapshots
+#[derive(Clone, Debug, PartialEq)]
+pub struct Difference {
+    /// Name of entry
+    pub name: String,
+    /// Stored interval, or `None` when entry is new
+    pub expected: Option<Interval<f64>>,
+    /// Current interval, or `None` when entry is missing
+    pub actual: Option<Interval<f64>>,
+}
+
+/// Error while reading or checking snapshot
+#[derive(Debug)]
+pub enum Error {
+    /// Underlying reader or writer failed
+    Io(io::Error),
+    /// Line is not a named interval with exact hexadecimal endpoints
+    Invalid { line: usize },
+    /// Current results differ from stored ones
+    Mismatch(Vec<Difference>),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Io(ref err) => write!(fmt, "I/O error: {}", err),
+            Error::Invalid { line } => write!(fmt, "line {}: invalid snapshot entry", line),
+            Error::Mismatch(ref differences) => {
+                write!(fmt, "{} entries differ from snapshot", differences.len())?;
+                for difference in differences {
+                    write!(fmt,
+                           "\n{}: expected {}, got {}",
+                           difference.name,
+                           describe(difference.expected),
+                           describe(difference.actual))?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            Error::Io(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+impl Snapshot {
+    /// Snapshot without entries
+    pub fn new() -> Self {
+        Snapshot { entries: Vec::new() }
+    }
+
+    /// Add interval `x` named `name`
+    ///
+    /// Empty intervals are all stored the same, whatever their bounds are.
+    ///
+    /// # Panics
+    ///
+    /// This will panic if `name` contains line break or ` = `, as it could not be read back.
+    pub fn push(&mut self, name: &str, x: Interval<f64>) {
+        assert!(!name.contains(['\n', '\r']) && !name.contains(" = "),
+                "invalid snapshot name: {:?}",
+                name);
+
+        self.entries.push((name.to_string(), x));
+    }
+
+    /// Names and intervals of all entries
+    pub fn entries(&self) -> &[(String, Interval<f64>)] {
+        &self.entries
+    }
+
+    /// Write all entries, one per line
+    pub fn write<W>(&self, mut writer: W) -> io::Result<()>
+        where W: Write
+    {
+        for &(ref name, x) in &self.entries {
+            writeln!(writer, "{} = {}", name, x.display_hex())?;
+        }
+
+        Ok(())
+    }
+
+    /// Read entries written by `write`, skipping empty lines
+    pub fn read<R>(reader: R) -> Result<Self, Error>
+        where R: BufRead
+    {
+        let mut snapshot = Snapshot::new();
+
+        for (i, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let invalid = || Error::Invalid { line: i + 1 };
+            let at = line.rfind(" = ").ok_or_else(invalid)?;
+            let (name, value) = (&line[..at], line[at + 3..].trim());
+            let x = match Interval::parse_with_info(value) {
+                Ok((x, info)) if info.is_exact() => x,
+                _ => return Err(invalid()),
+            };
+            snapshot.entries.push((name.to_string(), x));
+        }
+
+        Ok(snapshot)
+    }
+
+    /// Entries of this snapshot that are not bit for bit the same as in `expected`
+    ///
+    /// Entries are matched by name, and those present in only one of snapshots also differ.
+    /// Stored entries come first, in their order, followed by new ones.
+    pub fn diff(&self, expected: &Snapshot) -> Vec<Difference> {
+        let find = |snapshot: &Snapshot, name: &str| {
+            snapshot.entries.iter().find(|entry| entry.0 == name).map(|entry| entry.1)
+        };
+
+        let stored = expected.entries
+            .iter()
+            .map(|&(ref name, x)| (name, Some(x), find(self, name)));
+        let new = self.entries
+            .iter()
+            .filter(|entry| find(expected, &entry.0).is_none())
+            .map(|&(ref name, x)| (name, None, Some(x)));
+
+        stored.chain(new)
+            .filter(|&(_, expected, actual)| !same(expected, actual))
+            .map(|(name, expected, actual)| {
+                Difference {
+                    name: name.clone(),
+                    expected,
+                    actual,
+                }
+            })
+            .collect()
+    }
+
+    /// Compare with snapshot stored at `path`, creating it from this one when it does not exist
+    ///
+    /// The first run therefore records results and later runs check them. Delete the file to
+    /// record new results after an intended change.
+    pub fn check<P>(&self, path: P) -> Result<(), Error>
+        where P: AsRef<Path>
+    {
+        let file = match File::open(path.as_ref()) {
+            Ok(file) => file,
+            Err(ref err) if err.kind() == io::ErrorKind::NotFound => {
+                let mut writer = BufWriter::new(File::create(path)?);
+                self.write(&mut writer)?;
+                return writer.flush().map_err(Error::Io);
+            }
+            Err(err) => return Err(Error::Io(err)),
+        };
+
+        let differences = self.diff(&Snapshot::read(BufReader::new(file))?);
+        if differences.is_empty() { Ok(()) } else { Err(Error::Mismatch(differences)) }
+    }
+}
+
+/// Check if entries have the same representation
+fn same(a: Option<Interval<f64>>, b: Option<Interval<f64>>) -> bool {
+    describe(a) == describe(b)
+}
+
+fn describe(x: Option<Interval<f64>>) -> String {
+    match x {
+        Some(x) => x.display_hex().to_string(),
+        None => "nothing".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use std::fs;
+
+    fn sample() -> Snapshot {
+        let mut snapshot = Snapshot::new();
+        snapshot.push("tenth", Interval::with_range(0.1, 0.3));
+        snapshot.push("with spaces", Interval::with_range(-0., f64::INFINITY));
+        snapshot.push("empty", Interval::empty());
+        snapshot
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut out = Vec::new();
+        sample().write(&mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.starts_with("tenth = [0x1.999999999999ap-4, "));
+        assert!(text.contains("with spaces = [-0x0p+0, inf]\nempty = [empty]\n"));
+
+        let back = Snapshot::read(text.as_bytes()).unwrap();
+        assert_eq!(back.entries(), sample().entries());
+        assert!(sample().diff(&back).is_empty());
+
+        match Snapshot::read("a = [0.1, 1]\n".as_bytes()) {
+            Err(Error::Invalid { line: 1 }) => (),
+            other => panic!("{:?}", other),
+        }
+        match Snapshot::read("\nno value\n".as_bytes()) {
+            Err(Error::Invalid { line: 2 }) => (),
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn differences() {
+        let stored = sample();
+        let mut current = Snapshot::new();
+        current.push("new", Interval::exact(1.));
+        current.push("empty", Interval::empty());
+        current.push("with spaces", Interval::with_range(0., f64::INFINITY));
+
+        // Sign of zero matters, missing and new entries differ
+        let differences = current.diff(&stored);
+        let names: Vec<_> = differences.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, ["tenth", "with spaces", "new"]);
+        assert_eq!((differences[0].actual, differences[2].expected), (None, None));
+
+        let path = ::std::env::temp_dir().join(format!("inter-snapshot-{}", ::std::process::id()));
+        let _ = fs::remove_file(&path);
+        stored.check(&path).unwrap();
+        stored.check(&path).unwrap();
+        match current.check(&path) {
+            Err(Error::Mismatch(ref found)) if *found == differences => (),
+            other => panic!("{:?}", other),
+        }
+        let message = current.check(&path).unwrap_err().to_string();
+        assert!(message.starts_with("3 entries differ from snapshot\ntenth: expected ["));
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid snapshot name")]
+    fn invalid_name() {
+        Snapshot::new().push("a = b", Interval::exact(0.));
+    }
+}
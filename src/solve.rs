@@ -0,0 +1,520 @@
+//! Rigorous root finding with the interval Newton method.
+//!
+//! Newton operator `N(X) = m - f(m) / f'(X)`, where `m` is a point of `X`, contains every root
+//! of `f` in `X`. Intersecting it with `X` never loses a root, and when `N(X)` lies in the
+//! interior of `X` the root exists and is unique. Extended division splits the operator in two
+//! when derivative can vanish, which separates close roots.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use inter::Interval;
+//! use inter::solve;
+//!
+//! let two = Interval::exact(2.);
+//! let domain = Interval::with_range(-3., 3.);
+//! let roots = solve::newton(|x| x * x - two, |x| two * x, domain, 1e-12, 100);
+//!
+//! assert_eq!(roots.len(), 2);
+//! assert!(roots[1].unique && roots[1].interval.contains(2f64.sqrt()));
+//! ```
+//!
+//! Roots of polynomials can also be counted without finding them, which gives exclusion and
+//! existence tests for whole families of polynomials with uncertain coefficients:
+//!
+//! ```rust
+//! use inter::Interval;
+//! use inter::solve;
+//!
+//! // x² - c for every c in [1.9, 2.1]
+//! let p = [Interval::with_range(-2.1, -1.9), Interval::exact(0.), Interval::exact(1.)];
+//!
+//! assert_eq!(solve::count_roots(&p, Interval::with_range(0., 2.), 20), 1..=1);
+//! assert_eq!(solve::count_roots(&p, Interval::with_range(-2., 2.), 20), 2..=2);
+//! assert_eq!(solve::count_roots(&p, Interval::with_range(2., 3.), 20), 0..=0);
+//! ```
+//!
+//! For transcendental equations `solve_scalar` does everything in one call, with derivative
+//! computed along the way:
+//!
+//! ```rust
+//! use inter::Interval;
+//! use inter::solve;
+//!
+//! // cos x = x
+//! let roots = solve::solve_scalar(|x| x.clone().cos() - x, Interval::with_range(-10., 10.));
+//!
+//! assert_eq!(roots.len(), 1);
+//! assert!(roots[0].unique && roots[0].interval.contains(0.7390851332151607));
+//! assert!(roots[0].residual.contains(0.));
+//! ```
+
+use std::ops::RangeInclusive;
+
+use interval::Interval;
+use strategy::{Strategy, Value};
+use utils::partial_max;
+
+/// Enclosure of root found by `newton`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Root {
+    /// Interval that can contain root
+    pub interval: Interval<f64>,
+    /// Interval is proved to contain exactly one root
+    pub unique: bool,
+}
+
+/// Find enclosures of all roots of `f` in `domain`
+///
+/// `df` has to enclose derivative of `f` over every interval it is called with. Every root from
+/// `domain` is contained in one of returned intervals, sorted by their position. Intervals are
+/// narrowed until they are not wider than `tolerance` or `max_iter` Newton steps (in total) were
+/// made, and bisected when Newton step does not shrink them enough. Interval that is not proved
+/// to contain a unique root (for example around multiple root or when iterations run out) may
+/// also contain no root at all.
+pub fn newton<F, D>(f: F,
+                    df: D,
+                    domain: Interval<f64>,
+                    tolerance: f64,
+                    max_iter: usize)
+                    -> Vec<Root>
+    where F: Fn(Interval<f64>) -> Interval<f64>,
+          D: Fn(Interval<f64>) -> Interval<f64>
+{
+    let mut roots = Vec::new();
+    let mut queue = vec![(domain, false)];
+    let mut iterations = 0;
+
+    while let Some((x, unique)) = queue.pop() {
+        if x.is_empty() || !f(x).contains_zero() {
+            continue;
+        }
+        if x.width() <= tolerance || iterations >= max_iter {
+            roots.push(Root {
+                interval: x,
+                unique,
+            });
+            continue;
+        }
+        iterations += 1;
+
+        let m = Interval::exact(x.mid());
+        let steps = match f(m).div_split(df(x)) {
+            Some(quotient) => quotient.iter().map(|q| m - q).collect(),
+            // Derivative is zero, so `f` is constant, or `f` is not defined at `m`
+            None => vec![x],
+        };
+        let unique = unique || steps.len() == 1 && steps[0].interior_of(&x);
+        let pieces: Vec<_> = steps.iter()
+            .map(|n| n.intersect(&x))
+            .filter(|y| !y.is_empty())
+            .collect();
+
+        match pieces[..] {
+            [y] if y.width() > 0.75 * x.width() => {
+                let mid = y.mid();
+                if mid == y.start || mid == y.end {
+                    // Cannot be split any further
+                    roots.push(Root {
+                        interval: y,
+                        unique,
+                    });
+                } else {
+                    // Root is in one half only, so uniqueness has to be proved again
+                    queue.push((Interval::with_range(mid, y.end), false));
+                    queue.push((Interval::with_range(y.start, mid), false));
+                }
+            }
+            [y] => queue.push((y, unique)),
+            _ => queue.extend(pieces.into_iter().rev().map(|y| (y, false))),
+        }
+    }
+
+    roots.sort_by(|a, b| a.interval.start.total_cmp(&b.interval.start));
+    roots
+}
+
+/// Root of scalar equation found by `solve_scalar`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CertifiedRoot {
+    /// Interval that can contain root
+    pub interval: Interval<f64>,
+    /// Interval is proved to contain exactly one root
+    pub unique: bool,
+    /// Interval is proved to contain at least one root
+    pub exists: bool,
+    /// Enclosure of values of `f` over `interval`
+    pub residual: Interval<f64>,
+}
+
+/// Find enclosures of all roots of `f` in `domain`, without giving derivative by hand
+///
+/// `f` is evaluated with `Strategy::Centered`, which encloses derivative together with the
+/// value, and roots are found by `newton` with tolerance relative to magnitude of `domain`.
+/// Beside uniqueness proved by Newton steps, existence of a root is proved when `f` has values
+/// of opposite signs at the endpoints of its interval and bounded derivative over it.
+///
+/// # Panics
+///
+/// This will panic if `domain` is not bounded.
+pub fn solve_scalar<F>(f: F, domain: Interval<f64>) -> Vec<CertifiedRoot>
+    where F: Fn(Value<f64>) -> Value<f64>
+{
+    assert!(domain.is_bounded(), "domain has to be bounded");
+
+    let eval = |x| {
+        Strategy::Centered.execute(|| {
+            let y = f(Value::variable(x));
+            (y.enclosure(), y.derivative())
+        })
+    };
+    let value = |x| eval(x).0;
+    let derivative = |x| eval(x).1.unwrap_or_else(Interval::entire);
+    let tolerance = 1e-12 * domain.mag().max(1.);
+
+    newton(value, derivative, domain, tolerance, 1000)
+        .into_iter()
+        .map(|root| {
+            let x = root.interval;
+            let (a, b) = (value(Interval::exact(x.start)), value(Interval::exact(x.end)));
+            // Bounded derivative proves continuity, otherwise sign can change at a pole
+            let sign_change = a.end < 0. && b.start > 0. || a.start > 0. && b.end < 0.;
+
+            CertifiedRoot {
+                interval: x,
+                unique: root.unique,
+                exists: root.unique || derivative(x).is_bounded() && sign_change,
+                residual: value(x),
+            }
+        })
+        .collect()
+}
+
+/// Range of the number of roots in `domain` of polynomials with coefficients from `coeffs`
+///
+/// Coefficients are given in ascending order, like in `Interval::eval_poly`, and roots are
+/// counted with multiplicity. Every polynomial from the family that is not identically zero has
+/// number of roots within returned range. `domain` is bisected at most `max_depth` times in
+/// every branch, where Descartes' rule of signs does not settle the count, so when roots of the
+/// family fill a whole interval the cost grows exponentially with `max_depth`.
+///
+/// # Panics
+///
+/// This will panic if `domain` is unbounded.
+pub fn count_roots(coeffs: &[Interval<f64>],
+                   domain: Interval<f64>,
+                   max_depth: usize)
+                   -> RangeInclusive<usize> {
+    assert!(domain.is_bounded(), "domain has to be bounded");
+
+    if domain.is_empty() {
+        return 0..=0;
+    }
+    if domain.start == domain.end {
+        return multiplicity(coeffs, domain.start);
+    }
+
+    let parts = [multiplicity(coeffs, domain.start),
+                 count_open(coeffs, domain.start, domain.end, max_depth),
+                 multiplicity(coeffs, domain.end)];
+
+    parts.iter().map(|r| *r.start()).sum()..=parts.iter().map(|r| *r.end()).sum()
+}
+
+/// Cauchy bound `1 + max |aᵢ / aₙ|` on roots of polynomials with coefficients from `coeffs`
+///
+/// Returned interval contains real parts of all complex roots of every polynomial from the
+/// family. It is empty for nonzero constants and entire when leading coefficient can be zero.
+///
+/// ## Example
+///
+/// ```rust
+/// use inter::Interval;
+/// use inter::solve;
+///
+/// // (x - 1)(x + 3) = x² + 2x - 3
+/// let p = [Interval::exact(-3.), Interval::exact(2.), Interval::exact(1.)];
+///
+/// assert_eq!(solve::cauchy_bound(&p), Interval::with_range(-4., 4.));
+/// assert_eq!(solve::lagrange_bound(&p), Interval::with_range(-5., 5.));
+/// assert!(solve::fujiwara_bound(&p).contains_all(&[-4., 4.]));
+/// ```
+pub fn cauchy_bound(coeffs: &[Interval<f64>]) -> Interval<f64> {
+    root_radius(coeffs, |ratios| {
+        let max = ratios.iter().fold(0., |acc, r| partial_max(acc, r.end));
+        (Interval::exact(1.) + Interval::exact(max)).end
+    })
+}
+
+/// Lagrange bound `max(1, Σ |aᵢ / aₙ|)` on roots, see `cauchy_bound`
+pub fn lagrange_bound(coeffs: &[Interval<f64>]) -> Interval<f64> {
+    root_radius(coeffs, |ratios| {
+        let sum = ratios.iter().fold(Interval::exact(0.), |acc, &r| acc + r);
+        partial_max(1., sum.end)
+    })
+}
+
+/// Fujiwara bound `2 max |aₙ₋ₖ / aₙ|^(1/k)` on roots, with `a₀` halved, see `cauchy_bound`
+///
+/// It is never worse than twice the smallest possible bound depending on absolute values of
+/// coefficients only.
+pub fn fujiwara_bound(coeffs: &[Interval<f64>]) -> Interval<f64> {
+    root_radius(coeffs, |ratios| {
+        let n = ratios.len();
+        let max = ratios.iter().enumerate().fold(0., |acc, (i, &r)| {
+            let r = if i == 0 { r / Interval::exact(2.) } else { r };
+            partial_max(acc, r.nth_root((n - i) as i32).end)
+        });
+        2. * max
+    })
+}
+
+/// The tightest of Cauchy, Lagrange and Fujiwara bounds on roots, see `cauchy_bound`
+///
+/// It can be used as initial domain of `newton` or `count_roots`.
+pub fn root_bound(coeffs: &[Interval<f64>]) -> Interval<f64> {
+    cauchy_bound(coeffs).intersect(&lagrange_bound(coeffs)).intersect(&fujiwara_bound(coeffs))
+}
+
+/// `[-r, r]`, where `r` is computed by `radius` from `|aᵢ / aₙ|` for `i < n`
+fn root_radius<F>(coeffs: &[Interval<f64>], radius: F) -> Interval<f64>
+    where F: FnOnce(&[Interval<f64>]) -> f64
+{
+    let degree = match coeffs.iter().rposition(|c| c.start != 0. || c.end != 0.) {
+        Some(degree) => degree,
+        None => return Interval::entire(),
+    };
+    let lead = coeffs[degree];
+    if lead.contains_zero() {
+        return Interval::entire();
+    }
+    if degree == 0 {
+        return Interval::empty();
+    }
+
+    let ratios: Vec<_> = coeffs[..degree].iter().map(|&c| (c / lead).abs()).collect();
+    let r = radius(&ratios);
+
+    Interval::with_range(-r, r)
+}
+
+/// Range of number of roots in open interval `(a, b)`
+fn count_open(coeffs: &[Interval<f64>],
+              a: f64,
+              b: f64,
+              depth: usize)
+              -> RangeInclusive<usize> {
+    let x = Interval::with_range(a, b);
+    let value = coeffs.iter().rev().fold(Interval::exact(0.), |acc, &c| acc * x + c);
+    if !value.contains_zero() {
+        return 0..=0;
+    }
+
+    let (lower, upper) = descartes(coeffs, a, b);
+    let m = x.mid();
+    if lower == upper || depth == 0 || m == a || m == b {
+        return lower..=upper;
+    }
+
+    let parts = [count_open(coeffs, a, m, depth - 1),
+                 multiplicity(coeffs, m),
+                 count_open(coeffs, m, b, depth - 1)];
+    let start = parts.iter().map(|r| *r.start()).sum::<usize>();
+    let end = parts.iter().map(|r| *r.end()).sum::<usize>();
+
+    lower.max(start)..=upper.min(end)
+}
+
+/// Range of multiplicity of `x` as a root
+///
+/// It is at most the index of the first coefficient of `p(x + y)` that cannot be zero.
+fn multiplicity(coeffs: &[Interval<f64>], x: f64) -> RangeInclusive<usize> {
+    let shifted = taylor_shift(coeffs.to_vec(), Interval::exact(x));
+    let degree = shifted.len().saturating_sub(1);
+    let zeros = shifted.iter().take_while(|c| c.start == 0. && c.end == 0.).count();
+    let upper = shifted.iter().position(|c| !c.contains_zero()).unwrap_or(degree);
+
+    zeros.min(upper)..=upper
+}
+
+/// Bounds of number of roots in `(a, b)` by Descartes' rule of signs
+///
+/// Roots of `p` in `(a, b)` are roots of `(1 + x)ⁿ p((a + bx) / (1 + x))` in `(0, ∞)`, and their
+/// number does not exceed the number of sign variations of its coefficients, with the same
+/// parity. Coefficients that can be zero are counted as any sign.
+fn descartes(coeffs: &[Interval<f64>], a: f64, b: f64) -> (usize, usize) {
+    // p(a + (b - a)y) on (0, 1)
+    let mut scale = Interval::exact(1.);
+    let width = Interval::exact(b) - Interval::exact(a);
+    let mut q: Vec<_> = taylor_shift(coeffs.to_vec(), Interval::exact(a))
+        .into_iter()
+        .map(|c| {
+            let c = c * scale;
+            scale *= width;
+            c
+        })
+        .collect();
+    q.reverse();
+    let q = taylor_shift(q, Interval::exact(1.));
+
+    // The most variations ending with positive and negative coefficient, and whether all
+    // coefficients so far can be zero
+    let (mut pos, mut neg, mut none) = (None, None, true);
+    for c in &q {
+        let after = |same: Option<usize>, other: Option<usize>| {
+            let start = if none { Some(0) } else { None };
+            same.max(other.map(|v| v + 1)).max(start)
+        };
+        let (p, n) = (after(pos, neg), after(neg, pos));
+        let zero = c.contains_zero();
+        if c.end > 0. {
+            pos = if zero { p.max(pos) } else { p };
+        } else if !zero {
+            pos = None;
+        }
+        if c.start < 0. {
+            neg = if zero { n.max(neg) } else { n };
+        } else if !zero {
+            neg = None;
+        }
+        none = none && zero;
+    }
+    let upper = pos.max(neg).unwrap_or(0);
+
+    // Parity is odd when the first and the last coefficient have opposite signs
+    let lower = match (q.first(), q.last()) {
+        (Some(first), Some(last)) => {
+            (first.start > 0. && last.end < 0. || first.end < 0. && last.start > 0.) as usize
+        }
+        _ => 0,
+    };
+
+    (lower.min(upper), upper)
+}
+
+/// Coefficients of `p(x + s)`
+fn taylor_shift(mut coeffs: Vec<Interval<f64>>, s: Interval<f64>) -> Vec<Interval<f64>> {
+    let n = coeffs.len();
+    for i in 0..n.saturating_sub(1) {
+        for j in (i..n - 1).rev() {
+            coeffs[j] = coeffs[j] + s * coeffs[j + 1];
+        }
+    }
+
+    coeffs
+}
+
+#[cfg(test)]
+mod test {
+    use std::f64::consts::PI;
+
+    use super::*;
+
+    #[test]
+    fn simple_roots() {
+        let roots = newton(|x| x.sin(), |x| x.cos(), Interval::with_range(1., 10.), 1e-12, 200);
+
+        assert_eq!(roots.len(), 3);
+        for (k, root) in roots.iter().enumerate() {
+            assert!(root.unique && root.interval.width() <= 1e-12);
+            assert!(root.interval.contains((k + 1) as f64 * PI));
+        }
+    }
+
+    #[test]
+    fn no_roots() {
+        let one = Interval::exact(1.);
+        let roots = newton(|x| x * x + one, |x| x * 2., Interval::with_range(-5., 5.), 1e-9, 100);
+
+        assert!(roots.is_empty());
+    }
+
+    #[test]
+    fn double_root() {
+        let one = Interval::exact(1.);
+        let f = |x: Interval<f64>| (x - one).powi(2);
+        let roots = newton(f, |x| (x - one) * 2., Interval::with_range(0., 3.), 1e-6, 50);
+
+        assert!(!roots.is_empty() && roots.iter().all(|root| !root.unique));
+        assert!(roots.iter().any(|root| root.interval.contains(1.)));
+        assert!(roots.iter().all(|root| root.interval.subset(&Interval::with_range(0.9, 1.1))));
+    }
+
+    #[test]
+    fn root_counting() {
+        let p = |coeffs: &[f64]| coeffs.iter().map(|&c| Interval::exact(c)).collect::<Vec<_>>();
+        // (x - 1)(x - 2)(x - 3)
+        let cubic = p(&[-6., 11., -6., 1.]);
+
+        assert_eq!(count_roots(&cubic, Interval::with_range(0., 4.), 30), 3..=3);
+        assert_eq!(count_roots(&cubic, Interval::with_range(1., 2.), 30), 2..=2);
+        assert_eq!(count_roots(&cubic, Interval::with_range(1.5, 2.5), 30), 1..=1);
+        assert_eq!(count_roots(&cubic, Interval::exact(3.), 30), 1..=1);
+        assert_eq!(count_roots(&p(&[1., 0., 1.]), Interval::with_range(-9., 9.), 30), 0..=0);
+        assert_eq!(count_roots(&cubic, Interval::empty(), 30), 0..=0);
+        assert_eq!(count_roots(&p(&[0., 0., 1.]), Interval::with_range(-1., 1.), 30), 2..=2);
+    }
+
+    #[test]
+    fn uncertain_root_counting() {
+        // x² - 2x + c for c in [0.99, 1.01] has two, one (double) or no roots near 1
+        let p = [Interval::with_range(0.99, 1.01), Interval::exact(-2.), Interval::exact(1.)];
+        let count = count_roots(&p, Interval::with_range(0., 2.), 10);
+
+        assert!(count.contains(&0) && count.contains(&2) && *count.end() <= 2);
+        assert_eq!(count_roots(&p, Interval::with_range(1.5, 3.), 10), 0..=0);
+
+        // Count without bisection is only bounded by sign variations
+        let wide = [Interval::with_range(-1., 1.), Interval::exact(0.), Interval::exact(1.)];
+        assert_eq!(count_roots(&wide, Interval::with_range(0.5, 2.), 0), 0..=2);
+        assert_eq!(count_roots(&wide, Interval::with_range(1.5, 2.), 0), 0..=0);
+    }
+
+    #[test]
+    fn scalar_equations() {
+        let roots = solve_scalar(|x| x.sin(), Interval::with_range(1., 10.));
+
+        assert_eq!(roots.len(), 3);
+        for (root, k) in roots.iter().zip(1..) {
+            assert!(root.unique && root.exists && root.interval.contains(k as f64 * PI));
+            assert!(root.interval.width() < 1e-10 && root.residual.width() < 1e-10);
+        }
+
+        // x e^x = 1 has one root
+        let roots = solve_scalar(|x| x.clone() * x.exp() - 1., Interval::with_range(-5., 5.));
+        assert_eq!(roots.len(), 1);
+        assert!(roots[0].interval.contains(0.5671432904097838));
+        assert!(solve_scalar(|x| x.exp(), Interval::with_range(-1., 1.)).is_empty());
+
+        // Double root can be enclosed, but not proved
+        let roots = solve_scalar(|x| x.powi(2), Interval::with_range(-1., 2.));
+        assert!(roots.iter().all(|r| !r.exists && r.residual.contains(0.)));
+        assert!(roots.iter().any(|r| r.interval.contains(0.)));
+
+        // Sign change at the pole of tan is not a root
+        let roots = solve_scalar(|x| x.clone().sin() / x.cos(), Interval::with_range(1., 2.));
+        assert!(roots.iter().all(|r| !r.exists));
+    }
+
+    #[test]
+    fn root_bounds() {
+        let p = |coeffs: &[f64]| coeffs.iter().map(|&c| Interval::exact(c)).collect::<Vec<_>>();
+        // (x - 1)(x - 2)(x + 10)
+        let cubic = p(&[20., -28., 7., 1.]);
+
+        for bound in &[cauchy_bound, lagrange_bound, fujiwara_bound, root_bound] {
+            assert!(bound(&cubic).contains_all(&[1., 2., -10.]));
+            assert!(bound(&p(&[1., 0., 0., 0., 2.])).contains(-0.5f64.powf(0.25)));
+            assert!(bound(&p(&[5.])).is_empty() && bound(&p(&[5., 0.])).is_empty());
+            assert_eq!(bound(&[Interval::exact(1.), Interval::with_range(-1., 1.)]),
+                       Interval::entire());
+            assert_eq!(bound(&[]), Interval::entire());
+        }
+        assert!(root_bound(&cubic).width() <= 2. * 21.);
+        assert_eq!(count_roots(&cubic, root_bound(&cubic), 20), 3..=3);
+
+        // Bounds hold for every polynomial from the family
+        let uncertain = [Interval::with_range(-3., -2.), Interval::exact(0.), Interval::exact(1.)];
+        assert!(root_bound(&uncertain).contains_all(&[-3f64.sqrt(), 3f64.sqrt()]));
+    }
+}
@@ -0,0 +1,561 @@
+//! Scoped choice of evaluation strategy.
+//!
+//! Plain interval arithmetic treats every occurrence of a variable as independent, so ranges of
+//! expressions like `x - x` or `x * (1 - x)` are overestimated. `Value` is a number whose
+//! operators follow the strategy that was in effect when its variables were created, so a whole
+//! region of code can be switched to a tighter (but slower) strategy without rewriting it:
+//!
+//! - `Strategy::Natural` is plain interval arithmetic,
+//! - `Strategy::Affine` tracks linear dependence on variables with affine forms,
+//! - `Strategy::Centered` tracks derivatives and uses mean value form `f(c) + f'(X) (X - c)`.
+//!
+//! Whatever the strategy, result is intersected with plain interval evaluation, so it is never
+//! wider than that. Values created under different strategies can be mixed, but then only plain
+//! interval arithmetic is used for them. Strategy is stored per thread.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use inter::Interval;
+//! use inter::strategy::{Strategy, Value};
+//!
+//! fn f(x: Value<f64>) -> Value<f64> {
+//!     x.clone() * x.clone() - x * 2.
+//! }
+//!
+//! let x = Interval::with_range(0.9, 1.1);
+//! let natural = f(Value::variable(x)).enclosure();
+//! let centered = Strategy::Centered.execute(|| f(Value::variable(x)).enclosure());
+//!
+//! assert!(natural.contains(-1.) && centered.contains(-1.));
+//! assert!(centered.width() < natural.width() / 5.);
+//! ```
+
+use std::cell::Cell;
+use std::ops::{Add, Sub, Mul, Div, Neg};
+
+use num::{Float, FloatConst};
+
+use affine::{Affine, fresh_symbol};
+use expr::Function;
+use interval::Interval;
+use rounding::RoundedArith;
+use semantics::{Indeterminate, Semantics};
+
+thread_local! {
+    static CURRENT: Cell<Strategy> = const { Cell::new(Strategy::Natural) };
+}
+
+/// Method of evaluating operations on `Value`s
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Strategy {
+    /// Plain interval arithmetic
+    #[default]
+    Natural,
+    /// Affine arithmetic
+    Affine,
+    /// Mean value form
+    Centered,
+}
+
+impl Strategy {
+    /// Strategy in effect on current thread
+    pub fn current() -> Strategy {
+        CURRENT.with(Cell::get)
+    }
+
+    /// Run `func` with this strategy in effect, restoring previous one afterwards
+    pub fn execute<R, F: FnOnce() -> R>(self, func: F) -> R {
+        struct Restore(Strategy);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                CURRENT.with(|current| current.set(self.0));
+            }
+        }
+
+        let _restore = Restore(self.replace());
+        func()
+    }
+
+    /// Put this strategy in effect until it is replaced, returning the previous one
+    pub(crate) fn replace(self) -> Strategy {
+        CURRENT.with(|current| current.replace(self))
+    }
+}
+
+/// Number evaluated with strategy chosen when its variables were created
+#[derive(Clone, Debug)]
+pub struct Value<T> {
+    range: Interval<T>,
+    form: Form<T>,
+}
+
+#[derive(Clone, Debug)]
+enum Form<T> {
+    /// Does not depend on any variable
+    Constant,
+    Natural,
+    Affine(Affine<T>),
+    Centered(Centered<T>),
+}
+
+/// Value at the center of variables and slopes with respect to them
+#[derive(Clone, Debug)]
+struct Centered<T> {
+    center: Interval<T>,
+    /// Symbol of variable, enclosure of derivative and of distance of variable from its center
+    slopes: Vec<(usize, Interval<T>, Interval<T>)>,
+}
+
+/// Forms of two operands converted to the same strategy
+enum Pair<T> {
+    Constant,
+    Natural,
+    Affine(Affine<T>, Affine<T>),
+    Centered(Centered<T>, Centered<T>),
+}
+
+impl<T> Value<T>
+    where T: Float + FloatConst + RoundedArith
+{
+    /// Independent variable ranging over `x`, using strategy in effect
+    pub fn variable(x: Interval<T>) -> Self {
+        let form = match Strategy::current() {
+            Strategy::Natural => Form::Natural,
+            Strategy::Affine if x.is_empty() => Form::Natural,
+            Strategy::Affine => Form::Affine(Affine::from_interval(x)),
+            Strategy::Centered => {
+                let center = Interval::exact(x.mid());
+                Form::Centered(Centered {
+                    center,
+                    slopes: vec![(fresh_symbol(), Interval::exact(T::one()), x - center)],
+                })
+            }
+        };
+
+        Value::new(x, form)
+    }
+
+    /// Constant, which can be combined with values of any strategy
+    pub fn constant(x: Interval<T>) -> Self {
+        Value::new(x, Form::Constant)
+    }
+
+    /// Enclosure of the value
+    pub fn enclosure(&self) -> Interval<T> {
+        self.range
+    }
+
+    /// Enclosure of derivative with respect to the only variable, tracked by `Strategy::Centered`
+    ///
+    /// Returns `None` for values of other strategies and for values of several variables.
+    pub fn derivative(&self) -> Option<Interval<T>> {
+        let zero = Interval::exact(T::zero());
+
+        match self.form {
+            Form::Constant => Some(zero),
+            Form::Centered(ref c) => {
+                match c.slopes[..] {
+                    [] => Some(zero),
+                    [(_, d, _)] => Some(d),
+                    _ => None,
+                }
+            }
+            Form::Natural | Form::Affine(_) => None,
+        }
+    }
+
+    /// Apply elementary function
+    pub fn apply(self, f: Function) -> Self {
+        let (x, one) = (self.range, Interval::exact(T::one()));
+        let value = self.apply_set_based(f);
+
+        match f {
+            Function::Recip => value.resolve(one, x, Semantics::div),
+            _ => value,
+        }
+    }
+
+    fn apply_set_based(self, f: Function) -> Self {
+        let range = f.apply(self.range);
+        let form = match self.form {
+            Form::Constant => Form::Constant,
+            Form::Natural => Form::Natural,
+            Form::Affine(a) => {
+                match f {
+                    Function::Powi(n) if n > 0 => {
+                        Form::Affine((1..n).fold(a.clone(), |acc, _| acc * a.clone()))
+                    }
+                    // Other functions are not linearized, result depends on new noise symbol
+                    _ if range.is_empty() => Form::Natural,
+                    _ => Form::Affine(Affine::from_interval(range)),
+                }
+            }
+            Form::Centered(c) => {
+                let derivative = f.derivative(self.range);
+                Form::Centered(Centered {
+                    center: f.apply(c.center),
+                    slopes: c.slopes
+                        .into_iter()
+                        .map(|(symbol, d, dev)| (symbol, d * derivative, dev))
+                        .collect(),
+                })
+            }
+        };
+
+        Value::new(range, form)
+    }
+
+    /// Square root
+    pub fn sqrt(self) -> Self {
+        self.apply(Function::Sqrt)
+    }
+
+    /// Exponential
+    pub fn exp(self) -> Self {
+        self.apply(Function::Exp)
+    }
+
+    /// Natural logarithm
+    pub fn ln(self) -> Self {
+        self.apply(Function::Ln)
+    }
+
+    /// Sine
+    pub fn sin(self) -> Self {
+        self.apply(Function::Sin)
+    }
+
+    /// Cosine
+    pub fn cos(self) -> Self {
+        self.apply(Function::Cos)
+    }
+
+    /// Integer power
+    pub fn powi(self, n: i32) -> Self {
+        self.apply(Function::Powi(n))
+    }
+
+    /// Value with enclosure tightened by its form
+    fn new(range: Interval<T>, form: Form<T>) -> Self {
+        let range = match form {
+            Form::Affine(ref a) => range.intersect(&a.to_interval()),
+            Form::Centered(ref c) => range.intersect(&c.enclosure()),
+            Form::Constant | Form::Natural => range,
+        };
+
+        Value { range, form }
+    }
+
+    /// Result of operation on `u` and `v` under semantics in effect, when it can differ from
+    /// set-based one
+    fn resolve<F>(self, u: Interval<T>, v: Interval<T>, op: F) -> Self
+        where F: Fn(Semantics, Interval<T>, Interval<T>) -> Result<Interval<T>, Indeterminate>
+    {
+        let bounded = |x: Interval<T>| x.is_bounded() && !x.is_empty();
+        match Semantics::current() {
+            Semantics::SetBased => self,
+            _ if bounded(u) && bounded(v) && bounded(self.range) => self,
+            semantics => {
+                match op(semantics, u, v) {
+                    Ok(range) => Value::new(range, Form::Natural),
+                    Err(err) => panic!("{}", err),
+                }
+            }
+        }
+    }
+
+    /// Forms of both operands, with constants converted to the strategy of the other operand
+    fn pair(self, other: Self) -> Pair<T> {
+        match (self.form, other.form) {
+            (Form::Constant, Form::Constant) => Pair::Constant,
+            (Form::Affine(a), Form::Affine(b)) => Pair::Affine(a, b),
+            (Form::Affine(a), Form::Constant) if !other.range.is_empty() => {
+                Pair::Affine(a, affine_constant(other.range))
+            }
+            (Form::Constant, Form::Affine(b)) if !self.range.is_empty() => {
+                Pair::Affine(affine_constant(self.range), b)
+            }
+            (Form::Centered(a), Form::Centered(b)) => Pair::Centered(a, b),
+            (Form::Centered(a), Form::Constant) => {
+                Pair::Centered(a, Centered::constant(other.range))
+            }
+            (Form::Constant, Form::Centered(b)) => {
+                Pair::Centered(Centered::constant(self.range), b)
+            }
+            _ => Pair::Natural,
+        }
+    }
+}
+
+/// Affine form of constant interval, exact for degenerate intervals
+fn affine_constant<T>(x: Interval<T>) -> Affine<T>
+    where T: Float + RoundedArith
+{
+    if x.is_exact() { Affine::constant(x.start) } else { Affine::from_interval(x) }
+}
+
+impl<T> Centered<T>
+    where T: Float + RoundedArith
+{
+    fn constant(x: Interval<T>) -> Self {
+        Centered {
+            center: x,
+            slopes: Vec::new(),
+        }
+    }
+
+    /// Mean value form `f(c) + Σ ∂f/∂xᵢ (xᵢ - cᵢ)`
+    fn enclosure(&self) -> Interval<T> {
+        self.slopes.iter().fold(self.center, |acc, &(_, d, dev)| acc + d * dev)
+    }
+
+    /// Combine slopes of the same variables, `f` is called with `None` for missing ones
+    fn merge<F>(self, other: Self, center: Interval<T>, mut f: F) -> Self
+        where F: FnMut(Option<Interval<T>>, Option<Interval<T>>) -> Interval<T>
+    {
+        let mut slopes = Vec::with_capacity(self.slopes.len() + other.slopes.len());
+        let mut a = self.slopes.into_iter().peekable();
+        let mut b = other.slopes.into_iter().peekable();
+
+        loop {
+            let next = match (a.peek(), b.peek()) {
+                (Some(x), Some(y)) if x.0 == y.0 => {
+                    let (x, y) = (a.next().unwrap(), b.next().unwrap());
+                    (x.0, f(Some(x.1), Some(y.1)), x.2)
+                }
+                (Some(x), Some(y)) if x.0 < y.0 => {
+                    let x = a.next().unwrap();
+                    (x.0, f(Some(x.1), None), x.2)
+                }
+                (_, Some(_)) => {
+                    let y = b.next().unwrap();
+                    (y.0, f(None, Some(y.1)), y.2)
+                }
+                (Some(_), None) => {
+                    let x = a.next().unwrap();
+                    (x.0, f(Some(x.1), None), x.2)
+                }
+                (None, None) => break,
+            };
+            slopes.push(next);
+        }
+
+        Centered { center, slopes }
+    }
+}
+
+impl<T> Add for Value<T>
+    where T: Float + FloatConst + RoundedArith
+{
+    type Output = Value<T>;
+
+    fn add(self, other: Self) -> Self {
+        let (u, v) = (self.range, other.range);
+        let range = u + v;
+        let form = match self.pair(other) {
+            Pair::Constant => Form::Constant,
+            Pair::Natural => Form::Natural,
+            Pair::Affine(a, b) => Form::Affine(a + b),
+            Pair::Centered(a, b) => {
+                let center = a.center + b.center;
+                Form::Centered(a.merge(b, center, |x, y| match (x, y) {
+                    (Some(x), Some(y)) => x + y,
+                    (x, y) => x.or(y).unwrap(),
+                }))
+            }
+        };
+
+        Value::new(range, form).resolve(u, v, Semantics::add)
+    }
+}
+
+impl<T> Sub for Value<T>
+    where T: Float + FloatConst + RoundedArith
+{
+    type Output = Value<T>;
+
+    fn sub(self, other: Self) -> Self {
+        self + -other
+    }
+}
+
+impl<T> Mul for Value<T>
+    where T: Float + FloatConst + RoundedArith
+{
+    type Output = Value<T>;
+
+    fn mul(self, other: Self) -> Self {
+        let (u, v) = (self.range, other.range);
+        let range = u * v;
+        let form = match self.pair(other) {
+            Pair::Constant => Form::Constant,
+            Pair::Natural => Form::Natural,
+            Pair::Affine(a, b) => Form::Affine(a * b),
+            Pair::Centered(a, b) => {
+                // Product rule with derivatives enclosed over the whole box
+                let center = a.center * b.center;
+                Form::Centered(a.merge(b, center, |x, y| match (x, y) {
+                    (Some(x), Some(y)) => x * v + u * y,
+                    (Some(x), None) => x * v,
+                    (None, y) => u * y.unwrap(),
+                }))
+            }
+        };
+
+        Value::new(range, form).resolve(u, v, Semantics::mul)
+    }
+}
+
+impl<T> Div for Value<T>
+    where T: Float + FloatConst + RoundedArith
+{
+    type Output = Value<T>;
+
+    // Division is multiplication by reciprocal, so that it has derivative in centered form
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, other: Self) -> Self {
+        let (u, v) = (self.range, other.range);
+
+        Semantics::SetBased.execute(|| self * other.apply(Function::Recip))
+            .resolve(u, v, Semantics::div)
+    }
+}
+
+impl<T> Neg for Value<T>
+    where T: Float + FloatConst + RoundedArith
+{
+    type Output = Value<T>;
+
+    fn neg(self) -> Self {
+        let form = match self.form {
+            Form::Affine(a) => Form::Affine(-a),
+            Form::Centered(c) => {
+                Form::Centered(Centered {
+                    center: -c.center,
+                    slopes: c.slopes
+                        .into_iter()
+                        .map(|(symbol, d, dev)| (symbol, -d, dev))
+                        .collect(),
+                })
+            }
+            form => form,
+        };
+
+        Value {
+            range: -self.range,
+            form,
+        }
+    }
+}
+
+macro_rules! scalar_ops {
+    ($($imp:ident, $method:ident;)*) => {
+        $(
+            impl<T> $imp<T> for Value<T>
+                where T: Float + FloatConst + RoundedArith
+            {
+                type Output = Value<T>;
+
+                fn $method(self, other: T) -> Self {
+                    self.$method(Value::from(other))
+                }
+            }
+        )*
+    };
+}
+
+scalar_ops! {
+    Add, add;
+    Sub, sub;
+    Mul, mul;
+    Div, div;
+}
+
+impl<T> From<T> for Value<T>
+    where T: Float + FloatConst + RoundedArith
+{
+    fn from(value: T) -> Self {
+        Value::constant(Interval::exact(value))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn f(x: Value<f64>, y: Value<f64>) -> Value<f64> {
+        (x.clone() - y.clone()) * (x + y) / 2.
+    }
+
+    #[test]
+    fn scoped() {
+        assert_eq!(Strategy::current(), Strategy::Natural);
+        Strategy::Affine.execute(|| {
+            assert_eq!(Strategy::current(), Strategy::Affine);
+            Strategy::Centered.execute(|| assert_eq!(Strategy::current(), Strategy::Centered));
+            assert_eq!(Strategy::current(), Strategy::Affine);
+        });
+
+        let panicked = ::std::panic::catch_unwind(|| Strategy::Affine.execute(|| panic!()));
+        assert!(panicked.is_err());
+        assert_eq!(Strategy::current(), Strategy::Natural);
+    }
+
+    #[test]
+    fn derivatives() {
+        let x = Interval::with_range(1., 2.);
+        let f = |x: Value<f64>| x.clone() * x.clone().sin() + x.exp() * 2.;
+        let derivative = Strategy::Centered.execute(|| f(Value::variable(x)).derivative());
+
+        // x cos x + sin x + 2eˣ
+        let d = derivative.unwrap();
+        for t in &[1., 1.5, 2.] {
+            assert!(d.contains(t * f64::cos(*t) + t.sin() + 2. * t.exp()));
+        }
+        assert_eq!(f(Value::variable(x)).derivative(), None);
+        assert_eq!(Value::constant(x).derivative(), Some(Interval::exact(0.)));
+        let (a, b) = Strategy::Centered.execute(|| (Value::variable(x), Value::variable(x)));
+        assert_eq!((a * b).derivative(), None);
+    }
+
+    #[test]
+    fn strategies_enclose_range() {
+        let (x, y) = (Interval::with_range(1., 2.), Interval::with_range(0., 0.5));
+        let eval = |strategy: Strategy| {
+            strategy.execute(|| f(Value::variable(x), Value::variable(y)).enclosure())
+        };
+
+        // Exact range of `(x² - y²) / 2` is `[0.375, 2]`
+        let natural = eval(Strategy::Natural);
+        assert_eq!(natural, Interval::with_range(0.25, 2.5));
+        for &strategy in &[Strategy::Affine, Strategy::Centered] {
+            let enclosure = eval(strategy);
+            assert!(enclosure.contains_all(&[0.375, 2.]), "{:?}", strategy);
+            assert!(enclosure.subset(&natural), "{:?}", strategy);
+        }
+        assert!(eval(Strategy::Affine).width() < natural.width());
+    }
+
+    #[test]
+    fn functions_and_mixing() {
+        let x = Interval::with_range(0., 1.);
+        let (a, c) = Strategy::Affine.execute(|| {
+            let v = Value::variable(x);
+            let c = Strategy::Centered.execute(|| Value::variable(x));
+            (v.clone().exp() - v, c)
+        });
+
+        // Range of `eˣ - x` is `[1, e - 1]`
+        assert!(a.enclosure().contains_all(&[1., 1.718]));
+        let mixed = Value::variable(x) + c;
+        assert_eq!(mixed.enclosure(), Interval::with_range(0., 2.));
+
+        let s = Strategy::Centered.execute(|| {
+            let v = Value::variable(Interval::with_range(-0.1, 0.1));
+            v.clone().sin() - v
+        });
+        assert!(s.enclosure().contains(0.) && s.enclosure().width() < 0.01);
+    }
+}
@@ -0,0 +1,80 @@
+//! Aligned table formatting shared by `Display` of interval collections.
+
+use std::fmt::{self, Write};
+use std::ops::Sub;
+
+use interval::Interval;
+
+/// Write `rows` of intervals as table with every column aligned
+///
+/// Formatter precision is applied to all endpoints. Alternate flag (`{:#}`) appends width of
+/// every interval after it.
+pub fn write_table<T, R>(fmt: &mut fmt::Formatter, rows: &[R]) -> fmt::Result
+    where T: fmt::Display + Sub<Output = T> + Copy,
+          R: AsRef<[Interval<T>]>
+{
+    let number = |x: T| match fmt.precision() {
+        Some(precision) => format!("{:.*}", precision, x),
+        None => format!("{}", x),
+    };
+
+    let cells: Vec<Vec<[String; 3]>> = rows.iter()
+        .map(|row| {
+            row.as_ref()
+                .iter()
+                .map(|i| [number(i.start), number(i.end), number(i.end - i.start)])
+                .collect()
+        })
+        .collect();
+
+    let columns = cells.iter().map(Vec::len).max().unwrap_or(0);
+    let mut widths = vec![[0; 3]; columns];
+    for row in &cells {
+        for (cell, width) in row.iter().zip(&mut widths) {
+            for k in 0..3 {
+                width[k] = width[k].max(cell[k].chars().count());
+            }
+        }
+    }
+
+    for (i, row) in cells.iter().enumerate() {
+        if i > 0 {
+            writeln!(fmt)?;
+        }
+        for (j, (cell, width)) in row.iter().zip(&widths).enumerate() {
+            if j > 0 {
+                write!(fmt, "  ")?;
+            }
+            write!(fmt, "[{:>w0$}, {:>w1$}]", cell[0], cell[1], w0 = width[0], w1 = width[1])?;
+            if fmt.alternate() {
+                write!(fmt, " {:>w$}", cell[2], w = width[2])?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Write `s` padded to formatter width
+///
+/// Unlike `Formatter::pad` it does not truncate `s` to precision, which is already applied to
+/// numbers inside.
+pub fn pad(fmt: &mut fmt::Formatter, s: &str) -> fmt::Result {
+    let padding = fmt.width().map_or(0, |width| width.saturating_sub(s.chars().count()));
+    let (before, after) = match fmt.align() {
+        Some(fmt::Alignment::Right) => (padding, 0),
+        Some(fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+        _ => (0, padding),
+    };
+
+    let fill = fmt.fill();
+    for _ in 0..before {
+        fmt.write_char(fill)?;
+    }
+    fmt.write_str(s)?;
+    for _ in 0..after {
+        fmt.write_char(fill)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,189 @@
+//! Newtype wrappers that keep values of different units apart.
+//!
+//! `Tagged<T, U>` wraps a value together with zero-sized unit marker `U`. Addition and
+//! subtraction are only defined for values with the same unit, while multiplication and division
+//! are allowed by untagged values only, so mixing units is a compile-time error.
+//!
+//! ## Example
+//!
+//! ```rust
+//! #[macro_use]
+//! extern crate inter;
+//!
+//! use inter::Interval;
+//! use inter::tagged::Tagged;
+//!
+//! unit!(Meters);
+//! unit!(Seconds);
+//!
+//! fn main() {
+//!     let a: Tagged<_, Meters> = Tagged::new(Interval::with_range(1., 2.));
+//!     let b = Tagged::new(Interval::with_range(0.5, 1.));
+//!
+//!     assert_eq!((a + b).into_inner(), Interval::with_range(1.5, 3.));
+//!     assert_eq!((a * Interval::exact(2.)).into_inner(), Interval::with_range(2., 4.));
+//!
+//!     let _t: Tagged<Interval<f64>, Seconds> = Tagged::new(Interval::exact(1.));
+//!     // a + _t does not compile
+//! }
+//! ```
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::{Add, Sub, Mul, Div, Neg};
+
+/// Declare zero-sized unit marker to be used with `Tagged`
+#[macro_export]
+macro_rules! unit {
+    ($(#[$attr:meta])* $name:ident) => {
+        $(#[$attr])*
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        pub enum $name {}
+    };
+}
+
+/// Value `T` measured in unit `U`
+pub struct Tagged<T, U> {
+    value: T,
+    unit: PhantomData<fn() -> U>,
+}
+
+impl<T, U> Tagged<T, U> {
+    /// Tag value with unit
+    pub fn new(value: T) -> Self {
+        Tagged {
+            value,
+            unit: PhantomData,
+        }
+    }
+
+    /// Reference to wrapped value
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// Drop unit and return wrapped value
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+
+    /// Apply `f` to wrapped value keeping the unit
+    pub fn map<F>(self, f: F) -> Self
+        where F: FnOnce(T) -> T
+    {
+        Tagged::new(f(self.value))
+    }
+}
+
+// Implemented by hand, as derives would require `U` to implement the traits as well
+impl<T: Clone, U> Clone for Tagged<T, U> {
+    fn clone(&self) -> Self {
+        Tagged::new(self.value.clone())
+    }
+}
+
+impl<T: Copy, U> Copy for Tagged<T, U> {}
+
+impl<T: PartialEq, U> PartialEq for Tagged<T, U> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T: PartialOrd, U> PartialOrd for Tagged<T, U> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.value.partial_cmp(&other.value)
+    }
+}
+
+impl<T: fmt::Debug, U> fmt::Debug for Tagged<T, U> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        self.value.fmt(fmt)
+    }
+}
+
+impl<T: fmt::Display, U> fmt::Display for Tagged<T, U> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        self.value.fmt(fmt)
+    }
+}
+
+macro_rules! forward_same_unit {
+    ($($imp:ident, $method:ident;)*) => {
+        $(
+            impl<T, U> $imp for Tagged<T, U>
+                where T: $imp<Output = T>
+            {
+                type Output = Self;
+
+                fn $method(self, other: Self) -> Self {
+                    Tagged::new(self.value.$method(other.value))
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! forward_scalar {
+    ($($imp:ident, $method:ident;)*) => {
+        $(
+            impl<T, U> $imp<T> for Tagged<T, U>
+                where T: $imp<Output = T>
+            {
+                type Output = Self;
+
+                fn $method(self, other: T) -> Self {
+                    Tagged::new(self.value.$method(other))
+                }
+            }
+        )*
+    };
+}
+
+forward_same_unit! {
+    Add, add;
+    Sub, sub;
+}
+
+forward_scalar! {
+    Mul, mul;
+    Div, div;
+}
+
+impl<T, U> Neg for Tagged<T, U>
+    where T: Neg<Output = T>
+{
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Tagged::new(-self.value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Tagged;
+    use interval::Interval;
+
+    unit!(Meters);
+
+    #[test]
+    fn arithmetic() {
+        let a: Tagged<_, Meters> = Tagged::new(Interval::with_range(1., 2.));
+        let b = Tagged::new(Interval::with_range(3., 5.));
+
+        assert_eq!((b - a).into_inner(), Interval::with_range(1., 4.));
+        assert_eq!((-a).into_inner(), Interval::with_range(-2., -1.));
+        assert_eq!((b / Interval::exact(2.)).into_inner(), Interval::with_range(1.5, 2.5));
+        assert_eq!(a.map(|x| x.hull(&Interval::exact(0.))).value(), &Interval::with_range(0., 2.));
+    }
+
+    #[test]
+    fn display() {
+        let a: Tagged<_, Meters> = Tagged::new(Interval::with_range(1., 2.));
+
+        assert_eq!(format!("{}", a), "[1, 2]");
+        assert_eq!(format!("{:?}", a), format!("{:?}", a.into_inner()));
+    }
+}
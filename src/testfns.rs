@@ -0,0 +1,321 @@
+//! Standard global-optimization test functions with known minima.
+//!
+//! Every function is written as an interval expression, so it can be used both to validate the
+//! enclosures computed by a solver and to check them against documented global minima.
+//! Coefficients that are not representable in binary (like `1.05`) are rounded to the nearest
+//! `f64`, which does not move the documented minimum.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use inter::testfns;
+//!
+//! for function in testfns::all() {
+//!     let domain = function.domain();
+//!     let range = function.eval(&domain);
+//!
+//!     assert!(range.intersection(&function.minimum()).is_some());
+//! }
+//! ```
+
+use std::f64;
+
+use interval::Interval;
+use interval_box::IntervalBox;
+
+/// Test function over box domain with documented global minimum
+#[derive(Clone, Copy)]
+pub struct TestFunction {
+    name: &'static str,
+    domain: &'static [(f64, f64)],
+    minimum: (f64, f64),
+    minimizers: &'static [&'static [f64]],
+    function: fn(&[Interval<f64>]) -> Interval<f64>,
+}
+
+impl TestFunction {
+    /// Name of the function
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Number of arguments
+    pub fn dimension(&self) -> usize {
+        self.domain.len()
+    }
+
+    /// Standard search domain
+    pub fn domain(&self) -> IntervalBox<f64> {
+        self.domain.iter().map(|&(start, end)| Interval::with_range(start, end)).collect()
+    }
+
+    /// Enclosure of the global minimum over the search domain
+    pub fn minimum(&self) -> Interval<f64> {
+        Interval::with_range(self.minimum.0, self.minimum.1)
+    }
+
+    /// Points (possibly rounded) where the global minimum is attained
+    pub fn minimizers(&self) -> &'static [&'static [f64]] {
+        self.minimizers
+    }
+
+    /// Evaluate function over the box `x`
+    ///
+    /// # Panics
+    ///
+    /// This will panic if `x` has different length than `dimension()`.
+    pub fn eval(&self, x: &[Interval<f64>]) -> Interval<f64> {
+        assert_eq!(x.len(), self.dimension());
+
+        (self.function)(x)
+    }
+}
+
+/// Sphere function `Σ xᵢ²`, minimum `0` at the origin
+pub const SPHERE: TestFunction = TestFunction {
+    name: "sphere",
+    domain: &[(-5.12, 5.12), (-5.12, 5.12)],
+    minimum: (0., 0.),
+    minimizers: &[&[0., 0.]],
+    function: sphere,
+};
+
+/// Rosenbrock function, minimum `0` at `(1, 1)`
+pub const ROSENBROCK: TestFunction = TestFunction {
+    name: "rosenbrock",
+    domain: &[(-5., 10.), (-5., 10.)],
+    minimum: (0., 0.),
+    minimizers: &[&[1., 1.]],
+    function: rosenbrock,
+};
+
+/// Booth function, minimum `0` at `(1, 3)`
+pub const BOOTH: TestFunction = TestFunction {
+    name: "booth",
+    domain: &[(-10., 10.), (-10., 10.)],
+    minimum: (0., 0.),
+    minimizers: &[&[1., 3.]],
+    function: booth,
+};
+
+/// Matyas function, minimum `0` at the origin
+pub const MATYAS: TestFunction = TestFunction {
+    name: "matyas",
+    domain: &[(-10., 10.), (-10., 10.)],
+    minimum: (0., 0.),
+    minimizers: &[&[0., 0.]],
+    function: matyas,
+};
+
+/// Three-hump camel function, minimum `0` at the origin
+pub const THREE_HUMP_CAMEL: TestFunction = TestFunction {
+    name: "three-hump camel",
+    domain: &[(-5., 5.), (-5., 5.)],
+    minimum: (0., 0.),
+    minimizers: &[&[0., 0.]],
+    function: three_hump_camel,
+};
+
+/// Six-hump camel function, minimum `-1.0316284534…` at `(±0.0898…, ∓0.7126…)`
+pub const SIX_HUMP_CAMEL: TestFunction = TestFunction {
+    name: "six-hump camel",
+    domain: &[(-3., 3.), (-2., 2.)],
+    minimum: (-1.0316284535, -1.0316284534),
+    minimizers: &[&[0.08984201368301331, -0.7126564032704135],
+                  &[-0.08984201368301331, 0.7126564032704135]],
+    function: six_hump_camel,
+};
+
+/// Beale function, minimum `0` at `(3, 0.5)`
+pub const BEALE: TestFunction = TestFunction {
+    name: "beale",
+    domain: &[(-4.5, 4.5), (-4.5, 4.5)],
+    minimum: (0., 0.),
+    minimizers: &[&[3., 0.5]],
+    function: beale,
+};
+
+/// Goldstein–Price function, minimum `3` at `(0, -1)`
+pub const GOLDSTEIN_PRICE: TestFunction = TestFunction {
+    name: "goldstein-price",
+    domain: &[(-2., 2.), (-2., 2.)],
+    minimum: (3., 3.),
+    minimizers: &[&[0., -1.]],
+    function: goldstein_price,
+};
+
+/// Rastrigin function, minimum `0` at the origin
+pub const RASTRIGIN: TestFunction = TestFunction {
+    name: "rastrigin",
+    domain: &[(-5.12, 5.12), (-5.12, 5.12)],
+    minimum: (0., 0.),
+    minimizers: &[&[0., 0.]],
+    function: rastrigin,
+};
+
+/// Griewank function, minimum `0` at the origin
+pub const GRIEWANK: TestFunction = TestFunction {
+    name: "griewank",
+    domain: &[(-600., 600.), (-600., 600.)],
+    minimum: (0., 0.),
+    minimizers: &[&[0., 0.]],
+    function: griewank,
+};
+
+/// Branin function, minimum `5 / 4π` at `(-π, 12.275)`, `(π, 2.275)` and `(3π, 2.475)`
+pub const BRANIN: TestFunction = TestFunction {
+    name: "branin",
+    domain: &[(-5., 10.), (0., 15.)],
+    minimum: (0.3978873577, 0.3978873578),
+    minimizers: &[&[-f64::consts::PI, 12.275],
+                  &[f64::consts::PI, 2.275],
+                  &[3. * f64::consts::PI, 2.475]],
+    function: branin,
+};
+
+/// All test functions shipped with the crate
+pub fn all() -> Vec<TestFunction> {
+    vec![SPHERE, ROSENBROCK, BOOTH, MATYAS, THREE_HUMP_CAMEL, SIX_HUMP_CAMEL, BEALE,
+         GOLDSTEIN_PRICE, RASTRIGIN, GRIEWANK, BRANIN]
+}
+
+fn c(value: f64) -> Interval<f64> {
+    Interval::exact(value)
+}
+
+fn sqr(x: Interval<f64>) -> Interval<f64> {
+    x * x
+}
+
+/// Sphere function in any dimension
+pub fn sphere(x: &[Interval<f64>]) -> Interval<f64> {
+    x.iter().fold(c(0.), |acc, &xi| acc + sqr(xi))
+}
+
+/// Rosenbrock function in any dimension
+pub fn rosenbrock(x: &[Interval<f64>]) -> Interval<f64> {
+    x.windows(2).fold(c(0.), |acc, w| {
+        acc + c(100.) * sqr(w[1] - sqr(w[0])) + sqr(c(1.) - w[0])
+    })
+}
+
+/// Rastrigin function in any dimension
+pub fn rastrigin(x: &[Interval<f64>]) -> Interval<f64> {
+    let two_pi = c(2.) * Interval::pi();
+
+    x.iter().fold(c(10. * x.len() as f64),
+                  |acc, &xi| acc + sqr(xi) - c(10.) * (two_pi * xi).cos())
+}
+
+/// Griewank function in any dimension
+pub fn griewank(x: &[Interval<f64>]) -> Interval<f64> {
+    let (sum, product) = x.iter().enumerate().fold((c(0.), c(1.)), |(sum, product), (i, &xi)| {
+        // Square root is correctly rounded, so neighbours enclose the exact value
+        let root = ((i + 1) as f64).sqrt();
+        let root = Interval::with_range(root.next_down(), root.next_up());
+
+        (sum + sqr(xi), product * (xi / root).cos())
+    });
+
+    c(1.) + sum / c(4000.) - product
+}
+
+fn booth(x: &[Interval<f64>]) -> Interval<f64> {
+    let (x, y) = (x[0], x[1]);
+
+    sqr(x + c(2.) * y - c(7.)) + sqr(c(2.) * x + y - c(5.))
+}
+
+fn matyas(x: &[Interval<f64>]) -> Interval<f64> {
+    let (x, y) = (x[0], x[1]);
+
+    c(0.26) * (sqr(x) + sqr(y)) - c(0.48) * x * y
+}
+
+fn three_hump_camel(x: &[Interval<f64>]) -> Interval<f64> {
+    let (x, y) = (x[0], x[1]);
+    let x2 = sqr(x);
+
+    c(2.) * x2 - c(1.05) * sqr(x2) + x2 * x2 * x2 / c(6.) + x * y + sqr(y)
+}
+
+fn six_hump_camel(x: &[Interval<f64>]) -> Interval<f64> {
+    let (x, y) = (x[0], x[1]);
+    let x2 = sqr(x);
+    let y2 = sqr(y);
+
+    (c(4.) - c(2.1) * x2 + sqr(x2) / c(3.)) * x2 + x * y + (c(-4.) + c(4.) * y2) * y2
+}
+
+fn beale(x: &[Interval<f64>]) -> Interval<f64> {
+    let (x, y) = (x[0], x[1]);
+
+    sqr(c(1.5) - x + x * y) + sqr(c(2.25) - x + x * sqr(y)) +
+    sqr(c(2.625) - x + x * y * sqr(y))
+}
+
+fn goldstein_price(x: &[Interval<f64>]) -> Interval<f64> {
+    let (x, y) = (x[0], x[1]);
+
+    let a = c(1.) +
+            sqr(x + y + c(1.)) *
+            (c(19.) - c(14.) * x + c(3.) * sqr(x) - c(14.) * y + c(6.) * x * y +
+             c(3.) * sqr(y));
+    let b = c(30.) +
+            sqr(c(2.) * x - c(3.) * y) *
+            (c(18.) - c(32.) * x + c(12.) * sqr(x) + c(48.) * y - c(36.) * x * y +
+             c(27.) * sqr(y));
+
+    a * b
+}
+
+fn branin(x: &[Interval<f64>]) -> Interval<f64> {
+    let (x, y) = (x[0], x[1]);
+    let pi = Interval::pi();
+
+    let b = c(5.1) / (c(4.) * sqr(pi));
+    let d = c(5.) / pi;
+    let t = c(1.) / (c(8.) * pi);
+
+    sqr(y - b * sqr(x) + d * x - c(6.)) + c(10.) * (c(1.) - t) * x.cos() + c(10.)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn domain_encloses_minimum() {
+        for function in all() {
+            let range = function.eval(&function.domain());
+            let minimum = function.minimum();
+
+            assert!(range.contains(minimum.start) && range.contains(minimum.end),
+                    "{}",
+                    function.name());
+        }
+    }
+
+    #[test]
+    fn minimizers_attain_minimum() {
+        for function in all() {
+            let minimum = function.minimum();
+
+            for point in function.minimizers() {
+                let x: Vec<_> = point.iter().map(|&xi| Interval::exact(xi)).collect();
+                let value = function.eval(&x);
+
+                assert!(value.end >= minimum.start, "{}", function.name());
+                assert!(value.start <= minimum.end + 1e-9, "{}", function.name());
+            }
+        }
+    }
+
+    #[test]
+    fn sphere_in_higher_dimension() {
+        let x = vec![Interval::with_range(1., 2.); 3];
+        assert_eq!(sphere(&x), Interval::with_range(3., 12.));
+        assert!(rastrigin(&[Interval::exact(0.); 5]).contains(0.));
+        assert!(griewank(&[Interval::exact(0.); 5]).contains(0.));
+    }
+}
@@ -0,0 +1,181 @@
+//! Three-valued logic for predicates over intervals.
+
+use std::ops::{BitAnd, BitOr, Not};
+
+use interval::Interval;
+
+/// Truth value that can be unknown
+///
+/// Predicates over intervals hold for some values from intervals and not for others. `Tribool`
+/// keeps apart predicates that are certainly true, certainly false and those that cannot be
+/// decided. Operators follow Kleene logic, so `False & Unknown` is `False`, but
+/// `True & Unknown` is `Unknown`.
+///
+/// ## Example
+///
+/// ```rust
+/// use inter::{Interval, Tribool};
+///
+/// let a = Interval::with_range(1., 2.);
+/// let b = Interval::with_range(1.5, 3.);
+///
+/// assert_eq!(a.less_than(&Interval::exact(5.)), Tribool::True);
+/// assert_eq!(a.less_than(&b), Tribool::Unknown);
+/// assert_eq!(!a.less_than(&b) | a.less_than(&Interval::exact(5.)), Tribool::True);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Tribool {
+    /// Certainly false
+    False,
+    /// Certainly true
+    True,
+    /// Can be both true or false
+    Unknown,
+}
+
+impl Tribool {
+    /// Check if value is certainly true
+    pub fn is_true(self) -> bool {
+        self == Tribool::True
+    }
+
+    /// Check if value is certainly false
+    pub fn is_false(self) -> bool {
+        self == Tribool::False
+    }
+
+    /// Check if value is unknown
+    pub fn is_unknown(self) -> bool {
+        self == Tribool::Unknown
+    }
+
+    /// Check if value can be true
+    pub fn possibly(self) -> bool {
+        self != Tribool::False
+    }
+}
+
+impl From<bool> for Tribool {
+    fn from(value: bool) -> Self {
+        if value { Tribool::True } else { Tribool::False }
+    }
+}
+
+impl Not for Tribool {
+    type Output = Tribool;
+
+    fn not(self) -> Tribool {
+        match self {
+            Tribool::False => Tribool::True,
+            Tribool::True => Tribool::False,
+            Tribool::Unknown => Tribool::Unknown,
+        }
+    }
+}
+
+impl BitAnd for Tribool {
+    type Output = Tribool;
+
+    fn bitand(self, other: Tribool) -> Tribool {
+        match (self, other) {
+            (Tribool::False, _) | (_, Tribool::False) => Tribool::False,
+            (Tribool::True, Tribool::True) => Tribool::True,
+            _ => Tribool::Unknown,
+        }
+    }
+}
+
+impl BitOr for Tribool {
+    type Output = Tribool;
+
+    fn bitor(self, other: Tribool) -> Tribool {
+        !(!self & !other)
+    }
+}
+
+/// Decide predicate by checking when it certainly holds and when it certainly does not
+fn decide(certainly: bool, impossible: bool) -> Tribool {
+    match (certainly, impossible) {
+        (true, _) => Tribool::True,
+        (_, true) => Tribool::False,
+        _ => Tribool::Unknown,
+    }
+}
+
+impl<T> Interval<T>
+    where T: PartialOrd + Copy
+{
+    /// Check if every value is less than every value of `other`
+    pub fn less_than(&self, other: &Interval<T>) -> Tribool {
+        decide(self.end < other.start, self.start >= other.end)
+    }
+
+    /// Check if every value is less than or equal to every value of `other`
+    pub fn less_or_equal(&self, other: &Interval<T>) -> Tribool {
+        decide(self.end <= other.start, self.start > other.end)
+    }
+
+    /// Check if every value is greater than every value of `other`
+    pub fn greater_than(&self, other: &Interval<T>) -> Tribool {
+        other.less_than(self)
+    }
+
+    /// Check if every value is greater than or equal to every value of `other`
+    pub fn greater_or_equal(&self, other: &Interval<T>) -> Tribool {
+        other.less_or_equal(self)
+    }
+
+    /// Check if values of both intervals are equal
+    ///
+    /// This is certainly true only when both intervals are the same single point.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::{Interval, Tribool};
+    ///
+    /// assert_eq!(Interval::exact(1).equal_to(&Interval::exact(1)), Tribool::True);
+    /// assert_eq!(Interval::with_range(1, 2).equal_to(&Interval::exact(1)), Tribool::Unknown);
+    /// assert_eq!(Interval::with_range(1, 2).equal_to(&Interval::exact(3)), Tribool::False);
+    /// ```
+    pub fn equal_to(&self, other: &Interval<T>) -> Tribool {
+        let point = self.start == self.end && other.start == other.end;
+
+        decide(point && self.start == other.start,
+               self.end < other.start || other.end < self.start)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Tribool::{self, False, True, Unknown};
+    use interval::Interval;
+
+    #[test]
+    fn kleene_logic() {
+        let values = [False, True, Unknown];
+
+        for &a in &values {
+            assert_eq!(a & True, a);
+            assert_eq!(a | False, a);
+            assert_eq!(a & False, False);
+            assert_eq!(a | True, True);
+            assert_eq!(!!a, a);
+        }
+        assert_eq!(Unknown & Unknown, Unknown);
+        assert_eq!(Tribool::from(true), True);
+    }
+
+    #[test]
+    fn comparisons() {
+        let i = Interval::with_range;
+
+        assert_eq!(i(1, 2).less_than(&i(2, 3)), Unknown);
+        assert_eq!(i(1, 2).less_or_equal(&i(2, 3)), True);
+        assert_eq!(i(2, 3).less_than(&i(1, 2)), False);
+        assert_eq!(i(2, 3).less_or_equal(&i(1, 2)), Unknown);
+        assert_eq!(i(3, 4).greater_than(&i(1, 2)), True);
+        assert_eq!(i(1, 2).greater_or_equal(&i(3, 4)), False);
+        assert!(i(1, 3).equal_to(&i(2, 4)).possibly());
+    }
+}
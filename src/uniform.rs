@@ -0,0 +1,223 @@
+//! Intervals interpreted as uniform distributions.
+//!
+//! Nothing in this module is rigorous. Interval arithmetic knows only that a value lies
+//! somewhere in an interval, not how likely each part is, and the results here hold only
+//! under the additional assumption that values are distributed uniformly and independently.
+//! This is still a useful heuristic for ranking uncertain scores, when `order::certain_order`
+//! finds overlapping ones, and for quick summaries of uncertainty by quantiles or mean and
+//! variance, so it is kept apart behind `Uniform` instead of methods of `Interval`.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use inter::Interval;
+//! use inter::uniform::Uniform;
+//!
+//! let a = Uniform::new(Interval::with_range(0., 1.)).unwrap();
+//! let b = Uniform::new(Interval::with_range(0.5, 1.5)).unwrap();
+//!
+//! assert_eq!(a.prob_less(&b), 0.875);
+//! assert_eq!(b.prob_less(&a), 0.125);
+//! ```
+
+use num::Float;
+
+use interval::Interval;
+
+/// Uniform distribution over a bounded, non-empty interval
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Uniform<T>(Interval<T>);
+
+impl<T> Uniform<T>
+    where T: Float
+{
+    /// Distribution over `x`, or `None` when it is empty or unbounded
+    ///
+    /// Interval with single point is a distribution concentrated in that point.
+    pub fn new(x: Interval<T>) -> Option<Self> {
+        if x.start.is_finite() && x.end.is_finite() && x.start <= x.end {
+            Some(Uniform(x))
+        } else {
+            None
+        }
+    }
+
+    /// Interval of the distribution
+    pub fn interval(&self) -> Interval<T> {
+        self.0
+    }
+
+    /// Probability that a value drawn from `self` is less than one drawn from `other`
+    ///
+    /// Ties, which have nonzero probability only between points, count half, so
+    /// `a.prob_less(&b) + b.prob_less(&a)` is always one (up to rounding).
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    /// use inter::uniform::Uniform;
+    ///
+    /// let x = Uniform::new(Interval::with_range(0., 4.)).unwrap();
+    /// let point = Uniform::new(Interval::exact(1.)).unwrap();
+    ///
+    /// assert_eq!(x.prob_less(&point), 0.25);
+    /// assert_eq!(point.prob_less(&point), 0.5);
+    /// ```
+    pub fn prob_less(&self, other: &Uniform<T>) -> T {
+        let (zero, one) = (T::zero(), T::one());
+        let half = one / (one + one);
+        let fraction = |x: T| x.max(zero).min(one);
+        let (a, b) = (self.0.start, self.0.end);
+        let (c, d) = (other.0.start, other.0.end);
+
+        match (a == b, c == d) {
+            (true, true) if a == c => half,
+            (true, true) => if a < c { one } else { zero },
+            // Part of `other` above the point
+            (true, false) => fraction((d - a) / (d - c)),
+            // Part of `self` below the point
+            (false, true) => fraction((c - a) / (b - a)),
+            (false, false) => {
+                // Primitive of probability that `other` is above `x`
+                let primitive = |x: T| if x <= c {
+                    x - c
+                } else if x < d {
+                    (x - c) - (x - c) * (x - c) / ((d - c) + (d - c))
+                } else {
+                    (d - c) * half
+                };
+
+                fraction((primitive(b) - primitive(a)) / (b - a))
+            }
+        }
+    }
+
+    /// Probability that a drawn value is at most `x`
+    pub fn cdf(&self, x: T) -> T {
+        let (a, b) = (self.0.start, self.0.end);
+        if x >= b {
+            T::one()
+        } else if x <= a {
+            T::zero()
+        } else {
+            (x - a) / (b - a)
+        }
+    }
+
+    /// Value that is not exceeded with probability `p`
+    ///
+    /// # Panics
+    ///
+    /// This will panic if `p` is not between `0` and `1`.
+    pub fn quantile(&self, p: T) -> T {
+        assert!(p >= T::zero() && p <= T::one(), "probability is not between 0 and 1");
+
+        let (a, b) = (self.0.start, self.0.end);
+        (a + (b - a) * p).max(a).min(b)
+    }
+
+    /// Mean of the distribution, the midpoint of interval
+    pub fn mean(&self) -> T {
+        let two = T::one() + T::one();
+        self.0.start / two + self.0.end / two
+    }
+
+    /// Variance of the distribution, `w² / 12` for interval of width `w`
+    pub fn variance(&self) -> T {
+        let width = self.0.end - self.0.start;
+        let twelve = T::from(12).unwrap();
+        width * width / twelve
+    }
+
+    /// Uniform distribution with given `mean` and `variance`
+    ///
+    /// Returns `None` when `variance` is negative or NaN, or the interval would not be bounded.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use inter::Interval;
+    /// use inter::uniform::Uniform;
+    ///
+    /// let x = Uniform::from_mean_variance(1., 3.).unwrap();
+    ///
+    /// assert_eq!(x.interval(), Interval::with_range(-2., 4.));
+    /// assert_eq!((x.mean(), x.variance()), (1., 3.));
+    /// assert_eq!(x.quantile(0.75), 2.5);
+    /// assert_eq!(x.cdf(2.5), 0.75);
+    /// ```
+    pub fn from_mean_variance(mean: T, variance: T) -> Option<Self> {
+        if variance < T::zero() {
+            return None;
+        }
+
+        let radius = (T::from(3).unwrap() * variance).sqrt();
+        Uniform::new(Interval {
+            start: mean - radius,
+            end: mean + radius,
+        })
+    }
+}
+
+impl<T> From<Uniform<T>> for Interval<T> {
+    fn from(x: Uniform<T>) -> Self {
+        x.0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn uniform(a: f64, b: f64) -> Uniform<f64> {
+        Uniform::new(Interval::with_range(a, b)).unwrap()
+    }
+
+    #[test]
+    fn comparisons() {
+        let xs = [uniform(0., 1.), uniform(0.5, 1.5), uniform(-3., 10.), uniform(2., 3.),
+                  uniform(1., 1.), uniform(0.2, 0.7), uniform(2., 2.)];
+        for x in &xs {
+            for y in &xs {
+                let p = x.prob_less(y);
+                assert!((p + y.prob_less(x) - 1.).abs() < 1e-15, "{:?} {:?}", x, y);
+
+                // Midpoint rule over values of `self`
+                let (a, b) = (x.0.start, x.0.end);
+                let n = 10_000;
+                let below = |t: f64| y.prob_less(&Uniform(Interval::exact(t)));
+                let estimate = (0..n)
+                    .map(|i| 1. - below(a + (b - a) * (i as f64 + 0.5) / n as f64))
+                    .sum::<f64>() / n as f64;
+                assert!((p - estimate).abs() < 1e-3, "{:?} {:?} {} {}", x, y, p, estimate);
+            }
+        }
+
+        assert_eq!(uniform(0., 1.).prob_less(&uniform(0., 1.)), 0.5);
+        assert_eq!(uniform(0., 1.).prob_less(&uniform(2., 3.)), 1.);
+        assert!(Uniform::new(Interval::with_range(0., f64::INFINITY)).is_none());
+        assert!(Uniform::new(Interval::<f64>::empty()).is_none());
+        assert_eq!(Interval::<f64>::from(uniform(1., 2.)), Interval::with_range(1., 2.));
+    }
+
+    #[test]
+    fn summaries() {
+        let x = uniform(-1., 3.);
+        for &(p, q) in &[(0., -1.), (0.25, 0.), (0.5, 1.), (1., 3.)] {
+            assert_eq!(x.quantile(p), q);
+            assert_eq!(x.cdf(q), p);
+        }
+        assert_eq!((x.cdf(-5.), x.cdf(4.)), (0., 1.));
+        assert_eq!((x.mean(), x.variance()), (1., 16. / 12.));
+
+        let point = uniform(2., 2.);
+        assert_eq!((point.cdf(1.9), point.cdf(2.), point.quantile(0.3)), (0., 1., 2.));
+        assert_eq!(Uniform::from_mean_variance(2., 0.), Some(point));
+        assert_eq!(Uniform::from_mean_variance(1., 16. / 12.).map(|x| x.mean()), Some(1.));
+        assert!(Uniform::from_mean_variance(0., -1.).is_none());
+        assert!(Uniform::from_mean_variance(0., f64::NAN).is_none());
+        assert!(Uniform::from_mean_variance(0., f64::MAX).is_none());
+        assert!(::std::panic::catch_unwind(|| x.quantile(1.5)).is_err());
+    }
+}
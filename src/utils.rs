@@ -1,15 +1,206 @@
+use num::Float;
+
+/// Smaller of `a` and `b`, poisoned by values that are not comparable
+///
+/// If `a` or `b` is not comparable with itself (NaN), it is returned whichever argument it is,
+/// so NaN bound propagates to the result and makes it empty, instead of being silently dropped
+/// or kept depending on order of arguments. Values comparable with themselves but not with each
+/// other give `a`.
+///
+/// ## Example
+///
+/// ```rust
+/// use inter::partial_min;
+///
+/// assert_eq!(partial_min(1., 2.), 1.);
+/// assert!(partial_min(f64::NAN, 1.).is_nan() && partial_min(1., f64::NAN).is_nan());
+/// ```
 pub fn partial_min<T: PartialOrd>(a: T, b: T) -> T {
-    if a <= b {
-        a
-    } else {
+    if b.partial_cmp(&b).is_none() || b < a {
         b
+    } else {
+        a
     }
 }
 
+/// Larger of `a` and `b`, poisoned by values that are not comparable
+///
+/// NaN arguments propagate in the same way as in `partial_min`.
+///
+/// ## Example
+///
+/// ```rust
+/// use inter::partial_max;
+///
+/// assert_eq!(partial_max(1., 2.), 2.);
+/// assert!(partial_max(f64::NAN, 1.).is_nan() && partial_max(1., f64::NAN).is_nan());
+/// ```
 pub fn partial_max<T: PartialOrd>(a: T, b: T) -> T {
-    if a >= b {
+    if b.partial_cmp(&b).is_none() || b > a {
+        b
+    } else {
         a
+    }
+}
+
+/// Multiply `x` by `2^exp`, returning lower and upper bound of the exact result
+///
+/// Both bounds are equal unless the result overflows or underflows into subnormals.
+pub fn scale_pow2<T: Float>(x: T, exp: i32) -> (T, T) {
+    const STEP: i32 = 60;
+
+    if x.is_zero() || !x.is_finite() || exp == 0 {
+        return (x, x);
+    }
+
+    let two = T::one() + T::one();
+    let mut ret = x;
+    let mut exp = exp;
+    while exp > STEP && ret.is_finite() {
+        ret = ret * two.powi(STEP);
+        exp -= STEP;
+    }
+    while exp < -STEP && !ret.is_zero() {
+        ret = ret * two.powi(-STEP);
+        exp += STEP;
+    }
+    ret = ret * two.powi(exp);
+
+    if ret.is_infinite() {
+        if x > T::zero() {
+            (T::max_value(), ret)
+        } else {
+            (ret, -T::max_value())
+        }
+    } else if ret.abs() < T::min_positive_value() {
+        // Result is subnormal, so it could be rounded. Smallest subnormal is exact ulp there.
+        let ulp = T::min_positive_value() * T::epsilon();
+        if x > T::zero() {
+            ((ret - ulp).max(T::zero()), ret + ulp)
+        } else {
+            (ret - ulp, (ret + ulp).min(T::zero()))
+        }
     } else {
-        b
+        (ret, ret)
+    }
+}
+
+/// Split finite non-zero `x` into mantissa in `[0.5, 1)` (by absolute value) and exponent
+///
+/// Zero and non-finite values are returned unchanged with exponent `0`.
+pub fn frexp<T: Float>(x: T) -> (T, i32) {
+    if x.is_zero() || !x.is_finite() {
+        return (x, 0);
+    }
+
+    let (mantissa, exp, _) = x.integer_decode();
+    let exp = exp as i32 + (64 - mantissa.leading_zeros() as i32);
+
+    (scale_pow2(x, -exp).0, exp)
+}
+
+/// Number of significant bits of `T` (53 for `f64`)
+pub fn precision<T: Float>() -> i32 {
+    2 - frexp(T::epsilon()).1
+}
+
+/// `2^exp`, exact whenever it is representable, even as subnormal
+fn pow2<T: Float>(exp: i32) -> T {
+    let tiny = T::min_positive_value() * T::epsilon();
+    let tiny_exp = frexp(tiny).1 - 1;
+
+    if exp - tiny_exp < precision::<T>() {
+        // Small powers of two are exact, and so is their product with `tiny`
+        tiny * (T::one() + T::one()).powi(exp - tiny_exp)
+    } else {
+        scale_pow2(T::one(), exp).0
+    }
+}
+
+/// Smallest representable value greater than `x`
+pub fn next_up<T: Float>(x: T) -> T {
+    if x.is_nan() || x == T::infinity() {
+        return x;
+    }
+    if x == T::neg_infinity() {
+        return -T::max_value();
+    }
+
+    let tiny = T::min_positive_value() * T::epsilon();
+    if x.is_zero() {
+        return tiny;
+    }
+
+    let (mantissa, exp) = frexp(x.abs());
+    let half = T::one() / (T::one() + T::one());
+    // Spacing of values in the binade of `x`, halved below a power of two when going toward zero
+    let exp = if x < T::zero() && mantissa == half {
+        exp - 1
+    } else {
+        exp
+    };
+    let ulp = pow2::<T>(exp - precision::<T>()).max(tiny);
+
+    x + ulp
+}
+
+/// Largest representable value less than `x`
+pub fn next_down<T: Float>(x: T) -> T {
+    -next_up(-x)
+}
+
+/// Position of `x` among all values of `T`, with `-0` and `0` at the same place
+///
+/// Consecutive values have consecutive positions, and infinities are next to largest finite
+/// values. `x` must not be NaN.
+pub fn ordinal<T: Float>(x: T) -> i128 {
+    if x.is_infinite() {
+        return ordinal(T::max_value()).saturating_add(1) * x.signum().to_i128().unwrap();
+    }
+
+    let (mantissa, exp, sign) = x.integer_decode();
+    let (_, min_exp, _) = T::min_positive_value().integer_decode();
+    let position = if exp < min_exp {
+        // Subnormals are scaled to the exponent of the smallest normal number
+        (mantissa >> (min_exp - exp).min(63)) as i128
+    } else {
+        ((exp as i128 - min_exp as i128) << (precision::<T>() - 1)) + mantissa as i128
+    };
+
+    position * sign as i128
+}
+
+#[cfg(test)]
+mod test {
+    use std::f64;
+
+    use super::{next_up, next_down, ordinal};
+
+    #[test]
+    fn next_up_matches_std() {
+        let samples = [0., -0., 1., -1., 0.1, -0.1, 1e300, -1e-310, f64::MIN_POSITIVE,
+                       -f64::MIN_POSITIVE, f64::MAX, -f64::MAX, 2f64.powi(-1070), 1024.,
+                       3. * f64::MIN_POSITIVE, -3. * f64::MIN_POSITIVE, 2f64.powi(-1000),
+                       -2f64.powi(-1000), 1e-300];
+
+        for &x in &samples {
+            assert_eq!(next_up(x), x.next_up(), "{:e}", x);
+            assert_eq!(next_down(x), x.next_down(), "{:e}", x);
+        }
+        assert_eq!(next_up(f64::NEG_INFINITY), -f64::MAX);
+        assert_eq!(next_up(f64::INFINITY), f64::INFINITY);
+        assert_eq!(next_up(1f32), 1f32.next_up());
+    }
+
+    #[test]
+    fn ordinals() {
+        let samples = [0., 5e-324, 1e-310, f64::MIN_POSITIVE, 0.1, 1., 1e300, f64::MAX];
+        for &x in &samples {
+            assert_eq!(ordinal(x), x.to_bits() as i128, "{:e}", x);
+            assert_eq!(ordinal(-x), -ordinal(x));
+            assert_eq!(ordinal(x.next_up()), ordinal(x) + 1);
+        }
+        assert_eq!(ordinal(f32::MIN_POSITIVE), f32::MIN_POSITIVE.to_bits() as i128);
+        assert_eq!(ordinal(-f32::INFINITY), -(f32::INFINITY.to_bits() as i128));
     }
 }
@@ -0,0 +1,94 @@
+//! Checks of interval invariants after arithmetic operations.
+//!
+//! Enabled by the `validate` feature, and only in debug builds. Every operator asserts that its
+//! result satisfies the invariants, so a bug in the rounding backend or in the case analysis of
+//! an operation panics where it happens, instead of silently giving wrong enclosures later.
+
+use interval::Interval;
+use rounding::RoundedArith;
+
+/// Validate `result` of operation `op` on non-empty `x` and `y`
+///
+/// `down` and `up` evaluate the operation on points with directed rounding. The result has to
+/// contain their values on every pair of endpoints, and they must not be ordered the wrong way.
+/// Bounds of result may be NaN only when the operation is undefined at some pair, like `∞ - ∞`.
+/// Points where `skip` is true are not checked, which is the case of division by zero.
+pub fn binary<T, F, G, S>(op: &str,
+                          x: Interval<T>,
+                          y: Interval<T>,
+                          result: Interval<T>,
+                          down: F,
+                          up: G,
+                          skip: S)
+    where T: RoundedArith,
+          F: Fn(T, T) -> T,
+          G: Fn(T, T) -> T,
+          S: Fn(T, T) -> bool
+{
+    if !cfg!(debug_assertions) {
+        return;
+    }
+
+    let comparable = |x: T| x.partial_cmp(&x).is_some();
+    if ![x.start, x.end, y.start, y.end].iter().all(|&x| comparable(x)) {
+        return;
+    }
+
+    let mut undefined = false;
+    for &a in &[x.start, x.end] {
+        for &b in &[y.start, y.end] {
+            if skip(a, b) {
+                continue;
+            }
+
+            let (low, high) = (down(a, b), up(a, b));
+            if !comparable(low) || !comparable(high) {
+                undefined = true;
+                continue;
+            }
+            assert!(low <= high,
+                    "interval validation: `{}` rounded downward is above upward rounding",
+                    op);
+            // NaN bound is checked below
+            let (start, end) = (result.start, result.end);
+            assert!((!comparable(start) || start <= low) && (!comparable(end) || high <= end),
+                    "interval validation: `{}` does not contain value at endpoints",
+                    op);
+        }
+    }
+
+    assert!(undefined || comparable(result.start) && comparable(result.end),
+            "interval validation: `{}` has NaN bound",
+            op);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn operators() {
+        let (a, b) = (Interval::with_range(-1., 0.1), Interval::with_range(0., f64::INFINITY));
+        for x in &[a + b, a - b, a * b, a / b, b / a, b - b] {
+            assert!(!x.is_empty());
+        }
+        let n = Interval::with_range(-3, 2);
+        assert_eq!(n * n - n, Interval::with_range(-8, 12));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "interval validation: `+` does not contain value at endpoints")]
+    fn wrong_result() {
+        let x = Interval::with_range(0.1, 0.2);
+        binary("+", x, x, Interval::exact(0.3), f64::add_down, f64::add_up, |_, _| false);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "rounded downward is above upward rounding")]
+    fn wrong_rounding() {
+        let x = Interval::with_range(0.1, 0.2);
+        binary("+", x, x, Interval::entire(), f64::add_up, f64::add_down, |_, _| false);
+    }
+}